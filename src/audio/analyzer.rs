@@ -0,0 +1,147 @@
+use std::path::Path;
+use std::process::Command;
+
+/// EBU R128 broadcast target
+pub const TARGET_LOUDNESS_LUFS: f64 = -23.0;
+/// Allowed deviation from the target for live/broadcast content
+pub const LOUDNESS_TOLERANCE_LU: f64 = 1.0;
+/// Highest true peak allowed by EBU R128
+pub const MAX_TRUE_PEAK_DBFS: f64 = -1.0;
+
+/// Integrated loudness and true peak of a range of a clip
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessResult {
+    pub integrated_lufs: f64,
+    /// None if FFmpeg didn't report a true peak (e.g. a silent range)
+    pub true_peak_dbfs: Option<f64>,
+}
+
+/// How far a measurement is from the broadcast standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoudnessCompliance {
+    /// Within the tolerance
+    Compliant,
+    /// Up to three times the tolerance away
+    Close,
+    Outside,
+}
+
+impl LoudnessCompliance {
+    pub fn of_integrated(lufs: f64) -> Self {
+        let deviation = (lufs - TARGET_LOUDNESS_LUFS).abs();
+        if deviation <= LOUDNESS_TOLERANCE_LU {
+            Self::Compliant
+        } else if deviation <= LOUDNESS_TOLERANCE_LU * 3.0 {
+            Self::Close
+        } else {
+            Self::Outside
+        }
+    }
+
+    pub fn of_true_peak(dbfs: f64) -> Self {
+        if dbfs <= MAX_TRUE_PEAK_DBFS {
+            Self::Compliant
+        } else if dbfs <= 0.0 {
+            Self::Close
+        } else {
+            Self::Outside
+        }
+    }
+
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            Self::Compliant => egui::Color32::GREEN,
+            Self::Close => egui::Color32::YELLOW,
+            Self::Outside => egui::Color32::RED,
+        }
+    }
+}
+
+pub struct AudioAnalyzer;
+
+impl AudioAnalyzer {
+    /// Measure EBU R128 loudness of `start`..`end` (all audio tracks mixed as FFmpeg decodes
+    /// them by default) by running `ffmpeg` with the `ebur128` filter
+    pub fn measure_loudness(path: &Path, start: f64, end: f64, ffmpeg: &Path) -> anyhow::Result<LoudnessResult> {
+        let output = Command::new(ffmpeg)
+            .arg("-hide_banner")
+            .arg("-nostats")
+            .arg("-ss").arg(format!("{:.3}", start))
+            .arg("-to").arg(format!("{:.3}", end))
+            .arg("-i").arg(path)
+            .arg("-vn")
+            .arg("-af").arg("ebur128=framelog=verbose:peak=true")
+            .arg("-f").arg("null")
+            .arg("-")
+            .output()
+            .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", ffmpeg.display(), e))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            let tail: Vec<&str> = stderr.lines().rev().take(5).collect();
+            return Err(anyhow::anyhow!(
+                "Loudness measurement failed: {}",
+                tail.into_iter().rev().collect::<Vec<_>>().join("\n")
+            ));
+        }
+
+        Self::parse_ebur128_summary(&stderr)
+            .ok_or_else(|| anyhow::anyhow!("No loudness summary in FFmpeg output for {}", path.display()))
+    }
+
+    /// Read integrated loudness and true peak from the summary FFmpeg prints at the end
+    pub fn parse_ebur128_summary(stderr: &str) -> Option<LoudnessResult> {
+        let summary = &stderr[stderr.rfind("Summary:")?..];
+
+        let value_after = |label: &str| -> Option<f64> {
+            summary.lines()
+                .map(str::trim)
+                .find_map(|line| line.strip_prefix(label))
+                .and_then(|rest| rest.split_whitespace().next())
+                .and_then(|value| value.parse::<f64>().ok())
+        };
+
+        let integrated_lufs = value_after("I:")?;
+        let true_peak_dbfs = value_after("Peak:").filter(|peak| peak.is_finite());
+        Some(LoudnessResult { integrated_lufs, true_peak_dbfs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ebur128_summary() {
+        let stderr = "\
+[Parsed_ebur128_0 @ 0x5581] t: 9.9   TARGET:-23 LUFS    M: -18.2 S: -19.0     I: -19.4 LUFS       LRA:   2.1 LU  FTPK:  -3.1 dBFS  TPK:  -1.9 dBFS
+[Parsed_ebur128_0 @ 0x5581] Summary:
+
+  Integrated loudness:
+    I:         -19.4 LUFS
+    Threshold: -29.6 LUFS
+
+  Loudness range:
+    LRA:         2.1 LU
+    Threshold: -39.5 LUFS
+    LRA low:   -20.6 LUFS
+    LRA high:  -18.5 LUFS
+
+  True peak:
+    Peak:       -1.9 dBFS
+";
+        let result = AudioAnalyzer::parse_ebur128_summary(stderr).expect("Failed to parse summary");
+        assert_eq!(result.integrated_lufs, -19.4);
+        assert_eq!(result.true_peak_dbfs, Some(-1.9));
+
+        assert!(AudioAnalyzer::parse_ebur128_summary("no summary here").is_none());
+    }
+
+    #[test]
+    fn test_loudness_compliance() {
+        assert_eq!(LoudnessCompliance::of_integrated(-23.5), LoudnessCompliance::Compliant);
+        assert_eq!(LoudnessCompliance::of_integrated(-20.5), LoudnessCompliance::Close);
+        assert_eq!(LoudnessCompliance::of_integrated(-14.0), LoudnessCompliance::Outside);
+        assert_eq!(LoudnessCompliance::of_true_peak(-0.5), LoudnessCompliance::Close);
+    }
+}
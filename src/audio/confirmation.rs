@@ -1,13 +1,59 @@
 use crate::audio::device_manager::{AudioDeviceManager, AudioDeviceInfo};
 use crate::core::config::AudioConfirmationConfig;
+use crate::core::ClipDuration;
 use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The sound to play for `duration`: the custom file from the config if it exists,
+/// otherwise the generated tone in `generated_dir`
+pub fn resolve_duration_sound(duration: ClipDuration, config: &AudioConfirmationConfig, generated_dir: &Path) -> PathBuf {
+    match config.duration_sound_files.get(&(duration as u32)) {
+        Some(custom) if custom.exists() => custom.clone(),
+        Some(custom) => {
+            log::warn!("Custom {} sound {} not found, using the generated tone", duration.label(), custom.display());
+            generated_dir.join(crate::audio::duration_sound_file_name(duration))
+        }
+        None => generated_dir.join(crate::audio::duration_sound_file_name(duration)),
+    }
+}
+
+/// The confirmation sounds that mean different things and are debounced separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundKind {
+    Confirmation,
+    Duration(ClipDuration),
+    Unmatched,
+}
+
+/// Remembers when each kind of sound last played, to skip rapid repeats
+#[derive(Debug, Default)]
+pub struct SoundDebouncer {
+    last_played: HashMap<SoundKind, Instant>,
+}
+
+impl SoundDebouncer {
+    /// Whether a `kind` sound may play at `now`, i.e. none played in the last `interval`.
+    /// Allowed sounds are recorded; skipped ones don't extend the quiet period.
+    pub fn allow(&mut self, kind: SoundKind, now: Instant, interval: Duration) -> bool {
+        if let Some(last) = self.last_played.get(&kind) {
+            if now.saturating_duration_since(*last) < interval {
+                return false;
+            }
+        }
+        self.last_played.insert(kind, now);
+        true
+    }
+}
 
 pub struct AudioConfirmation {
     device_manager: AudioDeviceManager,
     current_output_stream: Option<(OutputStream, OutputStreamHandle)>,
     current_sink: Option<Sink>,
+    debouncer: SoundDebouncer,
 }
 
 impl AudioConfirmation {
@@ -22,6 +68,7 @@ impl AudioConfirmation {
             device_manager,
             current_output_stream: None,
             current_sink: None,
+            debouncer: SoundDebouncer::default(),
         })
     }
     
@@ -29,12 +76,20 @@ impl AudioConfirmation {
         self.device_manager.get_devices()
     }
     
-    pub fn refresh_devices(&mut self) -> anyhow::Result<()> {
+    /// Enumerate devices again. Returns true if `selected_device` disappeared, in which case
+    /// the caller should switch playback over to the default device.
+    pub fn refresh_devices(&mut self, selected_device: Option<&str>) -> anyhow::Result<bool> {
         self.device_manager.refresh_devices()
             .map_err(|e| {
                 log::error!("Failed to refresh audio devices: {}", e);
                 anyhow::anyhow!("Failed to refresh audio devices: {}", e)
-            })
+            })?;
+        
+        let disappeared = selected_device.is_some_and(|name| !self.device_manager.has_device(name));
+        if disappeared {
+            log::warn!("Selected audio device '{}' is no longer available", selected_device.unwrap_or_default());
+        }
+        Ok(disappeared)
     }
     
     pub fn play_confirmation_sound(&mut self, config: &AudioConfirmationConfig) -> anyhow::Result<()> {
@@ -42,6 +97,9 @@ impl AudioConfirmation {
             log::debug!("Audio confirmation is disabled, skipping sound playback");
             return Ok(());
         }
+        if !self.debounce(SoundKind::Confirmation, config) {
+            return Ok(());
+        }
         
         let sound_file = match &config.sound_file_path {
             Some(path) => path.clone(),
@@ -63,28 +121,29 @@ impl AudioConfirmation {
         }
     }
 
-    /// Plays a duration-specific confirmation sound based on the clip duration
-    pub fn play_duration_confirmation(&mut self, duration: &crate::core::ClipDuration, config: &AudioConfirmationConfig) -> anyhow::Result<()> {
+    /// Plays a duration-specific confirmation sound based on the clip duration,
+    /// using the custom file configured for it if there is one
+    pub fn play_duration_confirmation(&mut self, duration: &ClipDuration, config: &AudioConfirmationConfig) -> anyhow::Result<()> {
         if !config.enabled {
             log::debug!("Audio confirmation is disabled, skipping duration sound playback");
             return Ok(());
         }
+        if !self.debounce(SoundKind::Duration(*duration), config) {
+            return Ok(());
+        }
+        self.preview_duration_confirmation(*duration, config)
+    }
 
+    /// Play the sound for `duration` even if confirmation sounds are disabled, for the settings dialog
+    pub fn preview_duration_confirmation(&mut self, duration: ClipDuration, config: &AudioConfirmationConfig) -> anyhow::Result<()> {
         // Ensure duration sounds exist
-        let sounds_dir = crate::audio::generate_duration_confirmation_sounds()
+        let sounds_dir = crate::audio::generate_duration_confirmation_sounds(config)
             .map_err(|e| {
                 log::error!("Failed to ensure duration confirmation sounds: {}", e);
                 anyhow::anyhow!("Failed to ensure duration sounds: {}", e)
             })?;
 
-        let sound_file = match duration {
-            crate::core::ClipDuration::Seconds15 => sounds_dir.join("duration_15s.wav"),
-            crate::core::ClipDuration::Seconds30 => sounds_dir.join("duration_30s.wav"),
-            crate::core::ClipDuration::Minutes1 => sounds_dir.join("duration_1m.wav"),
-            crate::core::ClipDuration::Minutes2 => sounds_dir.join("duration_2m.wav"),
-            crate::core::ClipDuration::Minutes5 => sounds_dir.join("duration_5m.wav"),
-        };
-
+        let sound_file = resolve_duration_sound(duration, config, &sounds_dir);
         log::debug!("Playing duration confirmation sound for {:?}: {}", duration, sound_file.display());
         self.play_sound_file(&sound_file, config)
     }
@@ -95,9 +154,12 @@ impl AudioConfirmation {
             log::debug!("Audio confirmation is disabled, skipping unmatched sound playback");
             return Ok(());
         }
+        if !self.debounce(SoundKind::Unmatched, config) {
+            return Ok(());
+        }
 
         // Ensure duration sounds exist (includes unmatched sound)
-        let sounds_dir = crate::audio::generate_duration_confirmation_sounds()
+        let sounds_dir = crate::audio::generate_duration_confirmation_sounds(config)
             .map_err(|e| {
                 log::error!("Failed to ensure duration confirmation sounds: {}", e);
                 anyhow::anyhow!("Failed to ensure duration sounds: {}", e)
@@ -108,6 +170,15 @@ impl AudioConfirmation {
         self.play_sound_file(&sound_file, config)
     }
 
+    /// Whether a `kind` sound may play now, logging when a repeat is skipped
+    fn debounce(&mut self, kind: SoundKind, config: &AudioConfirmationConfig) -> bool {
+        let allowed = self.debouncer.allow(kind, Instant::now(), config.sound_debounce());
+        if !allowed {
+            log::debug!("Skipping {:?} sound, one played less than {}ms ago", kind, config.sound_debounce_ms);
+        }
+        allowed
+    }
+
     /// Internal method to play a specific sound file
     fn play_sound_file(&mut self, sound_file: &std::path::Path, config: &AudioConfirmationConfig) -> anyhow::Result<()> {
         if !sound_file.exists() {
@@ -189,3 +260,44 @@ impl AudioConfirmation {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_duration_sound_prefers_existing_custom_file() {
+        let custom = std::env::temp_dir().join(format!("clip-helper-custom-sound-{}.wav", std::process::id()));
+        std::fs::write(&custom, b"RIFF").expect("Failed to write test file");
+        let generated = Path::new("/generated");
+
+        let mut config = AudioConfirmationConfig::default();
+        config.duration_sound_files.insert(30, custom.clone());
+        config.duration_sound_files.insert(60, PathBuf::from("/missing/sound.wav"));
+
+        assert_eq!(resolve_duration_sound(ClipDuration::Seconds30, &config, generated), custom);
+        assert_eq!(resolve_duration_sound(ClipDuration::Minutes1, &config, generated), generated.join("duration_1m.wav"));
+        assert_eq!(resolve_duration_sound(ClipDuration::Minutes5, &config, generated), generated.join("duration_5m.wav"));
+
+        let _ = std::fs::remove_file(&custom);
+    }
+
+    #[test]
+    fn test_debouncer_skips_repeats_per_kind() {
+        let mut debouncer = SoundDebouncer::default();
+        let interval = Duration::from_millis(500);
+        let start = Instant::now();
+
+        assert!(debouncer.allow(SoundKind::Confirmation, start, interval));
+        assert!(!debouncer.allow(SoundKind::Confirmation, start + Duration::from_millis(100), interval));
+        // Other sounds, and other durations, carry a different meaning and still play
+        assert!(debouncer.allow(SoundKind::Duration(ClipDuration::Seconds30), start + Duration::from_millis(100), interval));
+        assert!(debouncer.allow(SoundKind::Duration(ClipDuration::Minutes1), start + Duration::from_millis(150), interval));
+        assert!(!debouncer.allow(SoundKind::Duration(ClipDuration::Seconds30), start + Duration::from_millis(200), interval));
+        // The skipped repeat didn't push the window out
+        assert!(debouncer.allow(SoundKind::Confirmation, start + Duration::from_millis(500), interval));
+
+        assert!(debouncer.allow(SoundKind::Unmatched, start, Duration::ZERO));
+        assert!(debouncer.allow(SoundKind::Unmatched, start, Duration::ZERO));
+    }
+}
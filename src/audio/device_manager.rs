@@ -1,5 +1,6 @@
 use cpal::{Device, Host};
 use cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{OutputStream, OutputStreamHandle};
 
 #[derive(Debug, Clone)]
 pub struct AudioDeviceInfo {
@@ -66,6 +67,37 @@ impl AudioDeviceManager {
         &self.devices
     }
     
+    /// Whether the last refresh found an output device called `name`
+    pub fn has_device(&self, name: &str) -> bool {
+        self.devices.iter().any(|device| device.name == name)
+    }
+    
+    /// Whether an output device called `name` is connected right now. If the devices
+    /// can't be listed it counts as connected, since it can't be ruled out.
+    pub fn output_device_exists(name: &str) -> bool {
+        match cpal::default_host().output_devices() {
+            Ok(mut devices) => devices.any(|device| device.name().is_ok_and(|device_name| device_name == name)),
+            Err(e) => {
+                log::debug!("Failed to list audio devices: {}", e);
+                true
+            }
+        }
+    }
+    
+    /// Open an output stream on the named device, or the default one for None or a
+    /// device that is gone. Usable from any thread, since it doesn't need a manager.
+    pub fn open_output_stream(device_name: Option<&str>) -> anyhow::Result<(OutputStream, OutputStreamHandle)> {
+        let Some(name) = device_name else {
+            return OutputStream::try_default()
+                .map_err(|e| anyhow::anyhow!("Failed to open default audio output: {}", e));
+        };
+        
+        let manager = AudioDeviceManager { host: cpal::default_host(), devices: Vec::new() };
+        let device = manager.get_device_by_name(name)?;
+        OutputStream::try_from_device(&device)
+            .map_err(|e| anyhow::anyhow!("Failed to open audio output '{}': {}", name, e))
+    }
+    
     pub fn get_device_by_name(&self, name: &str) -> anyhow::Result<Device> {
         let devices = self.host.output_devices()
             .map_err(|e| {
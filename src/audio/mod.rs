@@ -1,6 +1,8 @@
+pub mod analyzer;
 pub mod confirmation;
 pub mod device_manager;
 pub mod sound_generator;
 
+pub use analyzer::{AudioAnalyzer, LoudnessCompliance, LoudnessResult};
 pub use confirmation::AudioConfirmation;
-pub use sound_generator::{ensure_default_confirmation_sound, generate_duration_confirmation_sounds};
+pub use sound_generator::{duration_sound_file_name, ensure_default_confirmation_sound, generate_duration_confirmation_sounds, note_name};
@@ -64,8 +64,27 @@ pub fn ensure_default_confirmation_sound() -> anyhow::Result<std::path::PathBuf>
     Ok(sound_path)
 }
 
-/// Generates duration-specific confirmation sounds
-pub fn generate_duration_confirmation_sounds() -> anyhow::Result<std::path::PathBuf> {
+/// File name of the generated beep pattern for `duration`, inside the directory
+/// returned by `generate_duration_confirmation_sounds`
+pub fn duration_sound_file_name(duration: crate::core::ClipDuration) -> &'static str {
+    match duration {
+        crate::core::ClipDuration::Seconds15 => "duration_15s.wav",
+        crate::core::ClipDuration::Seconds30 => "duration_30s.wav",
+        crate::core::ClipDuration::Minutes1 => "duration_1m.wav",
+        crate::core::ClipDuration::Minutes2 => "duration_2m.wav",
+        crate::core::ClipDuration::Minutes5 => "duration_5m.wav",
+    }
+}
+
+/// Nearest note to `frequency` in scientific pitch notation, e.g. "A4" for 440 Hz
+pub fn note_name(frequency: f32) -> String {
+    const NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+    let midi = (69.0 + 12.0 * (frequency.max(1.0) / 440.0).log2()).round() as i32;
+    format!("{}{}", NAMES[midi.rem_euclid(12) as usize], midi.div_euclid(12) - 1)
+}
+
+/// Generates duration-specific confirmation sounds at the pitches in `config`
+pub fn generate_duration_confirmation_sounds(config: &crate::core::config::AudioConfirmationConfig) -> anyhow::Result<std::path::PathBuf> {
     let config_dir = dirs::config_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("clip-helper");
@@ -76,12 +95,10 @@ pub fn generate_duration_confirmation_sounds() -> anyhow::Result<std::path::Path
             anyhow::anyhow!("Failed to create config directory: {}", e)
         })?;
     
-    // Generate beep patterns for each duration
-    generate_beep_pattern(&config_dir.join("duration_15s.wav"), 1000.0, 1, 100, 50)?; // 1 beep
-    generate_beep_pattern(&config_dir.join("duration_30s.wav"), 1000.0, 2, 100, 50)?; // 2 beeps
-    generate_beep_pattern(&config_dir.join("duration_1m.wav"), 1000.0, 3, 100, 50)?;  // 3 beeps
-    generate_beep_pattern(&config_dir.join("duration_2m.wav"), 1000.0, 4, 100, 50)?;  // 4 beeps
-    generate_beep_pattern(&config_dir.join("duration_5m.wav"), 1000.0, 5, 100, 50)?;  // 5 beeps
+    // One beep for the shortest duration, one more for each longer one
+    for (beeps, duration) in (1..).zip(crate::core::ClipDuration::ALL) {
+        generate_beep_pattern(&config_dir.join(duration_sound_file_name(duration)), config.tone_frequency(duration), beeps, 100, 50)?;
+    }
     
     // Generate low frequency sound for unmatched clips
     generate_test_beep(&config_dir.join("unmatched_clip.wav"), 400.0, 500)?; // 400Hz for 500ms
@@ -145,3 +162,16 @@ fn generate_beep_pattern(output_path: &Path, frequency: f32, beep_count: u32, be
     log::debug!("Generated beep pattern with {} beeps at: {}", beep_count, output_path.display());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_name() {
+        assert_eq!(note_name(440.0), "A4");
+        assert_eq!(note_name(261.63), "C4");
+        assert_eq!(note_name(1000.0), "B5");
+        assert_eq!(note_name(100.0), "G2");
+    }
+}
@@ -1,7 +1,74 @@
-use chrono::{DateTime, Local, TimeZone};
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+/// Default for the shortest trim kept when one trim point is set past the other
+pub const MIN_TRIM_GAP_SECONDS: f64 = 0.1;
+
+/// Outcome of a clip's most recent export
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum ClipExportState {
+    #[default]
+    NotExported,
+    /// Waiting in or running in the export queue
+    Exporting,
+    Exported,
+    /// With FFmpeg's (or verification's) error message
+    ExportFailed(String),
+}
+
+impl ClipExportState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClipExportState::NotExported => "Not exported",
+            ClipExportState::Exporting => "Exporting…",
+            ClipExportState::Exported => "Exported",
+            ClipExportState::ExportFailed(_) => "Export failed",
+        }
+    }
+}
+
+/// Durations this much apart count as the same; ffprobe's estimate jitters a little
+const DURATION_GROWTH_EPSILON: f64 = 0.01;
+
+/// How often clips whose files looked corrupted are probed again
+pub const CORRUPTED_CLIP_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Whether a replay's file is finished, still being written by OBS, or unreadable
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum VideoWriteState {
+    #[default]
+    Complete,
+    /// The duration grew between two probes, or the file did while it couldn't be read
+    StillWriting { measured_duration: f64 },
+    /// ffprobe failed (or found less than a second of video) and the file isn't changing
+    Corrupted { reason: String },
+    /// The file is gone; only pinned clips are kept in the list like this
+    Missing,
+}
+
+impl VideoWriteState {
+    /// Classify a file from two ffprobe durations taken a moment apart and whether its size
+    /// changed in between
+    pub fn from_probes(first: Result<f64, String>, second: Result<f64, String>, size_changed: bool) -> Self {
+        match (first, second) {
+            (Ok(first), Ok(second)) if second > first + DURATION_GROWTH_EPSILON || size_changed => {
+                Self::StillWriting { measured_duration: second }
+            }
+            (Ok(_), Ok(second)) if second < 1.0 => Self::Corrupted {
+                reason: format!("Only {:.2}s of video", second),
+            },
+            (Ok(_), Ok(_)) => Self::Complete,
+            // OBS hasn't written enough for ffprobe yet
+            (first, _) if size_changed => Self::StillWriting { measured_duration: first.unwrap_or(0.0) },
+            (Err(reason), _) | (Ok(_), Err(reason)) => Self::Corrupted { reason },
+        }
+    }
+}
+
+/// Tag that protects a clip from automatic cleanup
+pub const RETENTION_KEEP_TAG: &str = "keep";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Clip {
     pub id: String,
@@ -15,17 +82,212 @@ pub struct Clip {
     pub audio_tracks: Vec<AudioTrack>,
     pub is_deleted: bool,
     pub is_trimmed: bool,
+    #[serde(default)]
+    pub export_state: ClipExportState,
+    /// SHA-256 of the first and last 1 MB of the file, used to spot duplicates
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// Named chapter markers, kept sorted by time
+    #[serde(default)]
+    pub markers: Vec<Marker>,
+    /// Listed in the "Pinned" group and kept by rescans and automatic cleanup, however old.
+    /// Pinned clips are never flagged as near-duplicates either.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Category color picked from the clip list's context menu, tinting the clip's row
+    #[serde(default)]
+    pub color: Option<[u8; 3]>,
+    /// Set once the user moves a trim point by hand, so automatic
+    /// target-duration trims (and their padding) leave it alone
+    #[serde(default)]
+    pub trim_adjusted: bool,
+    /// Export here instead of the global trimmed directory
+    #[serde(default)]
+    pub export_directory_override: Option<PathBuf>,
+    /// EBU R128 integrated loudness of `loudness_range`
+    #[serde(default)]
+    pub loudness_lufs: Option<f64>,
+    #[serde(default)]
+    pub loudness_true_peak_dbfs: Option<f64>,
+    /// Trim range the loudness was measured over
+    #[serde(default)]
+    pub loudness_range: Option<(f64, f64)>,
+    /// Frame picked with "Set as thumbnail", shown in the list and preview
+    #[serde(default)]
+    pub poster_timestamp: Option<f64>,
+    /// Added by dropping it onto the window rather than found in the watched directory,
+    /// so rescans keep it
+    #[serde(default)]
+    pub imported: bool,
+    /// Free-form note on why the clip matters
+    #[serde(default)]
+    pub notes: String,
+    /// Labels for finding clips later, kept trimmed and non-empty by `set_tags`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether `timestamp` came from the file name or the less reliable modification time
+    #[serde(default)]
+    pub timestamp_source: crate::core::TimestampSource,
+    /// Where the black frames at the start of the replay end (0.0 if there are none),
+    /// None until checked
+    #[serde(default)]
+    pub leading_black_end: Option<f64>,
+    /// First frame with a big scene change, e.g. where a loading screen ends. Equal to 0.0
+    /// if nothing in the searched window changed; None until checked.
+    #[serde(default)]
+    pub motion_start: Option<f64>,
+    /// Re-checked every run, so not saved
+    #[serde(skip)]
+    pub write_state: VideoWriteState,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Extra footage kept before target-duration trims, in seconds. There's no post-roll:
+/// the replay ends when it's saved, so nothing can be kept after a trim that ends there.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrimPadding {
+    pub pre_roll: f64,
+}
+
+/// Grid trim points set by hand are rounded to, for clips with clean durations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TrimSnap {
+    #[default]
+    Off,
+    HalfSecond,
+    WholeSecond,
+}
+
+impl TrimSnap {
+    pub const ALL: [TrimSnap; 3] = [Self::Off, Self::HalfSecond, Self::WholeSecond];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::HalfSecond => "0.5s",
+            Self::WholeSecond => "1s",
+        }
+    }
+
+    fn step(&self) -> Option<f64> {
+        match self {
+            Self::Off => None,
+            Self::HalfSecond => Some(0.5),
+            Self::WholeSecond => Some(1.0),
+        }
+    }
+
+    /// Nearest grid point to `time`
+    pub fn round(&self, time: f64) -> f64 {
+        self.step().map_or(time, |step| (time / step).round() * step)
+    }
+
+    /// Latest grid point at or before `time`
+    pub fn floor(&self, time: f64) -> f64 {
+        self.step().map_or(time, |step| (time / step).floor() * step)
+    }
+
+    /// Earliest grid point at or after `time`
+    pub fn ceil(&self, time: f64) -> f64 {
+        self.step().map_or(time, |step| (time / step).ceil() * step)
+    }
+}
+
+/// How trim points set by hand are constrained
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrimRules {
+    /// Shortest trim kept when one trim point is set past the other
+    pub min_gap: f64,
+    pub snap: TrimSnap,
+}
+
+impl Default for TrimRules {
+    fn default() -> Self {
+        Self {
+            min_gap: MIN_TRIM_GAP_SECONDS,
+            snap: TrimSnap::Off,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Marker {
+    pub time: f64, // seconds from start
+    pub label: String,
+}
+
+/// How a track's channels are folded down before it's mixed, in exports and in preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SurroundMode {
+    /// Front left/right of a surround track as stereo
+    Stereo,
+    /// Front left only, on both output channels
+    SurroundLeft,
+    /// Front right only, on both output channels
+    SurroundRight,
+    /// Center channel (usually dialogue), on both output channels
+    Center,
+    /// The track as recorded
+    #[default]
+    Passthrough,
+}
+
+impl SurroundMode {
+    pub const ALL: [SurroundMode; 5] = [
+        Self::Stereo,
+        Self::SurroundLeft,
+        Self::SurroundRight,
+        Self::Center,
+        Self::Passthrough,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Stereo => "Stereo",
+            Self::SurroundLeft => "Surround left",
+            Self::SurroundRight => "Surround right",
+            Self::Center => "Center",
+            Self::Passthrough => "Passthrough",
+        }
+    }
+
+    /// FFmpeg `pan` filter for this mode, None for `Passthrough`
+    pub fn pan_filter(&self) -> Option<&'static str> {
+        match self {
+            Self::Stereo => Some("pan=stereo|c0=FL|c1=FR"),
+            Self::SurroundLeft => Some("pan=stereo|c0=FL|c1=FL"),
+            Self::SurroundRight => Some("pan=stereo|c0=FR|c1=FR"),
+            Self::Center => Some("pan=stereo|c0=FC|c1=FC"),
+            Self::Passthrough => None,
+        }
+    }
+}
+
+/// Older versions saved `surround_mode` as a bool, where true took the front left/right
+fn deserialize_surround_mode<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<SurroundMode, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Legacy(bool),
+        Mode(SurroundMode),
+    }
+
+    Ok(match Stored::deserialize(deserializer)? {
+        Stored::Legacy(true) => SurroundMode::Stereo,
+        Stored::Legacy(false) => SurroundMode::Passthrough,
+        Stored::Mode(mode) => mode,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AudioTrack {
     pub index: usize,
     pub enabled: bool,
-    pub surround_mode: bool, // true = surround left/right, false = normal
+    #[serde(default, deserialize_with = "deserialize_surround_mode")]
+    pub surround_mode: SurroundMode,
     pub name: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ClipDuration {
     Seconds15 = 15,
     Seconds30 = 30,
@@ -34,6 +296,26 @@ pub enum ClipDuration {
     Minutes5 = 300,
 }
 
+impl ClipDuration {
+    pub const ALL: [ClipDuration; 5] = [
+        ClipDuration::Seconds15,
+        ClipDuration::Seconds30,
+        ClipDuration::Minutes1,
+        ClipDuration::Minutes2,
+        ClipDuration::Minutes5,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClipDuration::Seconds15 => "15s",
+            ClipDuration::Seconds30 => "30s",
+            ClipDuration::Minutes1 => "1m",
+            ClipDuration::Minutes2 => "2m",
+            ClipDuration::Minutes5 => "5m",
+        }
+    }
+}
+
 impl Clip {
     pub fn new(file: PathBuf, duration: ClipDuration) -> anyhow::Result<Self> {
         let timestamp = Self::extract_timestamp_from_filename(&file)?;
@@ -51,13 +333,54 @@ impl Clip {
             audio_tracks: Vec::new(),
             is_deleted: false,
             is_trimmed: false,
+            export_state: ClipExportState::NotExported,
+            fingerprint: None,
+            markers: Vec::new(),
+            pinned: false,
+            color: None,
+            trim_adjusted: false,
+            export_directory_override: None,
+            loudness_lufs: None,
+            loudness_true_peak_dbfs: None,
+            loudness_range: None,
+            poster_timestamp: None,
+            imported: false,
+            notes: String::new(),
+            tags: Vec::new(),
+            timestamp_source: crate::core::TimestampSource::Filename,
+            leading_black_end: None,
+            motion_start: None,
+            write_state: VideoWriteState::Complete,
         })
     }
 
     pub fn new_without_target(file: PathBuf) -> anyhow::Result<Self> {
         let timestamp = Self::extract_timestamp_from_filename(&file)?;
-        
-        Ok(Clip {
+        Ok(Self::without_target_at(file, timestamp))
+    }
+
+    /// Clip for a video that isn't necessarily an OBS replay, e.g. one dropped onto the
+    /// window. Uses the file's modification time when the name has no replay timestamp.
+    pub fn new_imported(file: PathBuf) -> anyhow::Result<Self> {
+        let (timestamp, timestamp_source) = match Self::extract_timestamp_from_filename(&file) {
+            Ok(timestamp) => (timestamp, crate::core::TimestampSource::Filename),
+            Err(_) => {
+                let modified = std::fs::metadata(&file)
+                    .and_then(|metadata| metadata.modified())
+                    .map(DateTime::<Local>::from)
+                    .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+                (modified, crate::core::TimestampSource::Mtime)
+            }
+        };
+        let mut clip = Self::without_target_at(file, timestamp);
+        clip.imported = true;
+        clip.timestamp_source = timestamp_source;
+        Ok(clip)
+    }
+
+    /// Clip without a target duration, dated `timestamp` whatever its name says
+    pub fn without_target_at(file: PathBuf, timestamp: DateTime<Local>) -> Self {
+        Clip {
             id: uuid::Uuid::new_v4().to_string(),
             original_file: file,
             timestamp,
@@ -69,30 +392,78 @@ impl Clip {
             audio_tracks: Vec::new(),
             is_deleted: false,
             is_trimmed: false,
-        })
+            export_state: ClipExportState::NotExported,
+            fingerprint: None,
+            markers: Vec::new(),
+            pinned: false,
+            color: None,
+            trim_adjusted: false,
+            export_directory_override: None,
+            loudness_lufs: None,
+            loudness_true_peak_dbfs: None,
+            loudness_range: None,
+            poster_timestamp: None,
+            imported: false,
+            notes: String::new(),
+            tags: Vec::new(),
+            timestamp_source: crate::core::TimestampSource::Filename,
+            leading_black_end: None,
+            motion_start: None,
+            write_state: VideoWriteState::Complete,
+        }
     }
 
+    /// OBS replay timestamp in the file name, see `parse_obs_timestamp` for the patterns
     pub fn extract_timestamp_from_filename(file: &PathBuf) -> anyhow::Result<DateTime<Local>> {
-        let filename = file.file_stem()
+        let filename = file.file_name()
             .and_then(|s| s.to_str())
             .ok_or_else(|| anyhow::anyhow!("Invalid filename"))?;
-        
-        // Parse "Replay 2025-08-17 21-52-01" format
-        if let Some(date_part) = filename.strip_prefix("Replay ") {
-            // Split into date and time parts
-            let parts: Vec<&str> = date_part.split(' ').collect();
-            if parts.len() == 2 {
-                let date_part = parts[0]; // "2025-08-17"
-                let time_part = parts[1].replace('-', ":"); // "21-52-01" -> "21:52:01"
-                let datetime_str = format!("{} {}", date_part, time_part);
-                let dt = chrono::NaiveDateTime::parse_from_str(&datetime_str, "%Y-%m-%d %H:%M:%S")?;
-                Ok(Local.from_local_datetime(&dt).unwrap())
-            } else {
-                Err(anyhow::anyhow!("Filename doesn't match expected date format"))
+        crate::core::parse_obs_timestamp(filename)
+            .ok_or_else(|| anyhow::anyhow!("Filename doesn't match expected format"))
+    }
+
+    /// Export state to keep after loading clips.json. Exports don't survive a restart,
+    /// and files from before export states only know whether the clip was trimmed.
+    pub fn restored_export_state(&self) -> ClipExportState {
+        match &self.export_state {
+            ClipExportState::Exporting | ClipExportState::NotExported if self.is_trimmed => ClipExportState::Exported,
+            ClipExportState::Exporting => ClipExportState::NotExported,
+            state => state.clone(),
+        }
+    }
+
+    /// Set tags from comma-separated text, dropping whitespace, empty tags and
+    /// case-insensitive duplicates
+    pub fn set_tags(&mut self, text: &str) {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+            if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
             }
-        } else {
-            Err(anyhow::anyhow!("Filename doesn't match expected format"))
         }
+        self.tags = tags;
+    }
+
+    /// Whether every whitespace-separated term of `query` appears (ignoring case) in
+    /// the file name, output name, notes or tags. An empty query matches everything.
+    pub fn matches_search(&self, query: &str) -> bool {
+        let haystack = format!(
+            "{}\n{}\n{}\n{}",
+            self.original_file.file_name().unwrap_or_default().to_string_lossy(),
+            self.name.as_deref().unwrap_or_default(),
+            self.notes,
+            self.tags.join("\n"),
+        ).to_lowercase();
+        query.split_whitespace().all(|term| haystack.contains(&term.to_lowercase()))
+    }
+
+    /// Clips automatic cleanup never removes: pinned, imported, exported, annotated or tagged "keep"
+    pub fn is_retention_exempt(&self) -> bool {
+        self.pinned
+            || self.imported
+            || self.is_trimmed
+            || !self.notes.trim().is_empty()
+            || self.tags.iter().any(|tag| tag.eq_ignore_ascii_case(RETENTION_KEEP_TAG))
     }
 
     pub fn get_output_filename(&self) -> String {
@@ -145,8 +516,13 @@ impl Clip {
     }
 
     /// Checks if this clip needs video info to be loaded/updated
-    /// Returns true if video info is missing or if the file might still be being written
+    /// Returns true if video info is missing or if the file might still be being written,
+    /// false for corrupted files
     pub fn needs_video_info_update(&self) -> bool {
+        // Corrupted files are retried on a slower schedule, missing ones not at all
+        if matches!(self.write_state, VideoWriteState::Corrupted { .. } | VideoWriteState::Missing) {
+            return false;
+        }
         match self.video_length_seconds {
             None => true, // No video info loaded yet
             Some(length) => length < 1.0, // Invalid length, might still be writing
@@ -157,25 +533,166 @@ impl Clip {
     /// This is called when a hotkey assigns a specific duration to the clip
     /// The trim will be set to capture the LAST X seconds of the video
     pub fn set_target_duration(&mut self, duration: ClipDuration) {
+        self.set_target_duration_with_padding(duration, TrimPadding::default());
+    }
+
+    /// Set the target duration and trim to the last X seconds, widened by `padding`
+    pub fn set_target_duration_with_padding(&mut self, duration: ClipDuration, padding: TrimPadding) {
         self.target_duration_seconds = duration as u32;
         
         // If we have video length info, set trim to capture last X seconds
         // Otherwise, we'll update the trim when video info becomes available
         if let Some(video_length) = self.video_length_seconds {
             if video_length >= 1.0 {
-                let target_seconds = self.target_duration_seconds as f64;
-                // Trim to last X seconds: start = video_length - target, end = video_length
-                self.trim_start = (video_length - target_seconds).max(0.0);
-                self.trim_end = video_length;
+                self.apply_target_trim(video_length, padding);
             }
         }
     }
 
+    /// Trim to the last X seconds of a `video_length` long video, unless the user
+    /// has already adjusted the trim points by hand
+    pub fn apply_target_trim(&mut self, video_length: f64, padding: TrimPadding) {
+        if self.trim_adjusted {
+            return;
+        }
+        let (start, end) = Self::target_trim_bounds(video_length, self.target_duration_seconds as f64, padding);
+        self.trim_start = start;
+        self.trim_end = end;
+    }
+
+    /// Move the trim start past the leading black frames if it's inside them. Trims the user
+    /// set by hand are never moved. Returns whether the trim start changed.
+    pub fn skip_leading_black(&mut self) -> bool {
+        let Some(black_end) = self.leading_black_end else {
+            return false;
+        };
+        if self.trim_adjusted || black_end <= self.trim_start || black_end + MIN_TRIM_GAP_SECONDS >= self.trim_end {
+            return false;
+        }
+        self.trim_start = black_end;
+        true
+    }
+
+    /// Bounds covering the last `target_seconds` of the video with pre-roll before them,
+    /// clamped to [0, video_length]
+    pub fn target_trim_bounds(video_length: f64, target_seconds: f64, padding: TrimPadding) -> (f64, f64) {
+        let start = video_length - target_seconds - padding.pre_roll.max(0.0);
+        (start.clamp(0.0, video_length), video_length.max(0.0))
+    }
+
+    /// Directory this clip's exports go to: the override if set, otherwise `default`
+    pub fn export_directory<'a>(&'a self, default: &'a std::path::Path) -> &'a std::path::Path {
+        self.export_directory_override.as_deref().unwrap_or(default)
+    }
+
+    /// The chosen poster frame, unless it no longer fits in the video (e.g. the file was
+    /// re-recorded) or the length isn't known yet, in which case thumbnails pick automatically
+    pub fn effective_poster_timestamp(&self) -> Option<f64> {
+        let length = self.video_length_seconds?;
+        self.poster_timestamp.filter(|&timestamp| timestamp >= 0.0 && timestamp <= length)
+    }
+
+    /// Whether the stored loudness was measured over the current trim range
+    pub fn loudness_is_current(&self) -> bool {
+        self.loudness_lufs.is_some() && self.loudness_range == Some((self.trim_start, self.trim_end))
+    }
+
+    /// Set the trim start to `position`, snapped to the grid of `rules`. A position at or past
+    /// the trim end puts the start `rules.min_gap` before the end instead and returns false.
+    ///
+    /// The trim end is snapped as well unless that would leave less than the minimum gap,
+    /// in which case only the start (the point being set) is snapped.
+    pub fn set_trim_start_at(&mut self, position: f64, rules: TrimRules) -> bool {
+        self.trim_adjusted = true;
+        let latest_start = self.trim_end - rules.min_gap;
+        let fits = position < self.trim_end;
+        let snapped = rules.snap.round(position);
+        self.trim_start = if fits && snapped <= latest_start {
+            snapped
+        } else if rules.snap.floor(latest_start) >= 0.0 {
+            rules.snap.floor(latest_start)
+        } else {
+            latest_start
+        }
+        .max(0.0);
+
+        let max_end = self.video_length_seconds.unwrap_or(f64::MAX);
+        let snapped_end = rules.snap.round(self.trim_end).min(max_end);
+        if snapped_end - self.trim_start >= rules.min_gap {
+            self.trim_end = snapped_end;
+        }
+        fits
+    }
+
+    /// Set the trim end to `position`, snapped to the grid of `rules`, keeping it
+    /// `rules.min_gap` after the start (returning false) when the position is at or before it.
+    ///
+    /// The trim start is snapped as well unless that would leave less than the minimum gap.
+    pub fn set_trim_end_at(&mut self, position: f64, rules: TrimRules) -> bool {
+        self.trim_adjusted = true;
+        let max_end = self.video_length_seconds.unwrap_or(f64::MAX);
+        let earliest_end = self.trim_start + rules.min_gap;
+        let fits = position > self.trim_start;
+        let snapped = rules.snap.round(position).min(max_end);
+        self.trim_end = if fits && snapped >= earliest_end {
+            snapped
+        } else if rules.snap.ceil(earliest_end) <= max_end {
+            rules.snap.ceil(earliest_end)
+        } else {
+            earliest_end.min(max_end)
+        };
+
+        let snapped_start = rules.snap.round(self.trim_start).max(0.0);
+        if self.trim_end - snapped_start >= rules.min_gap {
+            self.trim_start = snapped_start;
+        }
+        fits
+    }
+
+    /// Trim to the target duration ending at `position`, the opposite of the usual
+    /// "last X seconds of the file". Starts at 0 when there isn't enough footage before
+    /// `position`. Returns the resulting trim length.
+    pub fn trim_target_ending_at(&mut self, position: f64) -> f64 {
+        let end = position.clamp(0.0, self.video_length_seconds.unwrap_or(f64::MAX));
+        self.trim_start = (end - self.target_duration_seconds as f64).max(0.0);
+        self.trim_end = end;
+        self.trim_adjusted = true;
+        self.trim_end - self.trim_start
+    }
+
+    /// Add a marker at `time` (clamped to the video) and keep markers sorted
+    pub fn add_marker(&mut self, time: f64, label: String) {
+        self.markers.push(Marker { time, label });
+        self.normalize_markers();
+    }
+
+    /// Clamp markers to [0, video length] and sort them by time
+    pub fn normalize_markers(&mut self) {
+        let max_time = self.video_length_seconds.unwrap_or(f64::MAX);
+        for marker in &mut self.markers {
+            marker.time = marker.time.clamp(0.0, max_time);
+        }
+        self.markers.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Segments between each pair of adjacent markers as (start, end, label of the first marker).
+    /// Zero-length segments (markers on top of each other) are skipped.
+    pub fn marker_segments(&self) -> Vec<(f64, f64, String)> {
+        const MIN_SEGMENT_SECONDS: f64 = 0.05;
+
+        self.markers
+            .windows(2)
+            .filter(|pair| pair[1].time - pair[0].time >= MIN_SEGMENT_SECONDS)
+            .map(|pair| (pair[0].time, pair[1].time, pair[0].label.clone()))
+            .collect()
+    }
+
     /// Attempts to populate video information from the file
     /// Returns Ok(true) if video info was successfully loaded and is valid
-    /// Returns Ok(false) if file exists but video info is invalid (still being written)
+    /// Returns Ok(false) if file exists but video info is invalid (still being written or corrupted,
+    /// see `write_state`)
     /// Returns Err if file doesn't exist or other error occurred
-    pub fn populate_video_info(&mut self) -> anyhow::Result<bool> {
+    pub fn populate_video_info(&mut self, padding: TrimPadding) -> anyhow::Result<bool> {
         use crate::video::VideoProcessor;
         
         // Check if file exists first
@@ -183,17 +700,17 @@ impl Clip {
             return Err(anyhow::anyhow!("File does not exist: {:?}", self.original_file));
         }
         
-        match VideoProcessor::get_video_info(&self.original_file) {
-            Ok(video_info) => {
+        let (info, write_state) = VideoProcessor::probe_write_state(&self.original_file);
+        self.write_state = write_state;
+        match info.filter(|_| self.write_state == VideoWriteState::Complete) {
+            Some(video_info) => {
                 self.video_length_seconds = Some(video_info.duration);
                 self.audio_tracks = video_info.audio_tracks;
                 
                 // Set trim points based on whether we have a target duration
                 if self.has_target_duration() {
                     // Trim to last X seconds of the video
-                    let target_seconds = self.target_duration_seconds as f64;
-                    self.trim_start = (video_info.duration - target_seconds).max(0.0);
-                    self.trim_end = video_info.duration;
+                    self.apply_target_trim(video_info.duration, padding);
                 } else if self.trim_end == 0.0 {
                     // No target duration set, use full video length
                     self.trim_start = 0.0;
@@ -203,12 +720,11 @@ impl Clip {
                 // Return whether the video is valid (duration >= 1 second)
                 Ok(video_info.duration >= 1.0)
             }
-            Err(e) => {
-                // If we can't get video info, the file might still be being written
-                // Set duration to 0 to indicate it's invalid but keep trying
+            None => {
+                // Set duration to 0 to indicate it's invalid; still-written files are retried
                 self.video_length_seconds = Some(0.0);
-                log::debug!("Video info not available yet for {}: {}", 
-                    self.get_output_filename(), e);
+                log::debug!("Video info not available yet for {}: {:?}",
+                    self.get_output_filename(), self.write_state);
                 Ok(false)
             }
         }
@@ -284,16 +800,30 @@ mod tests {
         let track = AudioTrack {
             index: 0,
             enabled: true,
-            surround_mode: false,
+            surround_mode: SurroundMode::Passthrough,
             name: "Desktop Audio".to_string(),
         };
         
         assert_eq!(track.index, 0);
         assert!(track.enabled);
-        assert!(!track.surround_mode);
+        assert_eq!(track.surround_mode, SurroundMode::Passthrough);
         assert_eq!(track.name, "Desktop Audio");
     }
 
+    #[test]
+    fn test_surround_mode_reads_legacy_bool() {
+        let legacy: AudioTrack = serde_json::from_str(
+            r#"{"index": 1, "enabled": true, "surround_mode": true, "name": "Game"}"#
+        ).unwrap();
+        assert_eq!(legacy.surround_mode, SurroundMode::Stereo);
+
+        let track = AudioTrack { surround_mode: SurroundMode::Center, ..legacy };
+        let roundtrip: AudioTrack = serde_json::from_str(&serde_json::to_string(&track).unwrap()).unwrap();
+        assert_eq!(roundtrip.surround_mode, SurroundMode::Center);
+        assert_eq!(SurroundMode::SurroundLeft.pan_filter(), Some("pan=stereo|c0=FL|c1=FL"));
+        assert_eq!(SurroundMode::Passthrough.pan_filter(), None);
+    }
+
     #[test]
     fn test_clip_without_target_duration() {
         let file_path = PathBuf::from("Replay 2025-08-17 21-52-01.mkv");
@@ -435,4 +965,270 @@ mod tests {
         
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_clip_without_fingerprint_deserializes() {
+        let clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        let mut value = serde_json::to_value(&clip).unwrap();
+        value.as_object_mut().unwrap().remove("fingerprint");
+
+        let loaded: Clip = serde_json::from_value(value).expect("Old clips.json entries should still load");
+        assert!(loaded.fingerprint.is_none());
+    }
+
+    #[test]
+    fn test_markers_sorted_and_clamped() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.video_length_seconds = Some(60.0);
+
+        clip.add_marker(30.0, "b".to_string());
+        clip.add_marker(-5.0, "a".to_string());
+        clip.add_marker(90.0, "c".to_string());
+
+        let times: Vec<f64> = clip.markers.iter().map(|m| m.time).collect();
+        assert_eq!(times, vec![0.0, 30.0, 60.0]);
+        assert_eq!(clip.markers[0].label, "a");
+    }
+
+    #[test]
+    fn test_marker_segments_skip_zero_length() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.video_length_seconds = Some(60.0);
+
+        clip.add_marker(10.0, "first".to_string());
+        clip.add_marker(10.0, "duplicate".to_string());
+        clip.add_marker(25.0, "second".to_string());
+        clip.add_marker(40.0, "end".to_string());
+
+        let segments = clip.marker_segments();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].0, 10.0);
+        assert_eq!(segments[0].1, 25.0);
+        assert_eq!(segments[1], (25.0, 40.0, "second".to_string()));
+    }
+
+    #[test]
+    fn test_target_trim_padding_is_clamped() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.video_length_seconds = Some(60.0);
+
+        let padding = TrimPadding { pre_roll: 3.0 };
+        clip.set_target_duration_with_padding(ClipDuration::Seconds30, padding);
+        assert_eq!(clip.trim_start, 27.0);
+        assert_eq!(clip.trim_end, 60.0);
+
+        // Pre-roll can't push the start before the beginning of the video
+        clip.set_target_duration_with_padding(ClipDuration::Minutes1, padding);
+        assert_eq!(clip.trim_start, 0.0);
+        assert_eq!(clip.trim_end, 60.0);
+    }
+
+    #[test]
+    fn test_target_trim_keeps_manual_adjustments() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.video_length_seconds = Some(60.0);
+        clip.trim_start = 12.0;
+        clip.trim_end = 20.0;
+        clip.trim_adjusted = true;
+
+        clip.set_target_duration_with_padding(ClipDuration::Seconds15, TrimPadding { pre_roll: 5.0 });
+        assert_eq!(clip.target_duration_seconds, 15);
+        assert_eq!(clip.trim_start, 12.0);
+        assert_eq!(clip.trim_end, 20.0);
+    }
+
+    #[test]
+    fn test_skip_leading_black() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.trim_end = 60.0;
+        assert!(!clip.skip_leading_black());
+
+        clip.leading_black_end = Some(1.5);
+        assert!(clip.skip_leading_black());
+        assert_eq!(clip.trim_start, 1.5);
+
+        // A trim start already past the black stays where it is
+        clip.trim_start = 10.0;
+        assert!(!clip.skip_leading_black());
+        assert_eq!(clip.trim_start, 10.0);
+
+        // So does one the user set by hand
+        clip.set_trim_start_at(0.5, TrimRules::default());
+        assert!(!clip.skip_leading_black());
+        assert_eq!(clip.trim_start, 0.5);
+    }
+
+    #[test]
+    fn test_video_write_state_from_probes() {
+        assert_eq!(VideoWriteState::from_probes(Ok(30.0), Ok(30.0), false), VideoWriteState::Complete);
+        assert_eq!(VideoWriteState::from_probes(Ok(30.0), Ok(30.5), false), VideoWriteState::StillWriting { measured_duration: 30.5 });
+        assert_eq!(VideoWriteState::from_probes(Ok(30.0), Ok(30.0), true), VideoWriteState::StillWriting { measured_duration: 30.0 });
+        // Unreadable while growing is still being written, unreadable and unchanged is broken
+        assert_eq!(VideoWriteState::from_probes(Err("moov atom not found".to_string()), Err("moov atom not found".to_string()), true),
+            VideoWriteState::StillWriting { measured_duration: 0.0 });
+        assert_eq!(VideoWriteState::from_probes(Err("moov atom not found".to_string()), Err("x".to_string()), false),
+            VideoWriteState::Corrupted { reason: "moov atom not found".to_string() });
+        assert!(matches!(VideoWriteState::from_probes(Ok(0.4), Ok(0.4), false), VideoWriteState::Corrupted { .. }));
+
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.video_length_seconds = Some(0.0);
+        assert!(clip.needs_video_info_update());
+        clip.write_state = VideoWriteState::Corrupted { reason: "broken".to_string() };
+        assert!(!clip.needs_video_info_update());
+    }
+
+    #[test]
+    fn test_export_directory_override() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        let trimmed = PathBuf::from("output/trimmed");
+        assert_eq!(clip.export_directory(&trimmed), trimmed.as_path());
+
+        clip.export_directory_override = Some(PathBuf::from("highlights"));
+        assert_eq!(clip.export_directory(&trimmed), PathBuf::from("highlights").as_path());
+
+        // Older clips.json files don't have the field
+        let mut json = serde_json::to_value(&clip).unwrap();
+        json.as_object_mut().unwrap().remove("export_directory_override");
+        let parsed: Clip = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.export_directory_override, None);
+    }
+
+    #[test]
+    fn test_poster_timestamp_falls_back_when_out_of_range() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.poster_timestamp = Some(42.0);
+        assert_eq!(clip.effective_poster_timestamp(), None);
+
+        clip.video_length_seconds = Some(60.0);
+        assert_eq!(clip.effective_poster_timestamp(), Some(42.0));
+
+        // Re-recorded shorter file
+        clip.video_length_seconds = Some(30.0);
+        assert_eq!(clip.effective_poster_timestamp(), None);
+    }
+
+    #[test]
+    fn test_set_trim_points_keep_order() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.video_length_seconds = Some(60.0);
+        clip.trim_start = 10.0;
+        clip.trim_end = 40.0;
+
+        let rules = TrimRules::default();
+        assert!(clip.set_trim_start_at(12.4, rules));
+        assert_eq!(clip.trim_start, 12.4);
+        assert!(clip.trim_adjusted);
+
+        assert!(!clip.set_trim_start_at(45.0, rules));
+        assert!((clip.trim_start - 39.9).abs() < 1e-9);
+
+        assert!(!clip.set_trim_end_at(5.0, rules));
+        assert!((clip.trim_end - 40.0).abs() < 1e-9);
+
+        assert!(clip.set_trim_end_at(90.0, rules));
+        assert_eq!(clip.trim_end, 60.0);
+
+        // A larger minimum gap
+        let rules = TrimRules { min_gap: 2.0, snap: TrimSnap::Off };
+        assert!(!clip.set_trim_end_at(30.0, rules));
+        assert!((clip.trim_end - 41.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_set_trim_points_snap() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.video_length_seconds = Some(60.0);
+        clip.trim_start = 10.3;
+        clip.trim_end = 40.2;
+
+        // Both ends land on the grid
+        let rules = TrimRules { min_gap: 0.1, snap: TrimSnap::HalfSecond };
+        assert!(clip.set_trim_start_at(12.4, rules));
+        assert_eq!((clip.trim_start, clip.trim_end), (12.5, 40.0));
+
+        let rules = TrimRules { min_gap: 0.1, snap: TrimSnap::WholeSecond };
+        assert!(clip.set_trim_end_at(30.6, rules));
+        assert_eq!((clip.trim_start, clip.trim_end), (13.0, 31.0));
+
+        // Snapping the start to 11 would leave less than the gap, so only the end is snapped
+        clip.trim_start = 10.6;
+        let rules = TrimRules { min_gap: 1.2, snap: TrimSnap::WholeSecond };
+        assert!(clip.set_trim_end_at(11.9, rules));
+        assert_eq!((clip.trim_start, clip.trim_end), (10.6, 12.0));
+
+        // Past the other end: the latest grid point the gap still allows
+        assert!(!clip.set_trim_start_at(20.0, rules));
+        assert_eq!((clip.trim_start, clip.trim_end), (10.0, 12.0));
+    }
+
+    #[test]
+    fn test_trim_target_ending_at() {
+        let mut clip = Clip::new(PathBuf::from("Replay 2025-08-17 21-52-01.mkv"), ClipDuration::Seconds30).unwrap();
+        clip.video_length_seconds = Some(120.0);
+
+        assert_eq!(clip.trim_target_ending_at(65.0), 30.0);
+        assert_eq!((clip.trim_start, clip.trim_end), (35.0, 65.0));
+        assert!(clip.trim_adjusted);
+
+        // Not enough footage before the playhead
+        assert_eq!(clip.trim_target_ending_at(12.0), 12.0);
+        assert_eq!((clip.trim_start, clip.trim_end), (0.0, 12.0));
+    }
+
+    #[test]
+    fn test_new_imported_accepts_any_file_name() {
+        let path = std::env::temp_dir().join(format!("clip-helper-imported-{}.mp4", std::process::id()));
+        std::fs::write(&path, b"").expect("Failed to write test file");
+
+        assert!(Clip::new_without_target(path.clone()).is_err());
+        let clip = Clip::new_imported(path.clone()).expect("Failed to import");
+        assert!(clip.imported);
+        assert!((Local::now() - clip.timestamp).num_seconds().abs() < 60);
+
+        // Replay names still use the timestamp in the name
+        let replay = Clip::new_imported(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        assert_eq!(replay.timestamp.format("%H:%M:%S").to_string(), "21:52:01");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_restored_export_state() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.export_state = ClipExportState::Exporting;
+        assert_eq!(clip.restored_export_state(), ClipExportState::NotExported);
+
+        // clips.json from before export states
+        clip.is_trimmed = true;
+        clip.export_state = ClipExportState::NotExported;
+        assert_eq!(clip.restored_export_state(), ClipExportState::Exported);
+
+        clip.export_state = ClipExportState::ExportFailed("Output file exists".to_string());
+        assert_eq!(clip.restored_export_state(), clip.export_state);
+    }
+
+    #[test]
+    fn test_tags_and_search() {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        clip.set_tags(" clutch, , Bug repro,CLUTCH ,");
+        assert_eq!(clip.tags, vec!["clutch", "Bug repro"]);
+        clip.notes = "Insane 1v4 on Mirage".to_string();
+
+        assert!(clip.matches_search(""));
+        assert!(clip.matches_search("bug mirage"));
+        assert!(clip.matches_search("2025-08-17"));
+        assert!(!clip.matches_search("clutch inferno"));
+    }
+
+    #[test]
+    fn test_clip_without_notes_and_tags_deserializes() {
+        let clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")).unwrap();
+        let mut json: serde_json::Value = serde_json::to_value(&clip).unwrap();
+        json.as_object_mut().unwrap().remove("notes");
+        json.as_object_mut().unwrap().remove("tags");
+
+        let restored: Clip = serde_json::from_value(json).expect("Old clips.json entries must still load");
+        assert!(restored.notes.is_empty());
+        assert!(restored.tags.is_empty());
+    }
 }
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::core::{AudioTrack, Clip};
+
+/// Export details written next to a trimmed file as `<output_stem>.json`.
+/// Lets clip state be recovered when clips.json doesn't know about a replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipMetadata {
+    pub original_file: PathBuf,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub trim_start: f64,
+    pub trim_end: f64,
+    pub export_duration: f64,
+    /// Filled in when the sidecar is written
+    #[serde(default)]
+    pub exported_at: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    pub audio_tracks: Vec<AudioTrack>,
+    pub preset_name: String,
+    #[serde(default)]
+    pub ffmpeg_version: Option<String>,
+}
+
+impl ClipMetadata {
+    pub fn from_clip(clip: &Clip, preset_name: &str) -> Self {
+        Self {
+            original_file: clip.original_file.clone(),
+            name: clip.name.clone(),
+            trim_start: clip.trim_start,
+            trim_end: clip.trim_end,
+            export_duration: clip.trim_end - clip.trim_start,
+            exported_at: None,
+            tags: clip.tags.clone(),
+            notes: Some(clip.notes.trim()).filter(|notes| !notes.is_empty()).map(str::to_string),
+            audio_tracks: clip.audio_tracks.clone(),
+            preset_name: preset_name.to_string(),
+            ffmpeg_version: None,
+        }
+    }
+
+    pub fn sidecar_path(output_path: &Path) -> PathBuf {
+        output_path.with_extension("json")
+    }
+
+    pub fn write_sidecar(&self, output_path: &Path) -> anyhow::Result<PathBuf> {
+        let sidecar_path = Self::sidecar_path(output_path);
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&sidecar_path, content)?;
+        Ok(sidecar_path)
+    }
+
+    /// Read every sidecar in `directory`, skipping JSON files that aren't sidecars
+    pub fn load_sidecars(directory: &Path) -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")))
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(&path).ok()?;
+                match serde_json::from_str::<Self>(&content) {
+                    Ok(metadata) => Some(metadata),
+                    Err(e) => {
+                        log::debug!("Skipping {} (not a clip sidecar: {})", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Restore trim range, name, tags, notes and audio layout onto a clip that has no saved state
+    pub fn apply_to(&self, clip: &mut Clip) {
+        clip.name = self.name.clone();
+        clip.tags = self.tags.clone();
+        clip.notes = self.notes.clone().unwrap_or_default();
+        clip.trim_start = self.trim_start;
+        clip.trim_end = self.trim_end;
+        if !self.audio_tracks.is_empty() {
+            clip.audio_tracks = self.audio_tracks.clone();
+        }
+        clip.is_trimmed = true;
+        clip.export_state = crate::core::ClipExportState::Exported;
+        clip.trim_adjusted = true;
+    }
+
+    /// Whether this sidecar was exported from `file`. Compared by file name so a
+    /// replay folder that moved still matches.
+    pub fn matches_file(&self, file: &Path) -> bool {
+        self.original_file.file_name().is_some() && self.original_file.file_name() == file.file_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_round_trip_restores_clip() {
+        let mut clip = Clip::new_without_target(PathBuf::from("replays/Replay 2025-08-17 21-52-01.mkv"))
+            .expect("Failed to create clip");
+        clip.name = Some("Clutch".to_string());
+        clip.trim_start = 5.0;
+        clip.trim_end = 20.0;
+        clip.set_tags("ace, ranked");
+        clip.notes = "  last round  ".to_string();
+
+        let metadata = ClipMetadata::from_clip(&clip, "default");
+        let json = serde_json::to_string(&metadata).expect("Failed to serialize metadata");
+        let parsed: ClipMetadata = serde_json::from_str(&json).expect("Failed to parse metadata");
+        assert_eq!(parsed, metadata);
+        assert_eq!(parsed.export_duration, 15.0);
+        assert_eq!(parsed.tags, vec!["ace".to_string(), "ranked".to_string()]);
+        assert_eq!(parsed.notes.as_deref(), Some("last round"));
+
+        let mut restored = Clip::new_without_target(PathBuf::from("moved/Replay 2025-08-17 21-52-01.mkv"))
+            .expect("Failed to create clip");
+        assert!(parsed.matches_file(&restored.original_file));
+        parsed.apply_to(&mut restored);
+        assert_eq!(restored.name.as_deref(), Some("Clutch"));
+        assert_eq!((restored.trim_start, restored.trim_end), (5.0, 20.0));
+        assert!(restored.is_trimmed);
+        assert_eq!(restored.tags, clip.tags);
+        assert_eq!(restored.notes, "last round");
+
+        // Blank notes are left out
+        clip.notes = " \n".to_string();
+        assert_eq!(ClipMetadata::from_clip(&clip, "default").notes, None);
+    }
+
+    #[test]
+    fn test_sidecar_path() {
+        assert_eq!(
+            ClipMetadata::sidecar_path(Path::new("trimmed/Replay - Clutch.mkv")),
+            PathBuf::from("trimmed/Replay - Clutch.json")
+        );
+    }
+}
@@ -2,11 +2,126 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use global_hotkey::hotkey::{Code, Modifiers};
 use std::collections::HashMap;
+use crate::video::WaveformScale;
+
+/// Waveform color on the timeline until the user picks another one
+pub const DEFAULT_WAVEFORM_COLOR: [u8; 3] = [90, 170, 255];
+
+fn default_confirm_before_delete() -> bool {
+    true
+}
+
+fn default_waveform_color() -> [u8; 3] {
+    DEFAULT_WAVEFORM_COLOR
+}
 
 fn default_unmatched_sound_enabled() -> bool {
     true
 }
 
+fn default_sound_debounce_ms() -> u64 {
+    500
+}
+
+/// Pitch of every generated duration tone until the user changes it
+pub const DEFAULT_TONE_FREQUENCY: f32 = 1000.0;
+/// Range the tone pitch sliders allow
+pub const TONE_FREQUENCY_RANGE: std::ops::RangeInclusive<f32> = 100.0..=4000.0;
+
+pub fn default_tone_frequencies() -> HashMap<u32, f32> {
+    crate::core::ClipDuration::ALL.iter()
+        .map(|&duration| (duration as u32, DEFAULT_TONE_FREQUENCY))
+        .collect()
+}
+
+fn default_left_panel_width() -> f32 {
+    300.0
+}
+
+fn default_log_level() -> log::LevelFilter {
+    log::LevelFilter::Info
+}
+
+fn default_filename_template() -> String {
+    crate::core::naming::DEFAULT_FILENAME_TEMPLATE.to_string()
+}
+
+fn default_timestamp_pattern() -> String {
+    crate::core::DEFAULT_TIMESTAMP_PATTERN.to_string()
+}
+
+fn default_max_animated_duration_secs() -> f64 {
+    10.0
+}
+
+fn default_session_gap_minutes() -> u32 {
+    60
+}
+
+fn default_hdr_tonemap_previews() -> bool {
+    true
+}
+
+fn default_select_session_export() -> bool {
+    true
+}
+
+/// Skip amounts used when the configured list has no usable entries
+pub const DEFAULT_JUMP_AMOUNTS_SECS: [f64; 3] = [3.0, 5.0, 10.0];
+
+/// More skip buttons than this (per direction) would overflow the control row
+pub const MAX_JUMP_AMOUNTS: usize = 5;
+
+/// Inner size of the window on first start and after resetting the window layout
+pub const DEFAULT_WINDOW_SIZE: egui::Vec2 = egui::vec2(1200.0, 800.0);
+
+/// Saved window sizes are clamped to this range; eframe additionally shrinks windows to the monitor
+pub const MIN_WINDOW_SIZE: egui::Vec2 = egui::vec2(640.0, 480.0);
+pub const MAX_WINDOW_SIZE: egui::Vec2 = egui::vec2(16384.0, 16384.0);
+
+/// How much of the window's title bar has to be on the desktop for the saved position to be kept
+const MIN_VISIBLE_TITLE_BAR: egui::Vec2 = egui::vec2(100.0, 30.0);
+
+/// Saved clip list widths are clamped to this range
+pub const MIN_LEFT_PANEL_WIDTH: f32 = 250.0;
+pub const MAX_LEFT_PANEL_WIDTH: f32 = 1200.0;
+
+fn default_scene_detection_threshold() -> f64 {
+    crate::video::DEFAULT_SCENE_THRESHOLD
+}
+
+fn default_thumbnail_cache_mb() -> usize {
+    crate::video::DEFAULT_THUMBNAIL_CACHE_MB
+}
+
+fn default_trim_step_fine_seconds() -> f64 {
+    0.1
+}
+
+fn default_min_trim_gap_seconds() -> f64 {
+    crate::core::MIN_TRIM_GAP_SECONDS
+}
+
+fn default_trim_step_small_seconds() -> f64 {
+    1.0
+}
+
+fn default_trim_step_large_seconds() -> f64 {
+    5.0
+}
+
+fn default_scroll_seek_step_seconds() -> f64 {
+    1.0
+}
+
+fn default_jump_amounts_secs() -> Vec<f64> {
+    DEFAULT_JUMP_AMOUNTS_SECS.to_vec()
+}
+
+fn default_ffmpeg_max_concurrent() -> usize {
+    crate::video::DEFAULT_MAX_CONCURRENT_FFMPEG
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioConfirmationConfig {
     pub enabled: bool,
@@ -17,6 +132,31 @@ pub struct AudioConfirmationConfig {
     pub duration_confirmation_enabled: bool, // For duration-specific beep sounds
     #[serde(default = "default_unmatched_sound_enabled")]
     pub unmatched_sound_enabled: bool, // For unmatched hotkey sounds
+    /// Custom sound per clip duration, keyed by duration in seconds. Durations
+    /// without an entry play the generated beep pattern.
+    #[serde(default)]
+    pub duration_sound_files: HashMap<u32, PathBuf>,
+    /// Pitch in Hz of the generated beep pattern per clip duration, keyed by duration in seconds
+    #[serde(default = "default_tone_frequencies")]
+    pub tone_frequencies: HashMap<u32, f32>,
+    /// Repeats of the same sound within this many milliseconds are skipped, so a scan that
+    /// adds many clips at once beeps once. Each duration sound is counted separately. 0 plays all.
+    #[serde(default = "default_sound_debounce_ms")]
+    pub sound_debounce_ms: u64,
+}
+
+impl AudioConfirmationConfig {
+    /// Pitch of the generated tone for `duration`, kept within `TONE_FREQUENCY_RANGE`
+    pub fn tone_frequency(&self, duration: crate::core::ClipDuration) -> f32 {
+        self.tone_frequencies.get(&(duration as u32))
+            .copied()
+            .unwrap_or(DEFAULT_TONE_FREQUENCY)
+            .clamp(*TONE_FREQUENCY_RANGE.start(), *TONE_FREQUENCY_RANGE.end())
+    }
+
+    pub fn sound_debounce(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.sound_debounce_ms)
+    }
 }
 
 impl Default for AudioConfirmationConfig {
@@ -28,10 +168,130 @@ impl Default for AudioConfirmationConfig {
             volume: 0.5,
             duration_confirmation_enabled: false,
             unmatched_sound_enabled: true,
+            duration_sound_files: HashMap::new(),
+            tone_frequencies: default_tone_frequencies(),
+            sound_debounce_ms: default_sound_debounce_ms(),
+        }
+    }
+}
+
+/// How long hotkey requests wait for OBS to write the matching replay file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipRequestRetryConfig {
+    pub retry_interval_ms: u64,
+    pub timeout_secs: u64,
+}
+
+impl Default for ClipRequestRetryConfig {
+    fn default() -> Self {
+        Self {
+            retry_interval_ms: 1000,
+            timeout_secs: 10,
+        }
+    }
+}
+
+impl ClipRequestRetryConfig {
+    /// Never retry more often than this, even if the config says 0
+    pub const MIN_RETRY_INTERVAL_MS: u64 = 100;
+
+    pub fn retry_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.retry_interval_ms.max(Self::MIN_RETRY_INTERVAL_MS))
+    }
+
+    /// Total time a request stays pending; always at least one retry interval
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout_secs).max(self.retry_interval())
+    }
+}
+
+/// How times are written on the timeline ruler and position display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimecodeFormat {
+    /// "65.3s"
+    Seconds,
+    /// "1:05", hours only shown when needed
+    HhMmSs,
+    /// "1:05.300"
+    #[default]
+    HhMmSsMs,
+    /// Frame number, "1959"
+    Frames,
+}
+
+impl TimecodeFormat {
+    pub const ALL: [TimecodeFormat; 4] = [Self::Seconds, Self::HhMmSs, Self::HhMmSsMs, Self::Frames];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Seconds => "Seconds",
+            Self::HhMmSs => "h:mm:ss",
+            Self::HhMmSsMs => "h:mm:ss.ms",
+            Self::Frames => "Frames",
+        }
+    }
+}
+
+/// What happens to a replay once a trim of it was exported and verified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SourceFilePolicy {
+    #[default]
+    Keep,
+    /// Move it to `archive_directory`; the clip keeps pointing at it there
+    MoveToArchive,
+    /// Move it to the recycle bin (the deleted folder where there is none)
+    DeleteSource,
+}
+
+impl SourceFilePolicy {
+    pub const ALL: [SourceFilePolicy; 3] = [Self::Keep, Self::MoveToArchive, Self::DeleteSource];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Keep => "Keep it",
+            Self::MoveToArchive => "Move it to the archive folder",
+            Self::DeleteSource => "Move it to the recycle bin",
         }
     }
 }
 
+/// Image format of "Export frame" screenshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScreenshotFormat {
+    /// Lossless, large at full resolution
+    #[default]
+    Png,
+    Jpeg,
+}
+
+impl ScreenshotFormat {
+    pub const ALL: [ScreenshotFormat; 2] = [Self::Png, Self::Jpeg];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+        }
+    }
+}
+
+/// How much each row of the clip list shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ClipListView {
+    /// Thumbnail, file name, original length and target
+    #[default]
+    Detailed,
+    /// One line per clip: file name and a duration badge
+    Compact,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HotkeyConfig {
     pub modifiers: String, // "Ctrl", "Alt", "Shift", "Ctrl+Alt", etc.
@@ -48,6 +308,15 @@ impl Default for HotkeyConfig {
 }
 
 impl HotkeyConfig {
+    /// The binding as written in the config, e.g. "Ctrl+Numpad1"
+    pub fn label(&self) -> String {
+        if self.modifiers.trim().is_empty() {
+            self.key.clone()
+        } else {
+            format!("{}+{}", self.modifiers, self.key)
+        }
+    }
+
     pub fn to_global_hotkey(&self) -> anyhow::Result<(Option<Modifiers>, Code)> {
         let modifiers = self.parse_modifiers()?;
         let code = self.parse_code()?;
@@ -130,6 +399,145 @@ pub struct AppConfig {
     pub audio_confirmation: AudioConfirmationConfig,
     #[serde(default)]
     pub use_system_file_dialog: bool, // True for system dialog, false for built-in browser
+    #[serde(default)]
+    pub clip_request_retry: ClipRequestRetryConfig,
+    /// Last window position (outer) and inner size, restored on startup. Not updated while
+    /// maximized, so un-maximizing returns to it.
+    #[serde(default)]
+    pub window_rect: Option<egui::Rect>,
+    /// Whether the window was maximized when it was last moved or resized
+    #[serde(default)]
+    pub window_maximized: bool,
+    #[serde(default = "default_left_panel_width")]
+    pub left_panel_width: f32,
+    /// GIF/WebP export is disabled for trims longer than this
+    #[serde(default = "default_max_animated_duration_secs")]
+    pub max_animated_duration_secs: f64,
+    /// Where preview screenshots are saved; empty means `trimmed_directory/screenshots`
+    #[serde(default)]
+    pub screenshot_directory: PathBuf,
+    #[serde(default)]
+    pub screenshot_format: ScreenshotFormat,
+    /// Where GIF exports are saved unless the clip has its own export directory;
+    /// empty means `trimmed_directory/gifs`
+    #[serde(default)]
+    pub gif_directory: PathBuf,
+    /// Output filename template, see `core::naming` for placeholders
+    #[serde(default = "default_filename_template")]
+    pub filename_template: String,
+    /// Layout of the date and time in replay file names, in OBS's filename formatting
+    /// specifiers. Has to match OBS's setting for replays to be dated and matched correctly.
+    #[serde(default = "default_timestamp_pattern")]
+    pub filename_timestamp_pattern: String,
+    #[serde(default = "default_log_level", with = "crate::core::logging::level_filter_serde")]
+    pub log_level: log::LevelFilter,
+    /// Extra seconds kept before target-duration trims
+    #[serde(default)]
+    pub trim_padding: crate::core::TrimPadding,
+    /// How many FFmpeg/ffprobe jobs may run at once
+    #[serde(default = "default_ffmpeg_max_concurrent")]
+    pub ffmpeg_max_concurrent: usize,
+    /// A gap of at least this many minutes between replays starts a new session
+    #[serde(default = "default_session_gap_minutes")]
+    pub session_gap_minutes: u32,
+    /// Replays that always start a new session, set with "Split session here"
+    #[serde(default)]
+    pub manual_session_breaks: Vec<PathBuf>,
+    /// Tonemap HDR (PQ/HLG) replays in still previews, thumbnails and screenshots
+    #[serde(default = "default_hdr_tonemap_previews")]
+    pub hdr_tonemap_previews: bool,
+    /// Also tonemap during continuous playback, which adds latency
+    #[serde(default)]
+    pub hdr_tonemap_playback: bool,
+    /// "Copy path" puts a file:// URL on the clipboard instead of a plain path
+    #[serde(default)]
+    pub copy_path_as_file_url: bool,
+    /// Format of timeline ruler labels, changed from the ruler's context menu
+    #[serde(default)]
+    pub timecode_format: TimecodeFormat,
+    /// Timeline waveform height mapping, toggled next to the timeline
+    #[serde(default)]
+    pub waveform_scale: WaveformScale,
+    /// Row layout of the clip list, toggled above the list
+    #[serde(default)]
+    pub clip_list_view: ClipListView,
+    /// Timeline waveform color as sRGB
+    #[serde(default = "default_waveform_color")]
+    pub waveform_color: [u8; 3],
+    /// Seconds skipped by the preview's skip buttons, each shown backwards and forwards
+    #[serde(default = "default_jump_amounts_secs")]
+    pub jump_amounts_secs: Vec<f64>,
+    /// Seconds one mouse wheel notch over the paused preview seeks
+    #[serde(default = "default_scroll_seek_step_seconds")]
+    pub scroll_seek_step_seconds: f64,
+    /// Seconds the small trim buttons move the start/end by
+    #[serde(default = "default_trim_step_small_seconds")]
+    pub trim_step_small_seconds: f64,
+    /// Seconds the large trim buttons move the start/end by
+    #[serde(default = "default_trim_step_large_seconds")]
+    pub trim_step_large_seconds: f64,
+    /// Replaces the small trim step while Shift is held
+    #[serde(default = "default_trim_step_fine_seconds")]
+    pub trim_step_fine_seconds: f64,
+    /// Shortest trim the trim buttons, timeline handles and playhead shortcuts allow
+    #[serde(default = "default_min_trim_gap_seconds")]
+    pub min_trim_gap_seconds: f64,
+    /// Grid trim points set on the timeline or at the playhead are rounded to
+    #[serde(default)]
+    pub trim_snap: crate::core::TrimSnap,
+    /// Scene score (0-1) above which "Detect Scenes" places a marker
+    #[serde(default = "default_scene_detection_threshold")]
+    pub scene_detection_threshold: f64,
+    /// Select the next clip in the list a few seconds after an export finishes
+    #[serde(default)]
+    pub auto_advance_after_export: bool,
+    /// Export clips matched to a duration hotkey as soon as OBS has finished writing them
+    #[serde(default)]
+    pub auto_export_on_match: bool,
+    /// Select the merged clip once "Export session as single file" finishes
+    #[serde(default = "default_select_session_export")]
+    pub select_session_export: bool,
+    /// Ask before the Delete button removes a clip
+    #[serde(default = "default_confirm_before_delete")]
+    pub confirm_before_delete: bool,
+    /// Move deleted clips to the OS recycle bin instead of the deleted directory
+    #[serde(default)]
+    pub delete_to_recycle_bin: bool,
+    /// Closing the window hides it to the system tray instead of quitting (Windows and macOS)
+    #[serde(default)]
+    pub minimize_to_tray: bool,
+    /// Run after every successful export, e.g. `rclone copy {path} nas:Clips`. Empty means off.
+    /// Split into arguments like a command line but not run through a shell, see
+    /// `core::post_export::build_command_args`.
+    #[serde(default)]
+    pub post_export_command: String,
+    /// URLs posted to when clips are detected, exports finish or errors happen
+    #[serde(default)]
+    pub webhooks: Vec<crate::core::webhooks::WebhookConfig>,
+    /// Older replays beyond this many clips in total are moved to the deleted directory
+    /// automatically. Pinned, imported, exported, annotated or "keep"-tagged clips count but never go.
+    /// None keeps everything.
+    #[serde(default)]
+    pub max_clips_to_keep: Option<usize>,
+    /// Output device for the preview's audio, None for the system default. Confirmation
+    /// sounds have their own device in `audio_confirmation`.
+    #[serde(default)]
+    pub playback_output_device: Option<String>,
+    /// Extra FFmpeg arguments for video exports
+    #[serde(default)]
+    pub export_preset: crate::video::ExportPreset,
+    /// Move the trim start of new clips past black frames OBS recorded before the capture started
+    #[serde(default)]
+    pub skip_leading_black: bool,
+    /// Texture memory the preview thumbnails may use before the least recently used are dropped
+    #[serde(default = "default_thumbnail_cache_mb")]
+    pub thumbnail_cache_mb: usize,
+    /// What to do with a replay after exporting a trim of it
+    #[serde(default)]
+    pub source_file_policy: SourceFilePolicy,
+    /// Where `SourceFilePolicy::MoveToArchive` puts replays; empty means `obs_replay_directory/archive`
+    #[serde(default)]
+    pub archive_directory: PathBuf,
 }
 
 impl Default for AppConfig {
@@ -168,11 +576,120 @@ impl Default for AppConfig {
             hotkeys,
             audio_confirmation: AudioConfirmationConfig::default(),
             use_system_file_dialog: false, // Default to built-in browser
+            clip_request_retry: ClipRequestRetryConfig::default(),
+            window_rect: None,
+            window_maximized: false,
+            left_panel_width: default_left_panel_width(),
+            max_animated_duration_secs: default_max_animated_duration_secs(),
+            screenshot_directory: PathBuf::from("./output/trimmed/screenshots"),
+            screenshot_format: ScreenshotFormat::default(),
+            gif_directory: PathBuf::from("./output/trimmed/gifs"),
+            filename_template: default_filename_template(),
+            filename_timestamp_pattern: default_timestamp_pattern(),
+            log_level: default_log_level(),
+            trim_padding: crate::core::TrimPadding::default(),
+            ffmpeg_max_concurrent: default_ffmpeg_max_concurrent(),
+            session_gap_minutes: default_session_gap_minutes(),
+            manual_session_breaks: Vec::new(),
+            hdr_tonemap_previews: default_hdr_tonemap_previews(),
+            hdr_tonemap_playback: false,
+            copy_path_as_file_url: false,
+            timecode_format: TimecodeFormat::default(),
+            waveform_scale: WaveformScale::default(),
+            clip_list_view: ClipListView::default(),
+            waveform_color: DEFAULT_WAVEFORM_COLOR,
+            jump_amounts_secs: default_jump_amounts_secs(),
+            scroll_seek_step_seconds: default_scroll_seek_step_seconds(),
+            trim_step_small_seconds: default_trim_step_small_seconds(),
+            trim_step_large_seconds: default_trim_step_large_seconds(),
+            trim_step_fine_seconds: default_trim_step_fine_seconds(),
+            min_trim_gap_seconds: default_min_trim_gap_seconds(),
+            trim_snap: crate::core::TrimSnap::Off,
+            scene_detection_threshold: default_scene_detection_threshold(),
+            auto_advance_after_export: false,
+            auto_export_on_match: false,
+            select_session_export: default_select_session_export(),
+            confirm_before_delete: default_confirm_before_delete(),
+            delete_to_recycle_bin: false,
+            minimize_to_tray: false,
+            post_export_command: String::new(),
+            webhooks: Vec::new(),
+            max_clips_to_keep: None,
+            playback_output_device: None,
+            export_preset: crate::video::ExportPreset::default(),
+            skip_leading_black: false,
+            thumbnail_cache_mb: crate::video::DEFAULT_THUMBNAIL_CACHE_MB,
+            source_file_policy: SourceFilePolicy::Keep,
+            archive_directory: PathBuf::from("./replays/archive"),
         }
     }
 }
 
 impl AppConfig {
+    /// Parsed `filename_timestamp_pattern`, or OBS's default naming if it's invalid
+    pub fn timestamp_pattern(&self) -> crate::core::TimestampPattern {
+        crate::core::TimestampPattern::parse(&self.filename_timestamp_pattern).unwrap_or_else(|e| {
+            log::warn!("Invalid filename timestamp pattern '{}' ({}), using the default", self.filename_timestamp_pattern, e);
+            crate::core::TimestampPattern::default()
+        })
+    }
+
+    /// Minimum gap and snapping for trim points set by hand
+    pub fn trim_rules(&self) -> crate::core::TrimRules {
+        crate::core::TrimRules {
+            min_gap: self.min_trim_gap_seconds.max(0.001),
+            snap: self.trim_snap,
+        }
+    }
+
+    /// Valid skip amounts in ascending order: positive, without duplicates and at most
+    /// `MAX_JUMP_AMOUNTS` of them. Falls back to the defaults if none are left.
+    pub fn jump_amounts(&self) -> Vec<f64> {
+        let mut amounts: Vec<f64> = self.jump_amounts_secs.iter()
+            .copied()
+            .filter(|amount| amount.is_finite() && *amount > 0.0)
+            .collect();
+        amounts.sort_by(f64::total_cmp);
+        amounts.dedup();
+        amounts.truncate(MAX_JUMP_AMOUNTS);
+
+        if amounts.is_empty() {
+            default_jump_amounts_secs()
+        } else {
+            amounts
+        }
+    }
+
+    /// The saved window rect, made safe to restore: None if it isn't finite, the size
+    /// clamped to sane values, and moved back onto `desktop` (the bounding box of all
+    /// monitors, if known) when too little of its title bar would be visible, e.g. after
+    /// unplugging the monitor it was on
+    pub fn restorable_window_rect(&self, desktop: Option<egui::Rect>) -> Option<egui::Rect> {
+        let saved = self.window_rect?;
+        if !saved.is_finite() {
+            return None;
+        }
+        let size = saved.size().clamp(MIN_WINDOW_SIZE, MAX_WINDOW_SIZE);
+        let mut rect = egui::Rect::from_min_size(saved.min, size);
+
+        if let Some(desktop) = desktop {
+            let title_bar = egui::Rect::from_min_size(rect.min, egui::vec2(size.x, MIN_VISIBLE_TITLE_BAR.y));
+            let visible = title_bar.intersect(desktop);
+            if !visible.is_positive() || visible.width() < MIN_VISIBLE_TITLE_BAR.x || visible.height() < MIN_VISIBLE_TITLE_BAR.y {
+                rect = rect.translate(desktop.min - rect.min + egui::vec2(50.0, 50.0));
+            }
+        }
+        Some(rect)
+    }
+
+    pub fn restorable_left_panel_width(&self) -> f32 {
+        if self.left_panel_width.is_finite() {
+            self.left_panel_width.clamp(MIN_LEFT_PANEL_WIDTH, MAX_LEFT_PANEL_WIDTH)
+        } else {
+            default_left_panel_width()
+        }
+    }
+
     pub fn load() -> anyhow::Result<Self> {
         let config_path = Self::config_path();
         let mut config = if config_path.exists() {
@@ -203,6 +720,17 @@ impl AppConfig {
             config
         };
         
+        // Configs from before screenshots existed get the default next to trimmed clips
+        if config.screenshot_directory.as_os_str().is_empty() {
+            config.screenshot_directory = config.trimmed_directory.join("screenshots");
+        }
+        if config.gif_directory.as_os_str().is_empty() {
+            config.gif_directory = config.trimmed_directory.join("gifs");
+        }
+        if config.archive_directory.as_os_str().is_empty() {
+            config.archive_directory = config.obs_replay_directory.join("archive");
+        }
+        
         // Ensure default confirmation sound exists if audio confirmation is enabled but no sound file is set
         if config.audio_confirmation.enabled && config.audio_confirmation.sound_file_path.is_none() {
             match crate::audio::ensure_default_confirmation_sound() {
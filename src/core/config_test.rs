@@ -2,7 +2,7 @@
 mod tests {
     
     use std::path::PathBuf;
-    use crate::core::{AppConfig, AudioConfirmationConfig};
+    use crate::core::{AppConfig, AudioConfirmationConfig, ClipRequestRetryConfig, DEFAULT_JUMP_AMOUNTS_SECS, MAX_JUMP_AMOUNTS, MAX_LEFT_PANEL_WIDTH, MAX_WINDOW_SIZE, MIN_WINDOW_SIZE};
 
     #[test]
     fn test_audio_confirmation_config_default() {
@@ -107,4 +107,76 @@ mod tests {
         config.unmatched_sound_enabled = true;
         assert!(config.unmatched_sound_enabled);
     }
+
+    #[test]
+    fn test_clip_request_retry_defaults() {
+        let config = AppConfig::default();
+        assert_eq!(config.clip_request_retry.retry_interval(), std::time::Duration::from_secs(1));
+        assert_eq!(config.clip_request_retry.timeout(), std::time::Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_clip_request_retry_guards_zero_values() {
+        let retry = ClipRequestRetryConfig {
+            retry_interval_ms: 0,
+            timeout_secs: 0,
+        };
+        // A zero interval would retry every frame, so it's raised to the minimum
+        assert_eq!(retry.retry_interval().as_millis() as u64, ClipRequestRetryConfig::MIN_RETRY_INTERVAL_MS);
+        assert!(retry.timeout() >= retry.retry_interval());
+    }
+
+    #[test]
+    fn test_log_level_serialized_as_lowercase_string() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.log_level, log::LevelFilter::Info);
+
+        config.log_level = log::LevelFilter::Debug;
+        let serialized = serde_json::to_value(&config).expect("Failed to serialize config");
+        assert_eq!(serialized["log_level"], "debug");
+
+        let deserialized: AppConfig = serde_json::from_value(serialized).expect("Failed to deserialize config");
+        assert_eq!(deserialized.log_level, log::LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_jump_amounts_are_validated() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.jump_amounts(), vec![3.0, 5.0, 10.0]);
+
+        config.jump_amounts_secs = vec![30.0, -1.0, 1.0, 0.0, f64::NAN, 1.0];
+        assert_eq!(config.jump_amounts(), vec![1.0, 30.0]);
+
+        config.jump_amounts_secs = (1..=10).map(f64::from).collect();
+        assert_eq!(config.jump_amounts().len(), MAX_JUMP_AMOUNTS);
+
+        config.jump_amounts_secs.clear();
+        assert_eq!(config.jump_amounts(), DEFAULT_JUMP_AMOUNTS_SECS.to_vec());
+    }
+
+    #[test]
+    fn test_window_rect_is_sanitized_before_restoring() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.restorable_window_rect(None), None);
+
+        let desktop = egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(1920.0, 1080.0));
+        let saved = egui::Rect::from_min_size(egui::pos2(100.0, 100.0), egui::vec2(1200.0, 800.0));
+        config.window_rect = Some(saved);
+        assert_eq!(config.restorable_window_rect(Some(desktop)), Some(saved));
+
+        // Saved on a monitor that was unplugged since
+        config.window_rect = Some(saved.translate(egui::vec2(1920.0, 0.0)));
+        let restored = config.restorable_window_rect(Some(desktop)).unwrap();
+        assert!(desktop.contains(restored.min));
+        assert_eq!(restored.size(), saved.size());
+
+        config.window_rect = Some(egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(10.0, 1e9)));
+        assert_eq!(config.restorable_window_rect(None).unwrap().size(), egui::vec2(MIN_WINDOW_SIZE.x, MAX_WINDOW_SIZE.y));
+
+        config.window_rect = Some(egui::Rect::from_min_size(egui::pos2(f32::NAN, 0.0), saved.size()));
+        assert_eq!(config.restorable_window_rect(None), None);
+
+        config.left_panel_width = 5000.0;
+        assert_eq!(config.restorable_left_panel_width(), MAX_LEFT_PANEL_WIDTH);
+    }
 }
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::core::AudioTrack;
+use crate::video::ExportJobSpec;
+
+/// Older exports are dropped from the history once it holds this many
+pub const MAX_EXPORT_HISTORY: usize = 50;
+
+/// A finished export, kept so it can be opened or run again later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportRecord {
+    /// Source, output, trim range and preset settings of the export
+    pub spec: ExportJobSpec,
+    /// Track layout the export was mixed with, reused when exporting again
+    #[serde(default)]
+    pub audio_tracks: Vec<AudioTrack>,
+    pub exported_at: DateTime<Local>,
+}
+
+impl ExportRecord {
+    /// Whether two exports are of the same clip in the same format, so the newer replaces the older
+    fn same_export(&self, other: &ExportRecord) -> bool {
+        self.spec.clip_path == other.spec.clip_path && self.spec.format == other.spec.format
+    }
+}
+
+/// Recent exports, newest first, persisted in the config directory
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportHistory {
+    pub records: Vec<ExportRecord>,
+}
+
+impl ExportHistory {
+    pub fn file_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("clip-helper")
+            .join("export_history.json")
+    }
+
+    /// History from `path`, empty if it doesn't exist or can't be read
+    pub fn load(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+        match std::fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| serde_json::from_str(&content).map_err(anyhow::Error::from))
+        {
+            Ok(history) => history,
+            Err(e) => {
+                log::warn!("Failed to read export history ({}), starting with an empty one", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Add an export at the top, replacing an earlier export of the same clip and format
+    pub fn record(&mut self, record: ExportRecord) {
+        self.records.retain(|existing| !existing.same_export(&record));
+        self.records.insert(0, record);
+        self.records.truncate(MAX_EXPORT_HISTORY);
+    }
+
+    /// Drop exports whose output file is gone. Returns whether anything was removed.
+    pub fn prune_missing(&mut self) -> bool {
+        let before = self.records.len();
+        self.records.retain(|record| record.spec.output_path.exists());
+        self.records.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::ExportFormat;
+
+    fn record(clip: &str, output: PathBuf, format: ExportFormat) -> ExportRecord {
+        ExportRecord {
+            spec: ExportJobSpec {
+                clip_path: PathBuf::from(clip),
+                output_path: output,
+                preset_name: crate::video::DEFAULT_PRESET_NAME.to_string(),
+                trim_start: 1.0,
+                trim_end: 11.0,
+                format,
+                animated: Default::default(),
+                sidecar: None,
+                export_audio_tracks_separately: false,
+                extra_ffmpeg_args: Vec::new(),
+                target_size_mb: None,
+                video_encoder: Default::default(),
+            },
+            audio_tracks: Vec::new(),
+            exported_at: Local::now(),
+        }
+    }
+
+    #[test]
+    fn test_record_replaces_same_clip_and_prunes_missing() {
+        let root = std::env::temp_dir().join(format!("clip-helper-export-history-test-{}", std::process::id()));
+        std::fs::create_dir_all(&root).expect("Failed to create test directory");
+        let kept = root.join("Replay 1.mkv");
+        std::fs::write(&kept, b"export").expect("Failed to write test file");
+
+        let mut history = ExportHistory::default();
+        history.record(record("Replay 1.mkv", kept.clone(), ExportFormat::Video));
+        history.record(record("Replay 2.mkv", root.join("missing.mkv"), ExportFormat::Video));
+        history.record(record("Replay 1.mkv", root.join("Replay 1.gif"), ExportFormat::Gif));
+        assert_eq!(history.records.len(), 3);
+
+        // Exporting Replay 1 as video again updates its entry and moves it to the top
+        let mut again = record("Replay 1.mkv", kept.clone(), ExportFormat::Video);
+        again.spec.trim_end = 12.0;
+        history.record(again);
+        assert_eq!(history.records.len(), 3);
+        assert_eq!(history.records[0].spec.trim_end, 12.0);
+
+        assert!(history.prune_missing());
+        assert_eq!(history.records.len(), 1);
+        assert_eq!(history.records[0].spec.output_path, kept);
+        assert!(!history.prune_missing());
+
+        let path = root.join("export_history.json");
+        history.save(&path).unwrap();
+        assert_eq!(ExportHistory::load(&path), history);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
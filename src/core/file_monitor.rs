@@ -1,23 +1,236 @@
 use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use tokio::sync::broadcast;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use crate::core::Clip;
+
+/// Where a replay's timestamp came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimestampSource {
+    /// The date and time OBS wrote into the file name
+    #[default]
+    Filename,
+    /// The file's modification time, for names without a timestamp. Less reliable,
+    /// since copying or remuxing the file changes it.
+    Mtime,
+}
 
 #[derive(Debug, Clone)]
 pub struct NewReplayFile {
     pub path: PathBuf,
     pub timestamp: DateTime<Local>,
+    pub timestamp_source: TimestampSource,
+}
+
+impl NewReplayFile {
+    /// Timestamp from the file name as laid out by `pattern`, falling back to the
+    /// modification time. None if neither can be read.
+    pub fn from_path(path: PathBuf, pattern: &TimestampPattern) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        if let Some(timestamp) = pattern.find_in(file_name) {
+            return Some(Self { path, timestamp, timestamp_source: TimestampSource::Filename });
+        }
+
+        let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified());
+        match modified {
+            Ok(modified) => {
+                log::warn!("No timestamp matching the filename pattern in {}, using its modification time", file_name);
+                Some(Self { path, timestamp: DateTime::<Local>::from(modified), timestamp_source: TimestampSource::Mtime })
+            }
+            Err(e) => {
+                log::warn!("Failed to read the modification time of {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Clip for this file, dated with the timestamp read here rather than by parsing the name again
+    pub fn to_clip(&self) -> Clip {
+        let mut clip = Clip::without_target_at(self.path.clone(), self.timestamp);
+        clip.timestamp_source = self.timestamp_source;
+        clip
+    }
+}
+
+/// OBS's default replay "Filename Formatting", as in "Replay 2025-08-19 15-42-03.mkv"
+pub const DEFAULT_TIMESTAMP_PATTERN: &str = "%CCYY-%MM-%DD %hh-%mm-%ss";
+
+/// One specifier or literal character of a `TimestampPattern`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatternPart {
+    Year,
+    ShortYear,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Literal(char),
+}
+
+impl PatternPart {
+    /// Characters the part takes up in a file name; OBS zero-pads every field
+    fn width(self) -> usize {
+        match self {
+            PatternPart::Year => 4,
+            PatternPart::Literal(_) => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// The OBS specifiers a pattern can use, longest first where one is a prefix of another
+const PATTERN_SPECIFIERS: [(&str, PatternPart); 7] = [
+    ("CCYY", PatternPart::Year),
+    ("YY", PatternPart::ShortYear),
+    ("MM", PatternPart::Month),
+    ("DD", PatternPart::Day),
+    ("hh", PatternPart::Hour),
+    ("mm", PatternPart::Minute),
+    ("ss", PatternPart::Second),
+];
+
+/// How the date and time are laid out in replay file names, written with OBS's filename
+/// formatting specifiers (%CCYY, %YY, %MM, %DD, %hh, %mm, %ss, %% for a literal '%')
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampPattern {
+    parts: Vec<PatternPart>,
+}
+
+impl Default for TimestampPattern {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TIMESTAMP_PATTERN).expect("The default timestamp pattern is valid")
+    }
+}
+
+impl TimestampPattern {
+    /// Parse an OBS-style pattern. It needs a year, month, day, hour and minute;
+    /// seconds are optional.
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = pattern;
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            if c != '%' {
+                parts.push(PatternPart::Literal(c));
+                continue;
+            }
+            if let Some(after) = rest.strip_prefix('%') {
+                parts.push(PatternPart::Literal('%'));
+                rest = after;
+                continue;
+            }
+            let Some((specifier, part)) = PATTERN_SPECIFIERS.iter().find(|(specifier, _)| rest.starts_with(specifier)) else {
+                let shown: String = rest.chars().take_while(char::is_ascii_alphabetic).collect();
+                return Err(format!("Unsupported specifier %{}", shown));
+            };
+            parts.push(*part);
+            rest = &rest[specifier.len()..];
+        }
+
+        let required: [(&[PatternPart], &str); 5] = [
+            (&[PatternPart::Year, PatternPart::ShortYear], "a year (%CCYY or %YY)"),
+            (&[PatternPart::Month], "a month (%MM)"),
+            (&[PatternPart::Day], "a day (%DD)"),
+            (&[PatternPart::Hour], "an hour (%hh)"),
+            (&[PatternPart::Minute], "a minute (%mm)"),
+        ];
+        for (options, name) in required {
+            if !parts.iter().any(|part| options.contains(part)) {
+                return Err(format!("Pattern needs {}", name));
+            }
+        }
+        Ok(Self { parts })
+    }
+
+    fn width(&self) -> usize {
+        self.parts.iter().map(|part| part.width()).sum()
+    }
+
+    /// Date and time if `text` starts with the pattern
+    fn match_at(&self, text: &[char]) -> Option<NaiveDateTime> {
+        let (mut year, mut month, mut day, mut hour, mut minute, mut second) = (0, 0, 0, 0, 0, 0);
+        let mut position = 0;
+        for part in &self.parts {
+            let field = text.get(position..position + part.width())?;
+            position += part.width();
+            if let PatternPart::Literal(expected) = *part {
+                // OBS writes underscores instead of spaces with "Generate File Name without Space"
+                if field[0] != expected && !(expected == ' ' && field[0] == '_') {
+                    return None;
+                }
+                continue;
+            }
+            if !field.iter().all(char::is_ascii_digit) {
+                return None;
+            }
+            let value: u32 = field.iter().collect::<String>().parse().ok()?;
+            match part {
+                PatternPart::Year => year = value as i32,
+                PatternPart::ShortYear => year = 2000 + value as i32,
+                PatternPart::Month => month = value,
+                PatternPart::Day => day = value,
+                PatternPart::Hour => hour = value,
+                PatternPart::Minute => minute = value,
+                PatternPart::Second => second = value,
+                PatternPart::Literal(_) => {}
+            }
+        }
+        NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, second)
+    }
+
+    /// Find the timestamp anywhere in a file name, e.g. "Replay 2025-08-19 15-42-03.mkv" for the
+    /// default pattern. Milliseconds right after it ("-123", also after a '.') are kept.
+    pub fn find_in(&self, filename: &str) -> Option<DateTime<Local>> {
+        let stem = Path::new(filename).file_stem()?.to_str()?;
+        let chars: Vec<char> = stem.chars().collect();
+        let width = self.width();
+
+        for start in 0..=chars.len().saturating_sub(width) {
+            let Some(datetime) = self.match_at(&chars[start..]) else {
+                continue;
+            };
+
+            // Optional milliseconds: a '-' or '.' and one to three digits
+            let rest: String = chars[start + width..].iter().collect();
+            let millis = rest.strip_prefix(['-', '.'])
+                .map(|rest| rest.chars().take_while(char::is_ascii_digit).collect::<String>())
+                .filter(|digits| (1..=3).contains(&digits.len()))
+                .and_then(|digits| format!("{:0<3}", digits).parse::<i64>().ok())
+                .unwrap_or(0);
+            let datetime = datetime + chrono::Duration::milliseconds(millis);
+            return Local.from_local_datetime(&datetime).earliest();
+        }
+        None
+    }
+}
+
+/// Find an OBS timestamp in a replay file name using OBS's default naming,
+/// see `TimestampPattern::find_in`
+pub fn parse_obs_timestamp(filename: &str) -> Option<DateTime<Local>> {
+    TimestampPattern::default().find_in(filename)
+}
+
+/// OBS replay buffer saves: "Replay" followed by a separator, as .mkv. Temp files of
+/// merges written into the replay directory are skipped.
+fn is_replay_file(filename: &str) -> bool {
+    (filename.starts_with("Replay ") || filename.starts_with("Replay_"))
+        && filename.ends_with(".mkv")
+        && !crate::video::VideoProcessor::is_temp_output(Path::new(filename))
 }
 
 pub struct FileMonitor {
     _watcher: RecommendedWatcher,
     event_sender: broadcast::Sender<NewReplayFile>,
+    /// Shared with the event thread, so a changed setting applies to the next file
+    timestamp_pattern: Arc<Mutex<TimestampPattern>>,
 }
 
 impl FileMonitor {
-    pub fn new(directory: &Path) -> anyhow::Result<(Self, broadcast::Receiver<NewReplayFile>)> {
+    pub fn new(directory: &Path, timestamp_pattern: TimestampPattern) -> anyhow::Result<(Self, broadcast::Receiver<NewReplayFile>)> {
         let (tx, rx) = mpsc::channel();
         let (event_sender, event_receiver) = broadcast::channel(32);
         
@@ -25,28 +238,13 @@ impl FileMonitor {
         watcher.watch(directory, RecursiveMode::NonRecursive)?;
 
         let event_sender_clone = event_sender.clone();
+        let timestamp_pattern = Arc::new(Mutex::new(timestamp_pattern));
+        let thread_pattern = timestamp_pattern.clone();
         thread::spawn(move || {
             while let Ok(event) = rx.recv() {
                 if let Ok(event) = event {
-                    if let Event { kind: notify::EventKind::Create(_), paths, .. } = event {
-                        for path in paths {
-                            if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
-                                if filename.starts_with("Replay ") && filename.ends_with(".mkv") {
-                                    // Extract timestamp from filename
-                                    if let Ok(timestamp) = crate::core::Clip::extract_timestamp_from_filename(&path) {
-                                        let new_file = NewReplayFile {
-                                            path: path.clone(),
-                                            timestamp,
-                                        };
-                                        if let Err(e) = event_sender_clone.send(new_file) {
-                                            log::error!("Failed to send file event for {:?}: {}", path, e);
-                                        }
-                                        log::info!("New replay file detected: {}", filename);
-                                    }
-                                }
-                            }
-                        }
-                    }
+                    let pattern = thread_pattern.lock().map(|pattern| pattern.clone()).unwrap_or_default();
+                    Self::process_event(event, &event_sender_clone, &pattern);
                 }
             }
         });
@@ -55,16 +253,51 @@ impl FileMonitor {
             FileMonitor {
                 _watcher: watcher,
                 event_sender,
+                timestamp_pattern,
             },
             event_receiver,
         ))
     }
 
+    /// Forward replay files created in the watched directory
+    fn process_event(event: Event, sender: &broadcast::Sender<NewReplayFile>, pattern: &TimestampPattern) {
+        let Event { kind: notify::EventKind::Create(_), paths, .. } = event else {
+            return;
+        };
+        for path in paths {
+            let Some(filename) = path.file_name().and_then(|s| s.to_str()).map(str::to_string) else {
+                continue;
+            };
+            if !is_replay_file(&filename) {
+                continue;
+            }
+            if let Some(new_file) = NewReplayFile::from_path(path.clone(), pattern) {
+                if let Err(e) = sender.send(new_file) {
+                    log::error!("Failed to send file event for {:?}: {}", path, e);
+                }
+                log::info!("New replay file detected: {}", filename);
+            }
+        }
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<NewReplayFile> {
         self.event_sender.subscribe()
     }
+
+    /// Use `pattern` for files created from now on
+    pub fn set_timestamp_pattern(&self, pattern: TimestampPattern) {
+        if let Ok(mut current) = self.timestamp_pattern.lock() {
+            *current = pattern;
+        }
+    }
     
-    pub fn scan_existing_files(directory: &Path) -> anyhow::Result<Vec<NewReplayFile>> {
+    /// Whether `scan_existing_files` on `directory` picks `path` up again
+    pub fn is_found_by_scan(directory: &Path, path: &Path) -> bool {
+        path.parent() == Some(directory)
+            && path.file_name().and_then(|s| s.to_str()).is_some_and(is_replay_file)
+    }
+
+    pub fn scan_existing_files(directory: &Path, pattern: &TimestampPattern) -> anyhow::Result<Vec<NewReplayFile>> {
         let mut files = Vec::new();
         
         if directory.exists() && directory.is_dir() {
@@ -73,14 +306,10 @@ impl FileMonitor {
                 let path = entry.path();
                 
                 if path.is_file() {
-                    if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
-                        if filename.starts_with("Replay ") && filename.ends_with(".mkv") {
-                            if let Ok(timestamp) = crate::core::Clip::extract_timestamp_from_filename(&path) {
-                                files.push(NewReplayFile {
-                                    path,
-                                    timestamp,
-                                });
-                            }
+                    let is_replay = path.file_name().and_then(|s| s.to_str()).is_some_and(is_replay_file);
+                    if is_replay {
+                        if let Some(new_file) = NewReplayFile::from_path(path, pattern) {
+                            files.push(new_file);
                         }
                     }
                 }
@@ -93,3 +322,45 @@ impl FileMonitor {
         Ok(files)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn test_parse_obs_timestamp_patterns() {
+        let plain = parse_obs_timestamp("Replay 2025-08-19 15-42-03.mkv").expect("Plain OBS name");
+        assert_eq!((plain.hour(), plain.minute(), plain.second()), (15, 42, 3));
+        assert_eq!(parse_obs_timestamp("Replay_2025-08-19_15-42-03.mkv"), Some(plain));
+
+        let with_millis = parse_obs_timestamp("Replay 2025-08-19 15-42-03-250.mkv").expect("Name with milliseconds");
+        assert_eq!(with_millis.timestamp_subsec_millis(), 250);
+        assert_eq!(parse_obs_timestamp("Replay 2025-08-19 15-42-03.25.mkv"), Some(with_millis));
+
+        assert!(parse_obs_timestamp("Replay clutch round.mkv").is_none());
+        assert!(parse_obs_timestamp("Replay 2025-13-40 15-42-03.mkv").is_none());
+    }
+
+    #[test]
+    fn test_custom_timestamp_pattern() {
+        let pattern = TimestampPattern::parse("%DD.%MM.%YY_%hh%mm").expect("Valid pattern");
+        let parsed = pattern.find_in("Replay 19.08.25_1542.mkv").expect("Custom OBS name");
+        assert_eq!(parsed, parse_obs_timestamp("Replay 2025-08-19 15-42-00.mkv").unwrap());
+        // A name in the default format no longer matches
+        assert!(pattern.find_in("Replay 2025-08-19 15-42-03.mkv").is_none());
+
+        assert_eq!(TimestampPattern::parse("100%% %CCYY-%MM-%DD %hh-%mm").unwrap().width(), 21);
+        assert_eq!(TimestampPattern::parse("%CCYY-%MM-%DD %FPS"), Err("Unsupported specifier %FPS".to_string()));
+        assert_eq!(TimestampPattern::parse("%CCYY-%MM-%DD"), Err("Pattern needs an hour (%hh)".to_string()));
+    }
+
+    #[test]
+    fn test_is_found_by_scan() {
+        let dir = Path::new("/replays");
+        assert!(FileMonitor::is_found_by_scan(dir, Path::new("/replays/Replay 2025-08-19 15-42-03.mkv")));
+        assert!(!FileMonitor::is_found_by_scan(dir, Path::new("/replays/Replay 2025-08-19 15-42-03.mp4")));
+        assert!(!FileMonitor::is_found_by_scan(dir, Path::new("/replays/clutch.mkv")));
+        assert!(!FileMonitor::is_found_by_scan(dir, Path::new("/replays/old/Replay 2025-08-19 15-42-03.mkv")));
+    }
+}
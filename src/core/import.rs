@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+use crate::video::{VideoInfo, VideoProcessor};
+
+/// Extensions considered when a folder is dropped; single dropped files are probed regardless
+pub const VIDEO_EXTENSIONS: [&str; 8] = ["mkv", "mp4", "mov", "webm", "avi", "flv", "ts", "m4v"];
+
+pub fn has_video_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| VIDEO_EXTENSIONS.iter().any(|video| ext.eq_ignore_ascii_case(video)))
+}
+
+/// Expand dropped paths into the files to import: files are kept as they are, folders
+/// are scanned recursively for files with a video extension. Sorted and deduplicated.
+pub fn collect_import_candidates(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            scan_folder(path, &mut files);
+        } else if path.is_file() {
+            files.push(path.clone());
+        }
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn scan_folder(folder: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        log::warn!("Failed to read dropped folder {}", folder.display());
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => scan_folder(&path, files),
+            // Skip half-written exports of our own
+            Ok(file_type) if file_type.is_file() && has_video_extension(&path) && !VideoProcessor::is_temp_output(&path) => {
+                files.push(path);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Check with ffprobe that `path` has a video stream and a usable duration.
+/// Blocks on ffprobe, so call this off the UI thread.
+pub fn probe_importable(path: &Path) -> anyhow::Result<VideoInfo> {
+    VideoProcessor::probe_geometry(path)
+        .map_err(|e| anyhow::anyhow!("{} is not a readable video: {}", path.display(), e))?;
+    let info = VideoProcessor::get_video_info(path)?;
+    if info.duration < 1.0 {
+        return Err(anyhow::anyhow!("{} is shorter than a second", path.display()));
+    }
+    Ok(info)
+}
+
+/// Copy `file` into `directory`, numbering the copy ("Replay (2).mkv") instead of
+/// overwriting a file of the same name. Keeps the modification time, which dates clips
+/// without a timestamp in their name. Blocks until copied, so call this off the UI thread.
+pub fn copy_into_directory(file: &Path, directory: &Path) -> anyhow::Result<PathBuf> {
+    let stem = file.file_stem().and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", file.display()))?;
+    let extension = file.extension().and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{}", ext))
+        .unwrap_or_default();
+    let mut destination = directory.join(format!("{}{}", stem, extension));
+    let mut number = 2;
+    while destination.exists() {
+        destination = directory.join(format!("{} ({}){}", stem, number, extension));
+        number += 1;
+    }
+
+    log::info!("Copying {} to {}", file.display(), destination.display());
+    std::fs::copy(file, &destination)
+        .map_err(|e| anyhow::anyhow!("Failed to copy {}: {}", file.display(), e))?;
+    let modified = std::fs::metadata(file).and_then(|metadata| metadata.modified());
+    if let Err(e) = modified.and_then(|time| std::fs::File::options().write(true).open(&destination)?.set_modified(time)) {
+        log::debug!("Failed to keep the modification time of {}: {}", destination.display(), e);
+    }
+    Ok(destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_import_candidates_scans_folders() {
+        let root = std::env::temp_dir().join(format!("clip-helper-import-test-{}", std::process::id()));
+        let nested = root.join("session").join("day 2");
+        std::fs::create_dir_all(&nested).expect("Failed to create test directory");
+        for file in [root.join("a.mp4"), root.join("notes.txt"), nested.join("b.MKV"), nested.join("c.mkv.part")] {
+            std::fs::write(file, b"").expect("Failed to write test file");
+        }
+        let loose = root.join("notes.txt");
+
+        let files = collect_import_candidates(&[root.clone(), loose.clone(), root.join("a.mp4")]);
+        assert_eq!(files, vec![root.join("a.mp4"), loose, nested.join("b.MKV")]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_copy_into_directory_numbers_duplicates() {
+        let root = std::env::temp_dir().join(format!("clip-helper-copy-test-{}", std::process::id()));
+        let (source, replays) = (root.join("downloads"), root.join("replays"));
+        std::fs::create_dir_all(&source).expect("Failed to create test directory");
+        std::fs::create_dir_all(&replays).expect("Failed to create test directory");
+        let file = source.join("Replay 2025-08-17 21-52-01.mkv");
+        std::fs::write(&file, b"replay").expect("Failed to write test file");
+
+        assert_eq!(copy_into_directory(&file, &replays).unwrap(), replays.join("Replay 2025-08-17 21-52-01.mkv"));
+        assert_eq!(copy_into_directory(&file, &replays).unwrap(), replays.join("Replay 2025-08-17 21-52-01 (2).mkv"));
+        assert!(file.exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
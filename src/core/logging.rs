@@ -0,0 +1,237 @@
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Mutex, OnceLock};
+
+/// Log levels offered in the settings dialog, least to most verbose
+pub const LOG_LEVELS: [log::LevelFilter; 6] = [
+    log::LevelFilter::Off,
+    log::LevelFilter::Error,
+    log::LevelFilter::Warn,
+    log::LevelFilter::Info,
+    log::LevelFilter::Debug,
+    log::LevelFilter::Trace,
+];
+
+/// Messages kept by the in-app log panel; older ones are dropped
+pub const LOG_PANEL_CAPACITY: usize = 1000;
+/// Messages that can wait for the UI to pick them up. Logging never waits for the UI:
+/// when this is full (e.g. the window is minimized and not repainting), new messages
+/// skip the panel and only reach stderr and the log file.
+const LOG_CHANNEL_CAPACITY: usize = 1000;
+
+/// Receiving end of the log panel channel, taken once by the UI
+static LOG_RECEIVER: OnceLock<Mutex<Option<Receiver<LogEntry>>>> = OnceLock::new();
+
+/// One message for the in-app log panel
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: log::Level,
+    pub target: String,
+    pub message: String,
+}
+
+/// env_logger's output, plus a copy of every record for the in-app log panel
+struct AppLogger {
+    inner: env_logger::Logger,
+    panel: SyncSender<LogEntry>,
+}
+
+impl log::Log for AppLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.inner.log(record);
+        let entry = LogEntry {
+            time: Local::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        // try_send so a slow UI can never stall the thread that's logging; a full
+        // channel just drops the panel's copy
+        let _ = self.panel.try_send(entry);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// The last `LOG_PANEL_CAPACITY` log messages, fed from the logger's channel
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    receiver: Option<Receiver<LogEntry>>,
+}
+
+impl LogBuffer {
+    /// Buffer fed by the global logger. Only the first call gets the messages; logging
+    /// isn't set up in tests, so there the buffer just stays empty.
+    pub fn from_logger() -> Self {
+        let receiver = LOG_RECEIVER.get()
+            .and_then(|receiver| receiver.lock().ok()?.take());
+        Self { receiver, ..Self::with_capacity(LOG_PANEL_CAPACITY) }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            receiver: None,
+        }
+    }
+
+    /// Move waiting messages from the logger into the buffer
+    pub fn collect(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        let waiting: Vec<LogEntry> = receiver.try_iter().collect();
+        for entry in waiting {
+            self.push(entry);
+        }
+    }
+
+    pub fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Log file for the current run, overwritten on every start
+pub fn log_file_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("clip-helper")
+        .join("clip-helper.log")
+}
+
+/// Sends log output to stderr and the log file
+struct TeeWriter {
+    file: Option<Mutex<File>>,
+}
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.write_all(buf);
+            }
+        }
+        std::io::stderr().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.flush();
+            }
+        }
+        std::io::stderr().flush()
+    }
+}
+
+/// Set up env_logger with the configured level. `RUST_LOG`, when set, still wins so
+/// per-module filters keep working for debugging.
+pub fn init_logging(level: log::LevelFilter) {
+    let log_path = log_file_path();
+    let file = log_path.parent()
+        .map(std::fs::create_dir_all)
+        .transpose()
+        .and_then(|_| File::create(&log_path))
+        .map_err(|e| eprintln!("Failed to create log file {}: {}", log_path.display(), e))
+        .ok();
+
+    let mut builder = env_logger::Builder::new();
+    builder.target(env_logger::Target::Pipe(Box::new(TeeWriter { file: file.map(Mutex::new) })));
+
+    let max_level = match std::env::var("RUST_LOG") {
+        Ok(filters) => {
+            builder.parse_filters(&filters);
+            None
+        }
+        Err(_) => {
+            // Let everything through env_logger and gate on log's global max level instead,
+            // so set_log_level can raise verbosity later without rebuilding the logger
+            builder.filter_level(log::LevelFilter::Trace);
+            Some(level)
+        }
+    };
+
+    let inner = builder.build();
+    let max_level = max_level.unwrap_or_else(|| inner.filter());
+    let (panel, receiver) = mpsc::sync_channel(LOG_CHANNEL_CAPACITY);
+    let _ = LOG_RECEIVER.set(Mutex::new(Some(receiver)));
+    match log::set_boxed_logger(Box::new(AppLogger { inner, panel })) {
+        Ok(()) => log::set_max_level(max_level),
+        Err(e) => eprintln!("Failed to set up logging: {}", e),
+    }
+}
+
+/// Change the log level at runtime
+pub fn set_log_level(level: log::LevelFilter) {
+    log::set_max_level(level);
+    log::info!("Log level set to {}", level);
+}
+
+/// Serialize `LevelFilter` as a lowercase string ("error", "warn", "info", ...)
+pub mod level_filter_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(level: &log::LevelFilter, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&level.as_str().to_lowercase())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<log::LevelFilter, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str) -> LogEntry {
+        LogEntry {
+            time: Local::now(),
+            level: log::Level::Info,
+            target: "clip_helper".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_log_buffer_drops_oldest_entries() {
+        let mut buffer = LogBuffer::with_capacity(2);
+        for message in ["first", "second", "third"] {
+            buffer.push(entry(message));
+        }
+        let messages: Vec<&str> = buffer.entries().map(|entry| entry.message.as_str()).collect();
+        assert_eq!(messages, vec!["second", "third"]);
+
+        buffer.clear();
+        assert_eq!(buffer.entries().count(), 0);
+    }
+}
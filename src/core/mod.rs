@@ -1,10 +1,23 @@
 pub mod clip;
+pub mod clip_metadata;
 pub mod config;
+pub mod export_history;
 pub mod file_monitor;
+pub mod import;
+pub mod logging;
+pub mod naming;
+pub mod post_export;
+pub mod near_duplicates;
+pub mod recycle_bin;
+pub mod shell;
+pub mod statistics;
+pub mod webhooks;
 
 #[cfg(test)]
 mod config_test;
 
 pub use clip::*;
+pub use clip_metadata::*;
 pub use config::*;
 pub use file_monitor::*;
+pub use statistics::*;
@@ -0,0 +1,223 @@
+use crate::core::Clip;
+
+/// Template matching the original "<file stem> - <name>" output names
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{original} - {name}";
+
+/// Placeholders understood by output filename templates
+pub const TEMPLATE_PLACEHOLDERS: [&str; 6] = ["original", "date", "time", "duration", "name", "index"];
+
+/// Characters that aren't allowed in filenames on at least one supported platform
+const ILLEGAL_FILENAME_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Device names Windows refuses as filenames, with or without an extension
+const RESERVED_WINDOWS_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+enum TemplatePart<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+fn parse_template(template: &str) -> Result<Vec<TemplatePart<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err("Unmatched '}' in template".to_string());
+        }
+        if open > 0 {
+            parts.push(TemplatePart::Literal(&rest[..open]));
+        }
+
+        let after_open = &rest[open + 1..];
+        let close = after_open.find('}')
+            .ok_or_else(|| "Unclosed '{' in template".to_string())?;
+        let placeholder = &after_open[..close];
+        if !TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            return Err(format!("Unknown placeholder {{{}}}", placeholder));
+        }
+        parts.push(TemplatePart::Placeholder(placeholder));
+        rest = &after_open[close + 1..];
+    }
+
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest));
+    }
+    Ok(parts)
+}
+
+/// Check that a template only uses known placeholders and always produces a unique-ish name
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let parts = parse_template(template)?;
+    if !parts.iter().any(|part| matches!(part, TemplatePart::Placeholder(_))) {
+        return Err("Template needs at least one placeholder".to_string());
+    }
+    Ok(())
+}
+
+/// Trimmed clip name, or None to clear it. Names with characters that can't appear in a
+/// filename are rejected rather than silently replaced, so the user sees what they typed.
+pub fn validate_clip_name(name: &str) -> Result<Option<String>, String> {
+    let name = name.trim();
+    if name.chars().any(char::is_control) {
+        return Err("Name can't contain control characters".to_string());
+    }
+    let illegal: String = name.chars().filter(|c| ILLEGAL_FILENAME_CHARS.contains(c)).collect();
+    if !illegal.is_empty() {
+        return Err(format!("Name can't contain {}", illegal));
+    }
+    Ok((!name.is_empty()).then(|| name.to_string()))
+}
+
+/// Expand a filename template for `clip` (without extension). `index` is the clip's 1-based
+/// position in the list. Empty placeholders also drop the separator text right before them,
+/// so "{original} - {name}" becomes just the original name for unnamed clips.
+/// Falls back to the default template if `template` is invalid.
+pub fn render_template(template: &str, clip: &Clip, index: usize) -> String {
+    let parts = parse_template(template)
+        .or_else(|_| parse_template(DEFAULT_FILENAME_TEMPLATE))
+        .unwrap_or_default();
+
+    let mut rendered = String::new();
+    let mut pending_literal = "";
+    for part in parts {
+        match part {
+            TemplatePart::Literal(text) => {
+                rendered.push_str(pending_literal);
+                pending_literal = text;
+            }
+            TemplatePart::Placeholder(placeholder) => {
+                let value = placeholder_value(placeholder, clip, index);
+                if !value.is_empty() {
+                    rendered.push_str(pending_literal);
+                    rendered.push_str(&value);
+                }
+                pending_literal = "";
+            }
+        }
+    }
+    rendered.push_str(pending_literal);
+
+    let sanitized = sanitize_filename(&rendered);
+    if sanitized.is_empty() {
+        sanitize_filename(&placeholder_value("original", clip, index))
+    } else {
+        sanitized
+    }
+}
+
+fn placeholder_value(placeholder: &str, clip: &Clip, index: usize) -> String {
+    match placeholder {
+        "original" => clip.original_file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("clip")
+            .to_string(),
+        "date" => clip.timestamp.format("%Y-%m-%d").to_string(),
+        "time" => clip.timestamp.format("%H-%M-%S").to_string(),
+        "duration" => format!("{}s", (clip.trim_end - clip.trim_start).max(0.0).round() as u64),
+        "name" => clip.name.clone().unwrap_or_default().trim().to_string(),
+        "index" => index.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Replace characters that are illegal in filenames and avoid reserved Windows names
+pub fn sanitize_filename(name: &str) -> String {
+    let replaced: String = name.chars()
+        .map(|c| if ILLEGAL_FILENAME_CHARS.contains(&c) || c.is_control() { '_' } else { c })
+        .collect();
+
+    // Windows strips trailing dots and spaces, which would change the name behind our back
+    let trimmed = replaced.trim().trim_end_matches(['.', ' ']).to_string();
+
+    let stem = trimmed.split('.').next().unwrap_or_default();
+    if RESERVED_WINDOWS_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        format!("_{}", trimmed)
+    } else {
+        trimmed
+    }
+}
+
+/// Append " (2)", " (3)", ... to names that already appeared earlier in `names`
+pub fn make_names_unique(names: &mut [String]) {
+    let mut seen = std::collections::HashSet::new();
+    for name in names.iter_mut() {
+        if !seen.insert(name.to_lowercase()) {
+            let mut suffix = 2;
+            while seen.contains(&format!("{} ({})", name, suffix).to_lowercase()) {
+                suffix += 1;
+            }
+            *name = format!("{} ({})", name, suffix);
+            seen.insert(name.to_lowercase());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn sample_clip() -> Clip {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv"))
+            .expect("Failed to create clip");
+        clip.trim_start = 10.0;
+        clip.trim_end = 40.0;
+        clip
+    }
+
+    #[test]
+    fn test_default_template_matches_legacy_names() {
+        let mut clip = sample_clip();
+        assert_eq!(render_template(DEFAULT_FILENAME_TEMPLATE, &clip, 1), clip.get_output_filename());
+
+        clip.name = Some("Epic Moment".to_string());
+        assert_eq!(render_template(DEFAULT_FILENAME_TEMPLATE, &clip, 1), clip.get_output_filename());
+    }
+
+    #[test]
+    fn test_template_placeholders() {
+        let mut clip = sample_clip();
+        clip.name = Some("ace".to_string());
+        assert_eq!(
+            render_template("{name}_{date}_{time}_{duration}_{index}", &clip, 3),
+            "ace_2025-08-17_21-52-01_30s_3"
+        );
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template("{date}_{name}").is_ok());
+        assert!(validate_template("{game}_{date}").is_err());
+        assert!(validate_template("{date").is_err());
+        assert!(validate_template("date}").is_err());
+        assert!(validate_template("no placeholders").is_err());
+    }
+
+    #[test]
+    fn test_validate_clip_name() {
+        assert_eq!(validate_clip_name("  Ace round  "), Ok(Some("Ace round".to_string())));
+        assert_eq!(validate_clip_name("   "), Ok(None));
+        assert_eq!(validate_clip_name("a/b:c"), Err("Name can't contain /:".to_string()));
+        assert!(validate_clip_name("tab\there").is_err());
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("a/b:c*d?"), "a_b_c_d_");
+        assert_eq!(sanitize_filename("clip. "), "clip");
+        assert_eq!(sanitize_filename("CON"), "_CON");
+    }
+
+    #[test]
+    fn test_make_names_unique() {
+        let mut names = vec!["clip".to_string(), "other".to_string(), "clip".to_string(), "Clip".to_string()];
+        make_names_unique(&mut names);
+        assert_eq!(names, vec!["clip", "other", "clip (2)", "Clip (3)"]);
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::Path;
+use crate::core::Clip;
+
+/// Replays saved closer together than this are candidates for being the same moment
+pub const NEAR_DUPLICATE_MAX_TIME_GAP_SECS: i64 = 5;
+
+/// Durations and file sizes may differ by at most this fraction of the larger value
+pub const NEAR_DUPLICATE_MAX_RELATIVE_DIFFERENCE: f64 = 0.1;
+
+fn relative_difference(a: f64, b: f64) -> f64 {
+    let larger = a.abs().max(b.abs());
+    if larger == 0.0 {
+        0.0
+    } else {
+        (a - b).abs() / larger
+    }
+}
+
+/// Cheap overlap check on timestamp, duration and (when known) file size.
+/// Clips without a known duration are never flagged.
+pub fn are_near_duplicates(a: &Clip, a_size: Option<u64>, b: &Clip, b_size: Option<u64>) -> bool {
+    let (Some(a_duration), Some(b_duration)) = (a.video_length_seconds, b.video_length_seconds) else {
+        return false;
+    };
+
+    let time_gap = (a.timestamp - b.timestamp).num_seconds().abs();
+    if time_gap > NEAR_DUPLICATE_MAX_TIME_GAP_SECS {
+        return false;
+    }
+    if relative_difference(a_duration, b_duration) > NEAR_DUPLICATE_MAX_RELATIVE_DIFFERENCE {
+        return false;
+    }
+    match (a_size, b_size) {
+        (Some(a_size), Some(b_size)) => {
+            relative_difference(a_size as f64, b_size as f64) <= NEAR_DUPLICATE_MAX_RELATIVE_DIFFERENCE
+        }
+        _ => true,
+    }
+}
+
+/// Group clips that look like overlapping saves of the same replay buffer.
+/// Deleted and pinned clips are left out. Returns groups of 2+ clip indices, oldest first.
+pub fn find_near_duplicate_groups(clips: &[Clip], file_size: impl Fn(&Path) -> Option<u64>) -> Vec<Vec<usize>> {
+    let mut candidates: Vec<(usize, Option<u64>)> = clips.iter()
+        .enumerate()
+        .filter(|(_, clip)| !clip.is_deleted && !clip.pinned && clip.video_length_seconds.is_some())
+        .map(|(index, clip)| (index, file_size(&clip.original_file)))
+        .collect();
+    candidates.sort_by_key(|(index, _)| clips[*index].timestamp);
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut current: Vec<(usize, Option<u64>)> = Vec::new();
+
+    for candidate in candidates {
+        let joins_group = current.last().is_some_and(|&(last, last_size)| {
+            are_near_duplicates(&clips[last], last_size, &clips[candidate.0], candidate.1)
+        });
+        if !joins_group && current.len() > 1 {
+            groups.push(current.iter().map(|(index, _)| *index).collect());
+        }
+        if !joins_group {
+            current.clear();
+        }
+        current.push(candidate);
+    }
+    if current.len() > 1 {
+        groups.push(current.iter().map(|(index, _)| *index).collect());
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn clip_at(file_name: &str, length: f64) -> Clip {
+        let mut clip = Clip::new_without_target(PathBuf::from(file_name)).expect("Failed to create clip");
+        clip.video_length_seconds = Some(length);
+        clip
+    }
+
+    #[test]
+    fn test_overlapping_saves_are_grouped() {
+        let clips = vec![
+            clip_at("Replay 2025-08-17 21-52-01.mkv", 120.0),
+            clip_at("Replay 2025-08-17 21-52-04.mkv", 118.0),
+            clip_at("Replay 2025-08-17 21-58-00.mkv", 120.0),
+        ];
+
+        let groups = find_near_duplicate_groups(&clips, |_| None);
+        assert_eq!(groups, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_different_lengths_or_sizes_are_not_grouped() {
+        let clips = vec![
+            clip_at("Replay 2025-08-17 21-52-01.mkv", 120.0),
+            clip_at("Replay 2025-08-17 21-52-03.mkv", 30.0),
+        ];
+        assert!(find_near_duplicate_groups(&clips, |_| None).is_empty());
+
+        let clips = vec![
+            clip_at("Replay 2025-08-17 21-52-01.mkv", 120.0),
+            clip_at("Replay 2025-08-17 21-52-03.mkv", 120.0),
+        ];
+        let sizes = |path: &Path| Some(if path.to_string_lossy().contains("52-01") { 100 } else { 300 });
+        assert!(find_near_duplicate_groups(&clips, sizes).is_empty());
+    }
+
+    #[test]
+    fn test_pinned_clips_are_not_flagged() {
+        let mut clips = vec![
+            clip_at("Replay 2025-08-17 21-52-01.mkv", 120.0),
+            clip_at("Replay 2025-08-17 21-52-04.mkv", 120.0),
+        ];
+        clips[1].pinned = true;
+
+        assert!(find_near_duplicate_groups(&clips, |_| None).is_empty());
+    }
+}
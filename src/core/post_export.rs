@@ -0,0 +1,81 @@
+use std::path::Path;
+use std::process::Command;
+use crate::video::ffmpeg_manager::output_with_stderr_tail;
+
+/// Replaced by the exported file's path in post-export command templates
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// Split a post-export command template into program and arguments, replacing `{path}`.
+///
+/// The template is split on whitespace, with "double" or 'single' quotes grouping words,
+/// and `{path}` is substituted afterwards. The command is started directly, never through
+/// a shell, so a file name containing `;`, `&&` or `$(...)` stays a single argument.
+/// Use e.g. `cmd /C ...` or `sh -c ...` explicitly if shell features are needed.
+pub fn build_command_args(template: &str, path: &Path) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in template.chars() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err("Unclosed quote in post-export command".to_string());
+    }
+    if in_word {
+        args.push(current);
+    }
+    if args.is_empty() {
+        return Err("Post-export command is empty".to_string());
+    }
+
+    let path = path.to_string_lossy();
+    Ok(args.into_iter().map(|arg| arg.replace(PATH_PLACEHOLDER, &path)).collect())
+}
+
+/// Run the post-export command for `path` and wait for it, so call this off the UI thread
+pub fn run_post_export_command(template: &str, path: &Path) -> anyhow::Result<()> {
+    let args = build_command_args(template, path).map_err(|e| anyhow::anyhow!(e))?;
+    log::info!("Running post-export command: {:?}", args);
+
+    let output = output_with_stderr_tail(Command::new(&args[0]).args(&args[1..]))
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", args[0], e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(output.failure_message(&format!("{} failed", args[0]))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_command_args_keeps_path_as_one_argument() {
+        let path = Path::new("/clips/Replay; rm -rf ~.mkv");
+        let args = build_command_args("rclone copy {path} 'nas:Clips/Game Night'", path).unwrap();
+        assert_eq!(args, vec!["rclone", "copy", "/clips/Replay; rm -rf ~.mkv", "nas:Clips/Game Night"]);
+
+        assert!(build_command_args("upload \"{path}", path).is_err());
+        assert!(build_command_args("   ", path).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local};
+
+/// A replay sitting in the deleted directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeletedFile {
+    pub path: PathBuf,
+    /// File name the replay had before it was deleted (deleting keeps the name)
+    pub original_name: String,
+    /// File modification time. Moving a file keeps its mtime, so this is only a
+    /// deletion time on filesystems that touch files on rename.
+    pub deleted_at: Option<DateTime<Local>>,
+    pub size: u64,
+}
+
+/// Where `delete_file` put a deleted replay
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeleteOutcome {
+    /// In the operating system's recycle bin / trash
+    MovedToTrash,
+    MovedToDeletedDirectory,
+    /// The recycle bin refused the file (e.g. on a network drive), so it went to the
+    /// deleted directory instead
+    TrashFailed(String),
+}
+
+impl DeleteOutcome {
+    /// Status bar message after deleting one clip
+    pub fn message(&self) -> String {
+        match self {
+            DeleteOutcome::MovedToTrash => "Clip moved to the recycle bin".to_string(),
+            DeleteOutcome::MovedToDeletedDirectory => "Clip moved to deleted folder".to_string(),
+            DeleteOutcome::TrashFailed(reason) => {
+                format!("Couldn't use the recycle bin ({}), clip moved to deleted folder instead", reason)
+            }
+        }
+    }
+}
+
+/// Move a replay out of the way, to the OS recycle bin if `use_trash` is set and that
+/// works, otherwise into `deleted_directory` (created if needed)
+pub fn delete_file(file: &Path, deleted_directory: &Path, use_trash: bool) -> anyhow::Result<DeleteOutcome> {
+    let mut trash_error = None;
+    if use_trash {
+        match trash::delete(file) {
+            Ok(()) => return Ok(DeleteOutcome::MovedToTrash),
+            Err(e) => {
+                log::warn!("Failed to move {} to the recycle bin: {}", file.display(), e);
+                trash_error = Some(e.to_string());
+            }
+        }
+    }
+
+    let file_name = file.file_name().ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", file.display()))?;
+    std::fs::create_dir_all(deleted_directory)
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", deleted_directory.display(), e))?;
+    let deleted_path = deleted_directory.join(file_name);
+    log::info!("Moving file to deleted directory: {} -> {}", file.display(), deleted_path.display());
+    std::fs::rename(file, &deleted_path)
+        .map_err(|e| anyhow::anyhow!("Failed to move file to deleted directory: {}", e))?;
+
+    Ok(match trash_error {
+        Some(reason) => DeleteOutcome::TrashFailed(reason),
+        None => DeleteOutcome::MovedToDeletedDirectory,
+    })
+}
+
+/// Move a replay into `archive_directory` (created if needed), refusing to overwrite a file
+/// there. Falls back to copying and removing when the archive is on another drive.
+pub fn archive_file(file: &Path, archive_directory: &Path) -> anyhow::Result<PathBuf> {
+    let file_name = file.file_name().ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", file.display()))?;
+    std::fs::create_dir_all(archive_directory)
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", archive_directory.display(), e))?;
+    let destination = archive_directory.join(file_name);
+    if destination.exists() {
+        return Err(anyhow::anyhow!("{} already exists", destination.display()));
+    }
+
+    log::info!("Archiving {} -> {}", file.display(), destination.display());
+    if let Err(e) = std::fs::rename(file, &destination) {
+        log::debug!("Renaming into the archive failed ({}), copying instead", e);
+        std::fs::copy(file, &destination)
+            .map_err(|e| anyhow::anyhow!("Failed to copy {} to the archive: {}", file.display(), e))?;
+        if let Err(e) = std::fs::remove_file(file) {
+            let _ = std::fs::remove_file(&destination);
+            return Err(anyhow::anyhow!("Failed to remove {} after archiving it: {}", file.display(), e));
+        }
+    }
+    Ok(destination)
+}
+
+/// List the files in `deleted_directory`, newest first
+pub fn scan_deleted_directory(deleted_directory: &Path) -> Vec<DeletedFile> {
+    let Ok(entries) = std::fs::read_dir(deleted_directory) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<DeletedFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            let path = entry.path();
+            Some(DeletedFile {
+                original_name: path.file_name()?.to_string_lossy().to_string(),
+                deleted_at: metadata.modified().ok().map(DateTime::<Local>::from),
+                size: metadata.len(),
+                path,
+            })
+        })
+        .collect();
+
+    files.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then_with(|| a.original_name.cmp(&b.original_name)));
+    files
+}
+
+/// Move a deleted file back into `replay_directory`, refusing to overwrite anything there
+pub fn restore_file(file: &DeletedFile, replay_directory: &Path) -> anyhow::Result<PathBuf> {
+    let destination = replay_directory.join(&file.original_name);
+    if destination.exists() {
+        return Err(anyhow::anyhow!("{} already exists", destination.display()));
+    }
+
+    std::fs::rename(&file.path, &destination)
+        .map_err(|e| anyhow::anyhow!("Failed to restore {}: {}", file.original_name, e))?;
+    Ok(destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_and_restore_deleted_files() {
+        let root = std::env::temp_dir().join(format!("clip-helper-recycle-bin-test-{}", std::process::id()));
+        let deleted = root.join("deleted");
+        std::fs::create_dir_all(&deleted).expect("Failed to create test directory");
+        std::fs::write(deleted.join("Replay 2025-08-17 21-52-01.mkv"), b"replay").expect("Failed to write test file");
+        std::fs::create_dir_all(deleted.join("subdirectory")).expect("Failed to create subdirectory");
+
+        let files = scan_deleted_directory(&deleted);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].original_name, "Replay 2025-08-17 21-52-01.mkv");
+        assert_eq!(files[0].size, 6);
+
+        let restored = restore_file(&files[0], &root).expect("Failed to restore");
+        assert!(restored.exists());
+        assert!(scan_deleted_directory(&deleted).is_empty());
+
+        // A second copy with the same name must not overwrite the restored file
+        std::fs::write(deleted.join("Replay 2025-08-17 21-52-01.mkv"), b"other").expect("Failed to write test file");
+        assert!(restore_file(&scan_deleted_directory(&deleted)[0], &root).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_delete_file_to_deleted_directory() {
+        let root = std::env::temp_dir().join(format!("clip-helper-delete-test-{}", std::process::id()));
+        std::fs::create_dir_all(&root).expect("Failed to create test directory");
+        let replay = root.join("Replay 2025-08-17 21-52-01.mkv");
+        std::fs::write(&replay, b"replay").expect("Failed to write test file");
+
+        // The deleted directory doesn't exist yet
+        let deleted = root.join("deleted");
+        assert_eq!(delete_file(&replay, &deleted, false).unwrap(), DeleteOutcome::MovedToDeletedDirectory);
+        assert!(!replay.exists());
+        assert!(deleted.join("Replay 2025-08-17 21-52-01.mkv").exists());
+
+        assert!(delete_file(&replay, &deleted, false).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_archive_file_refuses_to_overwrite() {
+        let root = std::env::temp_dir().join(format!("clip-helper-archive-test-{}", std::process::id()));
+        std::fs::create_dir_all(&root).expect("Failed to create test directory");
+        let replay = root.join("Replay 2025-08-17 21-52-01.mkv");
+        std::fs::write(&replay, b"replay").expect("Failed to write test file");
+
+        let archive = root.join("archive");
+        let archived = archive_file(&replay, &archive).unwrap();
+        assert_eq!(archived, archive.join("Replay 2025-08-17 21-52-01.mkv"));
+        assert!(!replay.exists());
+
+        std::fs::write(&replay, b"another").expect("Failed to write test file");
+        assert!(archive_file(&replay, &archive).is_err());
+        assert!(replay.exists());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,77 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Show a file in the platform file manager, selecting it where supported
+pub fn reveal_in_file_manager(path: &Path) -> anyhow::Result<()> {
+    #[cfg(target_os = "windows")]
+    let result = Command::new("explorer")
+        .arg(format!("/select,{}", path.display()))
+        .spawn();
+
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .spawn();
+
+    // Most Linux file managers can't select a file, so open its folder
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let result = Command::new("xdg-open")
+        .arg(path.parent().unwrap_or(path))
+        .spawn();
+
+    result
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("Failed to open file manager for {}: {}", path.display(), e))
+}
+
+/// Open a file with its default application. Waits for the launcher to exit so a
+/// missing file association is reported, so call this off the UI thread.
+pub fn open_with_default_app(path: &Path) -> anyhow::Result<()> {
+    // `cmd /C start` succeeds even without an association (Windows shows its own
+    // "How do you want to open this file?" dialog), so ask the shell directly
+    #[cfg(target_os = "windows")]
+    return shell_execute_open(path);
+
+    #[cfg(target_os = "macos")]
+    let status = Command::new("open")
+        .arg(path)
+        .status();
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let status = Command::new("xdg-open")
+        .arg(path)
+        .status();
+
+    #[cfg(not(target_os = "windows"))]
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(anyhow::anyhow!(
+            "No application could open {} ({})", path.display(), status
+        )),
+        Err(e) => Err(anyhow::anyhow!("Failed to launch player for {}: {}", path.display(), e)),
+    }
+}
+
+/// `ShellExecuteW` with the "open" verb, which reports a missing association as an error
+#[cfg(target_os = "windows")]
+fn shell_execute_open(path: &Path) -> anyhow::Result<()> {
+    use windows::core::{w, HSTRING, PCWSTR};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::{ShellExecuteW, SE_ERR_NOASSOC};
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let file = HSTRING::from(path.as_os_str());
+    // SAFETY: every string argument is a valid null-terminated wide string or null
+    let result = unsafe {
+        ShellExecuteW(HWND::default(), w!("open"), &file, PCWSTR::null(), PCWSTR::null(), SW_SHOWNORMAL)
+    };
+    // Values above 32 mean success, anything else is one of the SE_ERR_* codes
+    match result.0 {
+        code if code > 32 => Ok(()),
+        code if code == SE_ERR_NOASSOC as isize => Err(anyhow::anyhow!(
+            "No application is associated with {}", path.display()
+        )),
+        code => Err(anyhow::anyhow!("Failed to open {} (ShellExecute error {})", path.display(), code)),
+    }
+}
@@ -0,0 +1,281 @@
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local, NaiveDate};
+use crate::core::Clip;
+
+/// Aggregate numbers shown in the clip list's statistics panel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClipStatistics {
+    pub clip_count: usize,
+    pub total_trimmed_seconds: f64,
+    pub total_original_seconds: f64,
+    pub total_file_size: u64,
+    pub exported_today: usize,
+}
+
+impl ClipStatistics {
+    /// Statistics for all non-deleted clips, with the file numbers from a `ClipFileStatistics::scan`
+    pub fn new(clips: &[Clip], files: ClipFileStatistics) -> Self {
+        Self {
+            total_file_size: files.total_file_size,
+            exported_today: files.exported_today,
+            ..Self::from_clips(clips)
+        }
+    }
+
+    /// Durations and counts only, without touching the filesystem
+    pub fn from_clips(clips: &[Clip]) -> Self {
+        let mut stats = Self::default();
+        for clip in clips.iter().filter(|clip| !clip.is_deleted) {
+            stats.clip_count += 1;
+            stats.total_trimmed_seconds += (clip.trim_end - clip.trim_start).max(0.0);
+            stats.total_original_seconds += clip.video_length_seconds.unwrap_or(0.0);
+        }
+        stats
+    }
+
+    pub fn average_clip_seconds(&self) -> f64 {
+        if self.clip_count == 0 {
+            0.0
+        } else {
+            self.total_trimmed_seconds / self.clip_count as f64
+        }
+    }
+
+    /// Markdown summary for pasting into chat
+    pub fn to_markdown(&self) -> String {
+        format!(
+            "**ClipHelper stats**\n\
+             - Clips: {}\n\
+             - Trimmed duration: {}\n\
+             - Original duration: {}\n\
+             - Storage used: {}\n\
+             - Exported today: {}\n\
+             - Average clip length: {}",
+            self.clip_count,
+            Clip::format_duration(self.total_trimmed_seconds),
+            Clip::format_duration(self.total_original_seconds),
+            format_file_size(self.total_file_size),
+            self.exported_today,
+            Clip::format_duration(self.average_clip_seconds()),
+        )
+    }
+}
+
+/// The parts of `ClipStatistics` that stat every clip and list the trimmed directory,
+/// so they're gathered on a background thread like `DirectorySizes`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ClipFileStatistics {
+    pub total_file_size: u64,
+    pub exported_today: usize,
+}
+
+impl ClipFileStatistics {
+    /// `clip_files` are the non-deleted clips' files. Exports are counted from
+    /// files in `trimmed_directory` modified today.
+    pub fn scan(clip_files: &[PathBuf], trimmed_directory: &Path) -> Self {
+        Self {
+            total_file_size: clip_files.iter()
+                .filter_map(|file| std::fs::metadata(file).ok())
+                .map(|metadata| metadata.len())
+                .sum(),
+            exported_today: Self::count_exported_today(trimmed_directory),
+        }
+    }
+
+    fn count_exported_today(trimmed_directory: &Path) -> usize {
+        let Ok(entries) = std::fs::read_dir(trimmed_directory) else {
+            return 0;
+        };
+        let today = Local::now().date_naive();
+
+        entries
+            .flatten()
+            // Skip sidecars and in-progress exports
+            .filter(|entry| {
+                let path = entry.path();
+                !crate::video::VideoProcessor::is_temp_output(&path)
+                    && !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+            })
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(|metadata| metadata.is_file())
+            .filter_map(|metadata| metadata.modified().ok())
+            .filter(|modified| DateTime::<Local>::from(*modified).date_naive() == today)
+            .count()
+    }
+}
+
+/// Number of clips recorded on each of the `days` days up to `today`, oldest first.
+/// Days without clips are included with a count of 0.
+pub fn clips_per_day(clips: &[Clip], days: usize, today: NaiveDate) -> Vec<(NaiveDate, usize)> {
+    let mut counts: Vec<(NaiveDate, usize)> = (0..days as u64)
+        .rev()
+        .filter_map(|ago| today.checked_sub_days(chrono::Days::new(ago)))
+        .map(|day| (day, 0))
+        .collect();
+    for clip in clips.iter().filter(|clip| !clip.is_deleted) {
+        let day = clip.timestamp.date_naive();
+        if let Some((_, count)) = counts.iter_mut().find(|(date, _)| *date == day) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+/// Space taken up by the replay directories. Walking them can take a while on big
+/// folders, so this is computed on a background thread and only refreshed on request.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DirectorySizes {
+    /// The watched directory, without the trimmed and deleted directories if they're inside it
+    pub watched: u64,
+    pub trimmed: u64,
+    pub deleted: u64,
+}
+
+impl DirectorySizes {
+    pub fn scan(watched: Option<&Path>, trimmed: &Path, deleted: &Path) -> Self {
+        Self {
+            watched: watched.map_or(0, |watched| directory_size(watched, &[trimmed, deleted])),
+            trimmed: directory_size(trimmed, &[]),
+            deleted: directory_size(deleted, &[]),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.watched + self.trimmed + self.deleted
+    }
+}
+
+/// Total size of the files below `directory`, skipping the `excluded` subdirectories.
+/// Symlinks aren't followed and unreadable entries are skipped.
+pub fn directory_size(directory: &Path, excluded: &[&Path]) -> u64 {
+    let excluded: Vec<PathBuf> = excluded.iter()
+        .map(|path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf()))
+        .collect();
+    let mut total = 0;
+    let mut pending = vec![directory.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let path = entry.path();
+                let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+                if !excluded.contains(&canonical) {
+                    pending.push(path);
+                }
+            } else if file_type.is_file() {
+                total += entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            }
+        }
+    }
+    total
+}
+
+/// Human-readable byte count, e.g. "2.4 GB"
+pub fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn clip_with_trim(trim_start: f64, trim_end: f64, length: f64) -> Clip {
+        let mut clip = Clip::new_without_target(PathBuf::from("Replay 2025-08-17 21-52-01.mkv"))
+            .expect("Failed to create clip");
+        clip.trim_start = trim_start;
+        clip.trim_end = trim_end;
+        clip.video_length_seconds = Some(length);
+        clip
+    }
+
+    #[test]
+    fn test_statistics_skip_deleted_clips() {
+        let mut deleted = clip_with_trim(0.0, 50.0, 60.0);
+        deleted.is_deleted = true;
+        let clips = vec![clip_with_trim(10.0, 40.0, 60.0), clip_with_trim(0.0, 10.0, 20.0), deleted];
+
+        let stats = ClipStatistics::from_clips(&clips);
+        assert_eq!(stats.clip_count, 2);
+        assert_eq!(stats.total_trimmed_seconds, 40.0);
+        assert_eq!(stats.total_original_seconds, 80.0);
+        assert_eq!(stats.average_clip_seconds(), 20.0);
+    }
+
+    #[test]
+    fn test_clips_per_day() {
+        let mut deleted = clip_with_trim(0.0, 10.0, 20.0);
+        deleted.is_deleted = true;
+        let mut older = clip_with_trim(0.0, 10.0, 20.0);
+        older.timestamp -= chrono::Duration::days(2);
+        let clips = vec![clip_with_trim(0.0, 10.0, 20.0), clip_with_trim(0.0, 10.0, 20.0), older, deleted];
+
+        // The clips are from 2025-08-17 and 2025-08-15
+        let today = NaiveDate::from_ymd_opt(2025, 8, 18).unwrap();
+        let counts: Vec<usize> = clips_per_day(&clips, 4, today).into_iter().map(|(_, count)| count).collect();
+        assert_eq!(counts, vec![1, 0, 2, 0]);
+        assert_eq!(clips_per_day(&clips, 4, today)[0].0, NaiveDate::from_ymd_opt(2025, 8, 15).unwrap());
+    }
+
+    #[test]
+    fn test_directory_size_skips_excluded() {
+        let root = std::env::temp_dir().join(format!("clip-helper-directory-size-test-{}", std::process::id()));
+        let trimmed = root.join("trimmed");
+        std::fs::create_dir_all(root.join("nested")).expect("Failed to create test directory");
+        std::fs::create_dir_all(&trimmed).expect("Failed to create test directory");
+        std::fs::write(root.join("Replay.mkv"), [0u8; 100]).expect("Failed to write test file");
+        std::fs::write(root.join("nested").join("Replay.mkv"), [0u8; 50]).expect("Failed to write test file");
+        std::fs::write(trimmed.join("Replay.mkv"), [0u8; 30]).expect("Failed to write test file");
+
+        assert_eq!(directory_size(&root, &[]), 180);
+        assert_eq!(directory_size(&root, &[&trimmed]), 150);
+        let sizes = DirectorySizes::scan(Some(&root), &trimmed, &root.join("deleted"));
+        assert_eq!(sizes, DirectorySizes { watched: 150, trimmed: 30, deleted: 0 });
+        assert_eq!(sizes.total(), 180);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_clip_file_statistics_scan() {
+        let root = std::env::temp_dir().join(format!("clip-helper-clip-files-test-{}", std::process::id()));
+        let trimmed = root.join("trimmed");
+        std::fs::create_dir_all(&trimmed).expect("Failed to create test directory");
+        std::fs::write(root.join("Replay.mkv"), [0u8; 100]).expect("Failed to write test file");
+        std::fs::write(trimmed.join("Replay.mkv"), [0u8; 30]).expect("Failed to write test file");
+        std::fs::write(trimmed.join("Replay.json"), "{}").expect("Failed to write test file");
+
+        // Missing clip files are skipped, and so are sidecars in the trimmed directory
+        let files = ClipFileStatistics::scan(&[root.join("Replay.mkv"), root.join("Missing.mkv")], &trimmed);
+        assert_eq!(files, ClipFileStatistics { total_file_size: 100, exported_today: 1 });
+
+        let stats = ClipStatistics::new(&[clip_with_trim(0.0, 10.0, 20.0)], files);
+        assert_eq!((stats.clip_count, stats.total_file_size, stats.exported_today), (1, 100, 1));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(512), "512 B");
+        assert_eq!(format_file_size(1536), "1.5 KB");
+        assert_eq!(format_file_size(2_576_980_378), "2.4 GB");
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use crate::core::ClipMetadata;
+
+/// A failed webhook request is retried this many times
+pub const WEBHOOK_MAX_RETRIES: u32 = 3;
+/// Wait before the first retry, doubled for each one after it
+const WEBHOOK_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Event a webhook is posted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookTrigger {
+    /// A new replay appeared in the watched directory
+    ClipDetected,
+    ExportComplete,
+    /// Anything shown in the status bar as an error
+    Error,
+}
+
+impl WebhookTrigger {
+    pub const ALL: [WebhookTrigger; 3] = [Self::ClipDetected, Self::ExportComplete, Self::Error];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ClipDetected => "Clip detected",
+            Self::ExportComplete => "Export complete",
+            Self::Error => "Error",
+        }
+    }
+}
+
+/// A URL that gets a POST for every event of one kind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub trigger: WebhookTrigger,
+    /// Send the clip's trim range, name and audio tracks along with the event
+    #[serde(default)]
+    pub include_metadata: bool,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            trigger: WebhookTrigger::ExportComplete,
+            include_metadata: true,
+        }
+    }
+}
+
+/// JSON body of every webhook request, e.g.
+/// `{"event":"ExportComplete","timestamp":"2025-08-17T21:52:01+02:00","message":"Exported Replay.mkv",
+/// "file":"C:/Replays/Replay.mkv","output_path":"C:/Replays/trimmed/Replay.mkv","clip":{...}}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: WebhookTrigger,
+    pub timestamp: DateTime<Local>,
+    pub message: String,
+    /// The replay the event is about
+    pub file: Option<PathBuf>,
+    /// The exported file, for `ExportComplete`
+    pub output_path: Option<PathBuf>,
+    /// Only sent to webhooks with `include_metadata`
+    pub clip: Option<ClipMetadata>,
+}
+
+impl WebhookPayload {
+    pub fn new(event: WebhookTrigger, message: impl Into<String>) -> Self {
+        Self {
+            event,
+            timestamp: Local::now(),
+            message: message.into(),
+            file: None,
+            output_path: None,
+            clip: None,
+        }
+    }
+
+    /// Payload "Test Webhook" in the settings sends
+    pub fn test(trigger: WebhookTrigger) -> Self {
+        Self {
+            file: Some(PathBuf::from("Replay 2025-08-17 21-52-01.mkv")),
+            ..Self::new(trigger, "Test notification from Clip Helper")
+        }
+    }
+}
+
+/// How long to wait before retry number `retry` (starting at 1)
+pub fn webhook_retry_delay(retry: u32) -> Duration {
+    WEBHOOK_RETRY_BASE_DELAY * 2u32.pow(retry.saturating_sub(1))
+}
+
+/// Posts webhooks from a background thread so a slow or unreachable server never
+/// blocks the UI. Requests are fire-and-forget; failures are only logged.
+pub struct WebhookSender {
+    requests: mpsc::UnboundedSender<(String, WebhookPayload)>,
+}
+
+impl WebhookSender {
+    pub fn new() -> Self {
+        let (requests, mut request_rx) = mpsc::unbounded_channel::<(String, WebhookPayload)>();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().expect("Failed to create webhook runtime");
+            rt.block_on(async {
+                let client = reqwest::Client::builder()
+                    .timeout(WEBHOOK_TIMEOUT)
+                    .build()
+                    .unwrap_or_default();
+                while let Some((url, payload)) = request_rx.recv().await {
+                    tokio::spawn(post_with_retries(client.clone(), url, payload));
+                }
+            });
+        });
+
+        Self { requests }
+    }
+
+    /// Post `payload` to every webhook configured for its event. The clip metadata is
+    /// stripped for webhooks without `include_metadata`.
+    pub fn notify(&self, webhooks: &[WebhookConfig], payload: WebhookPayload) {
+        for webhook in webhooks.iter().filter(|w| w.trigger == payload.event && !w.url.trim().is_empty()) {
+            let mut payload = payload.clone();
+            if !webhook.include_metadata {
+                payload.clip = None;
+            }
+            if self.requests.send((webhook.url.trim().to_string(), payload)).is_err() {
+                log::error!("Webhook thread stopped, not posting to {}", webhook.url);
+            }
+        }
+    }
+}
+
+impl Default for WebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn post_with_retries(client: reqwest::Client, url: String, payload: WebhookPayload) {
+    for attempt in 0..=WEBHOOK_MAX_RETRIES {
+        if attempt > 0 {
+            tokio::time::sleep(webhook_retry_delay(attempt)).await;
+        }
+        match client.post(&url).json(&payload).send().await {
+            Ok(response) if response.status().is_success() => {
+                log::debug!("Webhook {} answered {}", url, response.status());
+                return;
+            }
+            Ok(response) => log::warn!("Webhook {} answered {} (attempt {})", url, response.status(), attempt + 1),
+            Err(e) => log::warn!("Webhook {} failed (attempt {}): {}", url, attempt + 1, e),
+        }
+    }
+    log::error!("Giving up on webhook {} after {} retries", url, WEBHOOK_MAX_RETRIES);
+}
+
+/// Post a test payload once, without retries, and return the HTTP status code.
+/// Blocks until the server answers, so call this off the UI thread.
+pub fn send_test_webhook(webhook: &WebhookConfig) -> anyhow::Result<u16> {
+    let mut payload = WebhookPayload::test(webhook.trigger);
+    if !webhook.include_metadata {
+        payload.clip = None;
+    }
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create webhook runtime: {}", e))?;
+    rt.block_on(async {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))?;
+        let response = client.post(webhook.url.trim()).json(&payload).send().await
+            .map_err(|e| anyhow::anyhow!("Request to {} failed: {}", webhook.url, e))?;
+        Ok(response.status().as_u16())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_retry_delay_doubles() {
+        assert_eq!(webhook_retry_delay(1), Duration::from_secs(1));
+        assert_eq!(webhook_retry_delay(2), Duration::from_secs(2));
+        assert_eq!(webhook_retry_delay(3), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_webhook_payload_serializes_event_name() {
+        let json = serde_json::to_value(WebhookPayload::new(WebhookTrigger::ClipDetected, "New clip")).unwrap();
+        assert_eq!(json["event"], "ClipDetected");
+        assert_eq!(json["message"], "New clip");
+        assert!(json["clip"].is_null());
+    }
+}
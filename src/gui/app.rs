@@ -1,9 +1,9 @@
 use eframe::egui;
-use crate::core::{Clip, AppConfig, FileMonitor, NewReplayFile, clip::ClipDuration};
+use crate::core::{Clip, AppConfig, FileMonitor, NewReplayFile, clip::ClipDuration, config::ClipListView};
 use crate::video::{VideoPreview, WaveformData};
-use crate::hotkeys::{HotkeyManager, HotkeyEvent};
-use crate::gui::timeline::TimelineWidget;
-use crate::gui::clip_list_renderer::ClipListRenderer;
+use crate::hotkeys::{HotkeyManager, HotkeyEvent, HotkeyConflict, BindingRegistration};
+use crate::gui::timeline::{TimelineWidget, TrimHandle};
+use crate::gui::clip_list_renderer::{ClipContextAction, ClipItemState, ClipListRenderer, RenameOutcome};
 use crate::audio::AudioConfirmation;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -12,6 +12,50 @@ use tokio::sync::broadcast;
 use chrono::Local;
 use serde::{Deserialize, Serialize};
 
+/// How long toast-style status messages stay visible
+const TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Delay before selecting the next clip after an export, so it can be cancelled
+const AUTO_ADVANCE_DELAY: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Shorter toast for feedback on repeated keyboard actions
+const TRIM_POINT_TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How often near-duplicate detection re-runs (it stats every clip's file)
+const NEAR_DUPLICATE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How often the status bar re-reads the free space of the export volume
+const FREE_SPACE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How long cached clip statistics stay valid
+const STATISTICS_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Days shown in the statistics panel's clips per day chart
+const CLIPS_PER_DAY_DAYS: usize = 14;
+
+/// How often the open recycle bin window rescans the deleted directory
+const RECYCLE_BIN_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Trackpad scroll distance treated as one mouse wheel notch
+const WHEEL_POINTS_PER_NOTCH: f32 = 50.0;
+
+/// Page-wise wheel scrolling (rare, e.g. with some mouse drivers) counted as this many notches
+const WHEEL_NOTCHES_PER_PAGE: f32 = 10.0;
+
+/// Peaks closer to the playhead than this count as the current one when hopping between peaks
+const PEAK_JUMP_EPSILON: f64 = 0.1;
+
+/// Target size offered when two-pass encoding is first turned on, Discord's upload limit
+const DEFAULT_TARGET_SIZE_MB: f64 = 10.0;
+
+/// Which loud moment the peak buttons seek to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PeakJump {
+    Previous,
+    Loudest,
+    Next,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DurationRequest {
     pub timestamp: chrono::DateTime<Local>,
@@ -24,6 +68,10 @@ pub struct SessionGroup {
     pub start_time: String, // "14:56"
     pub end_time: String, // "17:11"
     pub clips: Vec<usize>, // indices into the main clips vector
+    /// Started by a "Split session here" anchor rather than a time gap
+    pub manual_break: bool,
+    /// The "Pinned" group listed above the sessions, holding every pinned clip
+    pub pinned: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -40,40 +88,284 @@ pub struct ClipHelperApp {
     pub clips: Vec<Clip>,
     pub selected_clip_index: Option<usize>,
     pub video_preview: Option<VideoPreview>,
+    /// Waveform of the selected clip's first audio track, keyed by file path
     pub waveforms: HashMap<String, WaveformData>,
+    /// Waveform extractions running in the background
+    pub waveform_loads: Vec<WaveformLoad>,
+    /// Dropped files and folders being probed before they are added as clips
+    pub video_imports: Vec<VideoImport>,
     pub hotkey_receiver: broadcast::Receiver<HotkeyEvent>,
     pub file_monitor: Option<FileMonitor>,
     pub file_receiver: Option<broadcast::Receiver<NewReplayFile>>,
     pub new_clip_name: String,
+    /// Clip whose name is being edited in place in the clip list
+    pub renaming_clip_index: Option<usize>,
+    /// Name typed into the clip list's rename field
+    pub rename_buffer: String,
+    /// File name the settings' timestamp pattern is tested against
+    pub timestamp_pattern_sample: String,
+    /// Outcome of the last pattern test: the parsed time or why nothing matched
+    pub timestamp_pattern_test: Option<Result<String, String>>,
+    /// Comma-separated tags of the selected clip as typed in the editor
+    pub tags_text: String,
+    /// Tags typed in the batch panel, added to every multi-selected clip by "Tag All"
+    pub batch_tags_text: String,
+    /// Clip list filter, matched against names, notes and tags
+    pub clip_search: String,
+    /// Clips Ctrl+clicked together with the selected one, e.g. to merge them
+    pub selected_clip_indices: std::collections::HashSet<usize>,
+    /// Merge of several replays into one running in the background
+    pub clip_merge: Option<ClipMerge>,
+    /// "Export session as single file" in progress
+    pub session_export: Option<SessionExport>,
+    /// Text of the clip editor's "Export to" field
+    pub export_directory_text: String,
+    /// GIF or WebP, picked in the clip editor's animated export drop-down
+    pub animated_export_format: crate::video::ExportFormat,
     pub pending_clip_requests: Vec<PendingClipRequest>,
     pub duration_requests: Vec<DurationRequest>,
     pub watched_directory: Option<std::path::PathBuf>,
     pub show_directory_dialog: bool,
     pub show_settings_dialog: bool,
     pub status_message: String,
+    /// Output of the most recent successful export, for "Copy path"
+    pub last_export_path: Option<PathBuf>,
     pub directory_browser_path: std::path::PathBuf,
     pub file_browser_path: std::path::PathBuf, // For file browser dialog
     pub show_sound_file_browser: bool, // Whether to show the sound file browser
+    /// Duration (in seconds) whose custom sound the browser picks; None picks the main sound file
+    pub sound_file_browser_duration: Option<u32>,
     pub timeline_widget: TimelineWidget,
     pub show_drives_view: bool,
     /// Last time we checked for video info updates (for clips that might still be writing)
     pub last_video_info_check: std::time::Instant,
+    pub last_corrupted_clip_retry: std::time::Instant,
     /// Last time we processed thumbnail results (to avoid every-frame processing)
     pub last_thumbnail_processing: std::time::Instant,
     /// Whether we've done the initial file scan yet
     pub initial_scan_completed: bool,
     /// Audio confirmation system for clip detection sounds
     pub audio_confirmation: Option<AudioConfirmation>,
+    /// Shared limit on concurrently running FFmpeg/ffprobe jobs
+    pub ffmpeg_pool: Arc<crate::video::FfmpegProcessPool>,
     /// Smart thumbnail cache for video preview
     pub smart_thumbnail_cache: Option<Arc<crate::video::SmartThumbnailCache>>,
     /// New thread-safe media controller for in-UI playback
     pub media_controller: Option<Arc<std::sync::Mutex<crate::video::MediaController>>>,
+    /// Which playback/trim buttons are enabled this frame
+    pub playback_availability: crate::video::PlaybackAvailability,
+    pub availability_debouncer: crate::video::AvailabilityDebouncer,
     /// Async video info loader for non-blocking video info loading
     pub video_info_manager: crate::video::VideoInfoManager,
     /// Hover thumbnail manager for clip list previews
     pub hover_thumbnail_manager: crate::video::HoverThumbnailManager,
     /// Currently hovered clip file to avoid spam calling hover methods
     pub current_hover_target: Option<std::path::PathBuf>,
+    /// Background queue that runs exports one at a time
+    pub export_queue: crate::video::ExportQueue,
+    /// Exports left over from the previous session, waiting for the user to resume or discard them
+    pub pending_export_specs: Vec<crate::video::ExportJobSpec>,
+    pub show_resume_exports_dialog: bool,
+    /// Existing clip that the most recently added clip appears to duplicate
+    pub duplicate_of: Option<std::path::PathBuf>,
+    /// New replays to fingerprint once OBS has finished writing them
+    pub pending_duplicate_checks: std::collections::HashSet<PathBuf>,
+    /// Fingerprint comparisons of new replays running in the background
+    pub duplicate_checks: Vec<DuplicateCheck>,
+    /// Export whose output failed verification, offered for a re-export
+    pub failed_export: Option<crate::video::ExportJob>,
+    /// Window layout changed since it was last written to the config
+    pub layout_dirty: bool,
+    /// Last time the window layout was saved (saves are debounced to once per second)
+    pub last_layout_save: std::time::Instant,
+    /// Clip highlighted by keyboard navigation in the clip list
+    pub keyboard_focus_index: Option<usize>,
+    /// Whether arrow keys currently drive the clip list (false once focus moves to the editor)
+    pub clip_list_has_focus: bool,
+    /// Scroll the clip list so the keyboard-focused clip is visible on the next frame
+    pub scroll_to_keyboard_focus: bool,
+    /// `resolved_output_names` and the `output_names_key` they were resolved for
+    pub output_names: Option<(u64, Vec<Option<String>>)>,
+    /// Statistics panel results, recomputed at most every STATISTICS_TTL while expanded
+    pub statistics_cache: Option<(std::time::Instant, crate::core::ClipStatistics)>,
+    /// File sizes and today's exports for the statistics panel, gathered in the background
+    pub statistics_scan: Option<std::thread::JoinHandle<crate::core::ClipFileStatistics>>,
+    /// Last directory size walk and when it finished; only refreshed on request
+    pub directory_sizes: Option<(chrono::DateTime<chrono::Local>, crate::core::DirectorySizes)>,
+    pub directory_size_scan: Option<std::thread::JoinHandle<crate::core::DirectorySizes>>,
+    /// Recent log messages for the log panel
+    pub log_buffer: crate::core::logging::LogBuffer,
+    pub show_log_panel: bool,
+    /// Finished exports, shown in the "Recent Exports" window
+    pub export_history: crate::core::export_history::ExportHistory,
+    pub show_recent_exports: bool,
+    /// Least severe level the log panel lists
+    pub log_panel_level: log::LevelFilter,
+    /// Codec details for the editor's "Technical Info" section, probed on first expand
+    pub ffprobe_cache: crate::video::FfprobeCache,
+    /// Toast-style status message and when it expires; cleared only if nothing replaced it
+    pub status_toast: Option<(std::time::Instant, String)>,
+    /// Free bytes on the export volume and when they were read, for the status bar
+    pub free_space: Option<(std::time::Instant, Option<u64>)>,
+    /// Clip to select once the instant passes, set after an export with auto-advance on
+    pub pending_auto_advance: Option<(std::time::Instant, usize)>,
+    /// Clips matched to a duration hotkey that get exported once their video info is valid
+    pub pending_auto_exports: std::collections::HashSet<PathBuf>,
+    /// Output paths of queued automatic exports, which play the confirmation sound when done
+    pub auto_export_outputs: std::collections::HashSet<PathBuf>,
+    pub post_export_hooks: Vec<PostExportHook>,
+    /// Posts the configured webhooks in the background
+    pub webhooks: crate::core::webhooks::WebhookSender,
+    /// "Test Webhook" request running for the webhook with this URL. Keyed by URL rather
+    /// than position, so removing another row while it runs can't misattribute it.
+    pub webhook_test: Option<(String, std::thread::JoinHandle<anyhow::Result<u16>>)>,
+    /// Outcome of the last "Test Webhook" and the URL it was for, shown next to that webhook
+    pub webhook_test_result: Option<(String, String)>,
+    /// Clip durations sharing a key combination in the loaded config
+    pub hotkey_conflicts: Vec<HotkeyConflict>,
+    /// Shared with the hotkey thread; None in tests
+    pub hotkey_manager: Option<Arc<std::sync::Mutex<HotkeyManager>>>,
+    /// Result of the last registration of each hotkey
+    pub hotkey_registrations: Vec<BindingRegistration>,
+    /// Full text (including FFmpeg output) of the last error shown in the status bar
+    pub error_details: Option<String>,
+    pub show_error_details_dialog: bool,
+    /// Partial exports from a crashed or killed session, offered for cleanup in the status bar
+    pub stale_temp_exports: Vec<PathBuf>,
+    /// Groups of clips that look like overlapping saves of the same moment
+    pub near_duplicate_groups: Vec<Vec<PathBuf>>,
+    pub last_near_duplicate_check: Option<std::time::Instant>,
+    /// Clips waiting for the user to confirm "keep one, delete others"
+    pub pending_duplicate_deletion: Option<Vec<PathBuf>>,
+    /// Dropped paths from outside the replay directory, waiting for "copy there first?"
+    pub pending_drop_copy: Option<Vec<PathBuf>>,
+    /// Clips of a session waiting for the user to confirm "Delete session"
+    pub pending_session_deletion: Option<Vec<PathBuf>>,
+    pub show_recycle_bin: bool,
+    /// Help overlay listing the keyboard shortcuts and hotkey bindings, toggled with ? or F1
+    pub show_keyboard_shortcuts: bool,
+    /// Set by the help overlay's "Customize…" so the settings open at the hotkey bindings
+    pub scroll_settings_to_hotkeys: bool,
+    pub recycle_bin_files: Vec<crate::core::recycle_bin::DeletedFile>,
+    pub last_recycle_bin_scan: Option<std::time::Instant>,
+    /// Deleted file waiting for the user to confirm permanent deletion
+    pub pending_permanent_deletion: Option<crate::core::recycle_bin::DeletedFile>,
+    /// Clip delete waiting for confirmation
+    pub pending_clip_deletion: Option<PendingClipDeletion>,
+    /// "Open in external player" launches that haven't reported back yet
+    pub external_player_launches: Vec<std::thread::JoinHandle<anyhow::Result<()>>>,
+    /// Loudness measurements running in the background
+    pub loudness_measurements: Vec<LoudnessMeasurement>,
+    /// Scene detections running in the background
+    pub scene_detections: Vec<SceneDetection>,
+    /// Leading black checks running in the background
+    pub black_detections: Vec<BlackDetection>,
+    pub motion_detections: Vec<MotionDetection>,
+    /// Single audio tracks being written from the audio controls
+    pub audio_track_exports: Vec<AudioTrackExport>,
+    /// Preview without decoding video; seeking plays audio snippets instead
+    pub audio_only_preview: bool,
+    /// Created the first time the window is closed with `minimize_to_tray` on
+    pub tray: Option<crate::gui::tray::AppTray>,
+    /// The window is hidden and only the tray icon is left
+    pub hidden_in_tray: bool,
+    /// "Quit" was chosen in the tray, so the next close request really closes
+    pub quit_requested: bool,
+}
+
+/// Post-export command running on a background thread
+pub struct PostExportHook {
+    pub output: PathBuf,
+    pub handle: std::thread::JoinHandle<anyhow::Result<()>>,
+}
+
+/// Timeline waveform being extracted on a background thread
+pub struct WaveformLoad {
+    pub file: PathBuf,
+    /// Set when another clip is selected, which kills the load's FFmpeg process
+    pub cancel: Arc<std::sync::atomic::AtomicBool>,
+    pub handle: std::thread::JoinHandle<anyhow::Result<WaveformData>>,
+}
+
+/// Selected clip the user pressed Delete on, shown in a confirmation dialog
+pub struct PendingClipDeletion {
+    pub index: usize,
+    pub dont_ask_again: bool,
+}
+
+/// Files dropped onto the window, probed on a background thread
+pub struct VideoImport {
+    pub handle: std::thread::JoinHandle<Vec<(PathBuf, anyhow::Result<crate::video::VideoInfo>)>>,
+}
+
+/// A new replay being fingerprinted and compared with the existing clips on a background thread
+pub struct DuplicateCheck {
+    pub file: PathBuf,
+    pub handle: std::thread::JoinHandle<anyhow::Result<DuplicateCheckResult>>,
+}
+
+pub struct DuplicateCheckResult {
+    pub fingerprint: String,
+    /// Existing clip with the same size and fingerprint
+    pub duplicate_of: Option<PathBuf>,
+    /// Fingerprints of existing clips computed along the way, to be stored on them
+    pub computed: Vec<(PathBuf, String)>,
+}
+
+/// A background loudness measurement of one clip's trim range
+pub struct LoudnessMeasurement {
+    pub file: PathBuf,
+    pub range: (f64, f64),
+    pub handle: std::thread::JoinHandle<anyhow::Result<crate::audio::LoudnessResult>>,
+}
+
+/// Replays being concatenated into `output` on a background thread
+pub struct ClipMerge {
+    pub output: PathBuf,
+    pub handle: std::thread::JoinHandle<anyhow::Result<crate::video::merge::ConcatMode>>,
+}
+
+/// A session's trimmed clips being joined into `output` on a background thread
+pub struct SessionExport {
+    pub output: PathBuf,
+    /// File names of the clips in the order they're joined, for the progress window
+    pub clip_names: Vec<String>,
+    /// Recording time of the session's first clip, used to date the merged clip
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    /// Index of the clip being cut, `clip_names.len()` while joining
+    pub progress: Arc<std::sync::atomic::AtomicUsize>,
+    pub handle: std::thread::JoinHandle<anyhow::Result<crate::video::merge::ConcatMode>>,
+}
+
+/// A background FFmpeg scene detection over a whole clip
+pub struct SceneDetection {
+    pub file: PathBuf,
+    /// Clip length in seconds, for the progress bar
+    pub duration: f64,
+    /// Milliseconds FFmpeg has decoded so far
+    pub progress: Arc<std::sync::atomic::AtomicU64>,
+    /// Set from the marker panel to stop FFmpeg early
+    pub cancel: Arc<std::sync::atomic::AtomicBool>,
+    pub handle: std::thread::JoinHandle<anyhow::Result<Vec<crate::video::SceneChange>>>,
+}
+
+/// Leading black frame check queued in the FFmpeg process pool
+pub struct BlackDetection {
+    pub file: PathBuf,
+    pub job: crate::video::JobHandle,
+    pub result: std::sync::mpsc::Receiver<anyhow::Result<f64>>,
+}
+
+/// First action frame search running on a background thread
+pub struct MotionDetection {
+    pub file: PathBuf,
+    pub handle: std::thread::JoinHandle<anyhow::Result<f64>>,
+}
+
+/// One audio track of a clip being written to its own file on a background thread
+pub struct AudioTrackExport {
+    pub output: PathBuf,
+    pub handle: std::thread::JoinHandle<anyhow::Result<()>>,
 }
 
 impl ClipHelperApp {
@@ -105,15 +397,22 @@ impl ClipHelperApp {
         };
 
         // Set up hotkeys
-        let (hotkey_manager, hotkey_receiver) = HotkeyManager::new(&config)?;
+        let hotkey_conflicts = HotkeyManager::validate_bindings(&config.hotkeys);
+        for conflict in &hotkey_conflicts {
+            log::warn!("Hotkey conflict: {}", conflict);
+        }
+        let (hotkey_manager, hotkey_receiver, hotkey_registrations) = HotkeyManager::new(&config)?;
         
-        // Store hotkey manager in a way that keeps it alive
-        // This is a simplified version - in practice you'd want better lifecycle management
+        // The GUI keeps a handle to re-register hotkeys after they're edited in settings
+        let hotkey_manager = Arc::new(std::sync::Mutex::new(hotkey_manager));
+        let thread_hotkey_manager = hotkey_manager.clone();
         log::info!("Starting hotkey processing thread...");
         std::thread::spawn(move || {
             let mut iteration = 0;
             loop {
-                hotkey_manager.process_events();
+                if let Ok(manager) = thread_hotkey_manager.lock() {
+                    manager.process_events();
+                }
                 iteration += 1;
                 
                 // Log heartbeat every 10 seconds (1000 iterations * 10ms)
@@ -130,7 +429,7 @@ impl ClipHelperApp {
             if let Some(ref last_dir) = config.last_watched_directory {
                 if last_dir.exists() {
                     log::info!("Restoring last watched directory: {}", last_dir.display());
-                    match FileMonitor::new(last_dir) {
+                    match FileMonitor::new(last_dir, config.timestamp_pattern()) {
                         Ok((monitor, receiver)) => {
                             log::info!("File monitoring initialized for {}", last_dir.display());
                             (Some(monitor), Some(receiver), Some(last_dir.clone()))
@@ -168,8 +467,16 @@ impl ClipHelperApp {
             }
         };
 
+        crate::video::set_hdr_tonemapping(config.hdr_tonemap_previews, config.hdr_tonemap_previews && config.hdr_tonemap_playback);
+
+        let mut timeline_widget = TimelineWidget::new();
+        timeline_widget.timecode_format = config.timecode_format;
+        timeline_widget.waveform_scale = config.waveform_scale;
+        timeline_widget.waveform_color = config.waveform_color;
+
         // Initialize smart thumbnail cache
-        let smart_thumbnail_cache = match crate::video::SmartThumbnailCache::new() {
+        let ffmpeg_pool = Arc::new(crate::video::FfmpegProcessPool::new(config.ffmpeg_max_concurrent));
+        let smart_thumbnail_cache = match crate::video::SmartThumbnailCache::new(ffmpeg_pool.clone(), config.thumbnail_cache_mb * 1024 * 1024) {
             Ok(cache) => {
                 log::info!("Smart thumbnail cache initialized successfully");
                 Some(Arc::new(cache))
@@ -180,36 +487,131 @@ impl ClipHelperApp {
             }
         };
 
+        // Restore exports that were still queued when the app last closed
+        let pending_export_specs = Self::load_export_queue();
+        let mut export_history = crate::core::export_history::ExportHistory::load(
+            &crate::core::export_history::ExportHistory::file_path());
+        export_history.prune_missing();
+        if !pending_export_specs.is_empty() {
+            log::info!("Found {} pending exports from previous session", pending_export_specs.len());
+        }
+
+        // Test-encoding with each hardware encoder takes a moment, so have the result
+        // ready before the settings or an export need it
+        std::thread::spawn(|| {
+            crate::video::available_encoders();
+        });
+
         let app = Self {
             config,
             clips,
             selected_clip_index: None,
             video_preview: None,
             waveforms: HashMap::new(),
+            waveform_loads: Vec::new(),
+            video_imports: Vec::new(),
             hotkey_receiver,
             file_monitor,
             file_receiver,
             new_clip_name: String::new(),
+            renaming_clip_index: None,
+            rename_buffer: String::new(),
+            timestamp_pattern_sample: String::new(),
+            timestamp_pattern_test: None,
+            tags_text: String::new(),
+            batch_tags_text: String::new(),
+            clip_search: String::new(),
+            selected_clip_indices: std::collections::HashSet::new(),
+            clip_merge: None,
+            session_export: None,
+            export_directory_text: String::new(),
+            animated_export_format: crate::video::ExportFormat::Gif,
             pending_clip_requests: Vec::new(),
             duration_requests: Vec::new(),
             watched_directory,
             show_directory_dialog: false,
             show_settings_dialog: false,
             status_message: unavailable_dir_message.unwrap_or_default(),
+            last_export_path: None,
             directory_browser_path: std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("C:\\")),
             file_browser_path: std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("C:\\")),
             show_sound_file_browser: false,
-            timeline_widget: TimelineWidget::new(),
+            sound_file_browser_duration: None,
+            timeline_widget,
             show_drives_view: false,
             last_video_info_check: std::time::Instant::now(),
+            last_corrupted_clip_retry: std::time::Instant::now(),
             last_thumbnail_processing: std::time::Instant::now(),
             initial_scan_completed: false,
             audio_confirmation,
+            ffmpeg_pool: ffmpeg_pool.clone(),
             smart_thumbnail_cache,
             media_controller: None,
-            video_info_manager: crate::video::VideoInfoManager::new(),
-            hover_thumbnail_manager: crate::video::HoverThumbnailManager::new(),
+            playback_availability: crate::video::PlaybackAvailability::ALL,
+            availability_debouncer: crate::video::AvailabilityDebouncer::new(),
+            video_info_manager: crate::video::VideoInfoManager::new(ffmpeg_pool.clone()),
+            hover_thumbnail_manager: crate::video::HoverThumbnailManager::new(ffmpeg_pool.clone()),
             current_hover_target: None,
+            export_queue: crate::video::ExportQueue::new(ffmpeg_pool.clone()),
+            show_resume_exports_dialog: !pending_export_specs.is_empty(),
+            pending_export_specs,
+            duplicate_of: None,
+            pending_duplicate_checks: std::collections::HashSet::new(),
+            duplicate_checks: Vec::new(),
+            failed_export: None,
+            layout_dirty: false,
+            last_layout_save: std::time::Instant::now(),
+            keyboard_focus_index: None,
+            clip_list_has_focus: true,
+            scroll_to_keyboard_focus: false,
+            output_names: None,
+            statistics_cache: None,
+            statistics_scan: None,
+            directory_sizes: None,
+            directory_size_scan: None,
+            log_buffer: crate::core::logging::LogBuffer::from_logger(),
+            show_log_panel: false,
+            export_history,
+            show_recent_exports: false,
+            log_panel_level: log::LevelFilter::Info,
+            ffprobe_cache: crate::video::FfprobeCache::default(),
+            status_toast: None,
+            free_space: None,
+            pending_auto_advance: None,
+            pending_auto_exports: std::collections::HashSet::new(),
+            auto_export_outputs: std::collections::HashSet::new(),
+            post_export_hooks: Vec::new(),
+            webhooks: crate::core::webhooks::WebhookSender::new(),
+            webhook_test: None,
+            webhook_test_result: None,
+            hotkey_conflicts,
+            hotkey_manager: Some(hotkey_manager),
+            hotkey_registrations,
+            error_details: None,
+            show_error_details_dialog: false,
+            stale_temp_exports: Vec::new(),
+            near_duplicate_groups: Vec::new(),
+            last_near_duplicate_check: None,
+            pending_duplicate_deletion: None,
+            pending_drop_copy: None,
+            pending_session_deletion: None,
+            show_recycle_bin: false,
+            show_keyboard_shortcuts: false,
+            scroll_settings_to_hotkeys: false,
+            recycle_bin_files: Vec::new(),
+            last_recycle_bin_scan: None,
+            pending_permanent_deletion: None,
+            pending_clip_deletion: None,
+            external_player_launches: Vec::new(),
+            loudness_measurements: Vec::new(),
+            scene_detections: Vec::new(),
+            black_detections: Vec::new(),
+            motion_detections: Vec::new(),
+            audio_track_exports: Vec::new(),
+            audio_only_preview: false,
+            tray: None,
+            hidden_in_tray: false,
+            quit_requested: false,
         };
 
         // Don't load saved clips here - we'll apply saved config after scanning files
@@ -243,6 +645,12 @@ impl ClipHelperApp {
             }
             
             self.selected_clip_index = Some(index);
+            self.timeline_widget.reset_zoom();
+            self.new_clip_name = self.clips[index].name.clone().unwrap_or_default();
+            self.tags_text = self.clips[index].tags.join(", ");
+            self.export_directory_text = self.clips[index].export_directory_override.as_ref()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default();
             
             // Request video info asynchronously if not already loaded or pending
             if let Some(clip) = self.clips.get(index) {
@@ -252,6 +660,10 @@ impl ClipHelperApp {
                 }
             }
             
+            self.request_loudness_measurement(index);
+            self.request_waveform(index);
+            self.request_motion_start(index);
+            
             // Initialize video preview for selected clip
             if let Some(clip) = self.clips.get(index) {
                 if let Some(duration) = clip.video_length_seconds {
@@ -266,14 +678,39 @@ impl ClipHelperApp {
                     self.video_preview = Some(preview);
                     
                     // Create media controller - video will be set when we have egui context
-                    let controller = Arc::new(std::sync::Mutex::new(crate::video::MediaController::new()));
-                    self.media_controller = Some(controller);
+                    self.media_controller = Some(self.new_media_controller());
                     log::info!("Created MediaController for clip: {}", clip.get_output_filename());
                 } else {
                     // Video info not loaded yet, create basic preview
                     self.video_preview = Some(VideoPreview::new(clip.trim_end));
                 }
             }
+            
+            self.prefetch_neighbours(index);
+        }
+    }
+    
+    /// Load video info and the opening thumbnail of the clips above and below `index` in
+    /// the list, so moving to them with the arrow keys shows them right away
+    fn prefetch_neighbours(&mut self, index: usize) {
+        let order = self.clip_list_display_order();
+        let neighbours: Vec<usize> = [-1, 1].into_iter()
+            .filter_map(|step| Self::step_focus_index(&order, Some(index), step))
+            .filter(|&neighbour| neighbour != index)
+            .collect();
+        
+        let mut thumbnails = Vec::new();
+        for neighbour in neighbours {
+            let clip = &self.clips[neighbour];
+            if clip.video_length_seconds.is_none() {
+                self.video_info_manager.request_if_needed(clip.original_file.clone());
+            }
+            thumbnails.push((clip.original_file.clone(), clip.effective_poster_timestamp().unwrap_or(0.0)));
+        }
+        // Replaces the previous selection's prefetches, so skipping through the list quickly
+        // doesn't leave a trail of thumbnails nobody looks at
+        if let Some(cache) = &self.smart_thumbnail_cache {
+            cache.prefetch_thumbnails(&thumbnails);
         }
     }
     
@@ -301,6 +738,14 @@ impl ClipHelperApp {
                                     log::error!("Failed to set video in MediaController: {}", e);
                                 } else {
                                     log::info!("Successfully initialized MediaController with video");
+                                    controller.set_audio_only(self.audio_only_preview);
+                                    // Open on the chosen poster frame instead of the first frame
+                                    if let Some(poster) = clip.effective_poster_timestamp() {
+                                        controller.seek(poster);
+                                        if let Some(ref mut preview) = self.video_preview {
+                                            preview.seek_to(poster);
+                                        }
+                                    }
                                 }
                             } else {
                                 log::debug!("Clip video info not yet loaded, skipping MediaController initialization");
@@ -312,7 +757,7 @@ impl ClipHelperApp {
         }
     }
 
-    pub fn delete_selected_clip(&mut self) -> anyhow::Result<()> {
+    pub fn delete_selected_clip(&mut self) -> anyhow::Result<Option<crate::core::recycle_bin::DeleteOutcome>> {
         // Stop media controller first to release file handles
         if let Some(_controller) = self.media_controller.take() {
             log::debug!("Stopping media controller before delete");
@@ -324,233 +769,2330 @@ impl ClipHelperApp {
             preview.stop();
         }
         
+        let mut outcome = None;
         if let Some(index) = self.selected_clip_index {
-            if let Some(clip) = self.clips.get_mut(index) {
-                clip.is_deleted = true;
-                
-                // Move file to deleted directory
-                let deleted_path = self.config.deleted_directory.join(
-                    clip.original_file.file_name()
-                        .and_then(|name| name.to_str())
-                        .unwrap_or("unknown_file")
-                );
-                
-                log::info!("Moving file to deleted directory: {} -> {}", 
-                    clip.original_file.display(), deleted_path.display());
-                
-                if let Err(e) = std::fs::rename(&clip.original_file, &deleted_path) {
-                    log::error!("Failed to move file to deleted directory: {}", e);
-                    return Err(anyhow::anyhow!("Failed to move file to deleted directory: {}", e));
-                }
-                
-                log::info!("File successfully moved to deleted directory");
-                
-                // Clear selection since the clip is now deleted
-                self.selected_clip_index = None;
+            outcome = Some(self.delete_clip(index)?);
+            
+            // Clear selection since the clip is now deleted
+            self.selected_clip_index = None;
+        }
+        Ok(outcome)
+    }
+
+    /// Mark a clip deleted and move its file to the recycle bin or the deleted directory,
+    /// depending on the config. A recycle bin failure is shown as a toast.
+    fn delete_clip(&mut self, index: usize) -> anyhow::Result<crate::core::recycle_bin::DeleteOutcome> {
+        let Some(clip) = self.clips.get(index) else {
+            return Err(anyhow::anyhow!("No clip at index {}", index));
+        };
+        
+        let outcome = crate::core::recycle_bin::delete_file(
+            &clip.original_file,
+            &self.config.deleted_directory,
+            self.config.delete_to_recycle_bin,
+        ).inspect_err(|e| log::error!("Failed to delete {}: {}", clip.original_file.display(), e))?;
+        
+        log::info!("Deleted {}: {:?}", clip.original_file.display(), outcome);
+        self.clips[index].is_deleted = true;
+        self.last_near_duplicate_check = None;
+        if let crate::core::recycle_bin::DeleteOutcome::TrashFailed(_) = outcome {
+            self.show_toast(outcome.message());
+        }
+        Ok(outcome)
+    }
+
+    /// Delete every multi-selected clip that isn't invalid or still being written,
+    /// then clear the selection
+    pub fn delete_selected_clips(&mut self) {
+        let files = self.multi_selected_files();
+        self.delete_session(files);
+        self.selected_clip_indices.clear();
+    }
+
+    /// Files of the multi-selected clips in recording order, deleted ones left out
+    fn multi_selected_files(&self) -> Vec<PathBuf> {
+        let mut clips: Vec<&Clip> = self.selected_clip_indices.iter()
+            .filter_map(|&index| self.clips.get(index))
+            .filter(|clip| !clip.is_deleted)
+            .collect();
+        clips.sort_by_key(|clip| clip.timestamp);
+        clips.into_iter().map(|clip| clip.original_file.clone()).collect()
+    }
+
+    /// Where deleted clips go, for status messages
+    fn deleted_destination_name(&self) -> &'static str {
+        if self.config.delete_to_recycle_bin {
+            "the recycle bin"
+        } else {
+            "deleted folder"
+        }
+    }
+
+    /// Delete the selected clip (or all multi-selected clips), asking first unless the
+    /// user turned that off
+    fn request_delete_selected_clip(&mut self) {
+        if self.selected_clip_indices.len() >= 2 {
+            if self.config.confirm_before_delete {
+                self.pending_session_deletion = Some(self.multi_selected_files());
+            } else {
+                self.delete_selected_clips();
             }
+            return;
+        }
+        if self.config.confirm_before_delete {
+            self.pending_clip_deletion = self.selected_clip_index.map(|index| PendingClipDeletion {
+                index,
+                dont_ask_again: false,
+            });
+        } else {
+            self.delete_selected_clip_with_status();
         }
-        Ok(())
     }
 
-    pub fn apply_trim(&mut self, force_overwrite: bool) -> anyhow::Result<()> {
-        if let Some(index) = self.selected_clip_index {
-            if let Some(clip) = self.clips.get_mut(index) {
-                let output_filename = format!("{}.mkv", clip.get_output_filename());
-                let output_path = self.config.trimmed_directory.join(output_filename);
-                
-                crate::video::VideoProcessor::trim_clip(clip, &output_path, force_overwrite)?;
-                clip.is_trimmed = true;
+    fn delete_selected_clip_with_status(&mut self) {
+        match self.delete_selected_clip() {
+            Ok(Some(outcome)) => self.status_message = outcome.message(),
+            Ok(None) => {}
+            Err(e) => {
+                log::error!("Failed to delete clip: {}", e);
+                self.status_message = format!("Error deleting clip: {}", e);
             }
         }
-        Ok(())
     }
 
-    fn process_hotkey_events(&mut self) {
-        while let Ok(event) = self.hotkey_receiver.try_recv() {
-            match event {
-                HotkeyEvent::ClipRequested(duration) => {
-                    let now = Local::now();
-                    log::info!("Hotkey triggered for {:?} at {}", duration, now);
-                    
-                    // Check if there are any recent clips that can be matched to this duration request
-                    let mut found_matching_clip = false;
-                    for clip in &self.clips {
-                        if Self::timestamps_match_static(now, clip.timestamp) {
-                            found_matching_clip = true;
-                            break;
-                        }
-                    }
-                    
-                    // Simply save the duration request - matching will happen at display time
-                    self.duration_requests.push(DurationRequest {
-                        timestamp: now,
-                        duration: duration.clone(),
-                    });
-                    
-                    // Clean up old duration requests (older than 1 hour)
-                    let cutoff = now - chrono::Duration::hours(1);
-                    self.duration_requests.retain(|req| req.timestamp > cutoff);
-                    
-                    // Save duration requests to persistence
-                    if let Err(e) = self.save_duration_requests() {
-                        log::error!("Failed to save duration requests: {}", e);
+    fn render_delete_clip_dialog(&mut self, ctx: &egui::Context) {
+        let Some(mut pending) = self.pending_clip_deletion.take() else {
+            return;
+        };
+        // The selection changed underneath the dialog
+        if self.selected_clip_index != Some(pending.index) || pending.index >= self.clips.len() {
+            return;
+        }
+        let name = self.clips[pending.index].get_output_filename();
+        let destination = self.deleted_destination_name();
+        let mut confirmed = false;
+        let mut cancelled = false;
+        
+        egui::Window::new("Delete Clip?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("{} will be moved to {}.", name, destination));
+                ui.checkbox(&mut pending.dont_ask_again, "Don't ask again");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("🗑 Delete").clicked() {
+                        confirmed = true;
                     }
-                    
-                    // Play unmatched sound if no clip was found to match this hotkey
-                    if !found_matching_clip {
-                        log::info!("No matching clip found for hotkey {} at {}", duration as u32, now);
-                        if let Some(ref mut audio_confirmation) = self.audio_confirmation {
-                            if self.config.audio_confirmation.unmatched_sound_enabled {
-                                if let Err(e) = audio_confirmation.play_unmatched_clip_sound(&self.config.audio_confirmation) {
-                                    log::warn!("Failed to play unmatched hotkey sound: {}", e);
-                                }
-                            }
-                        }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
                     }
-                    
-                    log::info!("Saved duration request for {} at {}", duration as u32, now);
+                });
+            });
+        
+        if confirmed {
+            if pending.dont_ask_again {
+                self.config.confirm_before_delete = false;
+                if let Err(e) = self.config.save() {
+                    log::error!("Failed to save delete confirmation setting: {}", e);
                 }
             }
+            self.delete_selected_clip_with_status();
+        } else if !cancelled {
+            self.pending_clip_deletion = Some(pending);
         }
     }
-    
-    fn process_file_events(&mut self) {
-        // Collect new files first
-        let mut new_files = Vec::new();
-        if let Some(ref mut receiver) = self.file_receiver {
-            while let Ok(new_file) = receiver.try_recv() {
-                log::info!("New file detected: {:?}", new_file.path);
-                new_files.push(new_file);
-            }
+
+    /// Re-run near-duplicate detection if the last check is stale
+    fn refresh_near_duplicates(&mut self) {
+        if self.last_near_duplicate_check.is_some_and(|checked| checked.elapsed() < NEAR_DUPLICATE_CHECK_INTERVAL) {
+            return;
         }
+        self.last_near_duplicate_check = Some(std::time::Instant::now());
         
-        // Process each new file
-        for new_file in new_files {
-            // First, check if this file matches any pending clip requests
-            let mut matched_requests = Vec::new();
-            
-            for (i, request) in self.pending_clip_requests.iter().enumerate() {
-                if Self::timestamps_match_static(request.timestamp, new_file.timestamp) {
-                    matched_requests.push((i, new_file.clone(), request.duration.clone()));
+        let groups = crate::core::near_duplicates::find_near_duplicate_groups(&self.clips, |path| {
+            std::fs::metadata(path).ok().map(|metadata| metadata.len())
+        });
+        self.near_duplicate_groups = groups.into_iter()
+            .map(|group| group.into_iter().map(|index| self.clips[index].original_file.clone()).collect())
+            .collect();
+    }
+
+    /// The other clips in the near-duplicate group containing `file`
+    fn near_duplicates_of(&self, file: &std::path::Path) -> Vec<PathBuf> {
+        self.near_duplicate_groups.iter()
+            .find(|group| group.iter().any(|path| path == file))
+            .map(|group| group.iter().filter(|path| *path != file).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Pin every clip in the group so they stop being flagged
+    fn keep_all_near_duplicates(&mut self, file: &std::path::Path) {
+        let mut group = self.near_duplicates_of(file);
+        group.push(file.to_path_buf());
+        for clip in self.clips.iter_mut().filter(|clip| group.contains(&clip.original_file)) {
+            clip.pinned = true;
+        }
+        self.last_near_duplicate_check = None;
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after pinning: {}", e);
+        }
+    }
+
+    /// Delete the clips confirmed in the "keep one, delete others" dialog
+    fn delete_confirmed_duplicates(&mut self, files: Vec<PathBuf>) {
+        let mut deleted = 0;
+        for file in &files {
+            match self.delete_clip_by_path(file) {
+                Ok(true) => deleted += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    self.status_message = format!("Error deleting clip: {}", e);
+                    return;
                 }
             }
-            
-            // Process matched requests
-            for (index, file, duration) in matched_requests.iter().rev() {
-                self.create_clip_from_file(file.clone(), Some(duration.clone()));
-                self.pending_clip_requests.remove(*index);
+        }
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after deleting duplicates: {}", e);
+        }
+        self.status_message = format!("Moved {} duplicate clip(s) to {}", deleted, self.deleted_destination_name());
+    }
+
+    /// Delete the clip for `file`, closing the preview first if it's selected.
+    /// Returns false if no clip has that file.
+    fn delete_clip_by_path(&mut self, file: &std::path::Path) -> anyhow::Result<bool> {
+        let Some(index) = self.clips.iter().position(|clip| clip.original_file == file) else {
+            return Ok(false);
+        };
+        if self.selected_clip_index == Some(index) {
+            self.media_controller = None;
+            if let Some(mut preview) = self.video_preview.take() {
+                preview.stop();
             }
-            
-            // If no hotkey request matched, still add the file to the clip list automatically
-            if matched_requests.is_empty() {
-                self.create_clip_from_file(new_file, None);
+            self.selected_clip_index = None;
+        }
+        self.delete_clip(index)?;
+        Ok(true)
+    }
+
+    /// Clips of a session that bulk actions can work on, plus how many were skipped
+    /// because they're invalid or still being written. Deleted clips are ignored.
+    pub(crate) fn session_clips_ready(&self, clip_indices: &[usize]) -> (Vec<usize>, usize) {
+        let mut ready = Vec::new();
+        let mut skipped = 0;
+        for &index in clip_indices {
+            let Some(clip) = self.clips.get(index) else {
+                continue;
+            };
+            if clip.is_deleted {
+                continue;
+            }
+            if clip.is_video_valid() && clip.trim_end > clip.trim_start && clip.original_file.exists() {
+                ready.push(index);
+            } else {
+                skipped += 1;
             }
         }
+        (ready, skipped)
     }
-    
-    fn try_match_clip_request(&mut self, request_time: chrono::DateTime<Local>, duration: crate::core::ClipDuration) {
-        if let Some(ref watched_dir) = self.watched_directory {
-            // Scan for existing files that might match
-            if let Ok(existing_files) = FileMonitor::scan_existing_files(watched_dir) {
-                for file in existing_files {
-                    if self.timestamps_match(request_time, file.timestamp) {
-                        self.create_clip_from_file(file, Some(duration));
-                        // Remove the pending request
-                        self.pending_clip_requests.retain(|req| req.timestamp != request_time);
-                        return;
-                    }
-                }
+
+    /// Queue a trim export for every ready clip in a session. Existing outputs are left alone.
+    fn trim_session(&mut self, clip_indices: &[usize]) {
+        let (ready, skipped_invalid) = self.session_clips_ready(clip_indices);
+        let mut queued = 0;
+        let mut skipped_existing = 0;
+        for index in ready {
+            let job = self.trim_export_job(index, false);
+            if job.output_path.exists() {
+                skipped_existing += 1;
+                continue;
             }
+            self.enqueue_export(job);
+            queued += 1;
         }
         
-        // Keep the request pending for a bit in case the file appears later
-        // Remove old pending requests (older than 30 seconds)
-        let cutoff = Local::now() - chrono::Duration::seconds(30);
-        self.pending_clip_requests.retain(|req| req.timestamp > cutoff);
+        if let Err(e) = self.save_export_queue() {
+            log::error!("Failed to save export queue: {}", e);
+        }
+        
+        let mut summary = format!("Queued {} trim(s)", queued);
+        if skipped_existing > 0 {
+            summary.push_str(&format!(", {} already exported", skipped_existing));
+        }
+        if skipped_invalid > 0 {
+            summary.push_str(&format!(", skipped {} invalid or still writing", skipped_invalid));
+        }
+        log::info!("Session trim: {}", summary);
+        self.status_message = summary;
     }
-    
-    fn try_match_file_to_requests(&mut self, new_file: &NewReplayFile) {
-        let mut clips_to_create = Vec::new();
-        let mut indices_to_remove = Vec::new();
+
+    /// Move every ready clip of a confirmed "Delete session" (or multi-selection) to the
+    /// deleted directory
+    fn delete_session(&mut self, files: Vec<PathBuf>) {
+        let indices: Vec<usize> = files.iter()
+            .filter_map(|file| self.clips.iter().position(|clip| &clip.original_file == file))
+            .collect();
+        let (ready, skipped) = self.session_clips_ready(&indices);
+        let ready_files: Vec<PathBuf> = ready.iter().map(|&index| self.clips[index].original_file.clone()).collect();
         
-        for (i, request) in self.pending_clip_requests.iter().enumerate() {
-            if Self::timestamps_match_static(request.timestamp, new_file.timestamp) {
-                clips_to_create.push((new_file.clone(), request.duration.clone()));
-                indices_to_remove.push(i);
+        let mut deleted = 0;
+        let mut failed = 0;
+        for file in &ready_files {
+            match self.delete_clip_by_path(file) {
+                Ok(true) => deleted += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    log::error!("Failed to delete {}: {}", file.display(), e);
+                    failed += 1;
+                }
             }
         }
-        
-        // Remove matched requests (in reverse order to maintain indices)
-        for &index in indices_to_remove.iter().rev() {
-            self.pending_clip_requests.remove(index);
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after deleting session: {}", e);
         }
         
-        // Create clips
-        for (file, duration) in clips_to_create {
-            self.create_clip_from_file(file, Some(duration));
+        let mut summary = format!("Moved {} clip(s) to {}", deleted, self.deleted_destination_name());
+        if skipped > 0 {
+            summary.push_str(&format!(", skipped {} invalid or still writing", skipped));
         }
-    }
-    
-    fn timestamps_match(&self, request_time: chrono::DateTime<Local>, file_time: chrono::DateTime<Local>) -> bool {
-        Self::timestamps_match_static(request_time, file_time)
-    }
-    
-    fn timestamps_match_static(request_time: chrono::DateTime<Local>, file_time: chrono::DateTime<Local>) -> bool {
-        let diff = (request_time - file_time).num_seconds().abs();
-        diff <= 10 // Within 10 seconds
-    }
-    
-    fn create_clip_from_file(&mut self, file: NewReplayFile, duration: Option<crate::core::ClipDuration>) {
-        // Check if a clip with this file path already exists
-        if self.clips.iter().any(|existing_clip| existing_clip.original_file == file.path) {
-            log::debug!("Clip already exists for file: {:?}", file.path);
-            return;
+        if failed > 0 {
+            summary.push_str(&format!(", {} failed (see log)", failed));
         }
+        self.status_message = summary;
+    }
 
-        // Always create clips without target duration - matching will happen at display time
-        let clip_result = Clip::new_without_target(file.path.clone());
+    fn render_session_deletion_dialog(&mut self, ctx: &egui::Context) {
+        let Some(files) = self.pending_session_deletion.clone() else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
         
-        match clip_result {
-            Ok(clip) => {
-                // Request video info asynchronously (non-blocking)
-                self.video_info_manager.request_if_needed(file.path);
-                
-                log::info!("Created clip: {}", clip.get_output_filename());
+        egui::Window::new("Delete Clips?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("{} clip(s) will be moved to {}:", files.len(), self.deleted_destination_name()));
+                egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    for file in &files {
+                        ui.label(format!("  • {}", file.file_name().unwrap_or_default().to_string_lossy()));
+                    }
+                });
+                ui.small("Clips that are invalid or still being written are skipped.");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("🗑 Delete all").clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+        
+        if confirmed {
+            self.pending_session_deletion = None;
+            self.delete_session(files);
+            self.selected_clip_indices.clear();
+        } else if cancelled {
+            self.pending_session_deletion = None;
+        }
+    }
+
+    fn render_duplicate_deletion_dialog(&mut self, ctx: &egui::Context) {
+        let Some(files) = self.pending_duplicate_deletion.clone() else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        
+        egui::Window::new("Delete Duplicates?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("These clips will be moved to the deleted folder:");
+                for file in &files {
+                    ui.label(format!("  • {}", file.file_name().unwrap_or_default().to_string_lossy()));
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("🗑 Delete").clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+        
+        if confirmed {
+            self.pending_duplicate_deletion = None;
+            self.delete_confirmed_duplicates(files);
+        } else if cancelled {
+            self.pending_duplicate_deletion = None;
+        }
+    }
+
+    fn refresh_recycle_bin(&mut self) {
+        if self.last_recycle_bin_scan.is_some_and(|scanned| scanned.elapsed() < RECYCLE_BIN_REFRESH_INTERVAL) {
+            return;
+        }
+        self.last_recycle_bin_scan = Some(std::time::Instant::now());
+        self.recycle_bin_files = crate::core::recycle_bin::scan_deleted_directory(&self.config.deleted_directory);
+    }
+
+    /// Move a deleted replay back to the replay directory and list it again
+    fn restore_deleted_file(&mut self, file: &crate::core::recycle_bin::DeletedFile) {
+        let restored = match crate::core::recycle_bin::restore_file(file, &self.config.obs_replay_directory) {
+            Ok(path) => path,
+            Err(e) => {
+                self.show_error("Restore failed", &e.to_string());
+                return;
+            }
+        };
+        log::info!("Restored {} to {}", file.original_name, restored.display());
+        
+        // Drop the stale deleted entry so the clip can be created again
+        let selected_file = self.get_selected_clip().map(|clip| clip.original_file.clone());
+        let keyboard_focus_file = self.keyboard_focus_index
+            .and_then(|index| self.clips.get(index))
+            .map(|clip| clip.original_file.clone());
+        self.clips.retain(|clip| clip.original_file != restored);
+        self.selected_clip_indices.clear();
+        self.selected_clip_index = selected_file.and_then(|file| self.clips.iter().position(|clip| clip.original_file == file));
+        self.keyboard_focus_index = keyboard_focus_file.and_then(|file| self.clips.iter().position(|clip| clip.original_file == file));
+        
+        if let Some(new_file) = NewReplayFile::from_path(restored, &self.config.timestamp_pattern()) {
+            self.create_clip_from_file(new_file, None);
+        }
+        self.last_recycle_bin_scan = None;
+        self.last_near_duplicate_check = None;
+        self.status_message = format!("Restored {}", file.original_name);
+    }
+
+    fn permanently_delete_file(&mut self, file: &crate::core::recycle_bin::DeletedFile) {
+        match std::fs::remove_file(&file.path) {
+            Ok(()) => {
+                log::info!("Permanently deleted {}", file.path.display());
+                self.status_message = format!("Permanently deleted {}", file.original_name);
+            }
+            Err(e) => self.show_error("Permanent delete failed", &format!("{}: {}", file.original_name, e)),
+        }
+        self.last_recycle_bin_scan = None;
+    }
+
+    fn render_recycle_bin(&mut self, ctx: &egui::Context) {
+        self.refresh_recycle_bin();
+        ctx.request_repaint_after(RECYCLE_BIN_REFRESH_INTERVAL);
+        
+        let mut open = true;
+        let mut to_restore = None;
+        egui::Window::new("Recycle Bin")
+            .open(&mut open)
+            .default_size([520.0, 360.0])
+            .show(ctx, |ui| {
+                ui.label(format!("Deleted clips in {}", self.config.deleted_directory.display()));
+                ui.separator();
+                
+                if self.recycle_bin_files.is_empty() {
+                    ui.label("No deleted clips");
+                    return;
+                }
+                
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("recycle_bin_grid").striped(true).num_columns(4).show(ui, |ui| {
+                        for file in &self.recycle_bin_files {
+                            ui.label(&file.original_name);
+                            ui.label(file.deleted_at
+                                .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                                .unwrap_or_else(|| "unknown".to_string()));
+                            ui.label(crate::core::format_file_size(file.size));
+                            ui.horizontal(|ui| {
+                                if ui.button("Restore").clicked() {
+                                    to_restore = Some(file.clone());
+                                }
+                                if ui.button("Permanently Delete").clicked() {
+                                    self.pending_permanent_deletion = Some(file.clone());
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        
+        if let Some(file) = to_restore {
+            self.restore_deleted_file(&file);
+        }
+        if !open {
+            self.show_recycle_bin = false;
+        }
+    }
+
+    fn render_permanent_deletion_dialog(&mut self, ctx: &egui::Context) {
+        let Some(file) = self.pending_permanent_deletion.clone() else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        
+        egui::Window::new("Permanently Delete?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("{} will be removed from disk. This can't be undone.", file.original_name));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("🗑 Delete permanently").clicked() {
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+        
+        if confirmed {
+            self.pending_permanent_deletion = None;
+            self.permanently_delete_file(&file);
+        } else if cancelled {
+            self.pending_permanent_deletion = None;
+        }
+    }
+
+    /// Warning box in the editor when the selected clip overlaps other replays
+    fn show_near_duplicate_warning(&mut self, ui: &mut egui::Ui, file: &std::path::Path) {
+        let others = self.near_duplicates_of(file);
+        if others.is_empty() {
+            return;
+        }
+        
+        ui.group(|ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(egui::Color32::from_rgb(230, 160, 60), "⧉ Possible duplicate of:");
+                for other in &others {
+                    let name = other.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    if ui.link(name).clicked() {
+                        if let Some(index) = self.clips.iter().position(|clip| &clip.original_file == other) {
+                            self.select_clip(index);
+                        }
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Keep this, delete others").clicked() {
+                    self.pending_duplicate_deletion = Some(others.clone());
+                }
+                if ui.button("📌 Keep all").on_hover_text("Pin these clips so they aren't flagged again").clicked() {
+                    self.keep_all_near_duplicates(file);
+                }
+            });
+        });
+    }
+
+    /// Output names (without extension) for every clip, expanded from the filename template.
+    /// Clips that would collide get an incrementing " (2)", " (3)" suffix, oldest recording
+    /// first, so a clip keeps its name when others are added or removed. Deleted clips don't
+    /// take part and get None.
+    pub fn resolved_output_names(&self) -> Vec<Option<String>> {
+        let mut kept: Vec<usize> = (0..self.clips.len())
+            .filter(|&index| !self.clips[index].is_deleted)
+            .collect();
+        kept.sort_by(|&a, &b| {
+            (self.clips[a].timestamp, &self.clips[a].original_file).cmp(&(self.clips[b].timestamp, &self.clips[b].original_file))
+        });
+        let mut unique: Vec<String> = kept.iter()
+            .map(|&index| crate::core::naming::render_template(&self.config.filename_template, &self.clips[index], index + 1))
+            .collect();
+        crate::core::naming::make_names_unique(&mut unique);
+        
+        let mut names = vec![None; self.clips.len()];
+        for (index, name) in kept.into_iter().zip(unique) {
+            names[index] = Some(name);
+        }
+        names
+    }
+
+    /// Changes whenever the template or anything a placeholder reads from the clips does
+    fn output_names_key(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.config.filename_template.hash(&mut hasher);
+        for clip in &self.clips {
+            clip.original_file.hash(&mut hasher);
+            clip.timestamp.hash(&mut hasher);
+            clip.name.hash(&mut hasher);
+            (clip.trim_end - clip.trim_start).to_bits().hash(&mut hasher);
+            clip.is_deleted.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Re-resolve the cached output names if the clips or the template changed
+    pub(crate) fn refresh_output_names(&mut self) {
+        let key = self.output_names_key();
+        if self.output_names.as_ref().is_none_or(|(cached_key, _)| *cached_key != key) {
+            self.output_names = Some((key, self.resolved_output_names()));
+        }
+    }
+
+    pub fn output_name_for(&self, index: usize) -> String {
+        let cached = self.output_names.as_ref()
+            .filter(|(key, _)| *key == self.output_names_key())
+            .and_then(|(_, names)| names.get(index).cloned().flatten());
+        match cached {
+            Some(name) => name,
+            // Not cached yet, changed since the last refresh, or deleted
+            None => self.resolved_output_names().swap_remove(index).unwrap_or_else(|| {
+                crate::core::naming::render_template(&self.config.filename_template, &self.clips[index], index + 1)
+            }),
+        }
+    }
+
+    /// Where "Apply Trim" writes the clip at `index`
+    pub fn trimmed_output_path(&self, index: usize) -> PathBuf {
+        self.clips[index].export_directory(&self.config.trimmed_directory)
+            .join(format!("{}.mkv", self.output_name_for(index)))
+    }
+
+    /// Set or clear the selected clip's export directory; only existing directories are accepted
+    fn set_export_directory_override(&mut self, index: usize, directory: Option<PathBuf>) {
+        if let Some(ref dir) = directory {
+            if !dir.is_dir() {
+                self.status_message = format!("Not a directory: {}", dir.display());
+                return;
+            }
+        }
+        
+        self.export_directory_text = directory.as_ref().map(|dir| dir.display().to_string()).unwrap_or_default();
+        if let Some(clip) = self.clips.get_mut(index) {
+            clip.export_directory_override = directory;
+        }
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after changing export directory: {}", e);
+        }
+    }
+
+    /// Export job trimming the clip at `index` to its trimmed output path
+    pub(crate) fn trim_export_job(&self, index: usize, force_overwrite: bool) -> crate::video::ExportJob {
+        let clip = &self.clips[index];
+        crate::video::ExportJob {
+            id: 0,
+            clip_path: clip.original_file.clone(),
+            output_path: self.trimmed_output_path(index),
+            preset_name: crate::video::DEFAULT_PRESET_NAME.to_string(),
+            trim_start: clip.trim_start,
+            trim_end: clip.trim_end,
+            audio_tracks: clip.audio_tracks.clone(),
+            force_overwrite,
+            format: crate::video::ExportFormat::Video,
+            animated: crate::video::AnimatedExportSettings::default(),
+            sidecar: self.config.export_preset.write_sidecar
+                .then(|| crate::core::ClipMetadata::from_clip(clip, crate::video::DEFAULT_PRESET_NAME)),
+            export_audio_tracks_separately: self.config.export_preset.export_audio_tracks_separately,
+            extra_ffmpeg_args: self.config.export_preset.ffmpeg_args(),
+            target_size_mb: self.config.export_preset.two_pass_target_mb(),
+            video_encoder: self.config.export_preset.video_encoder,
+        }
+    }
+
+    /// Queue an export and mark its clip as exporting
+    fn enqueue_export(&mut self, job: crate::video::ExportJob) -> u64 {
+        // The worker refuses exports that clearly don't fit; warn now so it isn't a surprise
+        let estimated_size = self.estimate_export_size(&job);
+        if let Some(message) = crate::video::check_disk_space(&job.output_path, estimated_size).message() {
+            log::warn!("{} ({})", message, job.output_path.display());
+            self.show_toast(message);
+        }
+        
+        if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == job.clip_path) {
+            clip.export_state = crate::core::ClipExportState::Exporting;
+        }
+        self.export_queue.enqueue(job)
+    }
+
+    /// Rough output size of an export job, without probing the source. Includes the WAV
+    /// files of separately exported audio tracks and a hardware encoder's overshoot.
+    pub(crate) fn estimate_export_size(&self, job: &crate::video::ExportJob) -> u64 {
+        let duration = job.trim_end - job.trim_start;
+        if job.format.is_animated() {
+            return job.animated.estimate_size_bytes(job.format, duration, 16.0 / 9.0);
+        }
+        let video_size = if let Some(target_size_mb) = job.target_size_mb {
+            (target_size_mb * 1024.0 * 1024.0 * job.video_encoder.target_size_overshoot()) as u64
+        } else {
+            let input_size = std::fs::metadata(&job.clip_path).map(|metadata| metadata.len()).unwrap_or(0);
+            let source_duration = self.clips.iter()
+                .find(|c| c.original_file == job.clip_path)
+                .and_then(|clip| clip.video_length_seconds)
+                .unwrap_or(0.0);
+            let mixes_audio = job.audio_tracks.iter().any(|track| track.enabled);
+            crate::video::estimate_stream_copy_size(input_size, source_duration, duration, mixes_audio)
+        };
+        // See VideoProcessor::extract_audio_tracks
+        let separate_tracks = if job.export_audio_tracks_separately {
+            let track_count = job.audio_tracks.iter().filter(|track| track.enabled).count() as u64;
+            track_count * crate::video::AudioExportFormat::Wav.estimate_size_bytes(duration)
+        } else {
+            0
+        };
+        video_size + separate_tracks
+    }
+
+    /// Free space on the export volume, re-read at most every FREE_SPACE_REFRESH_INTERVAL
+    fn export_free_space(&mut self) -> Option<u64> {
+        let stale = self.free_space
+            .is_none_or(|(read_at, _)| read_at.elapsed() >= FREE_SPACE_REFRESH_INTERVAL);
+        if stale {
+            let free = crate::video::available_space(&self.config.trimmed_directory);
+            self.free_space = Some((std::time::Instant::now(), free));
+        }
+        self.free_space.and_then(|(_, free)| free)
+    }
+
+    pub fn apply_trim(&mut self, force_overwrite: bool) -> anyhow::Result<()> {
+        if let Some(index) = self.selected_clip_index {
+            if index < self.clips.len() {
+                let job = self.trim_export_job(index, force_overwrite);
+                
+                if job.output_path.exists() && !force_overwrite {
+                    return Err(anyhow::anyhow!("Output file exists. Use shift+click to overwrite."));
+                }
+                
+                self.enqueue_export(job);
+                
+                if let Err(e) = self.save_export_queue() {
+                    log::error!("Failed to save export queue: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Open `path` in the system's default player on a background thread
+    fn open_in_external_player(&mut self, path: PathBuf) {
+        log::info!("Opening {} in external player", path.display());
+        self.external_player_launches.push(std::thread::spawn(move || {
+            crate::core::shell::open_with_default_app(&path)
+        }));
+    }
+
+    /// Measure the clip's loudness over its trim range once the FFmpeg pool has a free slot,
+    /// unless that range was already measured or is being measured
+    fn request_loudness_measurement(&mut self, index: usize) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        let range = (clip.trim_start, clip.trim_end);
+        if !clip.is_video_valid() || range.1 <= range.0 || clip.loudness_is_current() {
+            return;
+        }
+        if self.loudness_measurements.iter().any(|m| m.file == clip.original_file && m.range == range) {
+            return;
+        }
+        
+        let file = clip.original_file.clone();
+        let ffmpeg = self.config.ffmpeg_path.clone().unwrap_or_else(|| PathBuf::from("ffmpeg"));
+        log::debug!("Measuring loudness of {} ({:.1}s - {:.1}s)", file.display(), range.0, range.1);
+        let path = file.clone();
+        let pool = self.ffmpeg_pool.clone();
+        let handle = std::thread::spawn(move || {
+            pool.run(move || crate::audio::AudioAnalyzer::measure_loudness(&path, range.0, range.1, &ffmpeg))
+        });
+        self.loudness_measurements.push(LoudnessMeasurement { file, range, handle });
+    }
+
+    /// Look for the clip's first action frame once the FFmpeg pool has a free slot, once per replay
+    fn request_motion_start(&mut self, index: usize) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        let Some(duration) = clip.video_length_seconds.filter(|&duration| duration > 0.0) else {
+            return;
+        };
+        if clip.motion_start.is_some() || self.motion_detections.iter().any(|d| d.file == clip.original_file) {
+            return;
+        }
+        
+        let file = clip.original_file.clone();
+        log::debug!("Looking for the first action in {}", file.display());
+        let path = file.clone();
+        let pool = self.ffmpeg_pool.clone();
+        let handle = std::thread::spawn(move || {
+            pool.run(move || {
+                crate::video::VideoAnalyzer::detect_motion_start(&path, 0.0, duration, crate::video::DEFAULT_MOTION_THRESHOLD)
+            })
+        });
+        self.motion_detections.push(MotionDetection { file, handle });
+    }
+
+    /// Write one audio track of the clip's trimmed range next to where its video export goes
+    fn export_audio_track(&mut self, index: usize, track_index: usize, format: crate::video::AudioExportFormat) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        let Some(track) = clip.audio_tracks.iter().find(|track| track.index == track_index).cloned() else {
+            return;
+        };
+        let output = crate::video::VideoProcessor::audio_track_output_path(&self.trimmed_output_path(index), track_index, format);
+        if output.exists() || self.audio_track_exports.iter().any(|export| export.output == output) {
+            self.status_message = format!("{} already exists", output.display());
+            return;
+        }
+        
+        let (source, start, end) = (clip.original_file.clone(), clip.trim_start, clip.trim_end);
+        log::info!("Exporting audio track {} of {} to {}", track_index, source.display(), output.display());
+        self.status_message = format!("Exporting {}...", track.name);
+        let path = output.clone();
+        let handle = std::thread::spawn(move || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            crate::video::VideoProcessor::extract_audio_track(&source, &track, start, end, format, &path)
+        });
+        self.audio_track_exports.push(AudioTrackExport { output, handle });
+    }
+
+    fn process_audio_track_exports(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.audio_track_exports)
+            .into_iter()
+            .partition(|export| export.handle.is_finished());
+        self.audio_track_exports = running;
+        
+        for export in finished {
+            let file_name = export.output.file_name().unwrap_or_default().to_string_lossy().to_string();
+            match export.handle.join() {
+                Ok(Ok(())) => {
+                    log::info!("Exported audio track to {}", export.output.display());
+                    self.show_toast(format!("Saved {}", file_name));
+                }
+                Ok(Err(e)) => {
+                    log::error!("Audio track export to {} failed: {}", export.output.display(), e);
+                    self.status_message = format!("Failed to export {}: {}", file_name, e);
+                }
+                Err(_) => log::error!("Audio track export thread panicked"),
+            }
+        }
+    }
+
+    /// Extract the clip's waveform on a background thread unless it is loaded or loading.
+    /// Only the selected clip's waveform is kept, since a long replay has millions of samples.
+    fn request_waveform(&mut self, index: usize) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        let key = clip.original_file.to_string_lossy().to_string();
+        self.waveforms.retain(|file, _| *file == key);
+        // Loads of clips that are no longer selected would only be thrown away
+        for load in self.waveform_loads.iter().filter(|load| load.file != clip.original_file) {
+            if !load.cancel.swap(true, std::sync::atomic::Ordering::Relaxed) {
+                log::debug!("Cancelling waveform extraction of {}", load.file.display());
+            }
+        }
+        if !clip.is_video_valid() || self.waveforms.contains_key(&key) {
+            return;
+        }
+        // A cancelled load of this clip may still be winding down; start a fresh one
+        if self.waveform_loads.iter().any(|load| {
+            load.file == clip.original_file && !load.cancel.load(std::sync::atomic::Ordering::Relaxed)
+        }) {
+            return;
+        }
+        
+        let file = clip.original_file.clone();
+        let pool = self.ffmpeg_pool.clone();
+        log::debug!("Extracting waveform of {}", file.display());
+        let path = file.clone();
+        let cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        let handle = std::thread::spawn(move || WaveformData::generate_in_pool(&pool, &path, 0, thread_cancel));
+        self.waveform_loads.push(WaveformLoad { file, cancel, handle });
+    }
+
+    fn process_waveform_loads(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.waveform_loads)
+            .into_iter()
+            .partition(|load| load.handle.is_finished());
+        self.waveform_loads = running;
+        
+        let selected_file = self.selected_clip_index
+            .and_then(|index| self.clips.get(index))
+            .map(|clip| clip.original_file.clone());
+        for load in finished {
+            match load.handle.join() {
+                // Drop waveforms of clips that were deselected while loading
+                Ok(Ok(waveform)) if selected_file.as_ref() == Some(&load.file) => {
+                    self.waveforms.insert(load.file.to_string_lossy().to_string(), waveform);
+                }
+                Ok(Ok(_)) => {}
+                Ok(Err(_)) if load.cancel.load(std::sync::atomic::Ordering::Relaxed) => {}
+                Ok(Err(e)) => log::warn!("Waveform extraction of {} failed: {}", load.file.display(), e),
+                Err(_) => log::error!("Waveform extraction thread panicked"),
+            }
+        }
+    }
+
+    fn process_loudness_measurements(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.loudness_measurements)
+            .into_iter()
+            .partition(|measurement| measurement.handle.is_finished());
+        self.loudness_measurements = running;
+        if finished.is_empty() {
+            return;
+        }
+        
+        for measurement in finished {
+            match measurement.handle.join() {
+                Ok(Ok(result)) => {
+                    if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == measurement.file) {
+                        clip.loudness_lufs = Some(result.integrated_lufs);
+                        clip.loudness_true_peak_dbfs = result.true_peak_dbfs;
+                        clip.loudness_range = Some(measurement.range);
+                    }
+                }
+                Ok(Err(e)) => log::warn!("Loudness measurement of {} failed: {}", measurement.file.display(), e),
+                Err(_) => log::error!("Loudness measurement thread panicked"),
+            }
+        }
+        
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after loudness measurement: {}", e);
+        }
+    }
+
+    /// Ctrl+click: add a clip to or remove it from the multi-selection, which starts
+    /// out with the selected clip
+    fn toggle_multi_selection(&mut self, index: usize) {
+        if self.selected_clip_indices.is_empty() {
+            self.selected_clip_indices.extend(self.selected_clip_index);
+        }
+        if !self.selected_clip_indices.remove(&index) {
+            self.selected_clip_indices.insert(index);
+        }
+    }
+
+    /// Shift+click: add every listed clip between the selected clip and `index` to the
+    /// multi-selection. Without a selected clip this is a plain click.
+    fn select_range(&mut self, index: usize) {
+        let Some(anchor) = self.selected_clip_index else {
+            self.select_clip(index);
+            return;
+        };
+        let order = self.clip_list_display_order();
+        self.selected_clip_indices.extend(Self::range_between(&order, anchor, index));
+        self.selected_clip_indices.insert(anchor);
+        self.selected_clip_indices.insert(index);
+    }
+
+    /// Entries of `order` from `from` to `to` inclusive, in either direction. Just the two
+    /// ends if either isn't in `order` (e.g. hidden by the search).
+    pub(crate) fn range_between(order: &[usize], from: usize, to: usize) -> Vec<usize> {
+        let from_position = order.iter().position(|&index| index == from);
+        let to_position = order.iter().position(|&index| index == to);
+        match (from_position, to_position) {
+            (Some(a), Some(b)) => order[a.min(b)..=a.max(b)].to_vec(),
+            _ => vec![from, to],
+        }
+    }
+
+    /// `file://` URL of an absolute path, with everything but unreserved characters percent-encoded
+    pub(crate) fn file_url(path: &std::path::Path) -> String {
+        let text = path.to_string_lossy().replace('\\', "/");
+        // Windows paths start with the drive letter and need the third slash added
+        let mut url = String::from(if text.starts_with('/') { "file://" } else { "file:///" });
+        for byte in text.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => url.push(byte as char),
+                _ => url.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        url
+    }
+
+    /// The export's absolute path, or its file URL. Export paths are relative with the
+    /// default trimmed directory; `canonicalize` is avoided for its `\\?\` prefix on Windows.
+    pub(crate) fn export_path_clipboard_text(path: &std::path::Path, as_file_url: bool) -> String {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        if as_file_url {
+            Self::file_url(&path)
+        } else {
+            path.to_string_lossy().to_string()
+        }
+    }
+
+    /// Put the last export's path (or file URL, see the settings) on the clipboard
+    fn copy_last_export_path(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.last_export_path.as_ref() else {
+            return;
+        };
+        let text = Self::export_path_clipboard_text(path, self.config.copy_path_as_file_url);
+        ctx.output_mut(|o| o.copied_text = text);
+        self.show_toast("Export path copied to clipboard".to_string());
+    }
+
+    /// Concatenate the multi-selected clips in recording order into a new replay next to the first
+    fn start_clip_merge(&mut self) {
+        let mut clips: Vec<&Clip> = self.selected_clip_indices.iter()
+            .filter_map(|&index| self.clips.get(index))
+            .filter(|clip| !clip.is_deleted)
+            .collect();
+        if clips.len() < 2 || self.clip_merge.is_some() {
+            return;
+        }
+        clips.sort_by_key(|clip| clip.timestamp);
+        
+        let inputs: Vec<PathBuf> = clips.iter().map(|clip| clip.original_file.clone()).collect();
+        let output = crate::video::merge::merged_output_path(&inputs[0]);
+        log::info!("Merging {} clips into {}", inputs.len(), output.display());
+        self.status_message = format!("Merging {} clips…", inputs.len());
+        let path = output.clone();
+        let encoder = self.config.export_preset.video_encoder;
+        let handle = std::thread::spawn(move || crate::video::merge::merge_files(&inputs, &path, encoder));
+        self.clip_merge = Some(ClipMerge { output, handle });
+    }
+
+    /// Add the merged replay as a new clip and select it for trimming
+    fn process_clip_merge(&mut self) {
+        if !self.clip_merge.as_ref().is_some_and(|merge| merge.handle.is_finished()) {
+            return;
+        }
+        let Some(merge) = self.clip_merge.take() else {
+            return;
+        };
+        
+        match merge.handle.join() {
+            Ok(Ok(mode)) => {
+                if let Some(new_file) = NewReplayFile::from_path(merge.output.clone(), &self.config.timestamp_pattern()) {
+                    self.create_clip_from_file(new_file, None);
+                }
+                self.selected_clip_indices.clear();
+                if let Some(index) = self.clips.iter().position(|c| c.original_file == merge.output) {
+                    self.select_clip(index);
+                }
+                let name = merge.output.file_name().unwrap_or_default().to_string_lossy().to_string();
+                self.status_message = match mode {
+                    crate::video::merge::ConcatMode::StreamCopy => format!("Merged into {}", name),
+                    crate::video::merge::ConcatMode::ReEncode => {
+                        format!("Merged into {} (re-encoded, the clips differ in codec or resolution)", name)
+                    }
+                };
+            }
+            Ok(Err(e)) => {
+                log::error!("Merging into {} failed: {}", merge.output.display(), e);
+                self.show_error("Error merging clips", &e.to_string());
+            }
+            Err(_) => log::error!("Clip merge thread panicked"),
+        }
+    }
+
+    /// Join the trimmed ranges of a session's ready clips, in recording order, into one file
+    /// in the trimmed directory
+    fn start_session_export(&mut self, session: &SessionGroup) {
+        if self.session_export.is_some() {
+            return;
+        }
+        let (mut ready, skipped) = self.session_clips_ready(&session.clips);
+        if ready.len() < 2 {
+            self.status_message = "A session needs at least two ready clips to export as one file".to_string();
+            return;
+        }
+        ready.sort_by_key(|&index| self.clips[index].timestamp);
+
+        let segments: Vec<crate::video::merge::MergeSegment> = ready.iter()
+            .map(|&index| crate::video::merge::MergeSegment {
+                path: self.clips[index].original_file.clone(),
+                start: self.clips[index].trim_start,
+                end: self.clips[index].trim_end,
+            })
+            .collect();
+        let clip_names = ready.iter().map(|&index| self.clips[index].get_output_filename()).collect();
+        let output = crate::video::merge::session_output_path(&self.config.trimmed_directory, &session.date, &session.start_time);
+        log::info!("Exporting {} clips of the session as {}", segments.len(), output.display());
+        if skipped > 0 {
+            log::info!("Skipping {} invalid or still writing clip(s) of the session", skipped);
+        }
+
+        let progress = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let thread_progress = progress.clone();
+        let path = output.clone();
+        let encoder = self.config.export_preset.video_encoder;
+        let handle = std::thread::spawn(move || {
+            crate::video::merge::merge_clips(&segments, &path, encoder, &thread_progress)
+        });
+        self.session_export = Some(SessionExport {
+            output,
+            clip_names,
+            timestamp: self.clips[ready[0]].timestamp,
+            progress,
+            handle,
+        });
+    }
+
+    /// Add the merged session as a new clip once it's written
+    fn process_session_export(&mut self) {
+        if !self.session_export.as_ref().is_some_and(|export| export.handle.is_finished()) {
+            return;
+        }
+        let Some(export) = self.session_export.take() else {
+            return;
+        };
+
+        match export.handle.join() {
+            Ok(Ok(mode)) => {
+                // It lives outside the watched directory, so it's kept like a dropped-in video
+                let mut clip = Clip::without_target_at(export.output.clone(), export.timestamp);
+                clip.imported = true;
+                self.video_info_manager.request_if_needed(export.output.clone());
                 self.clips.push(clip);
+                if let Err(e) = self.save_clips() {
+                    log::error!("Failed to save clips after exporting a session: {}", e);
+                }
+                if self.config.select_session_export {
+                    self.selected_clip_indices.clear();
+                    self.select_clip(self.clips.len() - 1);
+                }
+                self.last_export_path = Some(export.output.clone());
+                let name = export.output.file_name().unwrap_or_default().to_string_lossy().to_string();
+                self.status_message = match mode {
+                    crate::video::merge::ConcatMode::StreamCopy => format!("Exported session as {}", name),
+                    crate::video::merge::ConcatMode::ReEncode => {
+                        format!("Exported session as {} (re-encoded, the clips differ in codec or resolution)", name)
+                    }
+                };
+            }
+            Ok(Err(e)) => {
+                log::error!("Exporting the session to {} failed: {}", export.output.display(), e);
+                self.show_error("Error exporting session", &e.to_string());
+            }
+            Err(_) => log::error!("Session export thread panicked"),
+        }
+    }
+
+    /// Which clip of a running session export is being processed
+    fn render_session_export_window(&mut self, ctx: &egui::Context) {
+        let Some(export) = self.session_export.as_ref() else {
+            return;
+        };
+        let total = export.clip_names.len();
+        let current = export.progress.load(std::sync::atomic::Ordering::Relaxed).min(total);
+
+        egui::Window::new("Exporting session")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("→ {}", export.output.display()));
+                ui.add(egui::ProgressBar::new(current as f32 / (total + 1) as f32).show_percentage());
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    if current < total {
+                        ui.label(format!("Cutting clip {} of {}: {}", current + 1, total, export.clip_names[current]));
+                    } else {
+                        ui.label(format!("Joining {} clips…", total));
+                    }
+                });
+            });
+        // Progress comes from a background thread, so keep redrawing while it runs
+        ctx.request_repaint_after(std::time::Duration::from_millis(200));
+    }
+
+    /// Run FFmpeg scene detection over the selected clip once the FFmpeg pool has a free slot
+    fn request_scene_detection(&mut self) {
+        let Some(clip) = self.get_selected_clip() else {
+            return;
+        };
+        // A cancelled detection of this clip may still be winding down; start a fresh one
+        if !clip.is_video_valid() || self.scene_detections.iter().any(|d| {
+            d.file == clip.original_file && !d.cancel.load(std::sync::atomic::Ordering::Relaxed)
+        }) {
+            return;
+        }
+        
+        let file = clip.original_file.clone();
+        let duration = clip.video_length_seconds.unwrap_or(0.0);
+        let threshold = self.config.scene_detection_threshold;
+        let ffmpeg = self.config.ffmpeg_path.clone().unwrap_or_else(|| PathBuf::from("ffmpeg"));
+        let progress = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        log::debug!("Detecting scenes in {} (threshold {:.2})", file.display(), threshold);
+        let path = file.clone();
+        let thread_progress = Arc::clone(&progress);
+        let cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let thread_cancel = cancel.clone();
+        let pool = self.ffmpeg_pool.clone();
+        let handle = std::thread::spawn(move || {
+            pool.run(move || crate::video::detect_scenes(&path, threshold, &ffmpeg, &thread_progress, &thread_cancel))
+        });
+        self.scene_detections.push(SceneDetection { file, duration, progress, cancel, handle });
+    }
+
+    /// Add markers for finished scene detections, skipping cuts next to an existing marker
+    fn process_scene_detections(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.scene_detections)
+            .into_iter()
+            .partition(|detection| detection.handle.is_finished());
+        self.scene_detections = running;
+        if finished.is_empty() {
+            return;
+        }
+        
+        for detection in finished {
+            match detection.handle.join() {
+                Ok(Ok(changes)) => {
+                    let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == detection.file) else {
+                        continue;
+                    };
+                    let times = crate::video::select_scene_markers(
+                        &changes,
+                        crate::video::MAX_SCENE_MARKERS,
+                        crate::video::MIN_SCENE_SPACING_SECONDS,
+                    );
+                    let mut added = 0;
+                    for time in times {
+                        let near_existing = clip.markers.iter()
+                            .any(|marker| (marker.time - time).abs() < crate::video::MIN_SCENE_SPACING_SECONDS);
+                        if !near_existing {
+                            added += 1;
+                            clip.add_marker(time, format!("Scene {}", added));
+                        }
+                    }
+                    self.show_toast(format!("Added {} scene marker(s)", added));
+                }
+                Ok(Err(_)) if detection.cancel.load(std::sync::atomic::Ordering::Relaxed) => {
+                    log::debug!("Scene detection of {} cancelled", detection.file.display());
+                }
+                Ok(Err(e)) => {
+                    log::warn!("Scene detection of {} failed: {}", detection.file.display(), e);
+                    self.status_message = format!("Scene detection failed: {}", e);
+                }
+                Err(_) => log::error!("Scene detection thread panicked"),
+            }
+        }
+        
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after scene detection: {}", e);
+        }
+    }
+
+    fn process_motion_detections(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.motion_detections)
+            .into_iter()
+            .partition(|detection| detection.handle.is_finished());
+        self.motion_detections = running;
+        
+        for detection in finished {
+            match detection.handle.join() {
+                Ok(Ok(motion_start)) => {
+                    log::debug!("First action in {} at {:.2}s", detection.file.display(), motion_start);
+                    if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == detection.file) {
+                        clip.motion_start = Some(motion_start);
+                    }
+                }
+                // Only costs the "First action" button, which stays disabled
+                Ok(Err(e)) => log::warn!("Motion detection of {} failed: {}", detection.file.display(), e),
+                Err(_) => log::error!("Motion detection thread panicked"),
+            }
+        }
+    }
+
+    /// Store finished leading black checks and move trim starts that sit inside the black
+    fn process_black_detections(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.black_detections)
+            .into_iter()
+            .partition(|detection| detection.job.is_finished());
+        self.black_detections = running;
+        if finished.is_empty() {
+            return;
+        }
+        
+        for detection in finished {
+            match detection.result.try_recv() {
+                Ok(Ok(black_end)) => {
+                    let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == detection.file) else {
+                        continue;
+                    };
+                    clip.leading_black_end = Some(black_end);
+                    if clip.skip_leading_black() {
+                        log::info!("Skipped {:.2}s of leading black in {}", black_end, detection.file.display());
+                        let message = format!("Skipped {:.1}s of black at the start of {}", black_end, clip.get_output_filename());
+                        self.show_toast(message);
+                    }
+                }
+                // Not worth bothering the user about; the clip just keeps its trim
+                Ok(Err(e)) => log::warn!("Black frame detection of {} failed: {}", detection.file.display(), e),
+                Err(_) => log::error!("Black frame detection job panicked"),
+            }
+        }
+        
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after black frame detection: {}", e);
+        }
+    }
+
+    /// Loudness and true peak lines under the clip duration, coloured by EBU R128 compliance
+    fn show_loudness(&mut self, ui: &mut egui::Ui, index: usize) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        let measuring = self.loudness_measurements.iter().any(|m| m.file == clip.original_file);
+        let stale = !clip.loudness_is_current();
+        let (lufs, true_peak) = (clip.loudness_lufs, clip.loudness_true_peak_dbfs);
+        
+        ui.horizontal(|ui| {
+            ui.label("Loudness:");
+            match lufs {
+                Some(lufs) => {
+                    ui.colored_label(crate::audio::LoudnessCompliance::of_integrated(lufs).color(), format!("{:.1} LUFS", lufs))
+                        .on_hover_text("EBU R128 target: -23 LUFS ±1 LU");
+                }
+                None if measuring => {}
+                None => {
+                    ui.weak("not measured");
+                }
+            }
+            if measuring {
+                ui.spinner();
+            } else if stale && lufs.is_some() {
+                ui.weak("(old trim)");
+                if ui.small_button("↻").on_hover_text("Measure the current trim").clicked() {
+                    self.request_loudness_measurement(index);
+                }
+            }
+        });
+        if let Some(peak) = true_peak {
+            ui.horizontal(|ui| {
+                ui.label("True peak:");
+                ui.colored_label(crate::audio::LoudnessCompliance::of_true_peak(peak).color(), format!("{:.1} dBTP", peak))
+                    .on_hover_text("EBU R128 maximum: -1 dBTP");
+            });
+        }
+    }
+
+    /// Report launches that failed, e.g. because no player is associated with the file type
+    fn process_external_player_launches(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.external_player_launches)
+            .into_iter()
+            .partition(|launch| launch.is_finished());
+        self.external_player_launches = running;
+        
+        for launch in finished {
+            match launch.join() {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    log::error!("{}", e);
+                    self.show_error("Couldn't open external player", &e.to_string());
+                }
+                Err(_) => log::error!("External player launch thread panicked"),
+            }
+        }
+    }
+
+    /// Queue a GIF/WebP export of the selected clip's trim range
+    pub fn export_animated(&mut self, format: crate::video::ExportFormat, force_overwrite: bool) -> anyhow::Result<()> {
+        let Some(index) = self.selected_clip_index else {
+            return Ok(());
+        };
+        let Some(clip) = self.clips.get(index) else {
+            return Ok(());
+        };
+        
+        let trim_duration = clip.trim_end - clip.trim_start;
+        if trim_duration > self.config.max_animated_duration_secs {
+            return Err(anyhow::anyhow!(
+                "Trim is {:.1}s, animated exports are limited to {:.0}s",
+                trim_duration, self.config.max_animated_duration_secs
+            ));
+        }
+        
+        let output_filename = format!("{}.{}", self.output_name_for(index), format.extension());
+        let default_directory = match format {
+            crate::video::ExportFormat::Gif => &self.config.gif_directory,
+            _ => &self.config.trimmed_directory,
+        };
+        let output_path = clip.export_directory(default_directory).join(output_filename);
+        if output_path.exists() && !force_overwrite {
+            return Err(anyhow::anyhow!("Output file exists. Use shift+click to overwrite."));
+        }
+        
+        let job = crate::video::ExportJob {
+            id: 0,
+            clip_path: clip.original_file.clone(),
+            output_path,
+            preset_name: crate::video::DEFAULT_PRESET_NAME.to_string(),
+            trim_start: clip.trim_start,
+            trim_end: clip.trim_end,
+            audio_tracks: Vec::new(), // Animated exports have no audio
+            force_overwrite,
+            format,
+            animated: self.config.export_preset.animated_settings(),
+            sidecar: None,
+            export_audio_tracks_separately: false,
+            extra_ffmpeg_args: Vec::new(), // Meant for the video exports' stream copy
+            target_size_mb: None,
+            video_encoder: crate::video::VideoEncoder::default(),
+        };
+        self.enqueue_export(job);
+        
+        if let Err(e) = self.save_export_queue() {
+            log::error!("Failed to save export queue: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Estimated GIF/WebP size for the selected clip, using the preview's aspect ratio when known
+    fn estimate_animated_size(&self, format: crate::video::ExportFormat) -> Option<u64> {
+        let clip = self.get_selected_clip()?;
+        let aspect = self.media_controller.as_ref()
+            .and_then(|controller| controller.lock().ok()?.video_geometry())
+            .map(|geometry| {
+                let (width, height) = geometry.display_dimensions();
+                width as f64 / height.max(1) as f64
+            })
+            .unwrap_or(16.0 / 9.0);
+        Some(self.config.export_preset.animated_settings().estimate_size_bytes(format, clip.trim_end - clip.trim_start, aspect))
+    }
+
+    /// Use the frame at the current preview position as the clip's thumbnail
+    fn set_poster_to_playhead(&mut self, index: usize) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        let timestamp = self.media_controller.as_ref()
+            .and_then(|controller| controller.lock().ok().map(|c| c.current_position()))
+            .or_else(|| self.video_preview.as_ref().map(|preview| preview.current_time))
+            .unwrap_or(clip.trim_start);
+        
+        self.clips[index].poster_timestamp = Some(timestamp);
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after setting thumbnail: {}", e);
+        }
+        self.show_toast(format!("Thumbnail set to {}", Clip::format_duration(timestamp)));
+    }
+
+    /// Trim the clip to its target duration ending at the playhead
+    fn trim_target_ending_at_playhead(&mut self, index: usize) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        let position = self.media_controller.as_ref()
+            .and_then(|controller| controller.lock().ok().map(|c| c.current_position()))
+            .or_else(|| self.video_preview.as_ref().map(|preview| preview.current_time))
+            .unwrap_or(clip.trim_end);
+        
+        let clip = &mut self.clips[index];
+        let target = clip.target_duration_seconds as f64;
+        let length = clip.trim_target_ending_at(position);
+        let message = if length + 0.05 < target {
+            format!("Only {:.1}s of footage before the playhead, trim set to {:.1}s - {:.1}s", length, clip.trim_start, clip.trim_end)
+        } else {
+            format!("Trim set to {:.1}s - {:.1}s", clip.trim_start, clip.trim_end)
+        };
+        
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after trimming to playhead: {}", e);
+        }
+        self.show_toast(message);
+    }
+
+    /// Save the frame at the current preview position to the screenshot directory,
+    /// at the source's full resolution, named after the clip and the position
+    pub fn take_screenshot(&mut self) -> anyhow::Result<PathBuf> {
+        let clip = self.get_selected_clip()
+            .ok_or_else(|| anyhow::anyhow!("No clip selected"))?;
+        let timestamp = self.media_controller.as_ref()
+            .and_then(|controller| controller.lock().ok().map(|c| c.current_position()))
+            .unwrap_or(clip.trim_start);
+        
+        let output_path = crate::video::VideoProcessor::screenshot_path(
+            &self.config.screenshot_directory,
+            &clip.get_output_filename(),
+            timestamp,
+            self.config.screenshot_format,
+        );
+        crate::video::VideoProcessor::extract_frame(&clip.original_file, timestamp, &output_path, self.config.screenshot_format)?;
+        Ok(output_path)
+    }
+
+    /// Show a status message that clears itself after TOAST_DURATION
+    pub fn show_toast(&mut self, message: String) {
+        self.show_toast_for(message, TOAST_DURATION);
+    }
+
+    fn show_toast_for(&mut self, message: String, duration: std::time::Duration) {
+        self.status_message = message.clone();
+        self.status_toast = Some((std::time::Instant::now() + duration, message));
+    }
+
+    /// I and O set the selected clip's trim start and end to the playback position
+    fn handle_trim_point_keys(&mut self, ui: &egui::Ui) {
+        if ui.ctx().wants_keyboard_input() {
+            return;
+        }
+        let (set_start, set_end) = ui.input_mut(|i| (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::I),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::O),
+        ));
+        if !set_start && !set_end {
+            return;
+        }
+        
+        let Some(index) = self.selected_clip_index.filter(|&index| index < self.clips.len()) else {
+            return;
+        };
+        let Some(position) = self.media_controller.as_ref()
+            .and_then(|controller| controller.lock().ok()
+                .filter(|c| !c.is_shutting_down())
+                .map(|c| c.current_position()))
+        else {
+            return;
+        };
+        
+        let rules = self.config.trim_rules();
+        let clip = &mut self.clips[index];
+        let (handle, message) = if set_start {
+            let message = if clip.set_trim_start_at(position, rules) {
+                format!("Trim start set to {:.1}s", clip.trim_start)
+            } else {
+                log::warn!("Trim start at {:.2}s would be past the trim end, using {:.2}s", position, clip.trim_start);
+                format!("⚠ Playhead is past the trim end, trim start set to {:.1}s", clip.trim_start)
+            };
+            (TrimHandle::Start, message)
+        } else {
+            let message = if clip.set_trim_end_at(position, rules) {
+                format!("Trim end set to {:.1}s", clip.trim_end)
+            } else {
+                log::warn!("Trim end at {:.2}s would be before the trim start, using {:.2}s", position, clip.trim_end);
+                format!("⚠ Playhead is before the trim start, trim end set to {:.1}s", clip.trim_end)
+            };
+            (TrimHandle::End, message)
+        };
+        
+        self.timeline_widget.flash_trim_handle(handle);
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after setting trim point: {}", e);
+        }
+        self.show_toast_for(message, TRIM_POINT_TOAST_DURATION);
+    }
+
+    /// Delete partial exports found at startup
+    fn clean_up_stale_temp_exports(&mut self) {
+        let mut removed = 0;
+        for path in std::mem::take(&mut self.stale_temp_exports) {
+            match std::fs::remove_file(&path) {
+                Ok(()) => removed += 1,
+                Err(e) => log::error!("Failed to remove partial export {}: {}", path.display(), e),
+            }
+        }
+        self.status_message = format!("Removed {} partial export(s)", removed);
+    }
+
+    /// Show the first line of an error in the status bar, keeping the full text for the details dialog
+    pub fn show_error(&mut self, context: &str, error: &str) {
+        let summary = error.lines().next().unwrap_or_default();
+        self.status_message = format!("{}: {}", context, summary);
+        self.error_details = Some(format!("{}: {}", context, error));
+        self.notify_webhooks(crate::core::webhooks::WebhookPayload::new(
+            crate::core::webhooks::WebhookTrigger::Error,
+            format!("{}: {}", context, error),
+        ));
+    }
+
+    /// Post `payload` to the webhooks configured for its event
+    fn notify_webhooks(&self, payload: crate::core::webhooks::WebhookPayload) {
+        self.webhooks.notify(&self.config.webhooks, payload);
+    }
+
+    /// Surface errors from the preview's FFmpeg process in the status bar
+    fn report_media_controller_errors(&mut self) {
+        let error = self.media_controller.as_ref()
+            .and_then(|controller| controller.lock().ok()?.take_error());
+        if let Some(error) = error {
+            self.show_error("Preview error", &error);
+        }
+    }
+
+    fn update_playback_availability(&mut self) {
+        let state = self.media_controller.as_ref()
+            .and_then(|controller| controller.lock().ok().map(|controller| controller.state().clone()));
+        self.playback_availability = match state {
+            Some(state) => self.availability_debouncer.update(&state, std::time::Instant::now()),
+            None => crate::video::PlaybackAvailability::ALL,
+        };
+    }
+
+    /// Recreate the media controller for the selected clip after a playback error
+    fn retry_media_controller(&mut self) {
+        if let Some(preview) = &mut self.video_preview {
+            preview.is_playing = false;
+        }
+        if self.media_controller.is_some() {
+            // The new controller starts Unloaded and is set up again on the next frame
+            self.media_controller = Some(self.new_media_controller());
+        }
+    }
+
+    /// Media controller playing on the configured audio output device
+    fn new_media_controller(&self) -> Arc<std::sync::Mutex<crate::video::MediaController>> {
+        let output_device = self.config.playback_output_device.clone();
+        Arc::new(std::sync::Mutex::new(crate::video::MediaController::with_output_device(output_device)))
+    }
+
+    /// Move preview audio to the selected output device without restarting playback
+    fn apply_output_device(&mut self) {
+        if let Some(ref controller) = self.media_controller {
+            if let Ok(mut controller) = controller.lock() {
+                controller.set_output_device(self.config.playback_output_device.clone());
+            }
+        }
+        self.status_message = format!("Playback audio output: {}",
+            self.config.playback_output_device.as_deref().unwrap_or("default device"));
+    }
+
+    /// Handle exports finished by the background queue
+    fn process_export_results(&mut self) {
+        let results = self.export_queue.process_completed();
+        if results.is_empty() {
+            return;
+        }
+        
+        for export in results {
+            match export.result {
+                Ok(()) => {
+                    log::info!("Export completed: {}", export.job.output_path.display());
+                    if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == export.job.clip_path) {
+                        clip.is_trimmed = true;
+                        clip.export_state = crate::core::ClipExportState::Exported;
+                    }
+                    if self.config.auto_advance_after_export {
+                        self.schedule_auto_advance(&export.job.clip_path);
+                    }
+                    if self.auto_export_outputs.remove(&export.job.output_path) {
+                        if let Some(ref mut audio_confirmation) = self.audio_confirmation {
+                            if let Err(e) = audio_confirmation.play_confirmation_sound(&self.config.audio_confirmation) {
+                                log::warn!("Failed to play auto-export confirmation sound: {}", e);
+                            }
+                        }
+                    }
+                    if !self.config.post_export_command.trim().is_empty() {
+                        self.start_post_export_hook(export.job.output_path.clone());
+                    }
+                    let clip = self.clips.iter().find(|c| c.original_file == export.job.clip_path);
+                    self.notify_webhooks(crate::core::webhooks::WebhookPayload {
+                        file: Some(export.job.clip_path.clone()),
+                        output_path: Some(export.job.output_path.clone()),
+                        clip: export.job.sidecar.clone()
+                            .or_else(|| clip.map(|clip| crate::core::ClipMetadata::from_clip(clip, &export.job.preset_name))),
+                        ..crate::core::webhooks::WebhookPayload::new(
+                            crate::core::webhooks::WebhookTrigger::ExportComplete,
+                            format!("Exported {}", export.job.output_path.display()),
+                        )
+                    });
+                    self.status_message = format!("Exported {}", 
+                        export.job.output_path.file_name().unwrap_or_default().to_string_lossy());
+                    self.last_export_path = Some(export.job.output_path.clone());
+                    self.export_history.record(crate::core::export_history::ExportRecord {
+                        spec: export.job.spec(),
+                        audio_tracks: export.job.audio_tracks.clone(),
+                        exported_at: Local::now(),
+                    });
+                    self.save_export_history();
+                    
+                    for (track_index, track_result) in &export.audio_track_results {
+                        match track_result {
+                            Ok(path) => {
+                                self.status_message.push_str(&format!(", track {} → {}",
+                                    track_index, path.file_name().unwrap_or_default().to_string_lossy()));
+                            }
+                            Err(e) => {
+                                self.show_error(&format!("Error extracting audio track {}", track_index), e);
+                            }
+                        }
+                    }
+                    self.apply_source_file_policy(&export.job);
+                }
+                Err(e) => {
+                    if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == export.job.clip_path) {
+                        clip.export_state = crate::core::ClipExportState::ExportFailed(e.clone());
+                    }
+                    if let Some(corrupt_path) = &export.corrupt_output {
+                        self.show_error("Error applying trim", &format!("{}\nThe output was kept as {}", e, corrupt_path.display()));
+                        self.failed_export = Some(export.job);
+                    } else {
+                        self.show_error("Error applying trim", &e);
+                    }
+                }
+            }
+        }
+        
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after export: {}", e);
+        }
+        if let Err(e) = self.save_export_queue() {
+            log::error!("Failed to save export queue: {}", e);
+        }
+    }
+
+    /// Archive or delete the replay behind a finished export, as configured. Only runs for
+    /// video exports, which were verified before being reported as done, once no other export
+    /// of the same replay is waiting. Imported videos aren't replays and are left alone.
+    fn apply_source_file_policy(&mut self, job: &crate::video::ExportJob) {
+        let policy = self.config.source_file_policy;
+        if policy == crate::core::SourceFilePolicy::Keep || job.format.is_animated() {
+            return;
+        }
+        if self.export_queue.pending_specs().iter().any(|spec| spec.clip_path == job.clip_path) {
+            log::debug!("More exports of {} queued, keeping it for now", job.clip_path.display());
+            return;
+        }
+        let Some(index) = self.clips.iter().position(|c| c.original_file == job.clip_path && !c.is_deleted) else {
+            return;
+        };
+        if self.clips[index].imported {
+            return;
+        }
+        
+        // The preview keeps the file open, which stops it from being moved on Windows
+        let was_selected = self.selected_clip_index == Some(index);
+        if was_selected {
+            self.media_controller = None;
+            if let Some(mut preview) = self.video_preview.take() {
+                preview.stop();
+            }
+        }
+        
+        match policy {
+            crate::core::SourceFilePolicy::MoveToArchive => {
+                match crate::core::recycle_bin::archive_file(&job.clip_path, &self.config.archive_directory) {
+                    Ok(archived) => {
+                        let clip = &mut self.clips[index];
+                        clip.original_file = archived;
+                        // Outside the replay directory now, so rescans have to keep it like an imported video
+                        clip.imported = true;
+                        self.status_message.push_str(", replay archived");
+                    }
+                    Err(e) => self.show_error("Failed to archive the replay", &e.to_string()),
+                }
+            }
+            crate::core::SourceFilePolicy::DeleteSource => {
+                match crate::core::recycle_bin::delete_file(&job.clip_path, &self.config.deleted_directory, true) {
+                    Ok(outcome) => {
+                        log::info!("Deleted {} after exporting it: {:?}", job.clip_path.display(), outcome);
+                        self.clips[index].is_deleted = true;
+                        self.last_near_duplicate_check = None;
+                        if was_selected {
+                            self.selected_clip_index = None;
+                        }
+                        self.status_message = format!("{}. {}", self.status_message, outcome.message());
+                    }
+                    Err(e) => self.show_error("Failed to delete the replay", &e.to_string()),
+                }
+            }
+            crate::core::SourceFilePolicy::Keep => {}
+        }
+        
+        if was_selected && !self.clips[index].is_deleted {
+            self.select_clip(index);
+        }
+    }
+
+    /// Register the hotkeys from the current config again, replacing the old bindings
+    fn reregister_hotkeys(&mut self) {
+        let Some(manager) = self.hotkey_manager.clone() else {
+            return;
+        };
+        let registrations = match manager.lock() {
+            Ok(mut manager) => manager.register_bindings(&self.config),
+            Err(_) => {
+                log::error!("Hotkey manager lock poisoned, hotkeys not re-registered");
+                return;
+            }
+        };
+        self.hotkey_registrations = registrations;
+        self.hotkey_conflicts = HotkeyManager::validate_bindings(&self.config.hotkeys);
+        
+        let failed = self.failed_hotkey_registrations().len();
+        if failed > 0 {
+            self.status_message = format!("⚠ {} hotkey(s) could not be registered, see Settings", failed);
+        }
+    }
+
+    fn failed_hotkey_registrations(&self) -> Vec<String> {
+        self.hotkey_registrations.iter()
+            .filter_map(|registration| registration.result.as_ref().err()
+                .map(|e| format!("{}: {}", registration.description, e)))
+            .collect()
+    }
+
+    /// Webhook list in the settings, with a "Test Webhook" button per entry
+    fn show_webhook_settings(&mut self, ui: &mut egui::Ui) {
+        if self.webhook_test.as_ref().is_some_and(|(_, handle)| handle.is_finished()) {
+            if let Some((url, handle)) = self.webhook_test.take() {
+                let result = match handle.join() {
+                    Ok(Ok(status)) => format!("HTTP {}", status),
+                    Ok(Err(e)) => e.to_string(),
+                    Err(_) => "Webhook test thread panicked".to_string(),
+                };
+                self.webhook_test_result = Some((url, result));
+            }
+        }
+        
+        let mut removed = None;
+        let mut test = None;
+        for (index, webhook) in self.config.webhooks.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut webhook.url)
+                    .hint_text("https://example.com/hook")
+                    .desired_width(260.0));
+                egui::ComboBox::from_id_source(("webhook_trigger", index))
+                    .selected_text(webhook.trigger.label())
+                    .show_ui(ui, |ui| {
+                        for trigger in crate::core::webhooks::WebhookTrigger::ALL {
+                            ui.selectable_value(&mut webhook.trigger, trigger, trigger.label());
+                        }
+                    });
+                ui.checkbox(&mut webhook.include_metadata, "Clip metadata");
                 
-                // Play appropriate confirmation sound based on whether duration was matched
-                if let Some(ref mut audio_confirmation) = self.audio_confirmation {
-                    if let Some(duration) = duration {
-                        // Matched clip - play duration-specific sound
-                        if self.config.audio_confirmation.duration_confirmation_enabled {
-                            if let Err(e) = audio_confirmation.play_duration_confirmation(&duration, &self.config.audio_confirmation) {
-                                log::warn!("Failed to play duration confirmation sound: {}", e);
+                let testing = self.webhook_test.as_ref().is_some_and(|(url, _)| *url == webhook.url);
+                if testing {
+                    ui.spinner();
+                } else if ui.add_enabled(self.webhook_test.is_none() && !webhook.url.trim().is_empty(),
+                    egui::Button::new("Test Webhook")).clicked()
+                {
+                    test = Some(index);
+                }
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+                if let Some((_, result)) = self.webhook_test_result.as_ref().filter(|(url, _)| *url == webhook.url) {
+                    ui.label(result);
+                }
+            });
+        }
+        if let Some(index) = test {
+            let webhook = self.config.webhooks[index].clone();
+            let url = webhook.url.clone();
+            let handle = std::thread::spawn(move || crate::core::webhooks::send_test_webhook(&webhook));
+            self.webhook_test = Some((url, handle));
+            self.webhook_test_result = None;
+        }
+        if let Some(index) = removed {
+            self.config.webhooks.remove(index);
+        }
+        
+        if ui.button("➕ Add Webhook").clicked() {
+            self.config.webhooks.push(crate::core::webhooks::WebhookConfig::default());
+        }
+        ui.small(format!(
+            "Each webhook gets a JSON POST for its event. Failed requests are retried up to {} times.",
+            crate::core::webhooks::WEBHOOK_MAX_RETRIES
+        ));
+    }
+
+    /// Run the configured post-export command for a finished export in the background
+    fn start_post_export_hook(&mut self, output: PathBuf) {
+        let template = self.config.post_export_command.clone();
+        let path = output.clone();
+        let handle = std::thread::spawn(move || crate::core::post_export::run_post_export_command(&template, &path));
+        self.post_export_hooks.push(PostExportHook { output, handle });
+    }
+
+    fn process_post_export_hooks(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.post_export_hooks)
+            .into_iter()
+            .partition(|hook| hook.handle.is_finished());
+        self.post_export_hooks = running;
+        
+        for hook in finished {
+            let name = hook.output.file_name().unwrap_or_default().to_string_lossy().to_string();
+            match hook.handle.join() {
+                Ok(Ok(())) => {
+                    log::info!("Post-export command finished for {}", hook.output.display());
+                    self.show_toast(format!("Post-export command finished for {}", name));
+                }
+                Ok(Err(e)) => {
+                    log::error!("Post-export command failed for {}: {}", hook.output.display(), e);
+                    self.show_error(&format!("Post-export command failed for {}", name), &e.to_string());
+                }
+                Err(_) => log::error!("Post-export command thread panicked"),
+            }
+        }
+    }
+
+    /// Select the clip after `exported` in the list once `AUTO_ADVANCE_DELAY` has passed
+    fn schedule_auto_advance(&mut self, exported: &std::path::Path) {
+        let Some(index) = self.clips.iter().position(|c| c.original_file == exported) else {
+            return;
+        };
+        match self.next_clip_in_list(index) {
+            Some(next) => self.pending_auto_advance = Some((std::time::Instant::now() + AUTO_ADVANCE_DELAY, next)),
+            None => log::debug!("Auto-advance: no clip after {}", exported.display()),
+        }
+    }
+
+    /// The clip shown below `index` in the clip list, if any
+    pub(crate) fn next_clip_in_list(&self, index: usize) -> Option<usize> {
+        let order = self.clip_list_display_order();
+        let position = order.iter().position(|&i| i == index)?;
+        order.get(position + 1).copied()
+    }
+
+    fn process_auto_advance(&mut self, ctx: &egui::Context) {
+        let Some((due, index)) = self.pending_auto_advance else {
+            return;
+        };
+        let now = std::time::Instant::now();
+        if now < due {
+            ctx.request_repaint_after((due - now).min(std::time::Duration::from_millis(250)));
+            return;
+        }
+        
+        self.pending_auto_advance = None;
+        if index >= self.clips.len() || self.clips[index].is_deleted {
+            return;
+        }
+        // Start from the target-duration trim unless it was set by hand
+        let padding = self.config.trim_padding;
+        let clip = &mut self.clips[index];
+        if let Some(length) = clip.video_length_seconds.filter(|_| clip.has_target_duration()) {
+            clip.apply_target_trim(length, padding);
+        }
+        self.select_clip(index);
+    }
+
+    fn save_export_history(&self) {
+        if let Err(e) = self.export_history.save(&crate::core::export_history::ExportHistory::file_path()) {
+            log::error!("Failed to save export history: {}", e);
+        }
+    }
+
+    /// Queue an export from the history again with the same range and settings,
+    /// replacing its previous output
+    fn repeat_export(&mut self, record: crate::core::export_history::ExportRecord) {
+        let mut job = crate::video::ExportJob::from(record.spec);
+        job.audio_tracks = record.audio_tracks;
+        job.force_overwrite = true;
+        self.status_message = format!("Re-exporting {}…", job.output_path.file_name().unwrap_or_default().to_string_lossy());
+        self.enqueue_export(job);
+        if let Err(e) = self.save_export_queue() {
+            log::error!("Failed to save export queue: {}", e);
+        }
+    }
+
+    fn render_recent_exports_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_recent_exports;
+        let mut to_open = None;
+        let mut to_reveal = None;
+        let mut to_repeat = None;
+        
+        egui::Window::new("Recent Exports")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                if self.export_history.records.is_empty() {
+                    ui.label("Nothing exported yet");
+                    return;
+                }
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("recent_exports").striped(true).num_columns(4).show(ui, |ui| {
+                        for record in &self.export_history.records {
+                            let output = record.spec.output_path.file_name().unwrap_or_default().to_string_lossy();
+                            let source = record.spec.clip_path.file_name().unwrap_or_default().to_string_lossy();
+                            ui.label(output).on_hover_text(format!("From {}\n{}", source, record.spec.output_path.display()));
+                            ui.label(record.exported_at.format("%Y-%m-%d %H:%M").to_string());
+                            ui.label(&record.spec.preset_name);
+                            ui.horizontal(|ui| {
+                                if ui.small_button("▶").on_hover_text("Open").clicked() {
+                                    to_open = Some(record.spec.output_path.clone());
+                                }
+                                if ui.small_button("📂").on_hover_text("Show in folder").clicked() {
+                                    to_reveal = Some(record.spec.output_path.clone());
+                                }
+                                if ui.small_button("🔁").on_hover_text("Export again with the same range and settings").clicked() {
+                                    to_repeat = Some(record.clone());
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        
+        self.show_recent_exports = open;
+        
+        if let Some(path) = to_open {
+            self.open_in_external_player(path);
+        }
+        if let Some(path) = to_reveal {
+            if let Err(e) = crate::core::shell::reveal_in_file_manager(&path) {
+                self.show_error("Error opening folder", &e.to_string());
+            }
+        }
+        if let Some(record) = to_repeat {
+            self.repeat_export(record);
+        }
+    }
+
+    /// Re-queue exports left over from the previous session
+    fn resume_pending_exports(&mut self) {
+        let specs = std::mem::take(&mut self.pending_export_specs);
+        log::info!("Resuming {} pending exports", specs.len());
+        
+        for spec in specs {
+            let mut job = crate::video::ExportJob::from(spec);
+            // Reuse the audio track layout of the clip if it's already loaded
+            if let Some(clip) = self.clips.iter().find(|c| c.original_file == job.clip_path) {
+                job.audio_tracks = clip.audio_tracks.clone();
+            }
+            // The output of an interrupted export may be partial, so allow overwriting it
+            job.force_overwrite = true;
+            self.enqueue_export(job);
+        }
+        
+        if let Err(e) = self.save_export_queue() {
+            log::error!("Failed to save export queue: {}", e);
+        }
+    }
+
+    fn process_hotkey_events(&mut self) {
+        while let Ok(event) = self.hotkey_receiver.try_recv() {
+            match event {
+                HotkeyEvent::ClipRequested(duration) => {
+                    let now = Local::now();
+                    log::info!("Hotkey triggered for {:?} at {}", duration, now);
+                    
+                    // Check if there are any recent clips that can be matched to this duration request
+                    let mut found_matching_clip = false;
+                    for clip in &self.clips {
+                        if Self::timestamps_match_static(now, clip.timestamp) {
+                            found_matching_clip = true;
+                            break;
+                        }
+                    }
+                    
+                    // Simply save the duration request - matching will happen at display time
+                    self.duration_requests.push(DurationRequest {
+                        timestamp: now,
+                        duration: duration.clone(),
+                    });
+                    
+                    // Clean up old duration requests (older than 1 hour, or the retry timeout if longer)
+                    let cutoff = now - self.duration_request_max_age();
+                    self.duration_requests.retain(|req| req.timestamp > cutoff);
+                    
+                    // Save duration requests to persistence
+                    if let Err(e) = self.save_duration_requests() {
+                        log::error!("Failed to save duration requests: {}", e);
+                    }
+                    
+                    // Play unmatched sound if no clip was found to match this hotkey
+                    if !found_matching_clip {
+                        log::info!("No matching clip found for hotkey {} at {}", duration as u32, now);
+                        if let Some(ref mut audio_confirmation) = self.audio_confirmation {
+                            if self.config.audio_confirmation.unmatched_sound_enabled {
+                                if let Err(e) = audio_confirmation.play_unmatched_clip_sound(&self.config.audio_confirmation) {
+                                    log::warn!("Failed to play unmatched hotkey sound: {}", e);
+                                }
                             }
                         }
-                    } else {
-                        // Clip appeared without immediate hotkey match - play general confirmation sound
-                        if let Err(e) = audio_confirmation.play_confirmation_sound(&self.config.audio_confirmation) {
-                            log::warn!("Failed to play clip detection confirmation sound: {}", e);
-                        }
                     }
-                } else {
-                    log::debug!("Audio confirmation system not available");
+                    
+                    log::info!("Saved duration request for {} at {}", duration as u32, now);
                 }
-                
-                // Save clips after adding new clip
-                if let Err(e) = self.save_clips() {
-                    log::error!("Failed to save clips after creating new clip: {}", e);
+            }
+        }
+    }
+    
+    fn process_file_events(&mut self) {
+        // Collect new files first
+        let mut new_files = Vec::new();
+        if let Some(ref mut receiver) = self.file_receiver {
+            while let Ok(new_file) = receiver.try_recv() {
+                log::info!("New file detected: {:?}", new_file.path);
+                new_files.push(new_file);
+            }
+        }
+        
+        // Process each new file
+        for new_file in new_files {
+            // First, check if this file matches any pending clip requests
+            let mut matched_requests = Vec::new();
+            
+            for (i, request) in self.pending_clip_requests.iter().enumerate() {
+                if Self::timestamps_match_static(request.timestamp, new_file.timestamp) {
+                    matched_requests.push((i, new_file.clone(), request.duration.clone()));
                 }
             }
-            Err(e) => {
-                log::error!("Failed to create clip: {}", e);
+            
+            // Process matched requests
+            for (index, file, duration) in matched_requests.iter().rev() {
+                self.create_clip_from_file(file.clone(), Some(duration.clone()));
+                self.pending_clip_requests.remove(*index);
+            }
+            
+            // If no hotkey request matched, still add the file to the clip list automatically
+            if matched_requests.is_empty() {
+                self.create_clip_from_file(new_file, None);
+            }
+        }
+    }
+    
+    fn try_match_clip_request(&mut self, request_time: chrono::DateTime<Local>, duration: crate::core::ClipDuration) {
+        if let Some(ref watched_dir) = self.watched_directory {
+            // Scan for existing files that might match
+            if let Ok(existing_files) = FileMonitor::scan_existing_files(watched_dir, &self.config.timestamp_pattern()) {
+                for file in existing_files {
+                    if self.timestamps_match(request_time, file.timestamp) {
+                        self.create_clip_from_file(file, Some(duration));
+                        // Remove the pending request
+                        self.pending_clip_requests.retain(|req| req.timestamp != request_time);
+                        return;
+                    }
+                }
+            }
+        }
+        
+        // Keep the request pending for a bit in case the file appears later
+        // Remove old pending requests (older than the retry timeout)
+        let cutoff = Local::now() - self.pending_request_max_age();
+        self.pending_clip_requests.retain(|req| req.timestamp > cutoff);
+    }
+    
+    fn try_match_file_to_requests(&mut self, new_file: &NewReplayFile) {
+        let mut clips_to_create = Vec::new();
+        let mut indices_to_remove = Vec::new();
+        
+        for (i, request) in self.pending_clip_requests.iter().enumerate() {
+            if Self::timestamps_match_static(request.timestamp, new_file.timestamp) {
+                clips_to_create.push((new_file.clone(), request.duration.clone()));
+                indices_to_remove.push(i);
+            }
+        }
+        
+        // Remove matched requests (in reverse order to maintain indices)
+        for &index in indices_to_remove.iter().rev() {
+            self.pending_clip_requests.remove(index);
+        }
+        
+        // Create clips
+        for (file, duration) in clips_to_create {
+            self.create_clip_from_file(file, Some(duration));
+        }
+    }
+    
+    fn timestamps_match(&self, request_time: chrono::DateTime<Local>, file_time: chrono::DateTime<Local>) -> bool {
+        Self::timestamps_match_static(request_time, file_time)
+    }
+    
+    fn timestamps_match_static(request_time: chrono::DateTime<Local>, file_time: chrono::DateTime<Local>) -> bool {
+        let diff = (request_time - file_time).num_seconds().abs();
+        diff <= 10 // Within 10 seconds
+    }
+    
+    fn create_clip_from_file(&mut self, file: NewReplayFile, duration: Option<crate::core::ClipDuration>) {
+        // Check if a clip with this file path already exists
+        if self.clips.iter().any(|existing_clip| existing_clip.original_file == file.path) {
+            log::debug!("Clip already exists for file: {:?}", file.path);
+            return;
+        }
+
+        // Always create clips without target duration - matching will happen at display time.
+        // The timestamp is the one the monitor read with the configured pattern (or the mtime).
+        let mut clip = file.to_clip();
+        
+        // Request video info asynchronously (non-blocking)
+        self.video_info_manager.request_if_needed(file.path);
+        
+        // Checked for being a duplicate once OBS is done writing it, see process_async_video_info_results
+        self.pending_duplicate_checks.insert(clip.original_file.clone());
+        
+        log::info!("Created clip: {}", clip.get_output_filename());
+        self.notify_webhooks(crate::core::webhooks::WebhookPayload {
+            file: Some(clip.original_file.clone()),
+            clip: Some(crate::core::ClipMetadata::from_clip(&clip, crate::video::DEFAULT_PRESET_NAME)),
+            ..crate::core::webhooks::WebhookPayload::new(
+                crate::core::webhooks::WebhookTrigger::ClipDetected,
+                format!("New clip {}", clip.get_output_filename()),
+            )
+        });
+        if self.hidden_in_tray {
+            crate::gui::tray::show_notification("New clip", &clip.get_output_filename());
+        }
+        // The export waits for OBS to finish the file, see process_async_video_info_results
+        if let Some(duration) = duration.as_ref().filter(|_| self.config.auto_export_on_match) {
+            clip.set_target_duration_with_padding(*duration, self.config.trim_padding);
+            self.pending_auto_exports.insert(clip.original_file.clone());
+        }
+        let incoming = clip.original_file.clone();
+        self.clips.push(clip);
+        
+        // Play appropriate confirmation sound based on whether duration was matched
+        if let Some(ref mut audio_confirmation) = self.audio_confirmation {
+            if let Some(duration) = duration {
+                // Matched clip - play duration-specific sound
+                if self.config.audio_confirmation.duration_confirmation_enabled {
+                    if let Err(e) = audio_confirmation.play_duration_confirmation(&duration, &self.config.audio_confirmation) {
+                        log::warn!("Failed to play duration confirmation sound: {}", e);
+                    }
+                }
+            } else {
+                // Clip appeared without immediate hotkey match - play general confirmation sound
+                if let Err(e) = audio_confirmation.play_confirmation_sound(&self.config.audio_confirmation) {
+                    log::warn!("Failed to play clip detection confirmation sound: {}", e);
+                }
             }
+        } else {
+            log::debug!("Audio confirmation system not available");
+        }
+        
+        self.enforce_retention_policy(&incoming);
+        
+        // Save clips after adding new clip
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after creating new clip: {}", e);
         }
     }
     
+    /// Indices of the oldest clips that have to go to get back to `max_clips_to_keep`.
+    /// Every kept clip counts towards the limit, but exempt clips, the selected clip, `incoming`,
+    /// clips OBS may still be writing and pending auto-exports are never picked.
+    pub(crate) fn retention_candidates(&self, incoming: Option<&std::path::Path>) -> Vec<usize> {
+        let Some(max_clips) = self.config.max_clips_to_keep else {
+            return Vec::new();
+        };
+        
+        // Every kept clip counts towards the limit, even the ones that can't be removed
+        let kept = self.clips.iter().filter(|clip| !clip.is_deleted).count();
+        let excess = kept.saturating_sub(max_clips);
+        if excess == 0 {
+            return Vec::new();
+        }
+        
+        let mut candidates: Vec<usize> = self.clips.iter()
+            .enumerate()
+            .filter(|(index, clip)| {
+                !clip.is_deleted
+                    && !clip.is_retention_exempt()
+                    && clip.write_state == crate::core::VideoWriteState::Complete
+                    && Some(*index) != self.selected_clip_index
+                    && Some(clip.original_file.as_path()) != incoming
+                    && !self.pending_auto_exports.contains(&clip.original_file)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        candidates.sort_by_key(|&index| self.clips[index].timestamp);
+        candidates.truncate(excess);
+        candidates
+    }
+
+    /// Move the oldest unprotected clips beyond `max_clips_to_keep` to the deleted directory
+    fn enforce_retention_policy(&mut self, incoming: &std::path::Path) {
+        let mut removed = 0;
+        for index in self.retention_candidates(Some(incoming)) {
+            let file = self.clips[index].original_file.clone();
+            match crate::core::recycle_bin::delete_file(&file, &self.config.deleted_directory, false) {
+                Ok(_) => {
+                    log::info!("Retention policy moved {} to {}", file.display(), self.config.deleted_directory.display());
+                    self.clips[index].is_deleted = true;
+                    removed += 1;
+                }
+                Err(e) => log::warn!("Retention policy failed to remove {}: {}", file.display(), e),
+            }
+        }
+        
+        if removed > 0 {
+            self.last_near_duplicate_check = None;
+            self.show_toast(format!("Auto-removed {} old clip(s)", removed));
+        }
+    }
+
+    /// Fingerprint `file` and compare it with the existing clips on a background thread
+    fn request_duplicate_check(&mut self, file: &std::path::Path) {
+        if self.duplicate_checks.iter().any(|check| check.file == file) {
+            return;
+        }
+        let candidates: Vec<(PathBuf, Option<String>)> = self.clips.iter()
+            .filter(|c| !c.is_deleted && c.original_file != file)
+            .map(|c| (c.original_file.clone(), c.fingerprint.clone()))
+            .collect();
+        let path = file.to_path_buf();
+        let handle = std::thread::spawn(move || Self::find_duplicate(&path, &candidates));
+        self.duplicate_checks.push(DuplicateCheck { file: file.to_path_buf(), handle });
+    }
+
+    /// Find a candidate with the same size and fingerprint as `file`.
+    /// Fingerprints of candidates are computed lazily, only when the sizes match.
+    pub(crate) fn find_duplicate(file: &std::path::Path, candidates: &[(PathBuf, Option<String>)]) -> anyhow::Result<DuplicateCheckResult> {
+        let fingerprint = crate::video::VideoProcessor::compute_file_fingerprint(file)?;
+        let file_size = std::fs::metadata(file)?.len();
+        let mut result = DuplicateCheckResult { fingerprint, duplicate_of: None, computed: Vec::new() };
+        
+        for (candidate, known_fingerprint) in candidates {
+            let same_size = std::fs::metadata(candidate)
+                .map(|m| m.len() == file_size)
+                .unwrap_or(false);
+            if !same_size {
+                continue;
+            }
+            
+            let candidate_fingerprint = match known_fingerprint {
+                Some(known) => Some(known.clone()),
+                None => crate::video::VideoProcessor::compute_file_fingerprint(candidate).ok(),
+            };
+            if let (None, Some(computed)) = (known_fingerprint, &candidate_fingerprint) {
+                result.computed.push((candidate.clone(), computed.clone()));
+            }
+            if candidate_fingerprint.as_ref() == Some(&result.fingerprint) {
+                result.duplicate_of = Some(candidate.clone());
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Store fingerprints of finished duplicate checks and warn about duplicates
+    fn process_duplicate_checks(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.duplicate_checks)
+            .into_iter()
+            .partition(|check| check.handle.is_finished());
+        self.duplicate_checks = running;
+        if finished.is_empty() {
+            return;
+        }
+        
+        for check in finished {
+            match check.handle.join() {
+                Ok(Ok(result)) => {
+                    for (file, fingerprint) in result.computed {
+                        if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == file) {
+                            clip.fingerprint = Some(fingerprint);
+                        }
+                    }
+                    if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == check.file) {
+                        clip.fingerprint = Some(result.fingerprint);
+                    }
+                    // Warn (without blocking) if OBS appears to have saved the same buffer twice
+                    if let Some(duplicate) = result.duplicate_of {
+                        log::warn!("{} looks like a duplicate of {}", check.file.display(), duplicate.display());
+                        self.status_message = format!("Possible duplicate of {}",
+                            duplicate.file_name().unwrap_or_default().to_string_lossy());
+                        self.duplicate_of = Some(duplicate);
+                    }
+                }
+                Ok(Err(e)) => log::warn!("Failed to fingerprint {}: {}", check.file.display(), e),
+                Err(_) => log::error!("Duplicate check thread panicked"),
+            }
+        }
+        
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after duplicate check: {}", e);
+        }
+    }
+
     fn load_existing_clips(&mut self) {
         if let Some(ref watched_dir) = self.watched_directory {
-            if let Ok(existing_files) = FileMonitor::scan_existing_files(watched_dir) {
+            if let Ok(existing_files) = FileMonitor::scan_existing_files(watched_dir, &self.config.timestamp_pattern()) {
                 log::info!("Found {} existing replay files", existing_files.len());
                 // Files are logged during auto-refresh or manual scan
             }
@@ -561,26 +3103,19 @@ impl ClipHelperApp {
         // Force refresh regardless of current state
         if let Some(ref watched_dir) = self.watched_directory {
             log::debug!("Force refreshing clip list...");
-            self.clips.clear(); // Clear existing clips
+            self.clips.retain(|clip| clip.imported); // Clear existing clips, keeping imported videos
             
-            match FileMonitor::scan_existing_files(watched_dir) {
+            match FileMonitor::scan_existing_files(watched_dir, &self.config.timestamp_pattern()) {
                 Ok(existing_files) => {
                     if !existing_files.is_empty() {
                         log::info!("Force refresh found {} files", existing_files.len());
                         
                         // Create clips for all found files
                         for file in existing_files {
-                            let file_path = file.path.clone();
-                            match Clip::new_without_target(file.path) {
-                                Ok(clip) => {
-                                    // Don't block on video info during refresh
-                                    log::debug!("Force-loaded file: {}", clip.get_output_filename());
-                                    self.clips.push(clip);
-                                }
-                                Err(e) => {
-                                    log::error!("Failed to force-load clip for file {:?}: {}", file_path, e);
-                                }
-                            }
+                            // Don't block on video info during refresh
+                            let clip = file.to_clip();
+                            log::debug!("Force-loaded file: {}", clip.get_output_filename());
+                            self.clips.push(clip);
                         }
                         
                         self.status_message = format!("Refreshed {} clips", self.clips.len());
@@ -603,27 +3138,32 @@ impl ClipHelperApp {
         }
     }
 
-    fn group_clips_into_sessions(&self) -> Vec<SessionGroup> {
+    pub(crate) fn group_clips_into_sessions(&self) -> Vec<SessionGroup> {
         if self.clips.is_empty() {
             return Vec::new();
         }
 
+        let session_gap = chrono::Duration::minutes(self.config.session_gap_minutes.max(1) as i64);
         let mut sessions = Vec::new();
         let mut current_session_clips = Vec::new();
         let mut session_start_time: Option<chrono::DateTime<Local>> = None;
         let mut last_clip_time: Option<chrono::DateTime<Local>> = None;
+        let mut session_manual_break = false;
 
-        // Sort clips by timestamp
+        // Sort clips by timestamp; pinned clips get their own group instead of a session
         let mut sorted_indices: Vec<usize> = (0..self.clips.len()).collect();
         sorted_indices.sort_by(|&a, &b| self.clips[a].timestamp.cmp(&self.clips[b].timestamp));
+        let (pinned_clips, sorted_indices): (Vec<usize>, Vec<usize>) = sorted_indices.into_iter()
+            .partition(|&index| self.clips[index].pinned);
 
         for &index in &sorted_indices {
             let clip = &self.clips[index];
             
-            // Check if this clip starts a new session (gap > 1 hour)
+            // Check if this clip starts a new session (manual anchor or a long enough gap)
+            let is_manual_break = last_clip_time.is_some()
+                && self.config.manual_session_breaks.contains(&clip.original_file);
             let starts_new_session = if let Some(last_time) = last_clip_time {
-                let time_diff = clip.timestamp.signed_duration_since(last_time);
-                time_diff.num_hours() >= 1
+                is_manual_break || clip.timestamp.signed_duration_since(last_time) >= session_gap
             } else {
                 true // First clip always starts a new session
             };
@@ -637,6 +3177,8 @@ impl ClipHelperApp {
                             start_time: start_time.format("%H:%M").to_string(),
                             end_time: end_time.format("%H:%M").to_string(),
                             clips: current_session_clips.clone(),
+                            manual_break: session_manual_break,
+                            pinned: false,
                         };
                         sessions.push(session);
                     }
@@ -647,6 +3189,7 @@ impl ClipHelperApp {
             // Start new session if needed
             if current_session_clips.is_empty() {
                 session_start_time = Some(clip.timestamp);
+                session_manual_break = is_manual_break;
             }
 
             current_session_clips.push(index);
@@ -662,6 +3205,8 @@ impl ClipHelperApp {
                         start_time: start_time.format("%H:%M").to_string(),
                         end_time: end_time.format("%H:%M").to_string(),
                         clips: current_session_clips,
+                        manual_break: session_manual_break,
+                        pinned: false,
                     };
                     sessions.push(session);
                 }
@@ -669,9 +3214,100 @@ impl ClipHelperApp {
         }
 
         sessions.reverse(); // Show newest sessions first
+        if !pinned_clips.is_empty() {
+            sessions.insert(0, SessionGroup {
+                date: String::new(),
+                start_time: String::new(),
+                end_time: String::new(),
+                clips: pinned_clips,
+                manual_break: false,
+                pinned: true,
+            });
+        }
         sessions
     }
 
+    /// Edit a clip's name in place in the clip list, starting from its current name
+    fn start_clip_rename(&mut self, index: usize) {
+        let Some(clip) = self.clips.get(index) else {
+            return;
+        };
+        self.rename_buffer = clip.name.clone().unwrap_or_default();
+        self.renaming_clip_index = Some(index);
+    }
+
+    /// Apply or drop the in-place rename. An invalid name keeps the field open so it can be fixed.
+    fn finish_clip_rename(&mut self, outcome: RenameOutcome) {
+        let Some(index) = self.renaming_clip_index else {
+            return;
+        };
+        if outcome == RenameOutcome::Cancel || index >= self.clips.len() {
+            self.renaming_clip_index = None;
+            return;
+        }
+
+        let name = match crate::core::naming::validate_clip_name(&self.rename_buffer) {
+            Ok(name) => name,
+            Err(e) => {
+                self.show_toast(e);
+                return;
+            }
+        };
+        self.renaming_clip_index = None;
+        if self.clips[index].name == name {
+            return;
+        }
+        self.clips[index].name = name;
+        if self.selected_clip_index == Some(index) {
+            self.new_clip_name = self.clips[index].name.clone().unwrap_or_default();
+        }
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after rename: {}", e);
+        }
+        self.status_message = format!("Renamed to {}", self.clips[index].get_output_filename());
+    }
+
+    fn set_clip_color(&mut self, file: &std::path::Path, color: Option<[u8; 3]>) {
+        let Some(clip) = self.clips.iter_mut().find(|clip| clip.original_file == file) else {
+            return;
+        };
+        if clip.color == color {
+            return;
+        }
+        clip.color = color;
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after changing a color: {}", e);
+        }
+    }
+
+    fn toggle_pinned(&mut self, file: &std::path::Path) {
+        let Some(clip) = self.clips.iter_mut().find(|clip| clip.original_file == file) else {
+            return;
+        };
+        clip.pinned = !clip.pinned;
+        let message = if clip.pinned { "Clip pinned" } else { "Clip unpinned" };
+        // A missing clip was only listed because it was pinned
+        if !clip.pinned && clip.write_state == crate::core::VideoWriteState::Missing {
+            clip.is_deleted = true;
+        }
+        self.last_near_duplicate_check = None;
+        if let Err(e) = self.save_clips() {
+            log::error!("Failed to save clips after pinning: {}", e);
+        }
+        self.status_message = message.to_string();
+    }
+
+    /// Make `file` start a new session, or remove that anchor again
+    fn set_manual_session_break(&mut self, file: &std::path::Path, split: bool) {
+        self.config.manual_session_breaks.retain(|anchor| anchor != file);
+        if split {
+            self.config.manual_session_breaks.push(file.to_path_buf());
+        }
+        if let Err(e) = self.config.save() {
+            log::error!("Failed to save session breaks: {}", e);
+        }
+    }
+
     /// Ensures video info is loaded for a specific clip index
     /// Used for background loading when clips are displayed
     fn ensure_video_info_loaded(&mut self, clip_index: usize) {
@@ -700,6 +3336,16 @@ impl ClipHelperApp {
                 }
             }
         }
+        
+        // Corrupted files may be repaired (e.g. remuxed) outside the app
+        if now.duration_since(self.last_corrupted_clip_retry) >= crate::core::CORRUPTED_CLIP_RETRY_INTERVAL {
+            self.last_corrupted_clip_retry = now;
+            for clip in &self.clips {
+                if matches!(clip.write_state, crate::core::VideoWriteState::Corrupted { .. }) && !clip.is_deleted {
+                    self.video_info_manager.request_if_needed(clip.original_file.clone());
+                }
+            }
+        }
     }
     
     /// Process completed video info results from async loader
@@ -709,8 +3355,16 @@ impl ClipHelperApp {
         for result in results {
             // Find the clip that matches this file path
             if let Some(clip) = self.clips.iter_mut().find(|c| c.original_file == result.file_path) {
+                if let crate::core::VideoWriteState::Corrupted { reason } = &result.write_state {
+                    if clip.write_state != result.write_state {
+                        log::warn!("{} looks corrupted, retrying every {}s: {}",
+                            clip.original_file.display(), crate::core::CORRUPTED_CLIP_RETRY_INTERVAL.as_secs(), reason);
+                    }
+                }
+                let complete = result.write_state == crate::core::VideoWriteState::Complete;
+                clip.write_state = result.write_state;
                 match result.result {
-                    Ok(video_info) => {
+                    Ok(video_info) if complete => {
                         // Update clip with video info
                         clip.video_length_seconds = Some(video_info.duration);
                         clip.audio_tracks = video_info.audio_tracks;
@@ -720,43 +3374,113 @@ impl ClipHelperApp {
                         // Set trim points based on whether we have a target duration
                         if clip.has_target_duration() {
                             // Trim to last X seconds of the video
-                            let target_seconds = clip.target_duration_seconds as f64;
-                            clip.trim_start = (video_info.duration - target_seconds).max(0.0);
-                            clip.trim_end = video_info.duration;
+                            clip.apply_target_trim(video_info.duration, self.config.trim_padding);
                         } else if clip.trim_end == 0.0 {
                             // No target duration set, use full video length
                             clip.trim_start = 0.0;
                             clip.trim_end = video_info.duration;
                         }
                         
+                        if self.config.skip_leading_black {
+                            if clip.leading_black_end.is_some() {
+                                clip.skip_leading_black();
+                            } else if !clip.trim_adjusted && video_info.duration > 0.0
+                                && !self.black_detections.iter().any(|d| d.file == clip.original_file) {
+                                let path = clip.original_file.clone();
+                                let ffmpeg = self.config.ffmpeg_path.clone().unwrap_or_else(|| PathBuf::from("ffmpeg"));
+                                // Queued in the pool, since info for every clip arrives at once on startup
+                                let (result_tx, result) = std::sync::mpsc::channel();
+                                let job = self.ffmpeg_pool.submit(move || {
+                                    let _ = result_tx.send(crate::video::detect_leading_black(&path, &ffmpeg));
+                                    Ok(())
+                                });
+                                self.black_detections.push(BlackDetection { file: clip.original_file.clone(), job, result });
+                            }
+                        }
+                        
                         log::debug!("Async video info loaded for {}: {:.2}s duration", 
                             clip.get_output_filename(), video_info.duration);
+                        
+                        // Selecting a clip before its info loaded couldn't measure it yet
+                        if let Some(index) = self.selected_clip_index.filter(|&i| self.clips[i].original_file == result.file_path) {
+                            self.request_loudness_measurement(index);
+                            self.request_motion_start(index);
+                        }
+                        
+                        if self.pending_duplicate_checks.remove(&result.file_path) {
+                            self.request_duplicate_check(&result.file_path);
+                        }
+                        if self.pending_auto_exports.remove(&result.file_path) {
+                            self.start_auto_export(&result.file_path);
+                        }
+                    }
+                    Ok(_) => {
+                        // Readable but still growing; probed again until it stops
+                        clip.video_length_seconds = Some(0.0);
+                        log::debug!("{} is still being written", clip.get_output_filename());
                     }
                     Err(e) => {
-                        // Set invalid duration to indicate still being written
+                        // Set invalid duration to indicate still being written (or corrupted)
                         clip.video_length_seconds = Some(0.0);
                         log::debug!("Async video info failed for {}: {}", 
                             clip.get_output_filename(), e);
+                        if matches!(clip.write_state, crate::core::VideoWriteState::Corrupted { .. })
+                            && self.pending_auto_exports.remove(&result.file_path)
+                        {
+                            log::warn!("Not auto-exporting {}, the file is unreadable", result.file_path.display());
+                        }
                     }
                 }
             }
         }
     }
     
+    /// Queue the export of a clip matched to a duration hotkey, once its file is complete
+    fn start_auto_export(&mut self, file: &std::path::Path) {
+        let Some(index) = self.clips.iter().position(|clip| clip.original_file == file && !clip.is_deleted) else {
+            return;
+        };
+        let job = self.trim_export_job(index, false);
+        if job.output_path.exists() {
+            log::info!("Not auto-exporting {}, {} already exists", file.display(), job.output_path.display());
+            return;
+        }
+        log::info!("Auto-exporting {} ({:.1}s - {:.1}s)", file.display(), job.trim_start, job.trim_end);
+        self.auto_export_outputs.insert(job.output_path.clone());
+        self.enqueue_export(job);
+        if let Err(e) = self.save_export_queue() {
+            log::error!("Failed to save export queue: {}", e);
+        }
+    }
+
+    /// Age after which unmatched pending requests are dropped (read from config every frame
+    /// so settings changes apply immediately)
+    fn pending_request_max_age(&self) -> chrono::Duration {
+        let timeout = self.config.clip_request_retry.timeout();
+        chrono::Duration::from_std(timeout).unwrap_or_else(|_| chrono::Duration::seconds(30))
+    }
+
+    /// Duration requests are kept for an hour, but never less than the retry timeout
+    fn duration_request_max_age(&self) -> chrono::Duration {
+        self.pending_request_max_age().max(chrono::Duration::hours(1))
+    }
+
     fn process_pending_clip_retries(&mut self) {
         let now = std::time::Instant::now();
+        let retry_interval = self.config.clip_request_retry.retry_interval();
+        let timeout = self.config.clip_request_retry.timeout();
         let mut requests_to_remove = Vec::new();
         let mut clips_to_update = Vec::new();
         let mut files_to_create = Vec::new();
         
         for (i, request) in self.pending_clip_requests.iter_mut().enumerate() {
-            // Check if it's time to retry (every 1 second)
-            if now.duration_since(request.last_retry).as_secs() >= 1 {
+            // Check if it's time to retry
+            if now.duration_since(request.last_retry) >= retry_interval {
                 request.last_retry = now;
                 request.retry_count += 1;
                 
-                // Check if we've exceeded 10 seconds (10 retries)
-                if now.duration_since(request.created_at).as_secs() >= 10 {
+                // Check if the request has expired
+                if now.duration_since(request.created_at) >= timeout {
                     requests_to_remove.push(i);
                     continue;
                 }
@@ -775,7 +3499,7 @@ impl ClipHelperApp {
                 // If still no match, check for new files
                 if !found_existing {
                     if let Some(ref watched_dir) = self.watched_directory {
-                        if let Ok(existing_files) = FileMonitor::scan_existing_files(watched_dir) {
+                        if let Ok(existing_files) = FileMonitor::scan_existing_files(watched_dir, &self.config.timestamp_pattern()) {
                             for file in existing_files {
                                 if Self::timestamps_match_static(request.timestamp, file.timestamp) {
                                     files_to_create.push((file, request.duration.clone()));
@@ -792,7 +3516,7 @@ impl ClipHelperApp {
         // Apply updates outside the iteration to avoid borrow conflicts
         for (clip_index, duration) in clips_to_update {
             if let Some(clip) = self.clips.get_mut(clip_index) {
-                clip.set_target_duration(duration);
+                clip.set_target_duration_with_padding(duration, self.config.trim_padding);
                 // Save clips after setting target duration
                 if let Err(e) = self.save_clips() {
                     log::error!("Failed to save clips after setting target duration: {}", e);
@@ -823,21 +3547,14 @@ impl ClipHelperApp {
                 // Clear any existing clips first
                 self.clips.clear();
                 
-                match FileMonitor::scan_existing_files(dir) {
+                match FileMonitor::scan_existing_files(dir, &self.config.timestamp_pattern()) {
                     Ok(existing_files) => {
                         log::info!("Found {} existing replay files, loading most recent 50", existing_files.len());
                         
                         // Create clips from actual files without eager loading
                         // Video info will be loaded on-demand when clips scroll into view
                         for file in existing_files.into_iter().take(50) {
-                            match Clip::new_without_target(file.path.clone()) {
-                                Ok(clip) => {
-                                    self.clips.push(clip);
-                                }
-                                Err(e) => {
-                                    log::error!("Failed to create clip from existing file: {}", e);
-                                }
-                            }
+                            self.clips.push(file.to_clip());
                         }
                         
                         // Now apply saved configurations to matching clips
@@ -848,11 +3565,51 @@ impl ClipHelperApp {
                     }
                 }
             }
+            self.restore_imported_clips();
+            
+            self.stale_temp_exports = crate::video::VideoProcessor::find_stale_temp_outputs(&self.config.trimmed_directory);
+            if !self.stale_temp_exports.is_empty() {
+                log::warn!("Found {} partial exports in {}", self.stale_temp_exports.len(), self.config.trimmed_directory.display());
+                self.status_message = format!("Found {} partial export(s) from an interrupted session", self.stale_temp_exports.len());
+            }
             self.initial_scan_completed = true;
         }
     }
 
+    /// Add back videos imported by drag and drop in earlier sessions that still exist and
+    /// pinned clips, since the watched directory scan doesn't find them or stops at the 50
+    /// most recent files. Pinned clips whose file is gone are kept and marked missing.
+    fn restore_imported_clips(&mut self) {
+        let Ok(content) = std::fs::read_to_string(Self::clips_file_path()) else {
+            return;
+        };
+        let Ok(saved_clips) = serde_json::from_str::<Vec<Clip>>(&content) else {
+            return;
+        };
+        
+        let restored: Vec<Clip> = saved_clips.into_iter()
+            .filter(|saved| !saved.is_deleted && (saved.pinned || saved.imported))
+            .filter(|saved| !self.clips.iter().any(|clip| clip.original_file == saved.original_file))
+            .filter_map(|mut saved| {
+                if saved.original_file.exists() {
+                    Some(saved)
+                } else if saved.pinned {
+                    log::warn!("Pinned clip {} no longer exists", saved.original_file.display());
+                    saved.write_state = crate::core::VideoWriteState::Missing;
+                    Some(saved)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if !restored.is_empty() {
+            log::info!("Restored {} imported and pinned clips", restored.len());
+            self.clips.extend(restored);
+        }
+    }
+
     fn apply_saved_configurations(&mut self) {
+        let mut known_files = std::collections::HashSet::new();
         let clips_path = Self::clips_file_path();
         if clips_path.exists() {
             match std::fs::read_to_string(&clips_path) {
@@ -863,20 +3620,13 @@ impl ClipHelperApp {
                             
                             // For each current clip, find matching saved clip and apply configuration
                             for current_clip in &mut self.clips {
+                                if saved_clips.iter().any(|saved| saved.original_file == current_clip.original_file) {
+                                    known_files.insert(current_clip.original_file.clone());
+                                }
                                 for saved_clip in &saved_clips {
                                     // Match by original file path
                                     if current_clip.original_file == saved_clip.original_file {
-                                        if saved_clip.has_target_duration() {
-                                            current_clip.target_duration_seconds = saved_clip.target_duration_seconds;
-                                            current_clip.trim_start = saved_clip.trim_start;
-                                            current_clip.trim_end = saved_clip.trim_end;
-                                            log::debug!("Applied saved target duration {} to {}", 
-                                                saved_clip.target_duration_seconds, current_clip.get_output_filename());
-                                        }
-                                        current_clip.name = saved_clip.name.clone();
-                                        current_clip.audio_tracks = saved_clip.audio_tracks.clone();
-                                        current_clip.is_deleted = saved_clip.is_deleted;
-                                        current_clip.is_trimmed = saved_clip.is_trimmed;
+                                        Self::apply_saved_clip(current_clip, saved_clip);
                                         break;
                                     }
                                 }
@@ -894,6 +3644,79 @@ impl ClipHelperApp {
         } else {
             log::debug!("No saved clips file found, starting fresh");
         }
+        
+        self.import_sidecar_metadata(&known_files);
+    }
+
+    /// Copy what the user set up for a clip in an earlier run from its clips.json entry
+    pub(crate) fn apply_saved_clip(current_clip: &mut Clip, saved_clip: &Clip) {
+        if saved_clip.has_target_duration() {
+            current_clip.target_duration_seconds = saved_clip.target_duration_seconds;
+            current_clip.trim_start = saved_clip.trim_start;
+            current_clip.trim_end = saved_clip.trim_end;
+            log::debug!("Applied saved target duration {} to {}", 
+                saved_clip.target_duration_seconds, current_clip.get_output_filename());
+        }
+        current_clip.name = saved_clip.name.clone();
+        current_clip.audio_tracks = saved_clip.audio_tracks.clone();
+        current_clip.is_deleted = saved_clip.is_deleted;
+        current_clip.is_trimmed = saved_clip.is_trimmed;
+        current_clip.export_state = saved_clip.restored_export_state();
+        current_clip.fingerprint = saved_clip.fingerprint.clone();
+        current_clip.markers = saved_clip.markers.clone();
+        current_clip.pinned = saved_clip.pinned;
+        current_clip.trim_adjusted = saved_clip.trim_adjusted;
+        current_clip.export_directory_override = saved_clip.export_directory_override.clone();
+        current_clip.loudness_lufs = saved_clip.loudness_lufs;
+        current_clip.loudness_true_peak_dbfs = saved_clip.loudness_true_peak_dbfs;
+        current_clip.loudness_range = saved_clip.loudness_range;
+        current_clip.poster_timestamp = saved_clip.poster_timestamp;
+        current_clip.imported = saved_clip.imported;
+        current_clip.timestamp_source = saved_clip.timestamp_source;
+        current_clip.leading_black_end = saved_clip.leading_black_end;
+        current_clip.motion_start = saved_clip.motion_start;
+        current_clip.notes = saved_clip.notes.clone();
+        current_clip.tags = saved_clip.tags.clone();
+        current_clip.color = saved_clip.color;
+    }
+
+    /// Directories exports (and so their sidecars) can end up in: the trimmed directory
+    /// and every per-clip export directory override
+    pub(crate) fn sidecar_directories(&self) -> Vec<PathBuf> {
+        let mut directories = vec![self.config.trimmed_directory.clone()];
+        for directory in self.clips.iter().filter_map(|clip| clip.export_directory_override.as_ref()) {
+            if !directories.contains(directory) {
+                directories.push(directory.clone());
+            }
+        }
+        directories
+    }
+
+    /// Restore clips missing from clips.json from export sidecars, newest export first
+    fn import_sidecar_metadata(&mut self, known_files: &std::collections::HashSet<PathBuf>) {
+        if self.clips.iter().all(|clip| known_files.contains(&clip.original_file)) {
+            return;
+        }
+        
+        let mut sidecars: Vec<crate::core::ClipMetadata> = self.sidecar_directories().iter()
+            .flat_map(|directory| crate::core::ClipMetadata::load_sidecars(directory))
+            .collect();
+        if sidecars.is_empty() {
+            return;
+        }
+        sidecars.sort_by_key(|sidecar| std::cmp::Reverse(sidecar.exported_at));
+        
+        let mut imported = 0;
+        for clip in self.clips.iter_mut().filter(|clip| !known_files.contains(&clip.original_file)) {
+            if let Some(sidecar) = sidecars.iter().find(|sidecar| sidecar.matches_file(&clip.original_file)) {
+                sidecar.apply_to(clip);
+                imported += 1;
+            }
+        }
+        
+        if imported > 0 {
+            log::info!("Restored {} clips from export sidecars", imported);
+        }
     }
 
 
@@ -910,8 +3733,42 @@ impl eframe::App for ClipHelperApp {
         // Process completed hover thumbnails
         self.hover_thumbnail_manager.process_completed(ctx);
         
+        // Poster frames in the clip list come from the smart thumbnail cache, which the
+        // preview only processes while a clip is selected
+        if self.selected_clip_index.is_none() {
+            if let Some(ref cache) = self.smart_thumbnail_cache {
+                cache.process_completed_thumbnails(ctx);
+            }
+        }
+        
+        // Process finished exports and start the next queued one
+        self.process_export_results();
+        self.process_auto_advance(ctx);
+        self.process_post_export_hooks();
+        
+        self.refresh_near_duplicates();
+        self.refresh_output_names();
+        
         // Initialize MediaController with video if needed
         self.initialize_media_controller_if_needed(ctx);
+        self.report_media_controller_errors();
+        self.update_playback_availability();
+        self.process_external_player_launches();
+        self.process_loudness_measurements();
+        self.process_scene_detections();
+        self.process_black_detections();
+        self.process_duplicate_checks();
+        self.process_motion_detections();
+        self.process_audio_track_exports();
+        self.process_statistics_scan();
+        self.process_directory_size_scan();
+        self.process_clip_merge();
+        self.process_session_export();
+        self.process_waveform_loads();
+        self.handle_close_to_tray(ctx);
+        self.process_tray_actions(ctx);
+        self.handle_dropped_files(ctx);
+        self.process_video_imports();
         
         // Process events
         self.process_hotkey_events();
@@ -924,7 +3781,7 @@ impl eframe::App for ClipHelperApp {
         self.process_pending_clip_retries();
         
         // Periodic cleanup of old clip requests
-        let cutoff = chrono::Local::now() - chrono::Duration::seconds(30);
+        let cutoff = chrono::Local::now() - self.pending_request_max_age();
         self.pending_clip_requests.retain(|req| req.timestamp > cutoff);
         
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
@@ -962,6 +3819,12 @@ impl eframe::App for ClipHelperApp {
                         ui.close_menu();
                     }
                     
+                    if ui.button("Recycle Bin").clicked() {
+                        self.show_recycle_bin = true;
+                        self.last_recycle_bin_scan = None;
+                        ui.close_menu();
+                    }
+                    
                     ui.separator();
                     
                     if ui.button("Settings").clicked() {
@@ -974,6 +3837,17 @@ impl eframe::App for ClipHelperApp {
                 });
                 
                 ui.menu_button("Help", |ui| {
+                    if ui.button("Keyboard Shortcuts (F1)").clicked() {
+                        self.show_keyboard_shortcuts = true;
+                        ui.close_menu();
+                    }
+                    if ui.button("Reset Window")
+                        .on_hover_text("Un-maximize and go back to the default window size, e.g. if the window opens off screen")
+                        .clicked()
+                    {
+                        self.reset_window_layout(ctx);
+                        ui.close_menu();
+                    }
                     if ui.button("About").clicked() {
                         // TODO: Show about dialog
                         ui.close_menu();
@@ -982,6 +3856,9 @@ impl eframe::App for ClipHelperApp {
                 
                 // Show current directory status
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("?").on_hover_text("Keyboard shortcuts (F1)").clicked() {
+                        self.show_keyboard_shortcuts = !self.show_keyboard_shortcuts;
+                    }
                     if let Some(ref dir) = self.watched_directory {
                         ui.label(format!("📁 {}", dir.file_name().unwrap_or_default().to_string_lossy()));
                     } else {
@@ -991,15 +3868,37 @@ impl eframe::App for ClipHelperApp {
             });
         });
 
-        egui::SidePanel::left("clip_list")
-            .default_width(300.0)
-            .min_width(250.0)
+        let clip_list_panel = egui::SidePanel::left("clip_list")
+            .default_width(self.config.restorable_left_panel_width())
+            .min_width(crate::core::config::MIN_LEFT_PANEL_WIDTH)
             .show(ctx, |ui| {
                 self.show_clip_list(ui);
             });
+        // Clicking in the clip list gives it keyboard focus; clicking anywhere else takes it away
+        if ctx.input(|i| i.pointer.primary_clicked()) {
+            self.clip_list_has_focus = clip_list_panel.response.contains_pointer();
+        }
+        
+        let panel_width = clip_list_panel.response.rect.width();
+        if (panel_width - self.config.left_panel_width).abs() > 0.5 {
+            self.config.left_panel_width = panel_width;
+            self.layout_dirty = true;
+        }
+
+        self.log_buffer.collect();
+        if self.show_log_panel {
+            egui::TopBottomPanel::bottom("log_panel")
+                .resizable(true)
+                .default_height(160.0)
+                .show(ctx, |ui| {
+                    self.show_log_panel(ui);
+                });
+        }
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            if let Some(selected_index) = self.selected_clip_index {
+            if self.selected_clip_indices.len() >= 2 {
+                self.show_batch_actions(ui);
+            } else if let Some(selected_index) = self.selected_clip_index {
                 if selected_index < self.clips.len() {
                     self.show_clip_editor(ui);
                 }
@@ -1063,7 +3962,29 @@ impl eframe::App for ClipHelperApp {
             self.render_settings_dialog(ctx);
         }
 
+        if self.show_recent_exports {
+            self.render_recent_exports_window(ctx);
+        }
+
+        self.render_session_export_window(ctx);
+
+        // Offer to resume exports from the previous session
+        if self.show_resume_exports_dialog {
+            self.render_resume_exports_dialog(ctx);
+        }
+
         // Status bar at bottom
+        if let Some((expires, message)) = &self.status_toast {
+            if *message != self.status_message {
+                self.status_toast = None;
+            } else if std::time::Instant::now() >= *expires {
+                self.status_message.clear();
+                self.status_toast = None;
+            } else {
+                ctx.request_repaint_after(expires.saturating_duration_since(std::time::Instant::now()));
+            }
+        }
+        
         egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.label("Status:");
@@ -1073,26 +3994,344 @@ impl eframe::App for ClipHelperApp {
                     ui.label(&self.status_message);
                 }
                 
+                if self.last_export_path.is_some() && self.status_message.starts_with("Exported ") {
+                    let link = ui.add(egui::Label::new(egui::RichText::new("Copy path").color(ui.visuals().hyperlink_color).underline())
+                        .sense(egui::Sense::click()));
+                    if link.on_hover_cursor(egui::CursorIcon::PointingHand).clicked() {
+                        self.copy_last_export_path(ctx);
+                    }
+                }
+                
+                if !self.stale_temp_exports.is_empty() {
+                    if ui.small_button("Clean up").on_hover_text("Delete the partial export files").clicked() {
+                        self.clean_up_stale_temp_exports();
+                    }
+                    if ui.small_button("✖").on_hover_text("Keep them for now").clicked() {
+                        self.stale_temp_exports.clear();
+                        self.status_message.clear();
+                    }
+                }
+                
+                let failed_hotkeys = self.failed_hotkey_registrations();
+                let hotkey_warning = if !self.hotkey_conflicts.is_empty() {
+                    Some(("⚠ Fix hotkey conflicts in Settings".to_string(),
+                        self.hotkey_conflicts.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("\n")))
+                } else if !failed_hotkeys.is_empty() {
+                    Some((format!("⚠ {} hotkey(s) not registered", failed_hotkeys.len()), failed_hotkeys.join("\n")))
+                } else {
+                    None
+                };
+                if let Some((label, details)) = hotkey_warning {
+                    if ui.add(egui::Button::new(egui::RichText::new(label).color(egui::Color32::RED)).small())
+                        .on_hover_text(details)
+                        .clicked()
+                    {
+                        self.show_settings_dialog = true;
+                    }
+                }
+                
+                if let Some((due, _)) = self.pending_auto_advance {
+                    let remaining = due.saturating_duration_since(std::time::Instant::now()).as_secs_f32().ceil().max(1.0);
+                    ui.separator();
+                    ui.label(format!("Moving to next clip in {}…", remaining as u32));
+                    if ui.small_button("Cancel").clicked() {
+                        self.pending_auto_advance = None;
+                    }
+                }
+                
+                if self.error_details.is_some() && ui.small_button("Details").clicked() {
+                    self.show_error_details_dialog = true;
+                }
+                
+                if self.failed_export.is_some() && ui.small_button("Re-export").on_hover_text("Run the failed export again").clicked() {
+                    if let Some(mut job) = self.failed_export.take() {
+                        job.force_overwrite = true;
+                        self.enqueue_export(job);
+                        self.error_details = None;
+                        self.status_message = "Re-exporting…".to_string();
+                        if let Err(e) = self.save_export_queue() {
+                            log::error!("Failed to save export queue: {}", e);
+                        }
+                    }
+                }
+                
+                if let Some(duplicate) = self.duplicate_of.clone() {
+                    if ui.small_button("Show duplicate").clicked() {
+                        if let Some(index) = self.clips.iter().position(|c| c.original_file == duplicate) {
+                            self.select_clip(index);
+                        }
+                        self.duplicate_of = None;
+                    }
+                    if ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                        self.duplicate_of = None;
+                        self.status_message.clear();
+                    }
+                }
+                
                 // Hotkey status
+                let free_space = self.export_free_space();
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    ui.label("Hotkeys: Ctrl+Numpad1-5 (15s/30s/1m/2m/5m)");
+                    ui.label("Hotkeys: Ctrl+Numpad1-5 (15s/30s/1m/2m/5m)")
+                        .on_hover_text("Press ? or F1 for all shortcuts");
+                    ui.separator();
+                    ui.toggle_value(&mut self.show_log_panel, "📜 Log")
+                        .on_hover_text("Show what the app is doing: scans, matches, exports and errors");
+                    // Opening the list drops exports deleted since
+                    if ui.toggle_value(&mut self.show_recent_exports, "🕘 Recent")
+                        .on_hover_text("Exports from this and earlier sessions")
+                        .clicked()
+                        && self.show_recent_exports
+                        && self.export_history.prune_missing()
+                    {
+                        self.save_export_history();
+                    }
+                    
+                    if let Some(free) = free_space {
+                        ui.separator();
+                        ui.label(format!("💾 {} free", crate::core::format_file_size(free)))
+                            .on_hover_text(format!("Free space on the volume of {}", self.config.trimmed_directory.display()));
+                    }
+                    
+                    if !self.export_queue.is_empty() {
+                        ui.separator();
+                        ui.spinner();
+                        ui.label(format!("Exporting ({} pending)", self.export_queue.len()));
+                        if let Some(pass) = self.export_queue.current_pass() {
+                            ui.label(format!("Pass {}/2", pass));
+                        }
+                    }
                 });
             });
         });
 
+        self.handle_help_overlay_keys(ctx);
+        if self.show_keyboard_shortcuts {
+            self.render_keyboard_shortcuts(ctx);
+        }
+        
+        if self.show_error_details_dialog {
+            self.render_error_details_dialog(ctx);
+        }
+        
+        if self.pending_duplicate_deletion.is_some() {
+            self.render_duplicate_deletion_dialog(ctx);
+        }
+        
+        if self.pending_drop_copy.is_some() {
+            self.render_drop_copy_dialog(ctx);
+        }
+        
+        if self.pending_session_deletion.is_some() {
+            self.render_session_deletion_dialog(ctx);
+        }
+        
+        if self.show_recycle_bin {
+            self.render_recycle_bin(ctx);
+        }
+        
+        if self.pending_permanent_deletion.is_some() {
+            self.render_permanent_deletion_dialog(ctx);
+        }
+        
+        if self.pending_clip_deletion.is_some() {
+            self.render_delete_clip_dialog(ctx);
+        }
+
+        self.track_window_layout(ctx);
+
         // Request repaint to handle continuous updates
         ctx.request_repaint();
     }
 }
 
 impl ClipHelperApp {
+    /// Collapsible statistics summary at the bottom of the clip list
+    fn show_statistics(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("📊 Statistics")
+            .id_source("clip_statistics_header")
+            .default_open(false)
+            .show(ui, |ui| {
+                let is_stale = self.statistics_cache.as_ref()
+                    .is_none_or(|(computed_at, _)| computed_at.elapsed() >= STATISTICS_TTL);
+                if is_stale && self.statistics_scan.is_none() {
+                    self.start_statistics_scan();
+                }
+                let Some((_, stats)) = &self.statistics_cache else {
+                    ui.small("Calculating…");
+                    return;
+                };
+                
+                egui::Grid::new("clip_statistics_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Clips:");
+                        ui.label(stats.clip_count.to_string());
+                        ui.end_row();
+                        ui.label("Trimmed duration:");
+                        ui.label(Clip::format_duration(stats.total_trimmed_seconds));
+                        ui.end_row();
+                        ui.label("Original duration:");
+                        ui.label(Clip::format_duration(stats.total_original_seconds));
+                        ui.end_row();
+                        ui.label("Storage used:");
+                        ui.label(crate::core::format_file_size(stats.total_file_size));
+                        ui.end_row();
+                        ui.label("Exported today:");
+                        ui.label(stats.exported_today.to_string());
+                        ui.end_row();
+                        ui.label("Average length:");
+                        ui.label(Clip::format_duration(stats.average_clip_seconds()));
+                        ui.end_row();
+                        if let Some(cache) = &self.smart_thumbnail_cache {
+                            let cache_stats = cache.cache_stats();
+                            ui.label("Thumbnail cache:");
+                            ui.label(format!(
+                                "{} / {}",
+                                crate::core::format_file_size(cache_stats.current_bytes as u64),
+                                crate::core::format_file_size(cache_stats.max_bytes as u64)
+                            )).on_hover_text(format!(
+                                "{} hits, {} misses, {} evicted",
+                                cache_stats.hit_count, cache_stats.miss_count, cache_stats.eviction_count
+                            ));
+                            ui.end_row();
+                        }
+                    });
+                
+                if ui.button("📋 Copy to clipboard").clicked() {
+                    ui.output_mut(|o| o.copied_text = stats.to_markdown());
+                    self.status_message = "Statistics copied to clipboard".to_string();
+                }
+                
+                ui.separator();
+                self.show_directory_sizes(ui);
+                ui.separator();
+                self.show_clips_per_day(ui);
+            });
+    }
+
+    fn start_statistics_scan(&mut self) {
+        let clip_files: Vec<PathBuf> = self.clips.iter()
+            .filter(|clip| !clip.is_deleted)
+            .map(|clip| clip.original_file.clone())
+            .collect();
+        let trimmed = self.config.trimmed_directory.clone();
+        self.statistics_scan = Some(std::thread::spawn(move || {
+            crate::core::ClipFileStatistics::scan(&clip_files, &trimmed)
+        }));
+    }
+
+    fn process_statistics_scan(&mut self) {
+        if !self.statistics_scan.as_ref().is_some_and(|scan| scan.is_finished()) {
+            return;
+        }
+        match self.statistics_scan.take().map(|scan| scan.join()) {
+            Some(Ok(files)) => {
+                let stats = crate::core::ClipStatistics::new(&self.clips, files);
+                self.statistics_cache = Some((std::time::Instant::now(), stats));
+            }
+            Some(Err(_)) => log::error!("Statistics scan thread panicked"),
+            None => {}
+        }
+    }
+
+    /// Disk space of the replay directories, walked in the background the first time the
+    /// statistics are opened and then only when refreshed
+    fn show_directory_sizes(&mut self, ui: &mut egui::Ui) {
+        if self.directory_sizes.is_none() && self.directory_size_scan.is_none() {
+            self.start_directory_size_scan();
+        }
+        
+        ui.horizontal(|ui| {
+            ui.strong("Disk usage");
+            let scanning = self.directory_size_scan.is_some();
+            if scanning {
+                ui.spinner();
+            }
+            if ui.add_enabled(!scanning, egui::Button::new("🔄").small())
+                .on_hover_text("Measure the directories again")
+                .clicked()
+            {
+                self.start_directory_size_scan();
+            }
+        });
+        
+        let Some((scanned_at, sizes)) = &self.directory_sizes else {
+            ui.small("Measuring directories…");
+            return;
+        };
+        egui::Grid::new("directory_sizes_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, size) in [("Replays:", sizes.watched), ("Trimmed:", sizes.trimmed), ("Deleted:", sizes.deleted)] {
+                    ui.label(label);
+                    ui.label(crate::core::format_file_size(size));
+                    ui.end_row();
+                }
+                ui.label("Total:");
+                ui.strong(crate::core::format_file_size(sizes.total()));
+                ui.end_row();
+            });
+        ui.small(format!("Measured at {}", scanned_at.format("%H:%M")));
+    }
+
+    fn start_directory_size_scan(&mut self) {
+        let watched = self.watched_directory.clone();
+        let trimmed = self.config.trimmed_directory.clone();
+        let deleted = self.config.deleted_directory.clone();
+        self.directory_size_scan = Some(std::thread::spawn(move || {
+            crate::core::DirectorySizes::scan(watched.as_deref(), &trimmed, &deleted)
+        }));
+    }
+
+    fn process_directory_size_scan(&mut self) {
+        if !self.directory_size_scan.as_ref().is_some_and(|scan| scan.is_finished()) {
+            return;
+        }
+        match self.directory_size_scan.take().map(|scan| scan.join()) {
+            Some(Ok(sizes)) => self.directory_sizes = Some((chrono::Local::now(), sizes)),
+            Some(Err(_)) => log::error!("Directory size scan thread panicked"),
+            None => {}
+        }
+    }
+
+    /// Bar chart of how many clips were recorded on each of the last few days
+    fn show_clips_per_day(&self, ui: &mut egui::Ui) {
+        let counts = crate::core::clips_per_day(&self.clips, CLIPS_PER_DAY_DAYS, chrono::Local::now().date_naive());
+        let max = counts.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+        ui.strong(format!("Clips per day (last {} days)", CLIPS_PER_DAY_DAYS));
+        
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 40.0), egui::Sense::hover());
+        let bar_width = rect.width() / counts.len() as f32;
+        let hovered = response.hover_pos()
+            .map(|pos| ((pos.x - rect.min.x) / bar_width) as usize)
+            .filter(|&index| index < counts.len());
+        let painter = ui.painter_at(rect);
+        for (index, (_, count)) in counts.iter().enumerate() {
+            let height = rect.height() * *count as f32 / max as f32;
+            let left = rect.min.x + index as f32 * bar_width;
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(left + 1.0, rect.max.y - height.max(1.0)),
+                egui::pos2(left + bar_width - 1.0, rect.max.y),
+            );
+            let color = if hovered == Some(index) {
+                ui.visuals().selection.stroke.color
+            } else {
+                ui.visuals().selection.bg_fill
+            };
+            painter.rect_filled(bar, 1.0, color);
+        }
+        if let Some((day, count)) = hovered.map(|index| counts[index]) {
+            response.on_hover_text(format!("{}: {} clip{}", day.format("%a %d %b"), count, if count == 1 { "" } else { "s" }));
+        }
+    }
+
     fn show_clip_list(&mut self, ui: &mut egui::Ui) {
         ui.heading("Clips");
         
-        // Status message if no directory selected
-        if self.watched_directory.is_none() {
+        // Status message if no directory selected; dropped videos are still listed
+        if self.watched_directory.is_none() && self.clips.is_empty() {
             ui.label("❌ No directory selected");
-            ui.small("Select an OBS replay directory from the File menu");
+            ui.small("Select an OBS replay directory from the File menu, or drop videos here");
             return;
         }
         
@@ -1101,13 +4340,51 @@ impl ClipHelperApp {
             ui.small(format!("📁 {}", dir.file_name().unwrap_or_default().to_string_lossy()));
         }
         
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.clip_search)
+                .hint_text("🔍 Search names, notes, tags")
+                .desired_width(ui.available_width() - 60.0));
+            if !self.clip_search.is_empty() && ui.small_button("✖").on_hover_text("Clear search").clicked() {
+                self.clip_search.clear();
+            }
+            
+            if self.clip_merge.is_some() {
+                ui.spinner().on_hover_text("Merging clips");
+            } else if self.selected_clip_indices.len() >= 2 && ui.small_button(format!("🔗 {}", self.selected_clip_indices.len()))
+                .on_hover_text("Merge the selected clips into one new clip, in recording order")
+                .clicked()
+            {
+                self.start_clip_merge();
+            }
+            
+            let mut compact = self.config.clip_list_view == ClipListView::Compact;
+            if ui.toggle_value(&mut compact, "☰").on_hover_text("Compact list: one line per clip").changed() {
+                self.config.clip_list_view = if compact { ClipListView::Compact } else { ClipListView::Detailed };
+                if let Err(e) = self.config.save() {
+                    log::error!("Failed to save clip list view: {}", e);
+                }
+            }
+        });
+        
         ui.separator();
         
+        self.handle_clip_list_keyboard(ui);
+        
+        egui::TopBottomPanel::bottom("clip_statistics")
+            .resizable(false)
+            .show_inside(ui, |ui| {
+                self.show_statistics(ui);
+            });
+        
         // Show clips grouped by sessions
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
                 let mut clips_needing_info = Vec::new();
+                let mut context_actions = Vec::new();
+                let mut session_to_trim: Option<Vec<usize>> = None;
+                let mut session_to_export: Option<SessionGroup> = None;
+                let mut session_to_delete: Option<Vec<usize>> = None;
                 let mut clips_needing_duration_update: Vec<(usize, ClipDuration, chrono::DateTime<chrono::Local>)> = Vec::new();
                 
                 if self.clips.is_empty() {
@@ -1117,37 +4394,111 @@ impl ClipHelperApp {
                 } else {
                     let sessions = self.group_clips_into_sessions();
                     let mut selected_index = self.selected_clip_index;
+                    let mut toggled_index = None;
+                    let mut range_end_index = None;
+                    let mut rename_started = None;
+                    let mut rename_finished = None;
                     
                     for session in sessions {
+                        if !session.clips.iter().any(|&index| self.clips[index].matches_search(&self.clip_search)) {
+                            continue;
+                        }
+                        
                         // Session header
                         ui.group(|ui| {
-                            ui.label(format!("{} - session {} - {}", 
-                                session.date, session.start_time, session.end_time));
+                            ui.horizontal(|ui| {
+                                let header = format!("{} - session {} - {}", 
+                                    session.date, session.start_time, session.end_time);
+                                if session.pinned {
+                                    ui.strong("📌 Pinned");
+                                } else if session.manual_break {
+                                    ui.label(format!("✂ {}", header)).on_hover_text("Split manually");
+                                } else {
+                                    ui.label(header);
+                                }
+                                
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if !session.pinned && ui.small_button("🗑").on_hover_text("Delete session").clicked() {
+                                        session_to_delete = Some(session.clips.clone());
+                                    }
+                                    if ui.small_button("✂ Trim all").on_hover_text("Trim all in session").clicked() {
+                                        session_to_trim = Some(session.clips.clone());
+                                    }
+                                });
+                            });
+                        }).response.interact(egui::Sense::click()).context_menu(|ui| {
+                            if !session.pinned
+                                && ui.add_enabled(self.session_export.is_none(), egui::Button::new("🔗 Export session as single file"))
+                                    .on_hover_text("Joins the trimmed clips in recording order into <date>_<start>_session.mkv")
+                                    .clicked()
+                            {
+                                session_to_export = Some(session.clone());
+                                ui.close_menu();
+                            }
+                            if ui.button("✂ Export session individually").clicked() {
+                                session_to_trim = Some(session.clips.clone());
+                                ui.close_menu();
+                            }
                         });
                         
                         ui.indent("session_clips", |ui| {
                             for &clip_index in &session.clips {
                                 if let Some(clip) = self.clips.get(clip_index) {
-                                    // Skip deleted clips
-                                    if clip.is_deleted {
+                                    // Skip deleted clips and clips hidden by the search
+                                    if clip.is_deleted || !clip.matches_search(&self.clip_search) {
                                         continue;
                                     }
                                     
-                                    let is_selected = selected_index == Some(clip_index);
+                                    let is_selected = selected_index == Some(clip_index)
+                                        || self.selected_clip_indices.contains(&clip_index);
+                                    let is_keyboard_focused = self.clip_list_has_focus
+                                        && self.keyboard_focus_index == Some(clip_index);
+                                    
+                                    if is_keyboard_focused && self.scroll_to_keyboard_focus {
+                                        ui.scroll_to_cursor(Some(egui::Align::Center));
+                                        self.scroll_to_keyboard_focus = false;
+                                    }
                                     
                                     // Use ClipListRenderer to render the clip
                                     let result = ClipListRenderer::render_clip_item(
                                         ui,
                                         clip,
                                         clip_index,
-                                        is_selected,
+                                        ClipItemState {
+                                            view: self.config.clip_list_view,
+                                            is_selected,
+                                            is_keyboard_focused,
+                                            is_near_duplicate: self.near_duplicate_groups.iter().flatten().any(|path| path == &clip.original_file),
+                                            is_session_break: self.config.manual_session_breaks.contains(&clip.original_file),
+                                            is_hover_target: self.current_hover_target.as_ref() == Some(&clip.original_file),
+                                        },
                                         &mut self.hover_thumbnail_manager,
-                                        &self.current_hover_target,
+                                        self.smart_thumbnail_cache.as_deref(),
+                                        if self.renaming_clip_index == Some(clip_index) {
+                                            Some(&mut self.rename_buffer)
+                                        } else {
+                                            None
+                                        },
                                     );
                                     
                                     // Handle results
                                     if result.clicked {
-                                        selected_index = Some(clip_index);
+                                        let modifiers = ui.input(|i| i.modifiers);
+                                        if modifiers.shift {
+                                            range_end_index = Some(clip_index);
+                                        } else if modifiers.command {
+                                            toggled_index = Some(clip_index);
+                                        } else {
+                                            selected_index = Some(clip_index);
+                                            self.selected_clip_indices.clear();
+                                        }
+                                        self.keyboard_focus_index = Some(clip_index);
+                                    }
+                                    if result.double_clicked {
+                                        rename_started = Some(clip_index);
+                                    }
+                                    if let Some(outcome) = result.rename {
+                                        rename_finished = Some(outcome);
                                     }
                                     
                                     if let Some(file) = result.start_hover {
@@ -1164,6 +4515,10 @@ impl ClipHelperApp {
                                         clips_needing_info.push(clip_index);
                                     }
                                     
+                                    if let Some(action) = result.context_action {
+                                        context_actions.push((clip.original_file.clone(), action));
+                                    }
+                                    
                                     // Check for duration updates
                                     if let Some(matching_request) = self.find_matching_duration_request(clip) {
                                         if !clip.has_target_duration() || clip.target_duration_seconds != matching_request.duration as u32 {
@@ -1177,6 +4532,19 @@ impl ClipHelperApp {
                         ui.add_space(8.0);
                     }
                     
+                    if let Some(index) = toggled_index {
+                        self.toggle_multi_selection(index);
+                    }
+                    if let Some(index) = range_end_index {
+                        self.select_range(index);
+                    }
+                    if let Some(outcome) = rename_finished {
+                        self.finish_clip_rename(outcome);
+                    }
+                    if let Some(index) = rename_started {
+                        self.start_clip_rename(index);
+                    }
+                    
                     // Update selected clip
                     if selected_index != self.selected_clip_index {
                         if let Some(index) = selected_index {
@@ -1190,10 +4558,38 @@ impl ClipHelperApp {
                     self.ensure_video_info_loaded(clip_index);
                 }
                 
+                if let Some(session) = session_to_export {
+                    self.start_session_export(&session);
+                }
+                
+                if let Some(clip_indices) = session_to_trim {
+                    self.trim_session(&clip_indices);
+                }
+                
+                if let Some(clip_indices) = session_to_delete {
+                    let files: Vec<PathBuf> = clip_indices.iter()
+                        .filter_map(|&index| self.clips.get(index))
+                        .filter(|clip| !clip.is_deleted)
+                        .map(|clip| clip.original_file.clone())
+                        .collect();
+                    if !files.is_empty() {
+                        self.pending_session_deletion = Some(files);
+                    }
+                }
+                
+                for (file, action) in context_actions {
+                    match action {
+                        ClipContextAction::SplitSessionHere => self.set_manual_session_break(&file, true),
+                        ClipContextAction::MergeWithPreviousSession => self.set_manual_session_break(&file, false),
+                        ClipContextAction::TogglePin => self.toggle_pinned(&file),
+                        ClipContextAction::SetColor(color) => self.set_clip_color(&file, color),
+                    }
+                }
+                
                 // Apply duration updates for clips that matched duration requests
                 let duration_updates_applied = !clips_needing_duration_update.is_empty();
                 for (clip_index, duration, _request_timestamp) in clips_needing_duration_update {
-                    self.clips[clip_index].set_target_duration(duration);
+                    self.clips[clip_index].set_target_duration_with_padding(duration, self.config.trim_padding);
                     // Don't remove the duration request yet - allow multiple updates
                     // We'll clean up old requests periodically instead
                     
@@ -1221,33 +4617,112 @@ impl ClipHelperApp {
             });
     }
 
+    /// Clip indices in the order they appear in the list (newest session first),
+    /// skipping clips that can't be selected
+    fn clip_list_display_order(&self) -> Vec<usize> {
+        self.group_clips_into_sessions()
+            .into_iter()
+            .flat_map(|session| session.clips)
+            .filter(|&index| {
+                let clip = &self.clips[index];
+                !clip.is_deleted && clip.is_video_valid() && clip.matches_search(&self.clip_search)
+            })
+            .collect()
+    }
+
+    /// Mouse wheel movement in notches; trackpads report pixels, which are scaled to match
+    pub(crate) fn wheel_notches(unit: egui::MouseWheelUnit, delta: egui::Vec2) -> f32 {
+        match unit {
+            egui::MouseWheelUnit::Line => delta.y,
+            egui::MouseWheelUnit::Point => delta.y / WHEEL_POINTS_PER_NOTCH,
+            egui::MouseWheelUnit::Page => delta.y * WHEEL_NOTCHES_PER_PAGE,
+        }
+    }
+
+    /// Seek offset for wheel movement: scrolling up (positive) seeks forward
+    pub(crate) fn scroll_delta_to_seconds(notches: f32, step_seconds: f64) -> f64 {
+        notches as f64 * step_seconds
+    }
+
+    /// Move `step` entries from `current` within `order`, clamping at both ends.
+    /// Without a current entry, moving down starts at the top and moving up at the bottom.
+    pub(crate) fn step_focus_index(order: &[usize], current: Option<usize>, step: isize) -> Option<usize> {
+        if order.is_empty() {
+            return None;
+        }
+        
+        let position = match current.and_then(|index| order.iter().position(|&i| i == index)) {
+            Some(position) => (position as isize + step).clamp(0, order.len() as isize - 1) as usize,
+            None if step < 0 => order.len() - 1,
+            None => 0,
+        };
+        Some(order[position])
+    }
+
+    /// Up/Down move by one clip, Page Up/Page Down by ten, Enter hands focus to the editor
+    fn handle_clip_list_keyboard(&mut self, ui: &egui::Ui) {
+        // Don't steal keys from text fields or when the editor has focus
+        if !self.clip_list_has_focus || ui.ctx().wants_keyboard_input() {
+            return;
+        }
+        
+        let (step, enter) = ui.input_mut(|i| {
+            let step = if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) {
+                1
+            } else if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) {
+                -1
+            } else if i.consume_key(egui::Modifiers::NONE, egui::Key::PageDown) {
+                10
+            } else if i.consume_key(egui::Modifiers::NONE, egui::Key::PageUp) {
+                -10
+            } else {
+                0
+            };
+            (step, i.consume_key(egui::Modifiers::NONE, egui::Key::Enter))
+        });
+        
+        if step != 0 {
+            let order = self.clip_list_display_order();
+            let current = self.keyboard_focus_index.or(self.selected_clip_index);
+            if let Some(index) = Self::step_focus_index(&order, current, step) {
+                self.keyboard_focus_index = Some(index);
+                self.scroll_to_keyboard_focus = true;
+                if self.selected_clip_index != Some(index) {
+                    self.select_clip(index);
+                }
+            }
+        }
+        
+        if enter {
+            if let Some(index) = self.keyboard_focus_index {
+                if self.selected_clip_index != Some(index) {
+                    self.select_clip(index);
+                }
+                self.clip_list_has_focus = false;
+            }
+        }
+    }
+
     fn scan_and_load_replay_files(&mut self) {
         if let Some(ref watched_dir) = self.watched_directory {
             log::info!("Scanning for existing replay files in: {}", watched_dir.display());
             
-            match FileMonitor::scan_existing_files(watched_dir) {
+            match FileMonitor::scan_existing_files(watched_dir, &self.config.timestamp_pattern()) {
                 Ok(existing_files) => {
                     log::info!("Found {} existing replay files", existing_files.len());
                     
-                    // Clear existing clips first
-                    self.clips.clear();
+                    // Clear existing clips first, keeping imported videos
+                    self.clips.retain(|clip| clip.imported);
                     self.selected_clip_index = None;
                     
                     // Create clips for found files (limit to recent 20 files)
                     for file in existing_files.into_iter().take(20) {
                         // Create clips without target duration for existing files
-                        let file_path = file.path.clone();
-                        match Clip::new_without_target(file.path.clone()) {
-                            Ok(clip) => {
-                                // Request video info asynchronously (non-blocking)
-                                self.video_info_manager.request_if_needed(file_path.clone());
-                                log::debug!("Loaded existing file: {}", clip.get_output_filename());
-                                self.clips.push(clip);
-                            }
-                            Err(e) => {
-                                log::error!("Failed to create clip for existing file {:?}: {}", file_path, e);
-                            }
-                        }
+                        let clip = file.to_clip();
+                        // Request video info asynchronously (non-blocking)
+                        self.video_info_manager.request_if_needed(file.path);
+                        log::debug!("Loaded existing file: {}", clip.get_output_filename());
+                        self.clips.push(clip);
                     }
                     
                     self.status_message = format!("Loaded {} replay files", self.clips.len());
@@ -1258,15 +4733,71 @@ impl ClipHelperApp {
                         log::error!("Failed to save clips after loading from files: {}", e);
                     }
                 }
-                Err(e) => {
-                    log::error!("Failed to scan existing files: {}", e);
-                    self.status_message = format!("Error scanning files: {}", e);
+                Err(e) => {
+                    log::error!("Failed to scan existing files: {}", e);
+                    self.status_message = format!("Error scanning files: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Right panel while several clips are selected: what's selected and what can be done
+    /// to all of them at once
+    fn show_batch_actions(&mut self, ui: &mut egui::Ui) {
+        let files = self.multi_selected_files();
+        ui.heading(format!("{} clips selected", files.len()));
+        ui.small("Ctrl+click adds or removes a clip, Shift+click adds a range");
+        ui.add_space(8.0);
+        
+        egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+            for file in &files {
+                ui.label(format!("• {}", file.file_name().unwrap_or_default().to_string_lossy()));
+            }
+        });
+        ui.add_space(8.0);
+        
+        let indices: Vec<usize> = files.iter()
+            .filter_map(|file| self.clips.iter().position(|clip| &clip.original_file == file))
+            .collect();
+        ui.horizontal(|ui| {
+            if ui.button("✂ Export All").on_hover_text("Queue a trim export of every selected clip").clicked() {
+                self.trim_session(&indices);
+            }
+            if ui.button("🗑 Delete All").clicked() {
+                self.request_delete_selected_clip();
+            }
+            if ui.button("Clear Selection").clicked() {
+                self.selected_clip_indices.clear();
+            }
+        });
+        
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.batch_tags_text)
+                .hint_text("clutch, bug repro"));
+            let has_tags = !self.batch_tags_text.trim().is_empty();
+            if ui.add_enabled(has_tags, egui::Button::new("🏷 Tag All"))
+                .on_hover_text("Add these tags to every selected clip, keeping their existing tags")
+                .clicked()
+            {
+                for &index in &indices {
+                    let clip = &mut self.clips[index];
+                    let combined = format!("{}, {}", clip.tags.join(", "), self.batch_tags_text);
+                    clip.set_tags(&combined);
+                }
+                if let Some(index) = self.selected_clip_index {
+                    self.tags_text = self.clips[index].tags.join(", ");
+                }
+                if let Err(e) = self.save_clips() {
+                    log::error!("Failed to save clips after tagging: {}", e);
                 }
+                self.status_message = format!("Tagged {} clip(s)", indices.len());
+                self.batch_tags_text.clear();
             }
-        }
+        });
     }
 
     fn show_clip_editor(&mut self, ui: &mut egui::Ui) {
+        self.handle_trim_point_keys(ui);
         if let Some(selected_index) = self.selected_clip_index {
             if let Some(clip) = self.clips.get(selected_index) {
                 ui.heading("Clip Editor");
@@ -1276,6 +4807,9 @@ impl ClipHelperApp {
                 let duration = clip.target_duration_seconds;
                 let trim_start = clip.trim_start;
                 let trim_end = clip.trim_end;
+                let original_file = clip.original_file.clone();
+                
+                self.show_near_duplicate_warning(ui, &original_file);
                 
                 // Vertical layout: Video preview on top, controls below
                 ui.vertical(|ui| {
@@ -1299,38 +4833,236 @@ impl ClipHelperApp {
                                 ui.label("Duration:");
                                 ui.label(format!("{:.1}s", duration));
                             });
+                            self.show_loudness(ui, selected_index);
                             ui.horizontal(|ui| {
                                 ui.label("Trim:");
                                 ui.label(format!("{:.1}s - {:.1}s", trim_start, trim_end));
+                                if self.clips[selected_index].has_target_duration() {
+                                    let target = Clip::format_duration(duration as f64);
+                                    if ui.small_button(format!("Set {} ending here", target))
+                                        .on_hover_text("End the trim at the playhead and start it the target duration earlier")
+                                        .clicked()
+                                    {
+                                        self.trim_target_ending_at_playhead(selected_index);
+                                    }
+                                }
                             });
                             
-                            // Clip name input
+                            // Clip name input, feeds the {name} placeholder of the filename template
                             ui.horizontal(|ui| {
+                                let pinned = self.clips[selected_index].pinned;
+                                if ui.selectable_label(pinned, "📌")
+                                    .on_hover_text(if pinned { "Unpin" } else { "Pin: list above the sessions and keep through rescans and cleanup" })
+                                    .clicked()
+                                {
+                                    let file = self.clips[selected_index].original_file.clone();
+                                    self.toggle_pinned(&file);
+                                }
                                 ui.label("Output name:");
-                                ui.text_edit_singleline(&mut self.new_clip_name);
+                                let response = ui.text_edit_singleline(&mut self.new_clip_name);
+                                if response.changed() {
+                                    let name = self.new_clip_name.trim();
+                                    self.clips[selected_index].name = (!name.is_empty()).then(|| name.to_string());
+                                }
+                                if response.lost_focus() {
+                                    if let Err(e) = self.save_clips() {
+                                        log::error!("Failed to save clips after rename: {}", e);
+                                    }
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Output file:");
+                                ui.weak(format!("{}.mkv", self.output_name_for(selected_index)));
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Tags:");
+                                let response = ui.add(egui::TextEdit::singleline(&mut self.tags_text)
+                                    .hint_text("clutch, bug repro"));
+                                if response.lost_focus() {
+                                    self.clips[selected_index].set_tags(&self.tags_text);
+                                    self.tags_text = self.clips[selected_index].tags.join(", ");
+                                    if let Err(e) = self.save_clips() {
+                                        log::error!("Failed to save clips after editing tags: {}", e);
+                                    }
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Notes:");
+                                let response = ui.add(egui::TextEdit::multiline(&mut self.clips[selected_index].notes)
+                                    .desired_rows(2)
+                                    .hint_text("Why this clip matters"));
+                                if response.lost_focus() {
+                                    let notes = &mut self.clips[selected_index].notes;
+                                    *notes = notes.trim().to_string();
+                                    if let Err(e) = self.save_clips() {
+                                        log::error!("Failed to save clips after editing notes: {}", e);
+                                    }
+                                }
+                            });
+                            
+                            // Per-clip export directory, falls back to the trimmed directory
+                            let mut new_override = None;
+                            ui.horizontal(|ui| {
+                                ui.label("Export to:");
+                                let response = ui.add(egui::TextEdit::singleline(&mut self.export_directory_text)
+                                    .hint_text(self.config.trimmed_directory.display().to_string()));
+                                
+                                // Ctrl+V with the field focused takes the whole clipboard as the path
+                                let pasted = ui.input(|i| i.events.iter().find_map(|event| match event {
+                                    egui::Event::Paste(text) => Some(text.trim().to_string()),
+                                    _ => None,
+                                }));
+                                if let Some(text) = pasted.filter(|_| response.has_focus()) {
+                                    new_override = Some(Some(PathBuf::from(text)));
+                                } else if response.lost_focus() {
+                                    let text = self.export_directory_text.trim();
+                                    new_override = Some((!text.is_empty()).then(|| PathBuf::from(text)));
+                                }
+                                
+                                if ui.button("📂").on_hover_text("Browse").clicked() {
+                                    let start_dir = self.clips[selected_index].export_directory(&self.config.trimmed_directory).to_path_buf();
+                                    if let Some(folder) = rfd::FileDialog::new()
+                                        .set_title("Export Directory")
+                                        .set_directory(start_dir)
+                                        .pick_folder()
+                                    {
+                                        new_override = Some(Some(folder));
+                                    }
+                                }
+                                if ui.add_enabled(self.clips[selected_index].export_directory_override.is_some(), egui::Button::new("Clear override")).clicked() {
+                                    new_override = Some(None);
+                                }
                             });
+                            if let Some(directory) = new_override {
+                                if directory != self.clips[selected_index].export_directory_override {
+                                    self.set_export_directory_override(selected_index, directory);
+                                }
+                                // Drop rejected or unchanged text
+                                self.export_directory_text = self.clips[selected_index].export_directory_override.as_ref()
+                                    .map(|dir| dir.display().to_string())
+                                    .unwrap_or_default();
+                            }
                         });
                         
                         ui.separator();
                         
                         // Right side - Action buttons
                         ui.vertical(|ui| {
-                            if ui.button("✂ Apply Trim").clicked() {
-                                if let Err(e) = self.apply_trim(false) {
-                                    log::error!("Failed to apply trim: {}", e);
-                                    self.status_message = format!("Error applying trim: {}", e);
-                                } else {
-                                    self.status_message = "Trim applied successfully".to_string();
+                            ui.horizontal(|ui| {
+                                if ui.button("✂ Apply Trim").clicked() {
+                                    if let Err(e) = self.apply_trim(false) {
+                                        log::error!("Failed to apply trim: {}", e);
+                                        self.status_message = format!("Error applying trim: {}", e);
+                                    } else {
+                                        self.status_message = "Trim queued for export".to_string();
+                                    }
+                                }
+                                if let Some(path) = self.last_export_path.clone() {
+                                    if ui.button("📋 Copy path").on_hover_text(path.display().to_string()).clicked() {
+                                        self.copy_last_export_path(ui.ctx());
+                                    }
+                                }
+                            });
+                            
+                            ui.horizontal(|ui| {
+                                if ui.button("🖼 Set as thumbnail").on_hover_text("Use the current preview frame in the clip list").clicked() {
+                                    self.set_poster_to_playhead(selected_index);
+                                }
+                                if self.clips[selected_index].poster_timestamp.is_some()
+                                    && ui.small_button("✖").on_hover_text("Go back to the automatic thumbnail").clicked()
+                                {
+                                    self.clips[selected_index].poster_timestamp = None;
+                                    if let Err(e) = self.save_clips() {
+                                        log::error!("Failed to save clips after clearing thumbnail: {}", e);
+                                    }
+                                }
+                            });
+                            
+                            if ui.button("📷 Export frame")
+                                .on_hover_text(format!("Save the current frame at full resolution as {}", self.config.screenshot_format.label()))
+                                .clicked()
+                            {
+                                match self.take_screenshot() {
+                                    Ok(path) => {
+                                        self.show_toast(format!("Screenshot saved: {}",
+                                            path.file_name().unwrap_or_default().to_string_lossy()));
+                                    }
+                                    Err(e) => {
+                                        log::error!("Failed to save screenshot: {}", e);
+                                        self.show_error("Error saving screenshot", &e.to_string());
+                                    }
                                 }
                             }
                             
-                            if ui.button("🗑 Delete").clicked() {
-                                if let Err(e) = self.delete_selected_clip() {
-                                    log::error!("Failed to delete clip: {}", e);
-                                    self.status_message = format!("Error deleting clip: {}", e);
+                            // Animated exports, disabled for long trims where GIFs balloon in size
+                            let too_long = trim_end - trim_start > self.config.max_animated_duration_secs;
+                            ui.horizontal(|ui| {
+                                let format_label = |format: crate::video::ExportFormat| match format {
+                                    crate::video::ExportFormat::Gif => "GIF",
+                                    _ => "WebP",
+                                };
+                                let estimate = self.estimate_animated_size(self.animated_export_format).unwrap_or(0);
+                                let over_discord_limit = estimate > crate::video::DISCORD_UPLOAD_LIMIT_BYTES;
+                                let estimate_text = if over_discord_limit {
+                                    format!("Estimated size: {:.1} MB\n⚠ Over Discord's 8 MB upload limit, try a shorter trim, lower frame rate or smaller width",
+                                        estimate as f64 / 1_048_576.0)
                                 } else {
-                                    self.status_message = "Clip moved to deleted folder".to_string();
+                                    format!("Estimated size: {:.1} MB", estimate as f64 / 1_048_576.0)
+                                };
+                                
+                                egui::ComboBox::from_id_source("animated_export_format")
+                                    .selected_text(format!("🎞 {}", format_label(self.animated_export_format)))
+                                    .show_ui(ui, |ui| {
+                                        for format in [crate::video::ExportFormat::Gif, crate::video::ExportFormat::WebP] {
+                                            ui.selectable_value(&mut self.animated_export_format, format, format_label(format));
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text(estimate_text);
+                                if over_discord_limit {
+                                    ui.colored_label(egui::Color32::YELLOW, "⚠ > 8 MB");
                                 }
+                                
+                                let format = self.animated_export_format;
+                                let response = ui.add_enabled(!too_long, egui::Button::new("Export"))
+                                    .on_disabled_hover_text(format!("Trim must be at most {:.0}s for animated export", self.config.max_animated_duration_secs));
+                                if response.clicked() {
+                                    let force_overwrite = ui.input(|i| i.modifiers.shift);
+                                    match self.export_animated(format, force_overwrite) {
+                                        Ok(()) if over_discord_limit => {
+                                            self.status_message = format!("{} queued - warning: estimated {:.1} MB, over Discord's 8 MB limit",
+                                                format.extension().to_uppercase(), estimate as f64 / 1_048_576.0);
+                                        }
+                                        Ok(()) => {
+                                            self.status_message = format!("{} queued for export", format.extension().to_uppercase());
+                                        }
+                                        Err(e) => {
+                                            log::error!("Failed to queue animated export: {}", e);
+                                            self.status_message = format!("Error exporting {}: {}", format.extension().to_uppercase(), e);
+                                        }
+                                    }
+                                }
+                            });
+                            
+                            if let Some(index) = self.selected_clip_index {
+                                let trimmed_path = self.trimmed_output_path(index);
+                                ui.horizontal(|ui| {
+                                    if ui.button("▶ Open original").on_hover_text("Open the source replay in your default media player").clicked() {
+                                        self.open_in_external_player(self.clips[index].original_file.clone());
+                                    }
+                                    let trimmed_exists = trimmed_path.exists();
+                                    if ui.add_enabled(trimmed_exists, egui::Button::new("▶ Open trimmed"))
+                                        .on_hover_text("Open the trimmed export in your default media player")
+                                        .on_disabled_hover_text("Apply the trim first")
+                                        .clicked()
+                                    {
+                                        self.open_in_external_player(trimmed_path);
+                                    }
+                                });
+                            }
+                            
+                            if ui.button("🗑 Delete").clicked() {
+                                self.request_delete_selected_clip();
                             }
                             
                             ui.small("Hold Shift and click Apply to overwrite existing files");
@@ -1341,6 +5073,7 @@ impl ClipHelperApp {
                     
                     // Timeline
                     self.show_timeline(ui);
+                    self.show_markers(ui);
                     
                     ui.separator();
                     
@@ -1351,13 +5084,128 @@ impl ClipHelperApp {
                     
                     // Audio track controls
                     self.show_audio_controls(ui);
+                    
+                    self.show_technical_info(ui, &original_file);
                 });
             }
         }
     }
 
+    /// Recent log messages, newest at the bottom, filtered by level
+    fn show_log_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.strong("Log");
+            egui::ComboBox::from_id_source("log_panel_level")
+                .selected_text(self.log_panel_level.as_str())
+                .show_ui(ui, |ui| {
+                    // Off would hide everything, so start at Error
+                    for level in &crate::core::logging::LOG_LEVELS[1..] {
+                        ui.selectable_value(&mut self.log_panel_level, *level, level.as_str());
+                    }
+                });
+            if ui.small_button("Clear").clicked() {
+                self.log_buffer.clear();
+            }
+            ui.small(format!(
+                "Messages below the app's log level ({}) aren't recorded; change it in Settings",
+                log::max_level().as_str()
+            ));
+        });
+        ui.separator();
+        
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for entry in self.log_buffer.entries().filter(|entry| entry.level <= self.log_panel_level) {
+                    let color = match entry.level {
+                        log::Level::Error => egui::Color32::RED,
+                        log::Level::Warn => egui::Color32::YELLOW,
+                        log::Level::Info => ui.visuals().text_color(),
+                        log::Level::Debug | log::Level::Trace => egui::Color32::GRAY,
+                    };
+                    ui.label(egui::RichText::new(format!(
+                        "{} {:<5} {}",
+                        entry.time.format("%H:%M:%S"),
+                        entry.level,
+                        entry.message
+                    )).monospace().color(color))
+                        .on_hover_text(&entry.target);
+                }
+            });
+    }
+
+    /// Collapsible codec details. The body only runs while expanded, so the file is probed
+    /// the first time the section is opened and read from the cache after that.
+    fn show_technical_info(&mut self, ui: &mut egui::Ui, file: &std::path::Path) {
+        egui::CollapsingHeader::new("🔧 Technical Info")
+            .id_source("clip_technical_info_header")
+            .default_open(false)
+            .show(ui, |ui| {
+                let Some(result) = self.ffprobe_cache.get_or_probe(file) else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Probing...");
+                    });
+                    ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+                    return;
+                };
+                let info = match result {
+                    Ok(info) => info,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, format!("Couldn't read the file: {}", e));
+                        return;
+                    }
+                };
+                
+                let unknown = || "unknown".to_string();
+                let or_unknown = |value: &str| if value.is_empty() { unknown() } else { value.to_string() };
+                egui::Grid::new("clip_technical_info_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        let row = |ui: &mut egui::Ui, key: &str, value: String, warning: Option<(egui::Color32, &str)>| {
+                            ui.label(key);
+                            match warning {
+                                Some((color, hint)) => {
+                                    ui.colored_label(color, value).on_hover_text(hint);
+                                }
+                                None => {
+                                    ui.label(value);
+                                }
+                            }
+                            ui.end_row();
+                        };
+                        
+                        row(ui, "Container", or_unknown(&info.container), None);
+                        row(ui, "Video codec", or_unknown(&info.video_codec), None);
+                        row(ui, "Resolution", format!("{}x{}", info.width, info.height), None);
+                        let frame_rate = info.frame_rate.map_or_else(unknown, |fps| format!("{:.2} fps", fps));
+                        if info.variable_frame_rate {
+                            row(ui, "Frame rate", format!("{} (variable)", frame_rate),
+                                Some((egui::Color32::YELLOW, "Variable frame rate; some editors drift out of sync with the audio")));
+                        } else {
+                            row(ui, "Frame rate", frame_rate, None);
+                        }
+                        row(ui, "Bit depth", info.bit_depth.map_or_else(unknown, |depth| format!("{}-bit", depth)), None);
+                        let color_space = format!("{} / {}", or_unknown(&info.color_space), or_unknown(&info.color_transfer));
+                        if info.is_hdr() {
+                            row(ui, "Color space", format!("{} (HDR)", color_space),
+                                Some((egui::Color32::from_rgb(255, 165, 0), "HDR; looks washed out on SDR players unless tonemapped")));
+                        } else {
+                            row(ui, "Color space", color_space, None);
+                        }
+                        row(ui, "Audio codec", or_unknown(&info.audio_codec), None);
+                        row(ui, "Sample rate", info.sample_rate.map_or_else(unknown, |rate| format!("{} Hz", rate)), None);
+                        row(ui, "Channels", info.channels.map_or_else(unknown, |channels| channels.to_string()), None);
+                        row(ui, "Audio streams", info.audio_streams.to_string(), None);
+                    });
+            });
+    }
+
     fn show_video_preview(&mut self, ui: &mut egui::Ui) {
         ui.heading("Video Preview");
+        let mut retry_controller = false;
         
         // Process completed thumbnails more frequently for responsive user interaction
         if let Some(ref cache) = self.smart_thumbnail_cache {
@@ -1397,6 +5245,31 @@ impl ClipHelperApp {
             // Use allocate_exact_size to prevent container from changing size
             let (container_rect, _) = ui.allocate_exact_size(container_size, egui::Sense::hover());
             
+            // Wheel over the preview seeks while paused, Ctrl+wheel zooms the timeline
+            if ui.rect_contains_pointer(container_rect) {
+                let (notches, zoom) = ui.input(|i| {
+                    i.events.iter().fold((0.0f32, false), |(notches, zoom), event| match event {
+                        egui::Event::MouseWheel { unit, delta, modifiers } => {
+                            (notches + Self::wheel_notches(*unit, *delta), zoom || modifiers.command || modifiers.ctrl)
+                        }
+                        _ => (notches, zoom),
+                    })
+                });
+                if notches != 0.0 {
+                    if zoom {
+                        self.timeline_widget.request_zoom_steps(notches);
+                    } else if !preview.is_playing {
+                        let target = preview.current_time + Self::scroll_delta_to_seconds(notches, self.config.scroll_seek_step_seconds);
+                        preview.seek_to(target);
+                        if let Some(ref controller) = self.media_controller {
+                            if let Ok(mut controller) = controller.lock() {
+                                controller.seek(preview.current_time);
+                            }
+                        }
+                    }
+                }
+            }
+            
             ui.allocate_ui_at_rect(container_rect, |ui| {
                 ui.set_clip_rect(container_rect);
                 
@@ -1415,8 +5288,12 @@ impl ClipHelperApp {
                             preview.sync_position(controller_time);
                         }
                         
-                        // Get current video frame as texture (currently returns None in mock mode)
-                        if let Some(frame_texture) = ctrl.get_frame_texture(ui.ctx()) {
+                        // Audio-only mode: the timeline waveform is the visual, a stale frame would mislead
+                        if ctrl.is_audio_only() {
+                            ui.centered_and_justified(|ui| {
+                                ui.label(format!("🔊 Audio-only preview at {:.1}s", preview.current_time));
+                            });
+                        } else if let Some(frame_texture) = ctrl.get_frame_texture(ui.ctx()) {
                             log::trace!("Got frame texture with size {:?}", frame_texture.size_vec2());
                             
                             // Display video frame - scale to fill container while preserving aspect ratio
@@ -1501,11 +5378,36 @@ impl ClipHelperApp {
             
             ui.add_space(10.0);
             
+            let controller_state = self.media_controller.as_ref()
+                .and_then(|controller| controller.lock().ok().map(|controller| controller.state().clone()));
+            
             // Time display only - seeking handled by timeline below
             ui.horizontal(|ui| {
                 ui.label(format!("Time: {:.1}s / {:.1}s", preview.current_time, preview.total_duration));
+                if let Some(state) = controller_state.as_ref().filter(|state| !matches!(state, crate::video::MediaControllerState::Error(_))) {
+                    ui.separator();
+                    ui.label(state.display_text());
+                }
             });
             
+            if let Some(crate::video::MediaControllerState::Error(message)) = &controller_state {
+                ui.horizontal_wrapped(|ui| {
+                    ui.colored_label(egui::Color32::from_rgb(220, 80, 80), format!("⚠ {}", message.lines().next().unwrap_or_default()))
+                        .on_hover_text(message);
+                    if ui.small_button("Retry").clicked() {
+                        retry_controller = true;
+                    }
+                    if ui.small_button("Clear").clicked() {
+                        if let Some(ref controller) = self.media_controller {
+                            if let Ok(mut controller) = controller.lock() {
+                                controller.clear_error();
+                            }
+                        }
+                        preview.is_playing = false;
+                    }
+                });
+            }
+            
             // Process status - show embedded player status or fallback to preview
             if let Some(ref controller) = self.media_controller {
                 if preview.is_playing && !controller.lock().unwrap().is_playing() {
@@ -1520,12 +5422,37 @@ impl ClipHelperApp {
                 ui.label("No video preview available");
             });
         }
+        
+        if retry_controller {
+            self.retry_media_controller();
+        }
     }
 
     fn show_timeline(&mut self, ui: &mut egui::Ui) {
         if let Some(selected_index) = self.selected_clip_index {
+            if let Some(frame_rate) = self.media_controller.as_ref()
+                .and_then(|controller| controller.lock().ok().map(|c| c.frame_rate()))
+            {
+                self.timeline_widget.frame_rate = frame_rate;
+            }
+            
             if let Some(clip) = self.clips.get_mut(selected_index) {
-                let timeline_response = self.timeline_widget.show(ui, clip, &mut self.video_preview);
+                let waveform = self.waveforms.get(clip.original_file.to_string_lossy().as_ref());
+                self.timeline_widget.trim_rules = self.config.trim_rules();
+                let timeline_response = self.timeline_widget.show(
+                    ui, clip, &mut self.video_preview, waveform, self.smart_thumbnail_cache.as_deref());
+                
+                if self.timeline_widget.timecode_format != self.config.timecode_format
+                    || self.timeline_widget.waveform_scale != self.config.waveform_scale
+                    || self.timeline_widget.waveform_color != self.config.waveform_color
+                {
+                    self.config.timecode_format = self.timeline_widget.timecode_format;
+                    self.config.waveform_scale = self.timeline_widget.waveform_scale;
+                    self.config.waveform_color = self.timeline_widget.waveform_color;
+                    if let Err(e) = self.config.save() {
+                        log::error!("Failed to save timeline display settings: {}", e);
+                    }
+                }
                 
                 // If user interacted with timeline, handle seeking appropriately
                 if timeline_response.clicked() {
@@ -1537,22 +5464,35 @@ impl ClipHelperApp {
                             controller.lock().unwrap().seek(seek_time);
                         }
                     }
-                } else if timeline_response.dragged() {
-                    // During drag, use MediaController for seeking
+                } else if timeline_response.dragged() && self.timeline_widget.dragging_marker.is_none() {
+                    // During drag, only extract single frames; restarting playback
+                    // for every position would lag behind and flicker
                     if let Some(ref controller) = self.media_controller {
                         if let Some(preview) = &self.video_preview {
-                            let seek_time = preview.current_time;
-                            log::debug!("Timeline drag - seeking to {:.2}s", seek_time);
-                            controller.lock().unwrap().seek(seek_time);
+                            let mut controller = controller.lock().unwrap();
+                            if timeline_response.drag_started() {
+                                controller.begin_scrub();
+                            }
+                            controller.scrub_to(preview.current_time);
                         }
                     }
                 } else if timeline_response.drag_stopped() {
+                    // Marker positions may have changed
+                    if let Err(e) = self.save_clips() {
+                        log::error!("Failed to save clips after timeline drag: {}", e);
+                    }
+                    
                     // When drag ends, final seek to position
                     if let Some(ref controller) = self.media_controller {
                         if let Some(preview) = &self.video_preview {
                             let seek_time = preview.current_time;
                             log::debug!("Timeline drag released - seeking to {:.2}s", seek_time);
-                            controller.lock().unwrap().seek(seek_time);
+                            let mut controller = controller.lock().unwrap();
+                            if controller.is_scrubbing() {
+                                controller.end_scrub(seek_time);
+                            } else {
+                                controller.seek(seek_time);
+                            }
                         }
                     }
                 }
@@ -1562,31 +5502,206 @@ impl ClipHelperApp {
         }
     }
 
-    fn show_controls(&mut self, ui: &mut egui::Ui) {
+    /// Chapter marker list with "Add marker" and "Export between markers" actions
+    fn show_markers(&mut self, ui: &mut egui::Ui) {
+        let playhead = self.video_preview.as_ref().map(|preview| preview.current_time).unwrap_or(0.0);
+        let mut add_marker = false;
+        let mut export_segments = false;
+        let mut remove_marker = None;
+        let mut markers_changed = false;
+        let mut detect_scenes = false;
+        let mut threshold_changed = false;
+        let selected_file = self.get_selected_clip().map(|clip| clip.original_file.clone());
+        let scene_detection = self.scene_detections.iter()
+            .find(|d| Some(&d.file) == selected_file.as_ref() && !d.cancel.load(std::sync::atomic::Ordering::Relaxed));
+        let scene_cancel = scene_detection.map(|d| d.cancel.clone());
+        let scene_progress = scene_detection.map(|d| {
+            let done = d.progress.load(std::sync::atomic::Ordering::Relaxed) as f64 / 1000.0;
+            if d.duration > 0.0 { (done / d.duration).clamp(0.0, 1.0) as f32 } else { 0.0 }
+        });
+        let threshold = &mut self.config.scene_detection_threshold;
+        
+        let Some(clip) = self.selected_clip_index.and_then(|i| self.clips.get_mut(i)) else {
+            return;
+        };
+        
         ui.horizontal(|ui| {
-            if ui.button("⏮ Start").clicked() {
-                if let Some(preview) = &mut self.video_preview {
-                    preview.goto_start();
-                    // Force immediate seek on media controller
-                    if let Some(ref controller) = self.media_controller {
-                        controller.lock().unwrap().seek_immediate(preview.current_time);
+            if ui.button("📍 Add Marker").on_hover_text("Add a chapter marker at the playhead").clicked() {
+                add_marker = true;
+            }
+            
+            if let Some(progress) = scene_progress {
+                ui.add(egui::ProgressBar::new(progress).desired_width(120.0).show_percentage())
+                    .on_hover_text("Detecting scene changes");
+                if ui.small_button("✖").on_hover_text("Cancel scene detection").clicked() {
+                    if let Some(ref cancel) = scene_cancel {
+                        cancel.store(true, std::sync::atomic::Ordering::Relaxed);
                     }
                 }
+                ui.ctx().request_repaint_after(std::time::Duration::from_millis(250));
+            } else if ui.button("🎬 Detect Scenes")
+                .on_hover_text(format!(
+                    "Add markers at scene changes found by FFmpeg (at most {})",
+                    crate::video::MAX_SCENE_MARKERS
+                ))
+                .clicked() {
+                detect_scenes = true;
             }
+            threshold_changed = ui.add(egui::DragValue::new(threshold).range(0.05..=1.0).speed(0.01).max_decimals(2))
+                .on_hover_text("Scene change threshold: lower finds more, subtler cuts")
+                .changed();
             
-            if ui.button("⏪ -10s").clicked() {
-                if let Some(preview) = &mut self.video_preview {
-                    preview.skip_backward(10.0);
-                    // Force immediate seek on media controller
-                    if let Some(ref controller) = self.media_controller {
-                        controller.lock().unwrap().seek_immediate(preview.current_time);
-                    }
+            let segment_count = clip.marker_segments().len();
+            if ui.add_enabled(segment_count > 0, egui::Button::new("✂ Export Between Markers"))
+                .on_hover_text(format!("Export {} segment(s), one per adjacent marker pair", segment_count))
+                .clicked() {
+                export_segments = true;
+            }
+        });
+        
+        for (i, marker) in clip.markers.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.monospace(format!("{:>7.2}s", marker.time));
+                if ui.text_edit_singleline(&mut marker.label).lost_focus() {
+                    markers_changed = true;
+                }
+                if ui.small_button("🗑").on_hover_text("Remove marker").clicked() {
+                    remove_marker = Some(i);
+                }
+            });
+        }
+        
+        if add_marker {
+            let label = format!("Part {}", clip.markers.len() + 1);
+            clip.add_marker(playhead, label);
+            markers_changed = true;
+        }
+        if let Some(index) = remove_marker {
+            clip.markers.remove(index);
+            markers_changed = true;
+        }
+        
+        if markers_changed {
+            if let Err(e) = self.save_clips() {
+                log::error!("Failed to save clips after editing markers: {}", e);
+            }
+        }
+        
+        if threshold_changed {
+            if let Err(e) = self.config.save() {
+                log::error!("Failed to save config: {}", e);
+            }
+        }
+        if detect_scenes {
+            self.request_scene_detection();
+        }
+        
+        if export_segments {
+            match self.export_marker_segments() {
+                Ok(count) => self.status_message = format!("Queued {} marker segment(s) for export", count),
+                Err(e) => {
+                    log::error!("Failed to export marker segments: {}", e);
+                    self.status_message = format!("Error exporting segments: {}", e);
                 }
             }
+        }
+    }
+
+    /// Queue one export per adjacent marker pair of the selected clip, named after the markers
+    /// File name of one marker segment's export. The segment number keeps names unique
+    /// when labels repeat, e.g. a default "Part 2" left over after a marker was removed.
+    pub(crate) fn marker_segment_filename(base_name: &str, segment_index: usize, label: &str) -> String {
+        let safe_label: String = label.chars()
+            .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        format!("{} - {:02} {}.mkv", base_name, segment_index + 1, safe_label.trim())
+    }
+
+    fn export_marker_segments(&mut self) -> anyhow::Result<usize> {
+        let Some(index) = self.selected_clip_index else {
+            return Ok(0);
+        };
+        let Some(clip) = self.clips.get(index) else {
+            return Ok(0);
+        };
+        
+        let segments = clip.marker_segments();
+        let base_name = self.output_name_for(index);
+        let clip_path = clip.original_file.clone();
+        let output_directory = clip.export_directory(&self.config.trimmed_directory).to_path_buf();
+        let audio_tracks = clip.audio_tracks.clone();
+        let sidecar_template = self.config.export_preset.write_sidecar
+            .then(|| crate::core::ClipMetadata::from_clip(clip, crate::video::DEFAULT_PRESET_NAME));
+        
+        for (segment_index, (start, end, label)) in segments.iter().enumerate() {
+            let output_filename = Self::marker_segment_filename(&base_name, segment_index, label);
             
-            if ui.button("⏪ -5s").clicked() {
+            self.enqueue_export(crate::video::ExportJob {
+                id: 0,
+                clip_path: clip_path.clone(),
+                output_path: output_directory.join(output_filename),
+                preset_name: crate::video::DEFAULT_PRESET_NAME.to_string(),
+                trim_start: *start,
+                trim_end: *end,
+                audio_tracks: audio_tracks.clone(),
+                force_overwrite: false,
+                format: crate::video::ExportFormat::Video,
+                animated: crate::video::AnimatedExportSettings::default(),
+                sidecar: sidecar_template.clone().map(|mut sidecar| {
+                    sidecar.trim_start = *start;
+                    sidecar.trim_end = *end;
+                    sidecar.export_duration = end - start;
+                    sidecar
+                }),
+                export_audio_tracks_separately: self.config.export_preset.export_audio_tracks_separately,
+                extra_ffmpeg_args: self.config.export_preset.ffmpeg_args(),
+                target_size_mb: self.config.export_preset.two_pass_target_mb(),
+                video_encoder: self.config.export_preset.video_encoder,
+            });
+        }
+        
+        self.save_export_queue()?;
+        Ok(segments.len())
+    }
+
+    /// Seek to a loud moment of the selected clip's waveform
+    fn jump_to_peak(&mut self, jump: PeakJump) {
+        let Some(waveform) = self.get_selected_clip()
+            .and_then(|clip| self.waveforms.get(clip.original_file.to_string_lossy().as_ref()))
+        else {
+            return;
+        };
+        let Some(preview) = &self.video_preview else {
+            return;
+        };
+        let current = preview.current_time;
+        
+        let target = match jump {
+            PeakJump::Loudest => waveform.loudest_moment(),
+            // Skip the peak the playhead is already on
+            PeakJump::Next => waveform.loud_peaks().into_iter().find(|&time| time > current + PEAK_JUMP_EPSILON),
+            PeakJump::Previous => waveform.loud_peaks().into_iter().rev().find(|&time| time < current - PEAK_JUMP_EPSILON),
+        };
+        let Some(target) = target else {
+            self.show_toast("No loud moment found".to_string());
+            return;
+        };
+        
+        if let Some(preview) = &mut self.video_preview {
+            preview.seek_to(target);
+        }
+        if let Some(ref controller) = self.media_controller {
+            controller.lock().unwrap().seek_immediate(target);
+        }
+    }
+
+    fn show_controls(&mut self, ui: &mut egui::Ui) {
+        // Don't offer commands the media controller would reject
+        let availability = self.playback_availability;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(availability.can_seek, egui::Button::new("⏮ Start")).clicked() {
                 if let Some(preview) = &mut self.video_preview {
-                    preview.skip_backward(5.0);
+                    preview.goto_start();
                     // Force immediate seek on media controller
                     if let Some(ref controller) = self.media_controller {
                         controller.lock().unwrap().seek_immediate(preview.current_time);
@@ -1594,18 +5709,22 @@ impl ClipHelperApp {
                 }
             }
             
-            if ui.button("⏪ -3s").clicked() {
-                if let Some(preview) = &mut self.video_preview {
-                    preview.skip_backward(3.0);
-                    // Force immediate seek on media controller
-                    if let Some(ref controller) = self.media_controller {
-                        controller.lock().unwrap().seek_immediate(preview.current_time);
+            let jump_amounts = self.config.jump_amounts();
+            for &amount in jump_amounts.iter().rev() {
+                if ui.add_enabled(availability.can_seek, egui::Button::new(format!("⏪ -{}s", amount))).clicked() {
+                    if let Some(preview) = &mut self.video_preview {
+                        preview.skip_backward(amount);
+                        // Force immediate seek on media controller
+                        if let Some(ref controller) = self.media_controller {
+                            controller.lock().unwrap().seek_immediate(preview.current_time);
+                        }
                     }
                 }
             }
             
             if let Some(preview) = &mut self.video_preview {
-                if ui.button(if preview.is_playing { "⏸" } else { "▶" }).clicked() {
+                let can_toggle = if preview.is_playing { availability.can_pause } else { availability.can_play };
+                if ui.add_enabled(can_toggle, egui::Button::new(if preview.is_playing { "⏸" } else { "▶" })).clicked() {
                     let was_playing = preview.is_playing;
                     preview.toggle_playback();
                     
@@ -1623,19 +5742,21 @@ impl ClipHelperApp {
                 }
             }
             
-            if ui.button("3s ⏩").clicked() {
-                if let Some(preview) = &mut self.video_preview {
-                    preview.skip_forward(3.0);
-                    // Force immediate seek on media controller
-                    if let Some(ref controller) = self.media_controller {
-                        controller.lock().unwrap().seek_immediate(preview.current_time);
+            for &amount in &jump_amounts {
+                if ui.add_enabled(availability.can_seek, egui::Button::new(format!("{}s ⏩", amount))).clicked() {
+                    if let Some(preview) = &mut self.video_preview {
+                        preview.skip_forward(amount);
+                        // Force immediate seek on media controller
+                        if let Some(ref controller) = self.media_controller {
+                            controller.lock().unwrap().seek_immediate(preview.current_time);
+                        }
                     }
                 }
             }
             
-            if ui.button("5s ⏩").clicked() {
+            if ui.add_enabled(availability.can_seek, egui::Button::new("Last 5s ⏭")).clicked() {
                 if let Some(preview) = &mut self.video_preview {
-                    preview.skip_forward(5.0);
+                    preview.goto_last_5_seconds();
                     // Force immediate seek on media controller
                     if let Some(ref controller) = self.media_controller {
                         controller.lock().unwrap().seek_immediate(preview.current_time);
@@ -1643,82 +5764,145 @@ impl ClipHelperApp {
                 }
             }
             
-            if ui.button("10s ⏩").clicked() {
-                if let Some(preview) = &mut self.video_preview {
-                    preview.skip_forward(10.0);
-                    // Force immediate seek on media controller
+            let motion_start = self.get_selected_clip().and_then(|clip| clip.motion_start);
+            let first_action_hover = match motion_start {
+                Some(time) if time > 0.0 => format!("Jump to the first big scene change at {:.1}s, e.g. where a loading screen ends", time),
+                Some(_) => "No big scene change found near the start".to_string(),
+                None => "Looking for the first big scene change…".to_string(),
+            };
+            if ui.add_enabled(availability.can_seek && motion_start.is_some_and(|time| time > 0.0), egui::Button::new("⚡ First action"))
+                .on_hover_text(first_action_hover.clone())
+                .on_disabled_hover_text(first_action_hover)
+                .clicked()
+            {
+                if let Some(target) = motion_start {
+                    if let Some(preview) = &mut self.video_preview {
+                        preview.seek_to(target);
+                    }
                     if let Some(ref controller) = self.media_controller {
-                        controller.lock().unwrap().seek_immediate(preview.current_time);
+                        controller.lock().unwrap().seek_immediate(target);
                     }
                 }
             }
             
-            if ui.button("Last 5s ⏭").clicked() {
-                if let Some(preview) = &mut self.video_preview {
-                    preview.goto_last_5_seconds();
-                    // Force immediate seek on media controller
-                    if let Some(ref controller) = self.media_controller {
-                        controller.lock().unwrap().seek_immediate(preview.current_time);
-                    }
+            ui.separator();
+            let waveform = self.get_selected_clip()
+                .and_then(|clip| self.waveforms.get(clip.original_file.to_string_lossy().as_ref()));
+            let can_jump_to_peak = availability.can_seek && waveform.is_some();
+            let mut peak_jump = None;
+            if ui.add_enabled(can_jump_to_peak, egui::Button::new("⏴ Peak"))
+                .on_hover_text("Jump to the previous loud moment")
+                .clicked()
+            {
+                peak_jump = Some(PeakJump::Previous);
+            }
+            if ui.add_enabled(can_jump_to_peak, egui::Button::new("🔊 Loudest"))
+                .on_hover_text("Jump to the loudest moment of the clip")
+                .clicked()
+            {
+                peak_jump = Some(PeakJump::Loudest);
+            }
+            if ui.add_enabled(can_jump_to_peak, egui::Button::new("Peak ⏵"))
+                .on_hover_text("Jump to the next loud moment")
+                .clicked()
+            {
+                peak_jump = Some(PeakJump::Next);
+            }
+            if let Some(jump) = peak_jump {
+                self.jump_to_peak(jump);
+            }
+            
+            ui.separator();
+            if ui.selectable_label(self.audio_only_preview, "🔊 Audio only")
+                .on_hover_text("Don't decode video; seeking plays a short audio snippet. Useful for trimming on sound cues.")
+                .clicked()
+            {
+                self.audio_only_preview = !self.audio_only_preview;
+                if let Some(ref controller) = self.media_controller {
+                    controller.lock().unwrap().set_audio_only(self.audio_only_preview);
                 }
             }
         });
         
-        // Trim controls
+        // Trim controls. Holding Shift turns the small steps into fine steps.
+        let trims_before = self.get_selected_clip().map(|clip| (clip.trim_start, clip.trim_end));
+        let small_step = if ui.input(|i| i.modifiers.shift) {
+            self.config.trim_step_fine_seconds
+        } else {
+            self.config.trim_step_small_seconds
+        };
+        let large_step = self.config.trim_step_large_seconds;
+        let steps = [-large_step, -small_step, small_step, large_step];
+        let min_gap = self.config.min_trim_gap_seconds;
+        
         ui.horizontal(|ui| {
-            ui.label("Start:");
-            if ui.button("-5s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    clip.trim_start = (clip.trim_start - 5.0).max(0.0);
-                }
-            }
-            if ui.button("-1s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    clip.trim_start = (clip.trim_start - 1.0).max(0.0);
-                }
-            }
-            if ui.button("+1s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    clip.trim_start = (clip.trim_start + 1.0).min(clip.trim_end - 0.1);
+            ui.label("Snap:");
+            let snap_before = self.config.trim_snap;
+            egui::ComboBox::from_id_source("trim_snap")
+                .selected_text(self.config.trim_snap.label())
+                .show_ui(ui, |ui| {
+                    for snap in crate::core::TrimSnap::ALL {
+                        ui.selectable_value(&mut self.config.trim_snap, snap, snap.label());
+                    }
+                })
+                .response
+                .on_hover_text("Round trim points set on the timeline or at the playhead to whole or half seconds");
+            if self.config.trim_snap != snap_before {
+                if let Err(e) = self.config.save() {
+                    log::error!("Failed to save trim snapping: {}", e);
                 }
             }
-            if ui.button("+5s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    clip.trim_start = (clip.trim_start + 5.0).min(clip.trim_end - 0.1);
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label("Start:");
+            for step in steps {
+                if ui.add_enabled(availability.can_seek, egui::Button::new(format!("{:+}s", step)))
+                    .on_hover_text("Hold Shift for fine steps")
+                    .clicked()
+                {
+                    if let Some(clip) = self.get_selected_clip_mut() {
+                        clip.trim_start = if step < 0.0 {
+                            (clip.trim_start + step).max(0.0)
+                        } else {
+                            (clip.trim_start + step).min(clip.trim_end - min_gap)
+                        };
+                    }
                 }
             }
         });
         
         ui.horizontal(|ui| {
             ui.label("End:");
-            if ui.button("-5s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    clip.trim_end = (clip.trim_end - 5.0).max(clip.trim_start + 0.1);
-                }
-            }
-            if ui.button("-1s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    clip.trim_end = (clip.trim_end - 1.0).max(clip.trim_start + 0.1);
-                }
-            }
-            if ui.button("+1s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    let max_duration = clip.video_length_seconds.unwrap_or(clip.trim_end);
-                    clip.trim_end = (clip.trim_end + 1.0).min(max_duration);
-                }
-            }
-            if ui.button("+5s").clicked() {
-                if let Some(clip) = self.get_selected_clip_mut() {
-                    let max_duration = clip.video_length_seconds.unwrap_or(clip.trim_end);
-                    clip.trim_end = (clip.trim_end + 5.0).min(max_duration);
+            for step in steps {
+                if ui.add_enabled(availability.can_seek, egui::Button::new(format!("{:+}s", step)))
+                    .on_hover_text("Hold Shift for fine steps")
+                    .clicked()
+                {
+                    if let Some(clip) = self.get_selected_clip_mut() {
+                        clip.trim_end = if step < 0.0 {
+                            (clip.trim_end + step).max(clip.trim_start + min_gap)
+                        } else {
+                            let max_duration = clip.video_length_seconds.unwrap_or(clip.trim_end);
+                            (clip.trim_end + step).min(max_duration)
+                        };
+                    }
                 }
             }
         });
+        
+        if let Some(clip) = self.get_selected_clip_mut() {
+            if trims_before != Some((clip.trim_start, clip.trim_end)) {
+                clip.trim_adjusted = true;
+            }
+        }
     }
 
     fn show_audio_controls(&mut self, ui: &mut egui::Ui) {
         ui.heading("Audio Tracks");
         
+        let selected_index = self.selected_clip_index;
+        let mut track_export = None;
         if let Some(clip) = self.get_selected_clip_mut() {
             let mut audio_changed = false;
             
@@ -1728,7 +5912,25 @@ impl ClipHelperApp {
                     let old_surround = track.surround_mode;
                     
                     ui.checkbox(&mut track.enabled, &track.name);
-                    ui.checkbox(&mut track.surround_mode, "Surround L/R");
+                    egui::ComboBox::from_id_source(("surround_mode", track.index))
+                        .selected_text(track.surround_mode.label())
+                        .show_ui(ui, |ui| {
+                            for mode in crate::core::SurroundMode::ALL {
+                                ui.selectable_value(&mut track.surround_mode, mode, mode.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text("How the track's channels are folded to stereo in the preview and the export mix");
+                    ui.menu_button("💾", |ui| {
+                        for format in crate::video::AudioExportFormat::ALL {
+                            if ui.button(format!("Export as {}", format.label())).clicked() {
+                                track_export = Some((track.index, format));
+                                ui.close_menu();
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("Save just this track's trimmed audio next to the video export");
                     
                     // Check if settings changed
                     if track.enabled != old_enabled || track.surround_mode != old_surround {
@@ -1747,6 +5949,10 @@ impl ClipHelperApp {
                 }
             }
         }
+        
+        if let (Some(index), Some((track_index, format))) = (selected_index, track_export) {
+            self.export_audio_track(index, track_index, format);
+        }
     }
 
     fn show_directory_selection_dialog(&mut self, ctx: &egui::Context) {
@@ -1933,10 +6139,15 @@ impl ClipHelperApp {
                                     let label_text = format!("{} {}", icon, name);
                                     
                                     if ui.selectable_label(false, label_text).clicked() && is_audio_file {
-                                        self.config.audio_confirmation.sound_file_path = Some(path);
+                                        log::info!("Selected sound file: {}", path.display());
+                                        match self.sound_file_browser_duration.take() {
+                                            Some(seconds) => {
+                                                self.config.audio_confirmation.duration_sound_files.insert(seconds, path);
+                                            }
+                                            None => self.config.audio_confirmation.sound_file_path = Some(path),
+                                        }
                                         self.show_sound_file_browser = false;
                                         self.status_message = "Sound file selected".to_string();
-                                        log::info!("Selected sound file: {}", self.config.audio_confirmation.sound_file_path.as_ref().unwrap().display());
                                     }
                                 }
                             }
@@ -1964,7 +6175,7 @@ impl ClipHelperApp {
         self.file_receiver = None;
         
         // Start new file monitoring
-        match FileMonitor::new(&path) {
+        match FileMonitor::new(&path, self.config.timestamp_pattern()) {
             Ok((monitor, receiver)) => {
                 self.file_monitor = Some(monitor);
                 self.file_receiver = Some(receiver);
@@ -1974,6 +6185,9 @@ impl ClipHelperApp {
                 self.config.obs_replay_directory = path.clone();
                 self.config.deleted_directory = path.join("deleted");
                 self.config.trimmed_directory = path.join("trimmed");
+                self.config.screenshot_directory = self.config.trimmed_directory.join("screenshots");
+                self.config.gif_directory = self.config.trimmed_directory.join("gifs");
+                self.config.archive_directory = path.join("archive");
                 self.config.last_watched_directory = Some(path.clone());
                 
                 // Then save the config with all updated paths
@@ -2030,6 +6244,376 @@ impl ClipHelperApp {
         Ok(())
     }
 
+    /// Start importing files and folders dropped onto the window, and hint at the
+    /// drop while files are dragged over it. Drops from outside the replay directory
+    /// first ask whether to copy them there.
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let (hovering, dropped) = ctx.input(|i| {
+            let dropped: Vec<PathBuf> = i.raw.dropped_files.iter().filter_map(|file| file.path.clone()).collect();
+            (!i.raw.hovered_files.is_empty(), dropped)
+        });
+        
+        if hovering {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("drop_overlay")));
+            let screen = ctx.screen_rect();
+            painter.rect_filled(screen, 0.0, egui::Color32::from_rgba_unmultiplied(30, 90, 200, 150));
+            painter.rect_stroke(screen.shrink(4.0), 8.0, egui::Stroke::new(3.0, egui::Color32::from_rgb(100, 170, 255)));
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                "Drop videos or folders to add them as clips",
+                egui::FontId::proportional(24.0),
+                egui::Color32::WHITE,
+            );
+        }
+        if dropped.is_empty() {
+            return;
+        }
+        
+        let outside_watched = self.watched_directory.as_ref()
+            .is_some_and(|dir| dropped.iter().any(|path| !path.starts_with(dir)));
+        if outside_watched {
+            self.pending_drop_copy = Some(dropped);
+        } else {
+            self.start_video_import(dropped, None);
+        }
+    }
+    
+    /// Probe `paths` (files or folders) in the background and add the videos as clips.
+    /// With `copy_to`, files outside that directory are copied into it first.
+    fn start_video_import(&mut self, paths: Vec<PathBuf>, copy_to: Option<PathBuf>) {
+        log::info!("Importing {} dropped path(s)", paths.len());
+        self.status_message = if copy_to.is_some() {
+            "Copying dropped files...".to_string()
+        } else {
+            "Checking dropped files...".to_string()
+        };
+        let handle = std::thread::spawn(move || {
+            crate::core::import::collect_import_candidates(&paths)
+                .into_iter()
+                .map(|path| match &copy_to {
+                    Some(dir) if !path.starts_with(dir) => {
+                        match crate::core::import::copy_into_directory(&path, dir) {
+                            Ok(copy) => {
+                                let result = crate::core::import::probe_importable(&copy);
+                                (copy, result)
+                            }
+                            Err(e) => (path, Err(e)),
+                        }
+                    }
+                    _ => {
+                        let result = crate::core::import::probe_importable(&path);
+                        (path, result)
+                    }
+                })
+                .collect()
+        });
+        self.video_imports.push(VideoImport { handle });
+    }
+    
+    fn render_drop_copy_dialog(&mut self, ctx: &egui::Context) {
+        let (Some(paths), Some(watched)) = (self.pending_drop_copy.clone(), self.watched_directory.clone()) else {
+            self.pending_drop_copy = None;
+            return;
+        };
+        let mut choice = None;
+        
+        egui::Window::new("Copy to watched directory first?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("{} dropped item(s) are outside {}:", paths.len(), watched.display()));
+                for path in paths.iter().take(10) {
+                    ui.label(format!("  • {}", path.file_name().unwrap_or_default().to_string_lossy()));
+                }
+                if paths.len() > 10 {
+                    ui.label(format!("  … and {} more", paths.len() - 10));
+                }
+                ui.small("Copies are listed like any other replay. Imported in place, they stay where they are.");
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("📋 Copy and import").clicked() {
+                        choice = Some(Some(watched.clone()));
+                    }
+                    if ui.button("Import in place").clicked() {
+                        choice = Some(None);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.pending_drop_copy = None;
+                    }
+                });
+            });
+        
+        if let Some(copy_to) = choice {
+            self.pending_drop_copy = None;
+            self.start_video_import(paths, copy_to);
+        }
+    }
+
+    fn process_video_imports(&mut self) {
+        let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.video_imports)
+            .into_iter()
+            .partition(|import| import.handle.is_finished());
+        self.video_imports = running;
+        if finished.is_empty() {
+            return;
+        }
+        
+        let (mut imported, mut skipped) = (0, Vec::new());
+        for import in finished {
+            let Ok(results) = import.handle.join() else {
+                log::error!("Video import thread panicked");
+                continue;
+            };
+            for (path, result) in results {
+                if self.clips.iter().any(|clip| clip.original_file == path) {
+                    log::debug!("{} is already in the clip list", path.display());
+                    continue;
+                }
+                let clip = result.and_then(|info| {
+                    let mut clip = Clip::new_imported(path.clone())?;
+                    // Copies the replay scan picks up again don't need restoring as imports
+                    clip.imported = !self.watched_directory.as_ref().is_some_and(|dir| FileMonitor::is_found_by_scan(dir, &path));
+                    clip.video_length_seconds = Some(info.duration);
+                    clip.trim_end = info.duration;
+                    clip.audio_tracks = info.audio_tracks;
+                    Ok(clip)
+                });
+                match clip {
+                    Ok(clip) => {
+                        log::info!("Imported {}", path.display());
+                        self.clips.push(clip);
+                        imported += 1;
+                    }
+                    Err(e) => {
+                        log::warn!("Skipped dropped file: {}", e);
+                        skipped.push(path);
+                    }
+                }
+            }
+        }
+        
+        if imported > 0 {
+            if let Err(e) = self.save_clips() {
+                log::error!("Failed to save clips after import: {}", e);
+            }
+        }
+        self.status_message = match skipped.as_slice() {
+            [] => format!("Imported {} video(s)", imported),
+            [only] => format!("Imported {} video(s), skipped {} (not a readable video)", imported,
+                only.file_name().unwrap_or_default().to_string_lossy()),
+            _ => format!("Imported {} video(s), skipped {} files that aren't readable videos", imported, skipped.len()),
+        };
+    }
+
+    /// Hide the window to the tray instead of closing it when `minimize_to_tray` is on.
+    /// Falls back to closing if the tray icon can't be created.
+    fn handle_close_to_tray(&mut self, ctx: &egui::Context) {
+        if !ctx.input(|i| i.viewport().close_requested()) || self.quit_requested || !self.config.minimize_to_tray {
+            return;
+        }
+        
+        if self.tray.is_none() {
+            match crate::gui::tray::AppTray::new(ctx) {
+                Ok(tray) => self.tray = Some(tray),
+                Err(e) => {
+                    log::warn!("Closing instead of minimizing to tray: {}", e);
+                    return;
+                }
+            }
+        }
+        if let Some(tray) = &self.tray {
+            tray.set_visible(true);
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        self.hidden_in_tray = true;
+        log::info!("Window hidden to the system tray");
+    }
+
+    fn process_tray_actions(&mut self, ctx: &egui::Context) {
+        let Some(tray) = &self.tray else {
+            return;
+        };
+        let actions: Vec<_> = std::iter::from_fn(|| tray.try_recv_action()).collect();
+        
+        for action in actions {
+            match action {
+                crate::gui::tray::TrayAction::Open => {
+                    if let Some(tray) = &self.tray {
+                        tray.set_visible(false);
+                    }
+                    self.hidden_in_tray = false;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                }
+                crate::gui::tray::TrayAction::ExportNextClip => self.export_next_clip_from_tray(),
+                crate::gui::tray::TrayAction::Quit => {
+                    self.quit_requested = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+    }
+
+    /// Queue an export of the first clip in the list that hasn't been exported yet
+    fn export_next_clip_from_tray(&mut self) {
+        let queued: Vec<PathBuf> = self.export_queue.pending_specs().into_iter().map(|spec| spec.clip_path).collect();
+        let next = self.clip_list_display_order().into_iter()
+            .find(|&index| !self.clips[index].is_trimmed && !queued.contains(&self.clips[index].original_file));
+        let Some(index) = next else {
+            crate::gui::tray::show_notification("Nothing to export", "Every clip has already been exported");
+            return;
+        };
+        
+        let job = self.trim_export_job(index, false);
+        if job.output_path.exists() {
+            crate::gui::tray::show_notification("Export skipped", &format!("{} already exists", job.output_path.display()));
+            return;
+        }
+        log::info!("Exporting {} from the tray", self.clips[index].original_file.display());
+        self.enqueue_export(job);
+        if let Err(e) = self.save_export_queue() {
+            log::error!("Failed to save export queue: {}", e);
+        }
+    }
+
+    /// Record window position/size changes and save them at most once per second
+    fn track_window_layout(&mut self, ctx: &egui::Context) {
+        let (outer_rect, inner_rect, maximized, minimized) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (viewport.outer_rect, viewport.inner_rect, viewport.maximized, viewport.minimized)
+        });
+        if let Some(maximized) = maximized.filter(|&maximized| maximized != self.config.window_maximized) {
+            self.config.window_maximized = maximized;
+            self.layout_dirty = true;
+        }
+        // A maximized or minimized window's rect isn't the one to restore later
+        let restorable = maximized != Some(true) && minimized != Some(true);
+        if let (Some(outer), Some(inner), true) = (outer_rect, inner_rect, restorable) {
+            let window_rect = egui::Rect::from_min_size(outer.min, inner.size());
+            if self.config.window_rect != Some(window_rect) {
+                self.config.window_rect = Some(window_rect);
+                self.layout_dirty = true;
+            }
+        }
+        
+        // Don't write while the settings dialog has unsaved edits in self.config
+        if self.layout_dirty && !self.show_settings_dialog
+            && self.last_layout_save.elapsed() >= std::time::Duration::from_secs(1)
+        {
+            self.layout_dirty = false;
+            self.last_layout_save = std::time::Instant::now();
+            if let Err(e) = self.config.save() {
+                log::warn!("Failed to save window layout: {}", e);
+            }
+        }
+    }
+
+    /// Forget the saved window position and size and return to the default, un-maximized size
+    fn reset_window_layout(&mut self, ctx: &egui::Context) {
+        self.config.window_rect = None;
+        self.config.window_maximized = false;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(crate::core::config::DEFAULT_WINDOW_SIZE));
+        if let Err(e) = self.config.save() {
+            log::warn!("Failed to save window layout: {}", e);
+        }
+        self.show_toast("Window layout reset".to_string());
+    }
+
+    fn export_queue_file_path() -> std::path::PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("clip-helper")
+            .join("export_queue.json")
+    }
+
+    /// Persist unfinished exports, including ones from the previous session not yet resumed
+    fn save_export_queue(&self) -> anyhow::Result<()> {
+        let queue_path = Self::export_queue_file_path();
+        let mut specs = self.export_queue.pending_specs();
+        specs.extend(self.pending_export_specs.iter().cloned());
+        
+        if specs.is_empty() {
+            if queue_path.exists() {
+                std::fs::remove_file(&queue_path)?;
+            }
+            return Ok(());
+        }
+        
+        if let Some(parent) = queue_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&specs)?;
+        std::fs::write(&queue_path, content)?;
+        log::debug!("Saved {} queued exports to {}", specs.len(), queue_path.display());
+        Ok(())
+    }
+
+    fn load_export_queue() -> Vec<crate::video::ExportJobSpec> {
+        let queue_path = Self::export_queue_file_path();
+        if !queue_path.exists() {
+            return Vec::new();
+        }
+        
+        match std::fs::read_to_string(&queue_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| serde_json::from_str::<Vec<crate::video::ExportJobSpec>>(&content).map_err(anyhow::Error::from))
+        {
+            Ok(specs) => specs,
+            Err(e) => {
+                log::warn!("Failed to read export queue file ({}), starting with empty queue", e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn render_resume_exports_dialog(&mut self, ctx: &egui::Context) {
+        let mut resume = false;
+        let mut discard = false;
+        
+        egui::Window::new("Resume Exports")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("Resume {} pending exports?", self.pending_export_specs.len()));
+                ui.add_space(5.0);
+                
+                for spec in self.pending_export_specs.iter().take(10) {
+                    ui.small(spec.output_path.file_name().unwrap_or_default().to_string_lossy());
+                }
+                if self.pending_export_specs.len() > 10 {
+                    ui.small(format!("...and {} more", self.pending_export_specs.len() - 10));
+                }
+                
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Resume").clicked() {
+                        resume = true;
+                    }
+                    if ui.button("Discard").clicked() {
+                        discard = true;
+                    }
+                });
+            });
+        
+        if resume {
+            self.show_resume_exports_dialog = false;
+            self.resume_pending_exports();
+            self.status_message = "Resumed pending exports".to_string();
+        } else if discard {
+            self.show_resume_exports_dialog = false;
+            self.pending_export_specs.clear();
+            if let Err(e) = self.save_export_queue() {
+                log::error!("Failed to clear export queue file: {}", e);
+            }
+            self.status_message = "Discarded pending exports".to_string();
+        }
+    }
+
     fn duration_requests_file_path() -> std::path::PathBuf {
         let mut path = dirs::config_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
         path.push("clip-helper");
@@ -2046,8 +6630,8 @@ impl ClipHelperApp {
                     log::info!("Loaded {} duration requests from {}", requests.len(), requests_path.display());
                     self.duration_requests = requests;
                     
-                    // Clean up old requests (older than 1 hour)
-                    let cutoff = Local::now() - chrono::Duration::hours(1);
+                    // Clean up old requests (older than 1 hour, or the retry timeout if longer)
+                    let cutoff = Local::now() - self.duration_request_max_age();
                     let original_count = self.duration_requests.len();
                     self.duration_requests.retain(|req| req.timestamp > cutoff);
                     let cleaned_count = self.duration_requests.len();
@@ -2099,23 +6683,234 @@ impl ClipHelperApp {
                     Ok(())
                 }
                 Err(e) => {
-                    log::warn!("Failed to parse clips file ({}), starting with empty list", e);
-                    self.clips.clear();
-                    Ok(())
+                    log::warn!("Failed to parse clips file ({}), starting with empty list", e);
+                    self.clips.clear();
+                    Ok(())
+                }
+            }
+        } else {
+            log::debug!("No clips file found at {}", clips_path.display());
+            Ok(())
+        }
+    }
+
+    fn set_target_duration_and_save(&mut self, clip_index: usize, duration: crate::core::ClipDuration) {
+        let padding = self.config.trim_padding;
+        if let Some(clip) = self.clips.get_mut(clip_index) {
+            // Picking a duration explicitly replaces any hand-adjusted trim
+            clip.trim_adjusted = false;
+            clip.set_target_duration_with_padding(duration, padding);
+            if let Err(e) = self.save_clips() {
+                log::error!("Failed to save clips after setting target duration: {}", e);
+            }
+        }
+    }
+
+    /// ? or F1 toggles the help overlay, Escape closes it. Ignored while typing in a text field.
+    fn handle_help_overlay_keys(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let (toggle, escape) = ctx.input_mut(|i| (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Questionmark)
+                | i.consume_key(egui::Modifiers::NONE, egui::Key::F1),
+            self.show_keyboard_shortcuts && i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+        ));
+        if toggle {
+            self.show_keyboard_shortcuts = !self.show_keyboard_shortcuts;
+        } else if escape {
+            self.show_keyboard_shortcuts = false;
+        }
+    }
+
+    fn render_keyboard_shortcuts(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut customize = false;
+        // Read every frame so bindings changed in the settings show up right away
+        let hotkey_rows = crate::gui::shortcuts::global_hotkey_rows(&self.config.hotkeys);
+        
+        egui::Window::new("Keyboard Shortcuts")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.strong("Global hotkeys (work while the game has focus)");
+                egui::Grid::new("global_hotkeys_help_grid")
+                    .num_columns(2)
+                    .min_col_width(220.0)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (description, binding) in &hotkey_rows {
+                            ui.label(*description);
+                            ui.monospace(binding);
+                            ui.end_row();
+                        }
+                    });
+                
+                if ui.button("Customize…").on_hover_text("Change the global hotkeys in the settings").clicked() {
+                    customize = true;
+                }
+                
+                for (section, shortcuts) in crate::gui::shortcuts::APP_SHORTCUTS {
+                    ui.add_space(10.0);
+                    ui.strong(*section);
+                    egui::Grid::new(("app_shortcuts_help_grid", *section))
+                        .num_columns(2)
+                        .min_col_width(220.0)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.weak("Action");
+                            ui.weak("Shortcut");
+                            ui.end_row();
+                            for (action, shortcut) in *shortcuts {
+                                ui.label(*action);
+                                ui.monospace(*shortcut);
+                                ui.end_row();
+                            }
+                        });
+                }
+                
+                ui.add_space(10.0);
+                ui.small("Press ? or Escape to close");
+            });
+        
+        if customize {
+            self.show_settings_dialog = true;
+            self.scroll_settings_to_hotkeys = true;
+        }
+        if !open || customize {
+            self.show_keyboard_shortcuts = false;
+        }
+    }
+
+    fn render_error_details_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut dismiss = false;
+        
+        egui::Window::new("Error Details")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                let details = self.error_details.as_deref().unwrap_or("No error");
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(egui::Label::new(egui::RichText::new(details).monospace()).wrap());
+                    });
+                
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("📋 Copy").clicked() {
+                        ui.output_mut(|o| o.copied_text = details.to_string());
+                    }
+                    if ui.button("Dismiss").clicked() {
+                        dismiss = true;
+                    }
+                });
+            });
+        
+        if dismiss {
+            self.error_details = None;
+            self.failed_export = None;
+            self.status_message.clear();
+        }
+        if !open || dismiss {
+            self.show_error_details_dialog = false;
+        }
+    }
+
+    /// Table of per-duration confirmation sounds with file pickers and previews
+    fn show_duration_sound_settings(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("duration_sounds_grid")
+            .num_columns(4)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                for duration in crate::core::ClipDuration::ALL {
+                    let seconds = duration as u32;
+                    ui.label(format!("{} clip:", duration.label()));
+                    
+                    let custom = self.config.audio_confirmation.duration_sound_files.get(&seconds).cloned();
+                    match &custom {
+                        Some(path) if path.exists() => ui.label(path.file_name().unwrap_or_default().to_string_lossy())
+                            .on_hover_text(path.display().to_string()),
+                        Some(path) => ui.colored_label(egui::Color32::YELLOW, "⚠ Missing, using generated tone")
+                            .on_hover_text(path.display().to_string()),
+                        None => ui.weak("Generated tone"),
+                    };
+                    
+                    ui.horizontal(|ui| {
+                        let frequency = self.config.audio_confirmation.tone_frequencies
+                            .entry(seconds)
+                            .or_insert(crate::core::config::DEFAULT_TONE_FREQUENCY);
+                        ui.add(egui::Slider::new(frequency, crate::core::config::TONE_FREQUENCY_RANGE)
+                            .logarithmic(true)
+                            .suffix(" Hz")
+                            .fixed_decimals(0))
+                            .on_hover_text("Pitch of the generated tone; a custom sound file plays as it is");
+                        ui.weak(crate::audio::note_name(*frequency));
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        if ui.small_button("Browse...").clicked() {
+                            if self.config.use_system_file_dialog {
+                                let mut file_dialog = rfd::FileDialog::new()
+                                    .add_filter("Audio Files", &["wav", "mp3", "ogg", "flac"])
+                                    .add_filter("All Files", &["*"])
+                                    .set_title(format!("Select {} Clip Sound", duration.label()));
+                                if let Some(parent) = custom.as_ref().and_then(|path| path.parent()) {
+                                    file_dialog = file_dialog.set_directory(parent);
+                                }
+                                if let Some(file_path) = file_dialog.pick_file() {
+                                    log::info!("Selected {} sound file: {}", duration.label(), file_path.display());
+                                    self.config.audio_confirmation.duration_sound_files.insert(seconds, file_path);
+                                }
+                            } else {
+                                self.show_sound_file_browser = true;
+                                self.sound_file_browser_duration = Some(seconds);
+                                if let Some(parent) = custom.as_ref().and_then(|path| path.parent()) {
+                                    self.file_browser_path = parent.to_path_buf();
+                                }
+                            }
+                        }
+                        if ui.small_button("▶ Preview").clicked() {
+                            if let Some(ref mut audio_confirmation) = self.audio_confirmation {
+                                if let Err(e) = audio_confirmation.preview_duration_confirmation(duration, &self.config.audio_confirmation) {
+                                    log::error!("Failed to preview {} sound: {}", duration.label(), e);
+                                    self.status_message = format!("Failed to play {} sound: {}", duration.label(), e);
+                                }
+                            } else {
+                                self.status_message = "Audio system not available".to_string();
+                            }
+                        }
+                        if custom.is_some() && ui.small_button("✖").on_hover_text("Use the generated tone").clicked() {
+                            self.config.audio_confirmation.duration_sound_files.remove(&seconds);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        
+        if ui.button("Reset pitches to defaults").clicked() {
+            self.config.audio_confirmation.tone_frequencies = crate::core::config::default_tone_frequencies();
+        }
+        if ui.button("Generate all default tones").clicked() {
+            match crate::audio::generate_duration_confirmation_sounds(&self.config.audio_confirmation) {
+                Ok(sounds_dir) => {
+                    for duration in crate::core::ClipDuration::ALL {
+                        self.config.audio_confirmation.duration_sound_files
+                            .insert(duration as u32, sounds_dir.join(crate::audio::duration_sound_file_name(duration)));
+                    }
+                    log::info!("Generated default duration tones in {}", sounds_dir.display());
+                    self.status_message = "Default duration tones generated".to_string();
+                }
+                Err(e) => {
+                    log::error!("Failed to generate duration tones: {}", e);
+                    self.status_message = format!("Failed to generate duration tones: {}", e);
                 }
             }
-        } else {
-            log::debug!("No clips file found at {}", clips_path.display());
-            Ok(())
-        }
-    }
-
-    fn set_target_duration_and_save(&mut self, clip_index: usize, duration: crate::core::ClipDuration) {
-        if let Some(clip) = self.clips.get_mut(clip_index) {
-            clip.set_target_duration(duration);
-            if let Err(e) = self.save_clips() {
-                log::error!("Failed to save clips after setting target duration: {}", e);
-            }
         }
     }
 
@@ -2133,8 +6928,20 @@ impl ClipHelperApp {
                 
                 ui.checkbox(&mut self.config.audio_confirmation.duration_confirmation_enabled, "Play duration-specific sounds when clips are marked");
                 
+                if self.config.audio_confirmation.duration_confirmation_enabled {
+                    self.show_duration_sound_settings(ui);
+                }
+                
                 ui.checkbox(&mut self.config.audio_confirmation.unmatched_sound_enabled, "Play sound when hotkey pressed but no clips to match");
                 
+                ui.horizontal(|ui| {
+                    ui.label("Skip repeated sounds within:");
+                    ui.add(egui::DragValue::new(&mut self.config.audio_confirmation.sound_debounce_ms)
+                        .range(0..=5000)
+                        .speed(10.0)
+                        .suffix(" ms"));
+                }).response.on_hover_text("When many clips arrive at once, each kind of sound plays once per this interval. Different duration sounds are counted separately. 0 plays every sound.");
+                
                 ui.add_space(10.0);
                 
                 // File browser preference
@@ -2206,6 +7013,7 @@ impl ClipHelperApp {
                             } else {
                                 // Use built-in file browser - start in current sound file's directory
                                 self.show_sound_file_browser = true;
+                                self.sound_file_browser_duration = None;
                                 // Set starting path for browser
                                 if let Some(ref current_path) = self.config.audio_confirmation.sound_file_path {
                                     if let Some(parent) = current_path.parent() {
@@ -2272,18 +7080,55 @@ impl ClipHelperApp {
                             });
                         
                         if ui.button("Refresh").clicked() {
-                            if let Some(ref mut audio_confirmation) = self.audio_confirmation {
-                                if let Err(e) = audio_confirmation.refresh_devices() {
-                                    log::error!("Failed to refresh audio devices: {}", e);
-                                    self.status_message = format!("Failed to refresh audio devices: {}", e);
-                                } else {
+                            let selected_device = self.config.audio_confirmation.output_device_name.clone();
+                            let refreshed = self.audio_confirmation.as_mut()
+                                .map(|audio_confirmation| audio_confirmation.refresh_devices(selected_device.as_deref()));
+                            match refreshed {
+                                Some(Ok(true)) => {
+                                    // The selected device was unplugged, fall back to the default
+                                    self.config.audio_confirmation.output_device_name = None;
+                                    self.status_message = format!("{} is gone, switched to the default device",
+                                        selected_device.unwrap_or_default());
+                                }
+                                Some(Ok(false)) => {
                                     log::info!("Audio devices refreshed successfully");
                                     self.status_message = "Audio devices refreshed".to_string();
                                 }
+                                Some(Err(e)) => {
+                                    log::error!("Failed to refresh audio devices: {}", e);
+                                    self.status_message = format!("Failed to refresh audio devices: {}", e);
+                                }
+                                None => {}
                             }
                         }
                     });
                     
+                    ui.horizontal(|ui| {
+                        ui.label("Playback device:");
+                        
+                        let current_device = self.config.playback_output_device.as_deref().unwrap_or("(Default)");
+                        egui::ComboBox::from_id_source("playback_device_combo")
+                            .selected_text(current_device)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.config.playback_output_device, None, "(Default)");
+                                if let Some(ref audio_confirmation) = self.audio_confirmation {
+                                    for device in audio_confirmation.get_available_devices() {
+                                        let display_name = if device.is_default {
+                                            format!("{} (Default)", device.name)
+                                        } else {
+                                            device.name.clone()
+                                        };
+                                        ui.selectable_value(&mut self.config.playback_output_device, Some(device.name.clone()), display_name);
+                                    }
+                                }
+                            });
+                        
+                        if ui.button("Apply").on_hover_text("Play the preview on this device now").clicked() {
+                            self.apply_output_device();
+                        }
+                    });
+                    ui.small("Where clip previews play. If the device is unplugged, playback falls back to the default device.");
+                    
                     ui.add_space(10.0);
                     
                     // Test button
@@ -2303,6 +7148,542 @@ impl ClipHelperApp {
                     }
                 }
                 
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Replay File Names");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Timestamp pattern:");
+                    let mut changed = ui.text_edit_singleline(&mut self.config.filename_timestamp_pattern)
+                        .on_hover_text("How OBS writes the date and time into replay names (Settings > Advanced > Recording > Filename Formatting)")
+                        .changed();
+                    if ui.small_button("Reset").clicked() {
+                        self.config.filename_timestamp_pattern = crate::core::DEFAULT_TIMESTAMP_PATTERN.to_string();
+                        changed = true;
+                    }
+                    if changed {
+                        self.timestamp_pattern_test = None;
+                        if let (Ok(pattern), Some(monitor)) = (
+                            crate::core::TimestampPattern::parse(&self.config.filename_timestamp_pattern),
+                            self.file_monitor.as_ref(),
+                        ) {
+                            monitor.set_timestamp_pattern(pattern);
+                        }
+                    }
+                });
+                ui.small("Specifiers: %CCYY %YY %MM %DD %hh %mm %ss. Names that don't match are dated by their modification time.");
+                if let Err(e) = crate::core::TimestampPattern::parse(&self.config.filename_timestamp_pattern) {
+                    ui.colored_label(egui::Color32::RED, format!("{} - OBS's default pattern will be used", e));
+                }
+                ui.horizontal(|ui| {
+                    ui.label("Sample file name:");
+                    ui.add(egui::TextEdit::singleline(&mut self.timestamp_pattern_sample)
+                        .hint_text("Replay 2025-08-19 15-42-03.mkv"));
+                    if ui.button("Test").clicked() {
+                        let sample = if self.timestamp_pattern_sample.trim().is_empty() {
+                            "Replay 2025-08-19 15-42-03.mkv"
+                        } else {
+                            self.timestamp_pattern_sample.trim()
+                        };
+                        self.timestamp_pattern_test = Some(match self.config.timestamp_pattern().find_in(sample) {
+                            Some(timestamp) => Ok(timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string()),
+                            None => Err(format!("No timestamp found in \"{}\"", sample)),
+                        });
+                    }
+                });
+                match &self.timestamp_pattern_test {
+                    Some(Ok(timestamp)) => {
+                        ui.colored_label(egui::Color32::GREEN, format!("Parsed as {}", timestamp));
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                    None => {}
+                }
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Output Naming");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Filename template:");
+                    ui.text_edit_singleline(&mut self.config.filename_template);
+                    if ui.small_button("Reset").clicked() {
+                        self.config.filename_template = crate::core::naming::DEFAULT_FILENAME_TEMPLATE.to_string();
+                    }
+                });
+                ui.small(format!("Placeholders: {}", crate::core::naming::TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{}}}", p))
+                    .collect::<Vec<_>>()
+                    .join(" ")));
+                match crate::core::naming::validate_template(&self.config.filename_template) {
+                    Ok(()) => {
+                        if let Some(index) = self.selected_clip_index.filter(|&i| i < self.clips.len()) {
+                            ui.small(format!("Example: {}.mkv", self.output_name_for(index)));
+                        }
+                    }
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, format!("{} - the default template will be used", e));
+                    }
+                }
+                
+                ui.checkbox(&mut self.config.copy_path_as_file_url, "Copy export paths as file:// URLs")
+                    .on_hover_text("For pasting into chat apps and browsers; off copies the plain path");
+                ui.checkbox(&mut self.config.auto_advance_after_export, "Select the next clip after an export finishes")
+                    .on_hover_text("Waits a few seconds first; the status bar has a button to cancel");
+                ui.checkbox(&mut self.config.auto_export_on_match, "Export clips matched to a duration hotkey automatically")
+                    .on_hover_text("Trims to the hotkey's duration with the default preset once OBS has finished writing the replay, \
+                        then plays the confirmation sound");
+                ui.checkbox(&mut self.config.select_session_export, "Select a session's merged clip after exporting it as one file");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Run after export:");
+                    ui.add(egui::TextEdit::singleline(&mut self.config.post_export_command)
+                        .hint_text("e.g. rclone copy {path} nas:Clips"));
+                });
+                if !self.config.post_export_command.trim().is_empty() {
+                    if let Err(e) = crate::core::post_export::build_command_args(&self.config.post_export_command, std::path::Path::new("")) {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                }
+                ui.small("{path} is replaced by the exported file. The command is split into arguments like a command line \
+                    but not run through a shell, so pipes and && need an explicit `sh -c` or `cmd /C`.");
+                
+                ui.add_space(10.0);
+                ui.checkbox(&mut self.config.export_preset.write_sidecar, "Write a .json metadata file next to each export")
+                    .on_hover_text("Stores the trim range, name and audio tracks so they can be restored if clips.json is lost");
+                ui.checkbox(&mut self.config.export_preset.export_audio_tracks_separately, "Also extract each enabled audio track to a WAV")
+                    .on_hover_text("Writes <output>_track<index>.wav (24-bit PCM) after the video export finishes");
+                
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.config.export_preset.two_pass, "Two-pass encode to a target size:")
+                        .on_hover_text("Re-encodes instead of copying the video, so exports are slower. \
+                            Hardware encoders use a single pass.");
+                    let mut target_size = self.config.export_preset.target_size_mb.unwrap_or(DEFAULT_TARGET_SIZE_MB);
+                    if ui.add_enabled(self.config.export_preset.two_pass, egui::DragValue::new(&mut target_size)
+                        .range(1.0..=4096.0)
+                        .speed(0.5)
+                        .suffix(" MB")).changed()
+                        || (self.config.export_preset.two_pass && self.config.export_preset.target_size_mb.is_none()) {
+                        self.config.export_preset.target_size_mb = Some(target_size);
+                    }
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label("Video encoder:");
+                    let detected = crate::video::detected_encoders();
+                    egui::ComboBox::from_id_source("video_encoder")
+                        .selected_text(self.config.export_preset.video_encoder.label())
+                        .show_ui(ui, |ui| {
+                            for encoder in crate::video::VideoEncoder::ALL {
+                                let available = encoder == crate::video::VideoEncoder::Libx264
+                                    || detected.is_some_and(|encoders| encoders.contains(&encoder));
+                                ui.add_enabled_ui(available, |ui| {
+                                    ui.selectable_value(&mut self.config.export_preset.video_encoder, encoder, encoder.label());
+                                });
+                            }
+                        });
+                    match detected {
+                        None => {
+                            ui.spinner();
+                            ui.small("Detecting hardware encoders…");
+                        }
+                        Some(encoders) if !encoders.contains(&self.config.export_preset.video_encoder)
+                            && self.config.export_preset.video_encoder != crate::video::VideoEncoder::Libx264 => {
+                            ui.colored_label(egui::Color32::YELLOW, "⚠ Not available here, libx264 will be used");
+                        }
+                        Some(_) => {}
+                    }
+                });
+                ui.small("Used for target-size exports and for merges that have to re-encode.");
+                
+                ui.add_space(10.0);
+                ui.label("Extra FFmpeg arguments (one per line):");
+                // Split on every newline rather than lines() so a freshly typed empty line survives the round trip
+                let mut extra_args = self.config.export_preset.extra_ffmpeg_args.join("\n");
+                if ui.add(egui::TextEdit::multiline(&mut extra_args)
+                    .desired_rows(3)
+                    .code_editor()
+                    .hint_text("-movflags\n+faststart")).changed() {
+                    self.config.export_preset.extra_ffmpeg_args = extra_args.split('\n').map(str::to_string).collect();
+                }
+                ui.colored_label(egui::Color32::YELLOW, "⚠ Misconfigured arguments may cause export failure");
+                for warning in crate::video::VideoProcessor::validate_extra_args(&self.config.export_preset.ffmpeg_args()) {
+                    ui.colored_label(egui::Color32::RED, warning);
+                }
+                ui.small("Added to video exports after Clip Helper's own arguments, right before the output file.");
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Webhooks");
+                self.show_webhook_settings(ui);
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("GIF / WebP Export");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Frame rate:");
+                    ui.add(egui::DragValue::new(&mut self.config.export_preset.gif_fps).range(1..=60).suffix(" fps"));
+                    ui.label("Width:");
+                    ui.add(egui::DragValue::new(&mut self.config.export_preset.gif_width).range(64..=1920).speed(8.0).suffix(" px"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("WebP quality:");
+                    ui.add(egui::Slider::new(&mut self.config.export_preset.webp_quality, 0..=100));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Max trim length:");
+                    ui.add(egui::DragValue::new(&mut self.config.max_animated_duration_secs).range(1.0..=60.0).suffix(" s"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Screenshot folder:");
+                    let mut screenshot_directory = self.config.screenshot_directory.display().to_string();
+                    if ui.text_edit_singleline(&mut screenshot_directory).changed() {
+                        self.config.screenshot_directory = PathBuf::from(screenshot_directory);
+                    }
+                    if ui.small_button("Reset").clicked() {
+                        self.config.screenshot_directory = self.config.trimmed_directory.join("screenshots");
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Screenshot format:");
+                    for format in crate::core::config::ScreenshotFormat::ALL {
+                        ui.radio_value(&mut self.config.screenshot_format, format, format.label());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("GIF folder:");
+                    let mut gif_directory = self.config.gif_directory.display().to_string();
+                    if ui.text_edit_singleline(&mut gif_directory).changed() {
+                        self.config.gif_directory = PathBuf::from(gif_directory);
+                    }
+                    if ui.small_button("Reset").clicked() {
+                        self.config.gif_directory = self.config.trimmed_directory.join("gifs");
+                    }
+                });
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Clip Matching");
+                
+                // Applies immediately - retries read these values every frame
+                ui.horizontal(|ui| {
+                    ui.label("Retry interval:");
+                    ui.add(egui::DragValue::new(&mut self.config.clip_request_retry.retry_interval_ms)
+                        .range(crate::core::ClipRequestRetryConfig::MIN_RETRY_INTERVAL_MS..=10_000)
+                        .speed(50.0)
+                        .suffix(" ms"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Give up after:");
+                    ui.add(egui::DragValue::new(&mut self.config.clip_request_retry.timeout_secs)
+                        .range(1..=600)
+                        .suffix(" s"));
+                });
+                ui.small("Increase these if OBS takes a while to save replays and hotkey presses expire before the file appears.");
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Trim Padding");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Pre-roll:");
+                    ui.add(egui::DragValue::new(&mut self.config.trim_padding.pre_roll)
+                        .range(0.0..=30.0)
+                        .speed(0.1)
+                        .suffix(" s"));
+                });
+                ui.small("Extra footage kept before duration-based trims. Trims you adjusted by hand are left alone.");
+                ui.checkbox(&mut self.config.skip_leading_black, "Skip black frames at the start of new clips")
+                    .on_hover_text(format!(
+                        "Only near-black frames within the first {:.0}s count, so dark scenes aren't cut",
+                        crate::video::LEADING_BLACK_MAX_SECONDS
+                    ));
+                
+                ui.add_space(20.0);
+                ui.separator();
+                let hotkeys_heading = ui.heading("Hotkeys");
+                if std::mem::take(&mut self.scroll_settings_to_hotkeys) {
+                    hotkeys_heading.scroll_to_me(Some(egui::Align::TOP));
+                }
+                if let Some(warning) = crate::hotkeys::platform_hotkey_warning() {
+                    ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", warning));
+                }
+                
+                egui::Grid::new("hotkey_bindings").num_columns(4).show(ui, |ui| {
+                    ui.strong("Clip");
+                    ui.strong("Modifiers");
+                    ui.strong("Key");
+                    ui.strong("Status");
+                    ui.end_row();
+                    
+                    for (config_key, _, description) in crate::hotkeys::HOTKEY_BINDINGS {
+                        ui.label(description);
+                        let binding = self.config.hotkeys.entry(config_key.to_string())
+                            .or_insert_with(|| AppConfig::default().hotkeys.remove(config_key).unwrap_or_default());
+                        ui.add(egui::TextEdit::singleline(&mut binding.modifiers).desired_width(90.0).hint_text("Ctrl+Alt"));
+                        ui.add(egui::TextEdit::singleline(&mut binding.key).desired_width(90.0).hint_text("Numpad1"));
+                        
+                        if let Err(e) = binding.to_global_hotkey() {
+                            ui.colored_label(egui::Color32::RED, e.to_string());
+                        } else {
+                            match self.hotkey_registrations.iter().find(|r| r.description == description).map(|r| &r.result) {
+                                Some(Ok(())) => ui.colored_label(egui::Color32::GREEN, "✔ Registered"),
+                                Some(Err(e)) => ui.colored_label(egui::Color32::RED, format!("⚠ {}", e)),
+                                None => ui.weak("Not registered"),
+                            };
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Register now").on_hover_text("Register the bindings above without saving, e.g. after another app released a combination").clicked() {
+                        self.reregister_hotkeys();
+                    }
+                    ui.small("Saving also re-registers the hotkeys. Modifiers: Ctrl, Alt, Shift, Super, joined with +.");
+                });
+                let conflicts = HotkeyManager::validate_bindings(&self.config.hotkeys);
+                if !conflicts.is_empty() {
+                    ui.group(|ui| {
+                        ui.colored_label(egui::Color32::RED, "⚠ Hotkey conflicts - only the first binding of each is registered:");
+                        for conflict in &conflicts {
+                            ui.label(format!("• {}", conflict));
+                        }
+                        ui.small("Give each clip length its own combination.");
+                    });
+                }
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Skip Buttons");
+                
+                ui.horizontal(|ui| {
+                    let mut removed = None;
+                    for (index, amount) in self.config.jump_amounts_secs.iter_mut().enumerate() {
+                        ui.add(egui::DragValue::new(amount)
+                            .range(0.1..=3600.0)
+                            .speed(0.1)
+                            .suffix(" s"));
+                        if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                            removed = Some(index);
+                        }
+                    }
+                    if let Some(index) = removed {
+                        self.config.jump_amounts_secs.remove(index);
+                    }
+                    
+                    let can_add = self.config.jump_amounts_secs.len() < crate::core::MAX_JUMP_AMOUNTS;
+                    if ui.add_enabled(can_add, egui::Button::new("➕ Add")).clicked() {
+                        let next = self.config.jump_amounts_secs.iter().copied().fold(0.0, f64::max) * 2.0;
+                        self.config.jump_amounts_secs.push(if next > 0.0 { next } else { 1.0 });
+                    }
+                    if ui.button("Reset").clicked() {
+                        self.config.jump_amounts_secs = crate::core::DEFAULT_JUMP_AMOUNTS_SECS.to_vec();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Mouse wheel over the preview seeks:");
+                    ui.add(egui::DragValue::new(&mut self.config.scroll_seek_step_seconds)
+                        .range(0.01..=60.0)
+                        .speed(0.05)
+                        .suffix(" s per notch"));
+                });
+                ui.small(format!(
+                    "Each amount gets a backward and a forward button, up to {}. With none, {}s are used.",
+                    crate::core::MAX_JUMP_AMOUNTS,
+                    crate::core::DEFAULT_JUMP_AMOUNTS_SECS.map(|amount| amount.to_string()).join("/")
+                ));
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Trim Steps");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Small:");
+                    ui.add(egui::DragValue::new(&mut self.config.trim_step_small_seconds)
+                        .range(0.01..=600.0)
+                        .speed(0.05)
+                        .suffix(" s"));
+                    ui.label("Large:");
+                    ui.add(egui::DragValue::new(&mut self.config.trim_step_large_seconds)
+                        .range(0.01..=600.0)
+                        .speed(0.1)
+                        .suffix(" s"));
+                    ui.label("Fine (Shift):");
+                    ui.add(egui::DragValue::new(&mut self.config.trim_step_fine_seconds)
+                        .range(0.001..=60.0)
+                        .speed(0.01)
+                        .suffix(" s"));
+                });
+                ui.small("Amounts the Start/End trim buttons move by. Hold Shift to use the fine step instead of the small one.");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Minimum trim length:");
+                    ui.add(egui::DragValue::new(&mut self.config.min_trim_gap_seconds)
+                        .range(0.01..=60.0)
+                        .speed(0.05)
+                        .suffix(" s"));
+                });
+                ui.small("Trim points can't be set closer together than this, from the buttons, the timeline or the playhead.");
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Sessions");
+                
+                ui.horizontal(|ui| {
+                    ui.label("New session after a gap of:");
+                    ui.add(egui::DragValue::new(&mut self.config.session_gap_minutes)
+                        .range(1..=24 * 60)
+                        .suffix(" min"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(format!("Manual session breaks: {}", self.config.manual_session_breaks.len()));
+                    if ui.add_enabled(!self.config.manual_session_breaks.is_empty(), egui::Button::new("Clear")).clicked() {
+                        self.config.manual_session_breaks.clear();
+                    }
+                });
+                ui.small("Right-click a clip to split or merge sessions by hand.");
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("FFmpeg Processes");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Max concurrent jobs:");
+                    if ui.add(egui::DragValue::new(&mut self.config.ffmpeg_max_concurrent).range(1..=16)).changed() {
+                        self.ffmpeg_pool.set_max_concurrent(self.config.ffmpeg_max_concurrent);
+                    }
+                });
+                ui.small("Limits thumbnail, video info, waveform and export jobs running at the same time.");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Thumbnail cache:");
+                    if ui.add(egui::Slider::new(&mut self.config.thumbnail_cache_mb, 64..=4096)
+                        .logarithmic(true)
+                        .suffix(" MB")).changed() {
+                        if let Some(cache) = &self.smart_thumbnail_cache {
+                            cache.set_max_cache_bytes(self.config.thumbnail_cache_mb * 1024 * 1024);
+                        }
+                    }
+                });
+                ui.small("Preview thumbnails used least recently are dropped once they take up more memory than this.");
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("HDR");
+                
+                let mut tonemap_changed = ui.checkbox(&mut self.config.hdr_tonemap_previews, "Tonemap HDR previews and thumbnails").changed();
+                ui.add_enabled_ui(self.config.hdr_tonemap_previews, |ui| {
+                    tonemap_changed |= ui.checkbox(&mut self.config.hdr_tonemap_playback, "Also tonemap during playback").changed();
+                });
+                if tonemap_changed {
+                    crate::video::set_hdr_tonemapping(
+                        self.config.hdr_tonemap_previews,
+                        self.config.hdr_tonemap_previews && self.config.hdr_tonemap_playback,
+                    );
+                }
+                if self.config.hdr_tonemap_previews && self.config.hdr_tonemap_playback {
+                    ui.colored_label(egui::Color32::YELLOW, "⚠ Tonemapping is expensive: playback may start later and drop frames on slower CPUs.");
+                }
+                if !crate::video::VideoProcessor::zscale_available() {
+                    ui.colored_label(egui::Color32::YELLOW, "⚠ This FFmpeg build has no zscale filter, HDR replays are shown untouched.");
+                }
+                ui.small("HDR (PQ/HLG) replays look washed out without tonemapping. Turn it off to see the raw frames. Applies to newly extracted frames.");
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("After Exporting");
+                
+                ui.horizontal(|ui| {
+                    ui.label("The original replay:");
+                    egui::ComboBox::from_id_source("source_file_policy")
+                        .selected_text(self.config.source_file_policy.label())
+                        .show_ui(ui, |ui| {
+                            for policy in crate::core::SourceFilePolicy::ALL {
+                                ui.selectable_value(&mut self.config.source_file_policy, policy, policy.label());
+                            }
+                        });
+                });
+                if self.config.source_file_policy == crate::core::SourceFilePolicy::MoveToArchive {
+                    ui.horizontal(|ui| {
+                        ui.label("Archive folder:");
+                        let mut archive_directory = self.config.archive_directory.display().to_string();
+                        if ui.text_edit_singleline(&mut archive_directory).changed() {
+                            self.config.archive_directory = PathBuf::from(archive_directory);
+                        }
+                        if ui.small_button("Reset").clicked() {
+                            self.config.archive_directory = self.config.obs_replay_directory.join("archive");
+                        }
+                    });
+                }
+                ui.small("Only after a video export finished and passed verification. Failed exports always keep the replay.");
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Deleting Clips");
+                
+                ui.checkbox(&mut self.config.confirm_before_delete, "Ask before deleting a clip");
+                ui.checkbox(&mut self.config.delete_to_recycle_bin, "Move deleted clips to the recycle bin")
+                    .on_hover_text("Instead of the deleted folder. Falls back to the deleted folder where the recycle bin \
+                        isn't available, e.g. on network drives.");
+                
+                ui.horizontal(|ui| {
+                    let mut limit_enabled = self.config.max_clips_to_keep.is_some();
+                    if ui.checkbox(&mut limit_enabled, "Keep at most").changed() {
+                        self.config.max_clips_to_keep = limit_enabled.then_some(100);
+                    }
+                    if let Some(ref mut max_clips) = self.config.max_clips_to_keep {
+                        ui.add(egui::DragValue::new(max_clips).range(1..=100_000));
+                    }
+                    ui.label("clips");
+                });
+                ui.small(format!(
+                    "The oldest clips beyond the limit are moved to the deleted folder when a new one arrives. \
+                    Pinned, imported and exported clips, clips with notes and clips tagged \"{}\" \
+                    count towards the limit but are never removed.",
+                    crate::core::RETENTION_KEEP_TAG
+                ));
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Background");
+                
+                ui.add_enabled_ui(crate::gui::tray::TRAY_SUPPORTED, |ui| {
+                    ui.checkbox(&mut self.config.minimize_to_tray, "Minimize to the system tray when the window is closed")
+                        .on_hover_text("Keeps watching for replays and hotkeys. Quit from the tray icon's menu.");
+                });
+                if !crate::gui::tray::TRAY_SUPPORTED {
+                    ui.small("The system tray is only supported on Windows and macOS.");
+                }
+                
+                ui.add_space(20.0);
+                ui.separator();
+                ui.heading("Logging");
+                
+                ui.horizontal(|ui| {
+                    ui.label("Log level:");
+                    let previous_level = self.config.log_level;
+                    egui::ComboBox::from_id_source("log_level")
+                        .selected_text(self.config.log_level.as_str().to_lowercase())
+                        .show_ui(ui, |ui| {
+                            for level in crate::core::logging::LOG_LEVELS {
+                                ui.selectable_value(&mut self.config.log_level, level, level.as_str().to_lowercase());
+                            }
+                        });
+                    if self.config.log_level != previous_level {
+                        crate::core::logging::set_log_level(self.config.log_level);
+                    }
+                    
+                    if ui.button("📂 Open log file").clicked() {
+                        if let Err(e) = crate::core::shell::reveal_in_file_manager(&crate::core::logging::log_file_path()) {
+                            log::error!("{}", e);
+                            self.status_message = e.to_string();
+                        }
+                    }
+                });
+                
                 ui.add_space(20.0);
                 ui.separator();
                 ui.add_space(10.0);
@@ -2316,6 +7697,7 @@ impl ClipHelperApp {
                         } else {
                             log::info!("Settings saved successfully");
                             self.status_message = "Settings saved".to_string();
+                            self.reregister_hotkeys();
                             close_dialog = true;
                         }
                     }
@@ -2324,6 +7706,10 @@ impl ClipHelperApp {
                         // Reload config to discard changes
                         match AppConfig::load() {
                             Ok(config) => {
+                                if config.log_level != self.config.log_level {
+                                    crate::core::logging::set_log_level(config.log_level);
+                                }
+                                crate::video::set_hdr_tonemapping(config.hdr_tonemap_previews, config.hdr_tonemap_previews && config.hdr_tonemap_playback);
                                 self.config = config;
                                 log::debug!("Settings changes discarded");
                             }
@@ -2371,5 +7757,12 @@ impl Drop for ClipHelperApp {
         if let Some(ref cache) = self.smart_thumbnail_cache {
             cache.cleanup_old_thumbnails();
         }
+        
+        // Flush a layout change that was still waiting for the debounce
+        if self.layout_dirty {
+            if let Err(e) = self.config.save() {
+                log::warn!("Failed to save window layout on exit: {}", e);
+            }
+        }
     }
 }
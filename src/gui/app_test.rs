@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::collections::HashMap;
     use tokio::sync::broadcast;
     use crate::core::AppConfig;
@@ -11,6 +11,7 @@ mod tests {
     // Test helper to create a minimal app instance for testing
     fn create_test_app() -> ClipHelperApp {
         let (_, hotkey_receiver) = broadcast::channel(10);
+        let ffmpeg_pool = std::sync::Arc::new(crate::video::FfmpegProcessPool::default());
         
         ClipHelperApp {
             config: AppConfig::default(),
@@ -18,27 +19,110 @@ mod tests {
             selected_clip_index: None,
             video_preview: None,
             waveforms: HashMap::new(),
+            waveform_loads: Vec::new(),
+            video_imports: Vec::new(),
             hotkey_receiver,
             file_monitor: None,
             file_receiver: None,
             new_clip_name: String::new(),
+            renaming_clip_index: None,
+            rename_buffer: String::new(),
+            timestamp_pattern_sample: String::new(),
+            timestamp_pattern_test: None,
+            tags_text: String::new(),
+            batch_tags_text: String::new(),
+            clip_search: String::new(),
+            selected_clip_indices: std::collections::HashSet::new(),
+            clip_merge: None,
+            session_export: None,
+            export_directory_text: String::new(),
+            animated_export_format: crate::video::ExportFormat::Gif,
             pending_clip_requests: Vec::new(),
             duration_requests: Vec::new(),
             watched_directory: None,
             show_directory_dialog: false,
             show_settings_dialog: false,
             status_message: String::new(),
+            last_export_path: None,
             directory_browser_path: PathBuf::from("C:\\"),
             file_browser_path: PathBuf::from("C:\\"),
             show_sound_file_browser: false,
+            sound_file_browser_duration: None,
             timeline_widget: TimelineWidget::new(),
             show_drives_view: false,
             last_video_info_check: std::time::Instant::now(),
+            last_corrupted_clip_retry: std::time::Instant::now(),
             initial_scan_completed: false,
             audio_confirmation: None,
             last_thumbnail_processing: std::time::Instant::now(),
+            ffmpeg_pool: ffmpeg_pool.clone(),
             smart_thumbnail_cache: None,
             media_controller: None,
+            playback_availability: crate::video::PlaybackAvailability::ALL,
+            availability_debouncer: crate::video::AvailabilityDebouncer::new(),
+            video_info_manager: crate::video::VideoInfoManager::new(ffmpeg_pool.clone()),
+            hover_thumbnail_manager: crate::video::HoverThumbnailManager::new(ffmpeg_pool.clone()),
+            current_hover_target: None,
+            export_queue: crate::video::ExportQueue::new(ffmpeg_pool.clone()),
+            pending_export_specs: Vec::new(),
+            show_resume_exports_dialog: false,
+            duplicate_of: None,
+            pending_duplicate_checks: std::collections::HashSet::new(),
+            duplicate_checks: Vec::new(),
+            failed_export: None,
+            layout_dirty: false,
+            last_layout_save: std::time::Instant::now(),
+            keyboard_focus_index: None,
+            clip_list_has_focus: true,
+            scroll_to_keyboard_focus: false,
+            output_names: None,
+            statistics_cache: None,
+            statistics_scan: None,
+            directory_sizes: None,
+            directory_size_scan: None,
+            log_buffer: crate::core::logging::LogBuffer::with_capacity(crate::core::logging::LOG_PANEL_CAPACITY),
+            show_log_panel: false,
+            export_history: Default::default(),
+            show_recent_exports: false,
+            log_panel_level: log::LevelFilter::Info,
+            ffprobe_cache: crate::video::FfprobeCache::default(),
+            status_toast: None,
+            free_space: None,
+            pending_auto_advance: None,
+            pending_auto_exports: std::collections::HashSet::new(),
+            auto_export_outputs: std::collections::HashSet::new(),
+            post_export_hooks: Vec::new(),
+            webhooks: crate::core::webhooks::WebhookSender::new(),
+            webhook_test: None,
+            webhook_test_result: None,
+            hotkey_conflicts: Vec::new(),
+            hotkey_manager: None,
+            hotkey_registrations: Vec::new(),
+            error_details: None,
+            show_error_details_dialog: false,
+            stale_temp_exports: Vec::new(),
+            near_duplicate_groups: Vec::new(),
+            last_near_duplicate_check: None,
+            pending_duplicate_deletion: None,
+            pending_drop_copy: None,
+            pending_session_deletion: None,
+            show_recycle_bin: false,
+            show_keyboard_shortcuts: false,
+            scroll_settings_to_hotkeys: false,
+            recycle_bin_files: Vec::new(),
+            last_recycle_bin_scan: None,
+            pending_permanent_deletion: None,
+            pending_clip_deletion: None,
+            external_player_launches: Vec::new(),
+            loudness_measurements: Vec::new(),
+            scene_detections: Vec::new(),
+            black_detections: Vec::new(),
+            motion_detections: Vec::new(),
+            audio_track_exports: Vec::new(),
+            audio_only_preview: false,
+            tray: None,
+            hidden_in_tray: false,
+            quit_requested: false,
         }
     }
 
@@ -143,4 +227,320 @@ mod tests {
         }
         assert_eq!(app.file_browser_path, initial_path);
     }
+
+    #[test]
+    fn test_keyboard_focus_stepping() {
+        let order = vec![4, 2, 7, 0];
+
+        // No focus yet: down starts at the top, up starts at the bottom
+        assert_eq!(ClipHelperApp::step_focus_index(&order, None, 1), Some(4));
+        assert_eq!(ClipHelperApp::step_focus_index(&order, None, -1), Some(0));
+
+        assert_eq!(ClipHelperApp::step_focus_index(&order, Some(2), 1), Some(7));
+        assert_eq!(ClipHelperApp::step_focus_index(&order, Some(2), -1), Some(4));
+
+        // Page jumps clamp to the ends of the list
+        assert_eq!(ClipHelperApp::step_focus_index(&order, Some(2), 10), Some(0));
+        assert_eq!(ClipHelperApp::step_focus_index(&order, Some(7), -10), Some(4));
+
+        assert_eq!(ClipHelperApp::step_focus_index(&[], Some(1), 1), None);
+    }
+
+    #[test]
+    fn test_retention_candidates_skip_protected_clips() {
+        let mut app = create_test_app();
+        for name in [
+            "Replay 2025-08-17 20-00-00.mkv",
+            "Replay 2025-08-17 20-10-00.mkv",
+            "Replay 2025-08-17 20-20-00.mkv",
+            "Replay 2025-08-17 20-30-00.mkv",
+            "Replay 2025-08-17 20-40-00.mkv",
+        ] {
+            app.clips.push(crate::core::Clip::new_without_target(PathBuf::from(name)).unwrap());
+        }
+        assert!(app.retention_candidates(None).is_empty());
+
+        app.config.max_clips_to_keep = Some(1);
+        app.clips[0].is_trimmed = true;
+        app.clips[1].set_tags("Keep");
+        app.clips[2].notes = "ace".to_string();
+        // Protected clips still count towards the total, they just can't be removed
+        assert_eq!(app.retention_candidates(None), vec![3, 4]);
+        app.config.max_clips_to_keep = Some(4);
+        assert_eq!(app.retention_candidates(None), vec![3]);
+        app.config.max_clips_to_keep = Some(5);
+        assert!(app.retention_candidates(None).is_empty());
+        app.clips[4].is_deleted = true;
+        app.config.max_clips_to_keep = Some(3);
+        assert_eq!(app.retention_candidates(None), vec![3]);
+        app.clips[4].is_deleted = false;
+
+        app.config.max_clips_to_keep = Some(0);
+        assert_eq!(app.retention_candidates(None), vec![3, 4]);
+        // The selected clip and the replay that just arrived are never picked
+        app.selected_clip_index = Some(3);
+        assert_eq!(app.retention_candidates(None), vec![4]);
+        assert!(app.retention_candidates(Some(Path::new("Replay 2025-08-17 20-40-00.mkv"))).is_empty());
+        app.selected_clip_index = None;
+
+        // Neither are clips OBS is still writing, pending auto-exports or imported videos
+        app.clips[3].write_state = crate::core::VideoWriteState::StillWriting { measured_duration: 12.0 };
+        app.pending_auto_exports.insert(PathBuf::from("Replay 2025-08-17 20-40-00.mkv"));
+        assert!(app.retention_candidates(None).is_empty());
+        app.clips[3].write_state = crate::core::VideoWriteState::Complete;
+        app.clips[3].imported = true;
+        assert!(app.retention_candidates(None).is_empty());
+    }
+
+    #[test]
+    fn test_saved_clip_settings_survive_restart() {
+        let mut saved = crate::core::Clip::new_without_target(PathBuf::from("Replay 2025-08-17 20-00-00.mkv")).unwrap();
+        saved.color = Some([200, 60, 60]);
+        saved.pinned = true;
+        saved.set_tags("ace, keep");
+        let json = serde_json::to_string(&vec![saved]).unwrap();
+        let saved_clips: Vec<crate::core::Clip> = serde_json::from_str(&json).unwrap();
+
+        let mut current = crate::core::Clip::new_without_target(PathBuf::from("Replay 2025-08-17 20-00-00.mkv")).unwrap();
+        ClipHelperApp::apply_saved_clip(&mut current, &saved_clips[0]);
+        assert_eq!(current.color, Some([200, 60, 60]));
+        assert!(current.pinned);
+        assert_eq!(current.tags, saved_clips[0].tags);
+    }
+
+    #[test]
+    fn test_marker_segment_filenames_are_unique() {
+        let names: Vec<String> = ["Part 1", "Part 2", "Part 2"].iter()
+            .enumerate()
+            .map(|(index, label)| ClipHelperApp::marker_segment_filename("Replay", index, label))
+            .collect();
+        assert_eq!(names, vec!["Replay - 01 Part 1.mkv", "Replay - 02 Part 2.mkv", "Replay - 03 Part 2.mkv"]);
+        assert_eq!(ClipHelperApp::marker_segment_filename("Replay", 0, " Ace/Clutch? "), "Replay - 01 Ace_Clutch_.mkv");
+    }
+
+    #[test]
+    fn test_manual_session_breaks() {
+        let mut app = create_test_app();
+        for name in ["Replay 2025-08-17 20-00-00.mkv", "Replay 2025-08-17 20-10-00.mkv", "Replay 2025-08-17 20-20-00.mkv"] {
+            app.clips.push(crate::core::Clip::new_without_target(PathBuf::from(name)).unwrap());
+        }
+
+        let sessions = app.group_clips_into_sessions();
+        assert_eq!(sessions.len(), 1);
+        assert!(!sessions[0].manual_break);
+
+        app.config.manual_session_breaks.push(PathBuf::from("Replay 2025-08-17 20-10-00.mkv"));
+        let sessions = app.group_clips_into_sessions();
+        assert_eq!(sessions.len(), 2);
+        // Newest first
+        assert_eq!(sessions[0].clips, vec![1, 2]);
+        assert!(sessions[0].manual_break);
+        assert_eq!(sessions[1].clips, vec![0]);
+        assert!(!sessions[1].manual_break);
+
+        // An anchor on the very first clip doesn't create an empty session
+        app.config.manual_session_breaks = vec![PathBuf::from("Replay 2025-08-17 20-00-00.mkv")];
+        assert_eq!(app.group_clips_into_sessions().len(), 1);
+
+        // Shorter gap setting splits on time alone
+        app.config.manual_session_breaks.clear();
+        app.config.session_gap_minutes = 5;
+        assert_eq!(app.group_clips_into_sessions().len(), 3);
+    }
+
+    #[test]
+    fn test_export_size_estimate_includes_reencodes_and_separate_tracks() {
+        let mut app = create_test_app();
+        let mut clip = crate::core::Clip::new_without_target(PathBuf::from("Replay 2025-08-17 20-00-00.mkv")).unwrap();
+        clip.trim_end = 10.0;
+        clip.audio_tracks = (0..2).map(|index| crate::core::AudioTrack {
+            index,
+            enabled: true,
+            surround_mode: crate::core::SurroundMode::Passthrough,
+            name: format!("Track {}", index + 1),
+        }).collect();
+        app.clips.push(clip);
+
+        let mut job = app.trim_export_job(0, false);
+        job.export_audio_tracks_separately = false;
+        job.target_size_mb = Some(8.0);
+        job.video_encoder = crate::video::VideoEncoder::Libx264;
+        let target = 8 * 1024 * 1024;
+        assert_eq!(app.estimate_export_size(&job), target);
+
+        job.video_encoder = crate::video::VideoEncoder::H264Nvenc;
+        assert_eq!(app.estimate_export_size(&job), target * 3 / 2);
+
+        job.export_audio_tracks_separately = true;
+        let track_size = crate::video::AudioExportFormat::Wav.estimate_size_bytes(10.0);
+        assert_eq!(app.estimate_export_size(&job), target * 3 / 2 + 2 * track_size);
+
+        // Separate tracks are a preset setting
+        assert!(!app.trim_export_job(0, false).export_audio_tracks_separately);
+        app.config.export_preset.export_audio_tracks_separately = true;
+        assert!(app.trim_export_job(0, false).export_audio_tracks_separately);
+    }
+
+    #[test]
+    fn test_sidecars_follow_the_export_preset() {
+        let mut app = create_test_app();
+        app.clips.push(crate::core::Clip::new_without_target(PathBuf::from("Replay 2025-08-17 20-00-00.mkv")).unwrap());
+        app.clips.push(crate::core::Clip::new_without_target(PathBuf::from("Replay 2025-08-17 20-10-00.mkv")).unwrap());
+        assert!(app.trim_export_job(0, false).sidecar.is_none());
+        app.config.export_preset.write_sidecar = true;
+        assert!(app.trim_export_job(0, false).sidecar.is_some());
+
+        // Sidecars are looked for wherever clips were exported to
+        app.clips[0].export_directory_override = Some(PathBuf::from("highlights"));
+        app.clips[1].export_directory_override = Some(PathBuf::from("highlights"));
+        assert_eq!(app.sidecar_directories(), vec![app.config.trimmed_directory.clone(), PathBuf::from("highlights")]);
+    }
+
+    #[test]
+    fn test_output_name_suffixes_are_stable() {
+        let mut app = create_test_app();
+        app.config.filename_template = "{date}".to_string();
+        for name in ["Replay 2025-08-17 20-10-00.mkv", "Replay 2025-08-17 20-00-00.mkv"] {
+            app.clips.push(crate::core::Clip::new_without_target(PathBuf::from(name)).unwrap());
+        }
+        // The older recording keeps the plain name wherever it is in the list
+        assert_eq!(app.output_name_for(0), "2025-08-17 (2)");
+        assert_eq!(app.output_name_for(1), "2025-08-17");
+
+        // Deleted clips no longer claim a name, and adding a newer clip in front changes nothing
+        app.clips[1].is_deleted = true;
+        assert_eq!(app.output_name_for(0), "2025-08-17");
+        app.clips[1].is_deleted = false;
+        app.clips.insert(0, crate::core::Clip::new_without_target(PathBuf::from("Replay 2025-08-17 20-20-00.mkv")).unwrap());
+        app.refresh_output_names();
+        assert_eq!(app.output_name_for(0), "2025-08-17 (3)");
+        assert_eq!(app.output_name_for(1), "2025-08-17 (2)");
+        assert_eq!(app.output_name_for(2), "2025-08-17");
+
+        // A stale cache isn't used
+        app.clips[0].name = Some("ace".to_string());
+        app.config.filename_template = "{name}".to_string();
+        assert_eq!(app.output_name_for(0), "ace");
+    }
+
+    #[test]
+    fn test_file_url() {
+        assert_eq!(ClipHelperApp::file_url(Path::new("/home/me/Replay 1.mkv")), "file:///home/me/Replay%201.mkv");
+        assert_eq!(ClipHelperApp::file_url(Path::new("C:\\Clips\\Bob's #1.mkv")), "file:///C:/Clips/Bob%27s%20%231.mkv");
+    }
+
+    #[test]
+    fn test_relative_export_path_is_copied_absolute() {
+        let relative = Path::new("./output/trimmed/Replay 1.mkv");
+        let expected = std::env::current_dir().unwrap().join("output").join("trimmed").join("Replay 1.mkv");
+        assert_eq!(ClipHelperApp::export_path_clipboard_text(relative, false), expected.to_string_lossy());
+
+        let url = ClipHelperApp::export_path_clipboard_text(relative, true);
+        assert_eq!(url, ClipHelperApp::file_url(&expected));
+        assert!(!url.contains("/./"));
+    }
+
+    #[test]
+    fn test_range_between() {
+        let order = [4, 2, 7, 0, 5];
+        assert_eq!(ClipHelperApp::range_between(&order, 2, 0), vec![2, 7, 0]);
+        // Shift+clicking above the anchor works too
+        assert_eq!(ClipHelperApp::range_between(&order, 5, 7), vec![7, 0, 5]);
+        assert_eq!(ClipHelperApp::range_between(&order, 4, 4), vec![4]);
+        // Hidden clips only add the two ends
+        assert_eq!(ClipHelperApp::range_between(&order, 4, 9), vec![4, 9]);
+    }
+
+    #[test]
+    fn test_pinned_clips_get_their_own_group() {
+        let mut app = create_test_app();
+        for name in ["Replay 2025-08-10 20-00-00.mkv", "Replay 2025-08-17 20-00-00.mkv", "Replay 2025-08-17 20-10-00.mkv"] {
+            app.clips.push(crate::core::Clip::new_without_target(PathBuf::from(name)).unwrap());
+        }
+        app.clips[0].pinned = true;
+
+        let sessions = app.group_clips_into_sessions();
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions[0].pinned);
+        assert_eq!(sessions[0].clips, vec![0]);
+        assert_eq!(sessions[1].clips, vec![1, 2]);
+
+        // Pinned clips are never cleaned up, even when they're the oldest
+        app.config.max_clips_to_keep = Some(1);
+        assert_eq!(app.retention_candidates(None), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_find_duplicate_fingerprints_same_size_candidates() {
+        let dir = std::env::temp_dir().join(format!("clip-helper-duplicate-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let new_file = dir.join("Replay 2025-08-17 20-20-00.mkv");
+        let same = dir.join("Replay 2025-08-17 20-10-00.mkv");
+        let other = dir.join("Replay 2025-08-17 20-00-00.mkv");
+        std::fs::write(&new_file, b"replay").unwrap();
+        std::fs::write(&same, b"replay").unwrap();
+        std::fs::write(&other, b"longer replay").unwrap();
+
+        let candidates = vec![(other.clone(), None), (same.clone(), None)];
+        let result = ClipHelperApp::find_duplicate(&new_file, &candidates).unwrap();
+        assert_eq!(result.duplicate_of, Some(same.clone()));
+        // Only the same-size candidate had to be fingerprinted
+        assert_eq!(result.computed, vec![(same.clone(), result.fingerprint.clone())]);
+
+        let no_match = ClipHelperApp::find_duplicate(&new_file, &[(same, Some("other".to_string()))]).unwrap();
+        assert_eq!(no_match.duplicate_of, None);
+        assert!(no_match.computed.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_clips_ready_skips_invalid() {
+        let mut app = create_test_app();
+        let dir = std::env::temp_dir().join(format!("clip-helper-session-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("Replay 2025-08-17 20-00-00.mkv");
+        std::fs::write(&existing, b"replay").unwrap();
+
+        let mut ready = crate::core::Clip::new_without_target(existing).unwrap();
+        ready.video_length_seconds = Some(60.0);
+        ready.trim_end = 60.0;
+        let still_writing = crate::core::Clip::new_without_target(dir.join("Replay 2025-08-17 20-10-00.mkv")).unwrap();
+        let mut deleted = ready.clone();
+        deleted.is_deleted = true;
+        app.clips = vec![ready, still_writing, deleted];
+
+        assert_eq!(app.session_clips_ready(&[0, 1, 2]), (vec![0], 1));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_next_clip_in_list_follows_display_order() {
+        let mut app = create_test_app();
+        let mut clips: Vec<crate::core::Clip> = ["20-00-00", "20-05-00", "20-10-00"].iter()
+            .map(|time| {
+                let mut clip = crate::core::Clip::new_without_target(PathBuf::from(format!("Replay 2025-08-17 {}.mkv", time))).unwrap();
+                clip.video_length_seconds = Some(60.0);
+                clip
+            })
+            .collect();
+        clips[1].is_deleted = true;
+        app.clips = clips;
+
+        // One session, listed oldest first; the deleted clip is skipped
+        assert_eq!(app.next_clip_in_list(0), Some(2));
+        assert_eq!(app.next_clip_in_list(2), None);
+        assert_eq!(app.next_clip_in_list(1), None);
+    }
+
+    #[test]
+    fn test_wheel_scroll_to_seek_offset() {
+        let notch = ClipHelperApp::wheel_notches(egui::MouseWheelUnit::Line, egui::vec2(0.0, 1.0));
+        assert_eq!(ClipHelperApp::scroll_delta_to_seconds(notch, 1.0), 1.0);
+
+        // Scrolling down seeks backwards; trackpad pixels are scaled to notches
+        let pixels = ClipHelperApp::wheel_notches(egui::MouseWheelUnit::Point, egui::vec2(0.0, -25.0));
+        assert_eq!(ClipHelperApp::scroll_delta_to_seconds(pixels, 2.0), -1.0);
+    }
 }
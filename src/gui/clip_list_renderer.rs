@@ -1,19 +1,84 @@
 use egui;
-use crate::core::Clip;
-use crate::video::HoverThumbnailManager;
+use crate::core::{Clip, ClipExportState, VideoWriteState};
+use crate::core::config::ClipListView;
+use crate::video::{HoverThumbnailManager, SmartThumbnailCache};
 use std::path::PathBuf;
 
 pub struct ClipListRenderer;
 
+/// Per-item highlight and badge state, decided by the app before rendering
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClipItemState {
+    pub view: ClipListView,
+    pub is_selected: bool,
+    pub is_keyboard_focused: bool,
+    pub is_near_duplicate: bool,
+    /// The clip is a manual "Split session here" anchor
+    pub is_session_break: bool,
+    /// Hover thumbnails are currently being shown for this clip
+    pub is_hover_target: bool,
+}
+
+/// Preset row colors offered in the clip context menu
+pub const CLIP_COLOR_PALETTE: [(&str, [u8; 3]); 12] = [
+    ("Red", [220, 60, 60]),
+    ("Orange", [235, 140, 50]),
+    ("Yellow", [230, 200, 60]),
+    ("Lime", [150, 210, 70]),
+    ("Green", [60, 170, 90]),
+    ("Teal", [50, 170, 160]),
+    ("Cyan", [70, 190, 230]),
+    ("Blue", [60, 110, 220]),
+    ("Purple", [140, 90, 210]),
+    ("Pink", [220, 100, 180]),
+    ("Brown", [140, 95, 60]),
+    ("Gray", [130, 130, 130]),
+];
+/// Opacity of a clip's color over its row background
+const CLIP_COLOR_TINT_ALPHA: u8 = 77;
+/// Width of the color stripe on the row's left edge, drawn over the selection highlight
+const CLIP_COLOR_STRIPE_WIDTH: f32 = 4.0;
+
+/// Actions picked from a clip row's right-click menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipContextAction {
+    SplitSessionHere,
+    MergeWithPreviousSession,
+    TogglePin,
+    /// Tint the row with a color, or clear it with None
+    SetColor(Option<[u8; 3]>),
+}
+
+/// How an in-place rename in a clip row ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameOutcome {
+    /// Enter, or focus moved elsewhere
+    Commit,
+    Cancel,
+}
+
 impl ClipListRenderer {
-    /// Render a single clip item and return what actions need to be taken
+    /// Height of a clip's row. Hovering, clicking and the visibility check all use this
+    /// rect, so it has to match what the row's content takes up in each view.
+    pub fn row_height(ui: &egui::Ui, clip: &Clip, view: ClipListView) -> f32 {
+        let text_lines = match view {
+            ClipListView::Compact => 1.0,
+            ClipListView::Detailed if clip.export_directory_override.is_some() => 5.0,
+            ClipListView::Detailed => 4.0,
+        };
+        10.0 + ui.text_style_height(&egui::TextStyle::Body) * text_lines
+    }
+
+    /// Render a single clip item and return what actions need to be taken.
+    /// With `rename_buffer` set, the title is replaced by a text field editing it.
     pub fn render_clip_item(
         ui: &mut egui::Ui,
         clip: &Clip,
         clip_index: usize,
-        is_selected: bool,
+        state: ClipItemState,
         hover_thumbnail_manager: &mut HoverThumbnailManager,
-        current_hover_target: &Option<PathBuf>,
+        poster_cache: Option<&SmartThumbnailCache>,
+        rename_buffer: Option<&mut String>,
     ) -> ClipRenderResult {
         let mut result = ClipRenderResult::default();
         
@@ -22,8 +87,9 @@ impl ClipListRenderer {
         // Make the entire container clickable and take full width
         let container_rect = egui::Rect::from_min_size(
             ui.cursor().min,
-            egui::Vec2::new(ui.available_width(), 10.0 + ui.text_style_height(&egui::TextStyle::Body) * 3.0)
+            egui::Vec2::new(ui.available_width(), Self::row_height(ui, clip, state.view))
         );
+        let compact = state.view == ClipListView::Compact;
         
         let is_visible = ui.clip_rect().intersects(container_rect);
         
@@ -37,10 +103,10 @@ impl ClipListRenderer {
         
         // Handle hover state changes
         if is_hovering {
-            if current_hover_target.as_ref() != Some(&clip.original_file) {
+            if !state.is_hover_target {
                 result.start_hover = Some(clip.original_file.clone());
             }
-        } else if !is_hovering && current_hover_target.as_ref() == Some(&clip.original_file) {
+        } else if state.is_hover_target {
             result.stop_hover = true;
         }
         
@@ -50,9 +116,36 @@ impl ClipListRenderer {
         if container_response.clicked() && is_valid {
             result.clicked = true;
         }
+        if container_response.double_clicked() && is_valid {
+            result.double_clicked = true;
+        }
+        
+        container_response.context_menu(|ui| {
+            if state.is_session_break {
+                if ui.button("Merge with previous session").clicked() {
+                    result.context_action = Some(ClipContextAction::MergeWithPreviousSession);
+                    ui.close_menu();
+                }
+            } else if ui.button("✂ Split session here").clicked() {
+                result.context_action = Some(ClipContextAction::SplitSessionHere);
+                ui.close_menu();
+            }
+            if ui.button(if clip.pinned { "Unpin" } else { "📌 Pin" }).clicked() {
+                result.context_action = Some(ClipContextAction::TogglePin);
+                ui.close_menu();
+            }
+            ui.menu_button("🎨 Color", |ui| {
+                if let Some(color) = Self::render_color_palette(ui, clip.color) {
+                    result.context_action = Some(ClipContextAction::SetColor(color));
+                }
+            });
+        });
         
-        // Draw the container background
-        if is_selected {
+        // Draw the container background, tinted with the clip's color
+        if let Some([r, g, b]) = clip.color {
+            ui.painter().rect_filled(container_rect, 4.0, egui::Color32::from_rgba_unmultiplied(r, g, b, CLIP_COLOR_TINT_ALPHA));
+        }
+        if state.is_selected {
             ui.painter().rect_filled(container_rect, 4.0, ui.visuals().selection.bg_fill);
         } else if is_hovering {
             let mut hover_color = ui.visuals().selection.bg_fill;
@@ -60,12 +153,33 @@ impl ClipListRenderer {
             ui.painter().rect_filled(container_rect, 4.0, hover_color);
         }
         
-        if is_selected {
+        if state.is_selected {
             ui.painter().rect_stroke(container_rect, 4.0, ui.visuals().selection.stroke);
         }
         
-        // Get thumbnail data
-        let thumbnail_data = if is_hovering {
+        // The selection fill covers the tint, so the stripe keeps the color visible
+        if let Some([r, g, b]) = clip.color {
+            let stripe = egui::Rect::from_min_size(
+                container_rect.min,
+                egui::vec2(CLIP_COLOR_STRIPE_WIDTH, container_rect.height()),
+            );
+            let rounding = egui::Rounding { nw: 4.0, sw: 4.0, ne: 0.0, se: 0.0 };
+            ui.painter().rect_filled(stripe, rounding, egui::Color32::from_rgb(r, g, b));
+        }
+        
+        // Keyboard focus ring, drawn inside the selection outline so both stay visible
+        if state.is_keyboard_focused {
+            ui.painter().rect_stroke(
+                container_rect.shrink(2.0),
+                3.0,
+                egui::Stroke::new(1.5, egui::Color32::from_rgb(255, 200, 80)),
+            );
+        }
+        
+        // Get thumbnail data; compact rows have no room for one
+        let thumbnail_data = if compact {
+            None
+        } else if is_hovering {
             if let Some(handle) = hover_thumbnail_manager.get_current_hover_thumbnail(ui.ctx()) {
                 let texture_size = handle.size();
                 let texture_id = handle.id();
@@ -74,6 +188,12 @@ impl ClipListRenderer {
             } else {
                 None
             }
+        } else if let Some(poster) = clip.effective_poster_timestamp()
+            .filter(|_| is_visible)
+            .and_then(|timestamp| poster_cache?.request_thumbnail(&clip.original_file, timestamp))
+        {
+            // User-picked poster frame
+            Some((poster.texture_handle.id(), poster.texture_handle.size(), None))
         } else {
             if hover_thumbnail_manager.has_thumbnails(&clip.original_file) {
                 hover_thumbnail_manager.get_first_thumbnail(&clip.original_file, ui.ctx()).map(|handle| {
@@ -94,6 +214,7 @@ impl ClipListRenderer {
         
         // Request thumbnails directly for visible clips if cache not full
         if is_visible 
+            && !compact
             && clip.video_length_seconds.is_some()
             && !hover_thumbnail_manager.has_thumbnails(&clip.original_file)
             && !hover_thumbnail_manager.is_generating(&clip.original_file)
@@ -111,10 +232,35 @@ impl ClipListRenderer {
         // Evict thumbnails for clips outside viewport (unless currently hovering)
         if !is_visible 
             && hover_thumbnail_manager.has_thumbnails(&clip.original_file)
-            && current_hover_target.as_ref() != Some(&clip.original_file) {
+            && !state.is_hover_target {
             hover_thumbnail_manager.evict_thumbnails(&clip.original_file);
         }
         
+        if compact {
+            ui.allocate_ui_at_rect(content_rect, |ui| {
+                ui.horizontal(|ui| {
+                    if !is_valid {
+                        ui.visuals_mut().override_text_color = Some(egui::Color32::GRAY);
+                    }
+                    Self::render_write_state(ui, &clip.write_state, true);
+                    Self::render_export_state_icon(ui, &clip.export_state);
+                    result.rename = Self::render_title(ui, clip, state, rename_buffer);
+                    
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let badge = match clip.video_length_seconds {
+                            Some(length) if length >= 1.0 => Clip::format_duration(clip.trim_end - clip.trim_start),
+                            _ => "…".to_string(),
+                        };
+                        ui.small(badge).on_hover_text("Trimmed length");
+                    });
+                });
+            });
+            
+            ui.advance_cursor_after_rect(container_rect);
+            ui.add_space(2.0);
+            return result;
+        }
+        
         ui.allocate_ui_at_rect(content_rect, |ui| {
             ui.horizontal(|ui| {
                 // Thumbnail area
@@ -129,9 +275,18 @@ impl ClipListRenderer {
                             ui.visuals_mut().override_text_color = Some(egui::Color32::GRAY);
                         }
                         
-                        ui.label(&clip.get_output_filename());
+                        ui.horizontal(|ui| {
+                            result.rename = Self::render_title(ui, clip, state, rename_buffer);
+                        });
+                        
+                        if let Some(ref export_dir) = clip.export_directory_override {
+                            ui.small(format!("→ {}", export_dir.display()))
+                                .on_hover_text("Exports go to this directory");
+                        }
                         
-                        if let Some(video_length) = clip.video_length_seconds {
+                        if clip.write_state != VideoWriteState::Complete {
+                            Self::render_write_state(ui, &clip.write_state, false);
+                        } else if let Some(video_length) = clip.video_length_seconds {
                             if video_length >= 1.0 {
                                 ui.small(format!("Original: {}", Clip::format_duration(video_length)));
                                 if clip.has_target_duration() {
@@ -143,6 +298,11 @@ impl ClipListRenderer {
                         } else {
                             ui.small("Waiting...");
                         }
+                        
+                        ui.horizontal(|ui| {
+                            Self::render_export_state_icon(ui, &clip.export_state);
+                            ui.small(clip.export_state.label());
+                        });
                     });
                 });
             });
@@ -154,6 +314,149 @@ impl ClipListRenderer {
         result
     }
     
+    /// Small export state icon drawn with the painter: a grey ring, a spinner, a green
+    /// check mark or a red cross whose tooltip has the error
+    /// Spinner while OBS is still writing the file, red warning if it can't be read.
+    /// Compact rows get the icon only.
+    fn render_write_state(ui: &mut egui::Ui, write_state: &VideoWriteState, compact: bool) {
+        match write_state {
+            VideoWriteState::Complete => {}
+            VideoWriteState::StillWriting { measured_duration } => {
+                ui.horizontal(|ui| {
+                    ui.add(egui::Spinner::new().size(ui.text_style_height(&egui::TextStyle::Small)));
+                    if !compact {
+                        ui.small(format!("Still being written… {}", Clip::format_duration(*measured_duration)));
+                    }
+                }).response.on_hover_text("OBS is still saving this replay");
+            }
+            VideoWriteState::Corrupted { reason } => {
+                let text = if compact { "⚠" } else { "⚠ Unreadable file" };
+                ui.colored_label(egui::Color32::RED, text)
+                    .on_hover_text(format!(
+                        "{}\nChecked again every {} seconds",
+                        reason,
+                        crate::core::CORRUPTED_CLIP_RETRY_INTERVAL.as_secs()
+                    ));
+            }
+            VideoWriteState::Missing => {
+                let text = if compact { "⚠" } else { "⚠ File missing" };
+                ui.colored_label(egui::Color32::RED, text)
+                    .on_hover_text("The replay was moved or deleted outside Clip Helper. Unpin the clip to remove it from the list.");
+            }
+        }
+    }
+
+    fn render_export_state_icon(ui: &mut egui::Ui, export_state: &ClipExportState) {
+        let size = ui.text_style_height(&egui::TextStyle::Small);
+        let (rect, response) = ui.allocate_exact_size(egui::Vec2::splat(size), egui::Sense::hover());
+        let painter = ui.painter();
+        let center = rect.center();
+        let radius = size * 0.4;
+        
+        match export_state {
+            ClipExportState::NotExported => {
+                painter.circle_stroke(center, radius, egui::Stroke::new(1.0, egui::Color32::GRAY));
+            }
+            ClipExportState::Exporting => {
+                // Three quarters of a ring, turning
+                let start = ui.input(|i| i.time) as f32 * 4.0;
+                let points: Vec<egui::Pos2> = (0..=12)
+                    .map(|i| {
+                        let angle = start + i as f32 / 12.0 * std::f32::consts::TAU * 0.75;
+                        center + radius * egui::Vec2::angled(angle)
+                    })
+                    .collect();
+                painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, ui.visuals().text_color())));
+                ui.ctx().request_repaint();
+            }
+            ClipExportState::Exported => {
+                let stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 200, 100));
+                painter.line_segment([center + egui::vec2(-radius, 0.0), center + egui::vec2(-radius * 0.3, radius * 0.7)], stroke);
+                painter.line_segment([center + egui::vec2(-radius * 0.3, radius * 0.7), center + egui::vec2(radius, -radius * 0.7)], stroke);
+            }
+            ClipExportState::ExportFailed(error) => {
+                let stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(220, 70, 70));
+                let offset = radius * 0.75;
+                painter.line_segment([center + egui::vec2(-offset, -offset), center + egui::vec2(offset, offset)], stroke);
+                painter.line_segment([center + egui::vec2(-offset, offset), center + egui::vec2(offset, -offset)], stroke);
+                response.on_hover_text(format!("Export failed: {}", error));
+                return;
+            }
+        }
+        response.on_hover_text(export_state.label());
+    }
+    
+    /// Preset swatches, a custom picker and "Clear color". Returns the picked color
+    /// (None to clear) if it changed this frame.
+    fn render_color_palette(ui: &mut egui::Ui, current: Option<[u8; 3]>) -> Option<Option<[u8; 3]>> {
+        let mut picked = None;
+        egui::Grid::new("clip_color_palette").spacing([4.0, 4.0]).show(ui, |ui| {
+            for (i, (name, rgb)) in CLIP_COLOR_PALETTE.iter().enumerate() {
+                let [r, g, b] = *rgb;
+                let swatch = egui::Button::new("")
+                    .fill(egui::Color32::from_rgb(r, g, b))
+                    .min_size(egui::vec2(20.0, 20.0))
+                    .selected(current == Some(*rgb));
+                if ui.add(swatch).on_hover_text(*name).clicked() {
+                    picked = Some(Some(*rgb));
+                    ui.close_menu();
+                }
+                if i % 4 == 3 {
+                    ui.end_row();
+                }
+            }
+        });
+        
+        ui.horizontal(|ui| {
+            let mut rgb = current.unwrap_or([255, 255, 255]).map(|c| c as f32 / 255.0);
+            if egui::color_picker::color_edit_button_rgb(ui, &mut rgb).changed() {
+                picked = Some(Some(rgb.map(|c| (c * 255.0).round() as u8)));
+            }
+            ui.label("Custom…");
+        });
+        
+        if current.is_some() && ui.button("Clear color").clicked() {
+            picked = Some(None);
+            ui.close_menu();
+        }
+        picked
+    }
+    
+    /// File name with the pin and duplicate badges, or the rename field while renaming
+    fn render_title(ui: &mut egui::Ui, clip: &Clip, state: ClipItemState, rename_buffer: Option<&mut String>) -> Option<RenameOutcome> {
+        if clip.pinned {
+            ui.label("📌").on_hover_text("Pinned");
+        }
+        if let Some(buffer) = rename_buffer {
+            let original_name = clip.original_file.file_stem().and_then(|s| s.to_str()).unwrap_or("clip");
+            let response = ui.add(egui::TextEdit::singleline(buffer)
+                .hint_text(original_name)
+                .desired_width(ui.available_width() - 60.0));
+            // TextEdit gives up focus on both Enter and Escape, so the key tells them apart
+            if response.lost_focus() {
+                return Some(if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    RenameOutcome::Cancel
+                } else {
+                    RenameOutcome::Commit
+                });
+            }
+            if !response.has_focus() {
+                response.request_focus();
+            }
+            return None;
+        }
+        ui.label(clip.get_output_filename());
+        if clip.timestamp_source == crate::core::TimestampSource::Mtime {
+            ui.colored_label(egui::Color32::from_rgb(230, 160, 60), "⚠")
+                .on_hover_text("The file name has no OBS timestamp, so the clip is dated by the file's modification time");
+        }
+        if state.is_near_duplicate {
+            ui.colored_label(egui::Color32::from_rgb(230, 160, 60), "⧉")
+                .on_hover_text("Possible duplicate of another replay saved at the same time");
+        }
+        None
+    }
+    
     fn render_thumbnail(
         ui: &mut egui::Ui,
         thumbnail_data: Option<(egui::TextureId, [usize; 2], Option<(u8, f64)>)>,
@@ -214,7 +517,10 @@ impl ClipListRenderer {
 #[derive(Default)]
 pub struct ClipRenderResult {
     pub clicked: bool,
+    pub double_clicked: bool,
+    pub rename: Option<RenameOutcome>,
     pub start_hover: Option<PathBuf>,
     pub stop_hover: bool,
     pub needs_video_info: bool,
+    pub context_action: Option<ClipContextAction>,
 }
@@ -3,6 +3,8 @@ pub mod clip_list;
 pub mod clip_list_renderer;
 pub mod timeline;
 pub mod controls;
+pub mod tray;
+pub mod shortcuts;
 
 #[cfg(test)]
 mod app_test;
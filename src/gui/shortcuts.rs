@@ -0,0 +1,66 @@
+use crate::core::HotkeyConfig;
+use crate::hotkeys::HOTKEY_BINDINGS;
+use std::collections::HashMap;
+
+const PLAYBACK_SHORTCUTS: &[(&str, &str)] = &[
+    ("Seek while paused", "Mouse wheel over the preview"),
+    ("Zoom the timeline", "Ctrl+mouse wheel"),
+];
+
+const TRIM_SHORTCUTS: &[(&str, &str)] = &[
+    ("Set trim start at the playhead", "I"),
+    ("Set trim end at the playhead", "O"),
+    ("Fine trim steps", "Shift + trim buttons"),
+    ("Export and overwrite the existing file", "Shift+click Export"),
+];
+
+const NAVIGATION_SHORTCUTS: &[(&str, &str)] = &[
+    ("Move focus to the editor", "Enter"),
+    ("Show or hide this help", "? or F1"),
+    ("Close this help", "Escape"),
+];
+
+const CLIP_LIST_SHORTCUTS: &[(&str, &str)] = &[
+    ("Previous / next clip", "↑ / ↓ (clip list focused)"),
+    ("Jump ten clips", "Page Up / Page Down"),
+    ("Select clips to merge", "Ctrl+click"),
+];
+
+/// In-app keyboard and mouse shortcuts as (section, [(action, shortcut)]). These are fixed;
+/// the global clip hotkeys are configurable and listed by `global_hotkey_rows` instead.
+pub const APP_SHORTCUTS: &[(&str, &[(&str, &str)])] = &[
+    ("Playback", PLAYBACK_SHORTCUTS),
+    ("Trim", TRIM_SHORTCUTS),
+    ("Navigation", NAVIGATION_SHORTCUTS),
+    ("Clip List", CLIP_LIST_SHORTCUTS),
+];
+
+/// (description, binding) for every global clip hotkey, read from the config so rebinding
+/// shows up immediately. Bindings missing from the config are shown as "Not set".
+pub fn global_hotkey_rows(hotkeys: &HashMap<String, HotkeyConfig>) -> Vec<(&'static str, String)> {
+    HOTKEY_BINDINGS.iter()
+        .map(|(config_key, _, description)| {
+            let binding = hotkeys.get(*config_key)
+                .map_or_else(|| "Not set".to_string(), HotkeyConfig::label);
+            (*description, binding)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::AppConfig;
+
+    #[test]
+    fn test_global_hotkey_rows_follow_config() {
+        let mut hotkeys = AppConfig::default().hotkeys;
+        assert_eq!(global_hotkey_rows(&hotkeys)[0], ("15s clip", "Ctrl+Numpad1".to_string()));
+
+        hotkeys.insert("clip_15s".to_string(), HotkeyConfig { modifiers: "Alt+Shift".to_string(), key: "F5".to_string() });
+        hotkeys.remove("clip_5m");
+        let rows = global_hotkey_rows(&hotkeys);
+        assert_eq!(rows[0].1, "Alt+Shift+F5");
+        assert_eq!(rows[4], ("5m clip", "Not set".to_string()));
+    }
+}
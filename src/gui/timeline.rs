@@ -1,11 +1,95 @@
 use eframe::egui;
-use crate::core::Clip;
-use crate::video::VideoPreview;
+use crate::core::{Clip, TimecodeFormat, TrimRules};
+use crate::video::{SmartThumbnailCache, VideoPreview, WaveformData, WaveformScale};
+
+/// Zooming in stops once a single frame would be this many pixels wide
+const MAX_PIXELS_PER_FRAME: f64 = 24.0;
+
+/// Multiplier applied per +/- button press
+const ZOOM_STEP: f32 = 1.5;
+
+/// Upper bound on magnification, even for long clips where frames would still be narrow
+const MAX_ZOOM: f32 = 100.0;
+
+/// How long a trim handle stays highlighted after being set from the keyboard
+const TRIM_FLASH_DURATION: std::time::Duration = std::time::Duration::from_millis(800);
+
+/// Ruler labels get frame numbers once a frame is at least this many pixels wide
+const MIN_PIXELS_PER_LABELLED_FRAME: f64 = 4.0;
+
+/// The kept duration is only labelled when this much of the kept region is on screen
+const MIN_KEPT_LABEL_WIDTH: f32 = 70.0;
+
+/// Height of the thumbnail strip above the track; thumbnails are 16:9 at this height
+const THUMBNAIL_STRIP_HEIGHT: f32 = 36.0;
+
+/// Format `secs` for display; `fps` is only used by `TimecodeFormat::Frames`
+pub fn format_timecode(secs: f64, fps: f64, fmt: TimecodeFormat) -> String {
+    let secs = secs.max(0.0);
+    match fmt {
+        TimecodeFormat::Seconds => format!("{:.1}s", secs),
+        TimecodeFormat::HhMmSs => {
+            let total = secs.floor() as u64;
+            let (hours, mins, s) = (total / 3600, total / 60 % 60, total % 60);
+            if hours > 0 {
+                format!("{}:{:02}:{:02}", hours, mins, s)
+            } else {
+                format!("{}:{:02}", mins, s)
+            }
+        }
+        TimecodeFormat::HhMmSsMs => {
+            let total_ms = (secs * 1000.0).round() as u64;
+            let ms = total_ms % 1000;
+            let total = total_ms / 1000;
+            let (hours, mins, s) = (total / 3600, total / 60 % 60, total % 60);
+            if hours > 0 {
+                format!("{}:{:02}:{:02}.{:03}", hours, mins, s, ms)
+            } else {
+                format!("{}:{:02}.{:03}", mins, s, ms)
+            }
+        }
+        TimecodeFormat::Frames => format!("{}", (secs * fps.max(1.0)).round() as u64),
+    }
+}
+
+/// Identifies the waveform and visible window that cached peaks were computed for:
+/// sample buffer address and length, view start and duration bits, bucket count
+type WaveformPeaksKey = (usize, usize, u64, u64, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimHandle {
+    Start,
+    End,
+}
 
 pub struct TimelineWidget {
     pub scrub_position: f64,
     pub is_scrubbing: bool,
+    /// How many times the full clip is magnified; 1.0 shows everything
     pub zoom_level: f32,
+    /// Start of the visible time window in seconds, i.e. the scroll offset (only meaningful when zoomed in)
+    pub view_start: f64,
+    /// Used to limit zoom to single-frame resolution
+    pub frame_rate: f64,
+    /// Playhead position last frame, to tell playback movement apart from manual panning
+    last_playhead: f64,
+    /// Index of the chapter marker being dragged, if any
+    pub dragging_marker: Option<usize>,
+    /// Trim handle grabbed at the start of the current drag
+    dragging_handle: Option<TrimHandle>,
+    /// Ruler label format; the app syncs this with the config
+    pub timecode_format: TimecodeFormat,
+    /// Handle highlighted after a keyboard change, and when that happened
+    trim_flash: Option<(TrimHandle, std::time::Instant)>,
+    /// Waveform display mode and color; the app syncs these with the config
+    pub waveform_scale: WaveformScale,
+    pub waveform_color: [u8; 3],
+    /// Linear peaks of the visible window, recomputed only when the view changes
+    waveform_peaks: Option<(WaveformPeaksKey, Vec<f32>)>,
+    /// Zoom factor requested from outside the timeline, applied once its size is known
+    pending_zoom: f32,
+    /// Minimum gap and snapping for dragged trim handles; the app syncs this with the config
+    pub trim_rules: TrimRules,
 }
 
 impl TimelineWidget {
@@ -14,10 +98,189 @@ impl TimelineWidget {
             scrub_position: 0.0,
             is_scrubbing: false,
             zoom_level: 1.0,
+            view_start: 0.0,
+            frame_rate: 30.0,
+            last_playhead: 0.0,
+            dragging_marker: None,
+            dragging_handle: None,
+            timecode_format: TimecodeFormat::default(),
+            trim_flash: None,
+            waveform_scale: WaveformScale::default(),
+            waveform_color: crate::core::config::DEFAULT_WAVEFORM_COLOR,
+            waveform_peaks: None,
+            pending_zoom: 1.0,
+            trim_rules: TrimRules::default(),
+        }
+    }
+
+    /// Briefly highlight a trim handle, e.g. after it was moved with a shortcut
+    pub fn flash_trim_handle(&mut self, handle: TrimHandle) {
+        self.trim_flash = Some((handle, std::time::Instant::now()));
+    }
+
+    /// Fill color of a trim handle, fading from the highlight back to normal after a flash
+    fn trim_handle_color(&mut self, ui: &egui::Ui, handle: TrimHandle) -> egui::Color32 {
+        let normal = ui.visuals().selection.bg_fill;
+        let Some((flashed, since)) = self.trim_flash else {
+            return normal;
+        };
+        let elapsed = since.elapsed();
+        if elapsed >= TRIM_FLASH_DURATION {
+            self.trim_flash = None;
+            return normal;
+        }
+        if flashed != handle {
+            return normal;
+        }
+        
+        ui.ctx().request_repaint();
+        let progress = elapsed.as_secs_f32() / TRIM_FLASH_DURATION.as_secs_f32();
+        egui::Color32::YELLOW.lerp_to_gamma(normal, progress)
+    }
+
+    /// Zoom in (positive) or out by `steps` zoom button clicks around the playhead on the
+    /// next frame, e.g. for Ctrl+wheel over the preview
+    pub fn request_zoom_steps(&mut self, steps: f32) {
+        self.pending_zoom *= ZOOM_STEP.powf(steps);
+    }
+
+    /// Show the whole clip again
+    pub fn reset_zoom(&mut self) {
+        self.zoom_level = 1.0;
+        self.view_start = 0.0;
+    }
+
+    fn max_zoom(&self, duration: f64, track_width: f32) -> f32 {
+        let frame_duration = 1.0 / self.frame_rate.max(1.0);
+        let min_visible = frame_duration * track_width as f64 / MAX_PIXELS_PER_FRAME;
+        ((duration / min_visible) as f32).clamp(1.0, MAX_ZOOM)
+    }
+
+    /// Zoom by `factor`, keeping the time under `anchor` at the same screen position
+    fn zoom_around(&mut self, factor: f32, anchor: f64, duration: f64, track_width: f32) {
+        let old_visible = self.visible_duration(duration);
+        let anchor_fraction = ((anchor - self.view_start) / old_visible).clamp(0.0, 1.0);
+        
+        self.zoom_level = (self.zoom_level * factor).clamp(1.0, self.max_zoom(duration, track_width));
+        let new_visible = self.visible_duration(duration);
+        self.view_start = anchor - anchor_fraction * new_visible;
+        self.clamp_view(duration);
+    }
+
+    fn visible_duration(&self, duration: f64) -> f64 {
+        duration / self.zoom_level.max(1.0) as f64
+    }
+
+    fn clamp_view(&mut self, duration: f64) {
+        let max_start = (duration - self.visible_duration(duration)).max(0.0);
+        self.view_start = self.view_start.clamp(0.0, max_start);
+    }
+
+    /// Times of the thumbnails covering `[view_start, view_start + view_duration]` with about
+    /// `slots` of them, plus the time each one spans. The grid only halves when the zoom
+    /// doubles, so panning and small zoom changes reuse thumbnails that are already cached.
+    pub(crate) fn strip_thumbnail_times(view_start: f64, view_duration: f64, duration: f64, slots: usize) -> (Vec<f64>, f64) {
+        if slots == 0 || duration <= 0.0 || view_duration <= 0.0 {
+            return (Vec::new(), 0.0);
         }
+        let zoom = (duration / view_duration).max(1.0);
+        let step = duration / slots as f64 / 2f64.powi(zoom.log2().floor() as i32);
+        let first = (view_start / step).floor() as i64;
+        let last = ((view_start + view_duration) / step).ceil() as i64;
+        let times = (first..last)
+            .map(|i| i as f64 * step)
+            .filter(|&time| time >= 0.0 && time < duration)
+            .collect();
+        (times, step)
+    }
+
+    /// Frames of the visible window above the track. Only thumbnails in view are requested.
+    fn show_thumbnail_strip(&self, ui: &mut egui::Ui, file: &std::path::Path, thumbnails: &SmartThumbnailCache, duration: f64, width: f32) {
+        let (rect, _) = ui.allocate_exact_size(egui::Vec2::new(width, THUMBNAIL_STRIP_HEIGHT), egui::Sense::hover());
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
+        let strip = rect.shrink2(egui::Vec2::new(10.0, 0.0));
+        let painter = ui.painter_at(strip);
+        painter.rect_filled(strip, egui::Rounding::same(2.0), ui.visuals().extreme_bg_color);
+        
+        let view_duration = self.visible_duration(duration).max(f64::EPSILON);
+        let slots = (strip.width() / (THUMBNAIL_STRIP_HEIGHT * 16.0 / 9.0)).ceil().max(1.0) as usize;
+        let (times, step) = Self::strip_thumbnail_times(self.view_start, view_duration, duration, slots);
+        let time_to_x = |time: f64| strip.min.x + (((time - self.view_start) / view_duration) * strip.width() as f64) as f32;
+        let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        for time in times {
+            let slot = egui::Rect::from_min_max(
+                egui::pos2(time_to_x(time), strip.min.y),
+                egui::pos2(time_to_x((time + step).min(duration)), strip.max.y),
+            );
+            // Frame from the middle of the slot, so it represents what the slot covers
+            match thumbnails.request_thumbnail(file, (time + step / 2.0).min(duration)) {
+                Some(thumbnail) => {
+                    painter.image(thumbnail.texture_handle.id(), slot, uv, egui::Color32::WHITE);
+                }
+                None => {
+                    painter.rect_filled(slot.shrink(1.0), egui::Rounding::ZERO, ui.visuals().faint_bg_color);
+                }
+            }
+        }
+    }
+
+    /// Peaks of `waveform` for `buckets` columns of the visible window
+    fn visible_waveform_peaks(&mut self, waveform: &WaveformData, view_start: f64, view_duration: f64, buckets: usize) -> &[f32] {
+        let key = (
+            waveform.samples.as_ptr() as usize,
+            waveform.samples.len(),
+            view_start.to_bits(),
+            view_duration.to_bits(),
+            buckets,
+        );
+        if self.waveform_peaks.as_ref().map(|(cached, _)| *cached) != Some(key) {
+            let peaks = waveform.peaks_in_range(view_start, view_start + view_duration, buckets);
+            self.waveform_peaks = Some((key, peaks));
+        }
+        self.waveform_peaks.as_ref().map_or(&[], |(_, peaks)| peaks)
+    }
+
+    /// Zoom buttons, current visible range and waveform display options, shown above the timeline
+    fn show_zoom_controls(&mut self, ui: &mut egui::Ui, duration: f64, track_width: f32, playhead: f64) {
+        ui.horizontal(|ui| {
+            if ui.small_button("➖").on_hover_text("Zoom out").clicked() {
+                self.zoom_around(1.0 / ZOOM_STEP, playhead, duration, track_width);
+            }
+            if ui.small_button("➕").on_hover_text("Zoom in (or scroll over the timeline)").clicked() {
+                self.zoom_around(ZOOM_STEP, playhead, duration, track_width);
+            }
+            if ui.add_enabled(self.zoom_level > 1.0, egui::Button::new("Fit").small()).clicked() {
+                self.reset_zoom();
+            }
+            if self.zoom_level > 1.0 {
+                let view_end = self.view_start + self.visible_duration(duration);
+                ui.weak(format!("{:.1}x  {} - {}  (shift+scroll to pan)",
+                    self.zoom_level, self.format_time(self.view_start), self.format_time(view_end)));
+            }
+            
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.color_edit_button_srgb(&mut self.waveform_color).on_hover_text("Waveform color");
+                let mut decibels = self.waveform_scale == WaveformScale::Decibels;
+                if ui.toggle_value(&mut decibels, "dB")
+                    .on_hover_text("Show the waveform in decibels so quiet parts are visible")
+                    .changed()
+                {
+                    self.waveform_scale = if decibels { WaveformScale::Decibels } else { WaveformScale::Linear };
+                }
+            });
+        });
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui, clip: &mut Clip, video_preview: &mut Option<VideoPreview>) -> egui::Response {
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        clip: &mut Clip,
+        video_preview: &mut Option<VideoPreview>,
+        waveform: Option<&WaveformData>,
+        thumbnails: Option<&SmartThumbnailCache>,
+    ) -> egui::Response {
         // Use the original video duration, not the clip's target duration
         let duration = if let Some(preview) = video_preview {
             preview.total_duration
@@ -26,17 +289,61 @@ impl TimelineWidget {
         };
         let trim_start = clip.trim_start;
         let trim_end = clip.trim_end;
+        let playhead = video_preview.as_ref().map_or(self.scrub_position, |preview| preview.current_time);
         
         let available_width = ui.available_width() - 40.0; // Leave margin for labels
         let timeline_height = 60.0;
+        let track_width = available_width - 20.0;
+        
+        self.show_zoom_controls(ui, duration, track_width, playhead);
+        if let Some(thumbnails) = thumbnails.filter(|_| duration > 0.0) {
+            self.show_thumbnail_strip(ui, &clip.original_file, thumbnails, duration, available_width);
+        }
         
         let (rect, response) = ui.allocate_exact_size(
             egui::Vec2::new(available_width, timeline_height),
             egui::Sense::click_and_drag()
         );
         
+        if self.pending_zoom != 1.0 {
+            self.zoom_around(self.pending_zoom, playhead, duration, track_width);
+            self.pending_zoom = 1.0;
+        }
+        
+        // Wheel zooms around the pointer, horizontal scroll (shift+wheel) pans
+        if response.hovered() && duration > 0.0 {
+            let scroll = ui.input(|i| i.smooth_scroll_delta);
+            let track_left = rect.min.x + 10.0;
+            if scroll.y != 0.0 {
+                let pointer_x = response.hover_pos().map_or(rect.center().x, |pos| pos.x);
+                let fraction = ((pointer_x - track_left) / track_width).clamp(0.0, 1.0) as f64;
+                let anchor = self.view_start + fraction * self.visible_duration(duration);
+                self.zoom_around((scroll.y / 200.0).exp(), anchor, duration, track_width);
+            }
+            if scroll.x != 0.0 {
+                self.view_start -= scroll.x as f64 / track_width as f64 * self.visible_duration(duration);
+            }
+        }
+        
+        self.zoom_level = self.zoom_level.clamp(1.0, self.max_zoom(duration, track_width));
+        
+        // Follow the playhead when playback or a seek moves it out of view, but leave the
+        // window alone when the user pans away from a stationary playhead
+        let is_interacting = self.is_scrubbing || self.dragging_marker.is_some() || response.dragged();
+        if (playhead - self.last_playhead).abs() > f64::EPSILON && !is_interacting {
+            let visible = self.visible_duration(duration);
+            if playhead < self.view_start || playhead > self.view_start + visible {
+                self.view_start = playhead - visible * 0.1;
+            }
+        }
+        self.last_playhead = playhead;
+        self.clamp_view(duration);
+        
+        let view_start = self.view_start;
+        let view_duration = self.visible_duration(duration).max(f64::EPSILON);
+        
         if ui.is_rect_visible(rect) {
-            let painter = ui.painter();
+            let painter = ui.painter_at(rect);
             
             // Background
             painter.rect_filled(
@@ -57,14 +364,24 @@ impl TimelineWidget {
                 egui::Stroke::new(1.0, ui.visuals().text_color()),
             );
             
-            // Time markers
-            let time_per_pixel = duration / track_rect.width() as f64;
-            let marker_interval = self.calculate_marker_interval(time_per_pixel);
+            let time_to_x = |time: f64| track_rect.min.x + (((time - view_start) / view_duration) * track_rect.width() as f64) as f32;
+            // Anything drawn on the track itself is cut off at the edges of the visible window
+            let track_painter = painter.with_clip_rect(track_rect.expand2(egui::Vec2::new(6.0, 30.0)).intersect(rect));
             
-            for i in 0..((duration / marker_interval) as i32 + 1) {
+            // Time markers; zoomed in far enough, labels also get the frame number,
+            // which needs more room between them
+            let time_per_pixel = view_duration / track_rect.width() as f64;
+            let show_frame_numbers = self.timecode_format != TimecodeFormat::Frames
+                && 1.0 / (self.frame_rate.max(1.0) * time_per_pixel) >= MIN_PIXELS_PER_LABELLED_FRAME;
+            let label_spacing_factor = if show_frame_numbers { 2.0 } else { 1.0 };
+            let marker_interval = self.calculate_marker_interval(time_per_pixel * label_spacing_factor);
+            
+            let first_marker = (view_start / marker_interval).floor() as i64;
+            let last_marker = ((view_start + view_duration) / marker_interval).ceil() as i64;
+            for i in first_marker..=last_marker {
                 let time = i as f64 * marker_interval;
-                if time <= duration {
-                    let x = track_rect.min.x + ((time / duration) * track_rect.width() as f64) as f32;
+                if (0.0..=duration).contains(&time) && time >= view_start && time <= view_start + view_duration {
+                    let x = time_to_x(time);
                     
                     // Marker line
                     painter.line_segment(
@@ -73,7 +390,11 @@ impl TimelineWidget {
                     );
                     
                     // Time label
-                    let time_text = self.format_time(time);
+                    let time_text = if show_frame_numbers {
+                        format!("{} ({})", self.format_time(time), format_timecode(time, self.frame_rate, TimecodeFormat::Frames))
+                    } else {
+                        self.format_time(time)
+                    };
                     painter.text(
                         egui::Pos2::new(x, track_rect.min.y - 15.0),
                         egui::Align2::CENTER_BOTTOM,
@@ -84,22 +405,61 @@ impl TimelineWidget {
                 }
             }
             
-            // Trim region (selected area)
-            let trim_start_x = track_rect.min.x + ((trim_start / duration) * track_rect.width() as f64) as f32;
-            let trim_end_x = track_rect.min.x + ((trim_end / duration) * track_rect.width() as f64) as f32;
+            // Waveform, one bar per pixel column, mirrored around the middle of the track
+            if let Some(waveform) = waveform {
+                let columns = track_rect.width().max(0.0) as usize;
+                let scale = self.waveform_scale;
+                let color = egui::Color32::from_rgb(self.waveform_color[0], self.waveform_color[1], self.waveform_color[2]);
+                let half_height = track_rect.height() / 2.0 - 1.0;
+                let peaks = self.visible_waveform_peaks(waveform, view_start, view_duration, columns);
+                for (column, peak) in peaks.iter().enumerate() {
+                    let extent = scale.height(*peak) * half_height;
+                    if extent < 0.5 {
+                        continue;
+                    }
+                    let x = track_rect.min.x + column as f32 + 0.5;
+                    painter.line_segment(
+                        [egui::Pos2::new(x, track_rect.center().y - extent), egui::Pos2::new(x, track_rect.center().y + extent)],
+                        egui::Stroke::new(1.0, color),
+                    );
+                }
+            }
+            
+            // Trim region: the kept part is highlighted, the discarded head and tail are dimmed
+            let trim_start_x = time_to_x(trim_start).clamp(track_rect.min.x, track_rect.max.x);
+            let trim_end_x = time_to_x(trim_end).clamp(track_rect.min.x, track_rect.max.x);
             
             let trim_rect = egui::Rect::from_min_max(
                 egui::Pos2::new(trim_start_x, track_rect.min.y),
                 egui::Pos2::new(trim_end_x, track_rect.max.y),
             );
+            let discarded_color = egui::Color32::from_black_alpha(140);
+            for discarded in [
+                egui::Rect::from_min_max(track_rect.min, egui::Pos2::new(trim_start_x, track_rect.max.y)),
+                egui::Rect::from_min_max(egui::Pos2::new(trim_end_x, track_rect.min.y), track_rect.max),
+            ] {
+                if discarded.width() > 0.0 {
+                    painter.rect_filled(discarded, egui::Rounding::ZERO, discarded_color);
+                }
+            }
             
-            painter.rect_filled(
+            track_painter.rect_filled(
                 trim_rect,
                 egui::Rounding::same(2.0),
                 ui.visuals().selection.bg_fill.gamma_multiply(0.5),
             );
+            if trim_rect.width() >= MIN_KEPT_LABEL_WIDTH {
+                painter.text(
+                    trim_rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    format!("Keep {}", self.format_time(trim_end - trim_start)),
+                    egui::FontId::proportional(11.0),
+                    ui.visuals().strong_text_color(),
+                );
+            }
             
-            // Trim handles
+            // Trim handles; a point outside the visible window gets its handle pinned to
+            // that edge, so it can still be grabbed while zoomed in
             let handle_width = 8.0;
             let start_handle = egui::Rect::from_center_size(
                 egui::Pos2::new(trim_start_x, track_rect.center().y),
@@ -110,21 +470,49 @@ impl TimelineWidget {
                 egui::Vec2::new(handle_width, track_rect.height() + 10.0),
             );
             
-            painter.rect_filled(
+            track_painter.rect_filled(
                 start_handle,
                 egui::Rounding::same(4.0),
-                ui.visuals().selection.bg_fill,
+                self.trim_handle_color(ui, TrimHandle::Start),
             );
-            painter.rect_filled(
+            track_painter.rect_filled(
                 end_handle,
                 egui::Rounding::same(4.0),
-                ui.visuals().selection.bg_fill,
+                self.trim_handle_color(ui, TrimHandle::End),
             );
             
+            // Chapter markers - ticks across the track with a grab handle and label below it
+            let marker_color = egui::Color32::from_rgb(255, 200, 80);
+            for marker in &clip.markers {
+                let x = time_to_x(marker.time);
+                track_painter.line_segment(
+                    [egui::Pos2::new(x, track_rect.min.y), egui::Pos2::new(x, track_rect.max.y + 4.0)],
+                    egui::Stroke::new(1.5, marker_color),
+                );
+                track_painter.add(egui::Shape::convex_polygon(
+                    vec![
+                        egui::Pos2::new(x, track_rect.max.y + 2.0),
+                        egui::Pos2::new(x + 4.0, track_rect.max.y + 8.0),
+                        egui::Pos2::new(x - 4.0, track_rect.max.y + 8.0),
+                    ],
+                    marker_color,
+                    egui::Stroke::NONE,
+                ));
+                if !marker.label.is_empty() {
+                    track_painter.text(
+                        egui::Pos2::new(x + 5.0, track_rect.max.y + 8.0),
+                        egui::Align2::LEFT_TOP,
+                        &marker.label,
+                        egui::FontId::proportional(10.0),
+                        marker_color,
+                    );
+                }
+            }
+            
             // Current playback position
             if let Some(preview) = video_preview {
-                let current_x = track_rect.min.x + ((preview.current_time / duration) * track_rect.width() as f64) as f32;
-                painter.line_segment(
+                let current_x = time_to_x(preview.current_time);
+                track_painter.line_segment(
                     [egui::Pos2::new(current_x, rect.min.y), egui::Pos2::new(current_x, rect.max.y)],
                     egui::Stroke::new(2.0, egui::Color32::RED),
                 );
@@ -134,19 +522,45 @@ impl TimelineWidget {
                     egui::Pos2::new(current_x, rect.min.y + 5.0),
                     egui::Vec2::new(12.0, 10.0),
                 );
-                painter.rect_filled(
+                track_painter.rect_filled(
                     playhead_rect,
                     egui::Rounding::same(2.0),
                     egui::Color32::RED,
                 );
             }
             
+            // Grab a marker when a drag starts on (or just below) its tick
+            if response.drag_started() {
+                if let Some(pos) = response.interact_pointer_pos() {
+                    if pos.y >= track_rect.min.y {
+                        self.dragging_marker = clip.markers.iter().position(|marker| {
+                            (pos.x - time_to_x(marker.time)).abs() <= 5.0
+                        });
+                    }
+                    if self.dragging_marker.is_none() {
+                        self.dragging_handle = if start_handle.contains(pos) {
+                            Some(TrimHandle::Start)
+                        } else if end_handle.contains(pos) {
+                            Some(TrimHandle::End)
+                        } else {
+                            None
+                        };
+                        self.is_scrubbing |= self.dragging_handle.is_some();
+                    }
+                }
+            }
+            
             // Handle interactions
-            if response.clicked() || response.dragged() {
+            if let Some(marker_index) = self.dragging_marker {
+                if let (Some(pos), Some(marker)) = (response.interact_pointer_pos(), clip.markers.get_mut(marker_index)) {
+                    let relative_x = ((pos.x - track_rect.min.x) / track_rect.width()) as f64;
+                    marker.time = (view_start + relative_x * view_duration).clamp(0.0, duration);
+                }
+            } else if response.clicked() || response.dragged() {
                 if let Some(click_pos) = response.interact_pointer_pos() {
                     let click_x = click_pos.x;
                     let relative_x = ((click_x - track_rect.min.x) / track_rect.width()) as f64;
-                    let clicked_time = relative_x * duration;
+                    let clicked_time = (view_start + relative_x * view_duration).clamp(0.0, duration);
                     
                     // Check if clicking on trim handles
                     if response.clicked() {
@@ -171,17 +585,19 @@ impl TimelineWidget {
                             // Trim adjustment
                             let clamped_time = clicked_time.clamp(0.0, duration);
                             
-                            // Determine which handle is closer
-                            let dist_to_start = (clicked_time - trim_start).abs();
-                            let dist_to_end = (clicked_time - trim_end).abs();
+                            // The grabbed handle, otherwise whichever is closer
+                            let handle = self.dragging_handle.unwrap_or(
+                                if (clicked_time - trim_start).abs() < (clicked_time - trim_end).abs() {
+                                    TrimHandle::Start
+                                } else {
+                                    TrimHandle::End
+                                }
+                            );
                             
-                            if dist_to_start < dist_to_end {
-                                // Adjust start trim
-                                clip.trim_start = clamped_time.min(trim_end - 0.1);
-                            } else {
-                                // Adjust end trim
-                                clip.trim_end = clamped_time.max(trim_start + 0.1);
-                            }
+                            match handle {
+                                TrimHandle::Start => clip.set_trim_start_at(clamped_time, self.trim_rules),
+                                TrimHandle::End => clip.set_trim_end_at(clamped_time, self.trim_rules),
+                            };
                         } else {
                             // Timeline scrubbing - just update position for display
                             if let Some(preview) = video_preview {
@@ -195,6 +611,10 @@ impl TimelineWidget {
             
             if response.drag_stopped() {
                 self.is_scrubbing = false;
+                self.dragging_handle = None;
+                if self.dragging_marker.take().is_some() {
+                    clip.normalize_markers();
+                }
             }
             
             // Time display
@@ -213,6 +633,15 @@ impl TimelineWidget {
             );
         }
         
+        response.context_menu(|ui| {
+            ui.label("Timecode format");
+            for format in TimecodeFormat::ALL {
+                if ui.radio_value(&mut self.timecode_format, format, format.label()).clicked() {
+                    ui.close_menu();
+                }
+            }
+        });
+        
         response
     }
     
@@ -222,7 +651,9 @@ impl TimelineWidget {
         let base_interval = time_per_pixel * target_pixel_spacing;
         
         // Round to nice intervals
-        if base_interval <= 1.0 {
+        if base_interval <= 0.2 {
+            0.1 // Only reachable when zoomed in
+        } else if base_interval <= 1.0 {
             0.5
         } else if base_interval <= 5.0 {
             1.0
@@ -238,8 +669,37 @@ impl TimelineWidget {
     }
     
     fn format_time(&self, seconds: f64) -> String {
-        let mins = (seconds / 60.0) as u32;
-        let secs = seconds % 60.0;
-        format!("{}:{:04.1}", mins, secs)
+        format_timecode(seconds, self.frame_rate, self.timecode_format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timecode() {
+        assert_eq!(format_timecode(65.3, 30.0, TimecodeFormat::Seconds), "65.3s");
+        assert_eq!(format_timecode(65.3, 30.0, TimecodeFormat::HhMmSs), "1:05");
+        assert_eq!(format_timecode(3725.0, 30.0, TimecodeFormat::HhMmSs), "1:02:05");
+        assert_eq!(format_timecode(65.3, 30.0, TimecodeFormat::HhMmSsMs), "1:05.300");
+        assert_eq!(format_timecode(65.3, 30.0, TimecodeFormat::Frames), "1959");
+    }
+
+    #[test]
+    fn test_strip_thumbnail_times_cover_only_the_visible_window() {
+        let (times, step) = TimelineWidget::strip_thumbnail_times(0.0, 60.0, 60.0, 6);
+        assert_eq!(step, 10.0);
+        assert_eq!(times, vec![0.0, 10.0, 20.0, 30.0, 40.0, 50.0]);
+
+        // 3x zoom halves the grid once; only slots overlapping 25s-45s are loaded
+        let (times, step) = TimelineWidget::strip_thumbnail_times(25.0, 20.0, 60.0, 6);
+        assert_eq!(step, 5.0);
+        assert_eq!(times, vec![25.0, 30.0, 35.0, 40.0]);
+        // Panning a little keeps the same grid
+        let (times, _) = TimelineWidget::strip_thumbnail_times(27.0, 20.0, 60.0, 6);
+        assert_eq!(times, vec![25.0, 30.0, 35.0, 40.0, 45.0]);
+
+        assert!(TimelineWidget::strip_thumbnail_times(0.0, 60.0, 0.0, 6).0.is_empty());
     }
 }
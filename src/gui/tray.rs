@@ -0,0 +1,132 @@
+use std::sync::mpsc;
+use eframe::egui;
+
+/// Tray icon edge length in pixels
+const TRAY_ICON_SIZE: u32 = 32;
+
+/// Something the user asked for through the tray icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    /// "Open" or a double click: show the window again
+    Open,
+    ExportNextClip,
+    Quit,
+}
+
+/// Whether this platform has a tray icon ClipHelper can hide into
+pub const TRAY_SUPPORTED: bool = cfg!(any(target_os = "windows", target_os = "macos"));
+
+/// Tray icon shown while the window is hidden. Menu clicks and double clicks are
+/// forwarded as `TrayAction`s and wake up the UI, which polls them with `try_recv_action`.
+pub struct AppTray {
+    actions: mpsc::Receiver<TrayAction>,
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    icon: tray_icon::TrayIcon,
+}
+
+impl AppTray {
+    /// Create the tray icon. Must be called on the thread running the window's event loop.
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    pub fn new(ctx: &egui::Context) -> anyhow::Result<Self> {
+        use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+        use tray_icon::{MouseButton, TrayIconBuilder, TrayIconEvent};
+
+        let open = MenuItem::new("Open", true, None);
+        let export_next = MenuItem::new("Export next clip", true, None);
+        let quit = MenuItem::new("Quit", true, None);
+        let menu = Menu::new();
+        menu.append_items(&[&open, &export_next, &PredefinedMenuItem::separator(), &quit])
+            .map_err(|e| anyhow::anyhow!("Failed to build tray menu: {}", e))?;
+
+        let icon = tray_icon::Icon::from_rgba(tray_icon_rgba(TRAY_ICON_SIZE), TRAY_ICON_SIZE, TRAY_ICON_SIZE)
+            .map_err(|e| anyhow::anyhow!("Invalid tray icon: {}", e))?;
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip("Clip Helper")
+            .with_icon(icon)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create tray icon: {}", e))?;
+
+        // The handlers run on the event loop thread even while the window is hidden,
+        // so they also have to wake up the UI for the action to be handled
+        let (tx, actions) = mpsc::channel();
+        let (open_id, export_next_id, quit_id) = (open.id().clone(), export_next.id().clone(), quit.id().clone());
+        let menu_tx = tx.clone();
+        let menu_ctx = ctx.clone();
+        MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+            let action = if event.id == open_id {
+                TrayAction::Open
+            } else if event.id == export_next_id {
+                TrayAction::ExportNextClip
+            } else if event.id == quit_id {
+                TrayAction::Quit
+            } else {
+                return;
+            };
+            let _ = menu_tx.send(action);
+            menu_ctx.request_repaint();
+        }));
+        let icon_ctx = ctx.clone();
+        TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+            if let TrayIconEvent::DoubleClick { button: MouseButton::Left, .. } = event {
+                let _ = tx.send(TrayAction::Open);
+                icon_ctx.request_repaint();
+            }
+        }));
+
+        Ok(Self { actions, icon })
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    pub fn new(_ctx: &egui::Context) -> anyhow::Result<Self> {
+        Err(anyhow::anyhow!("Minimizing to the tray is only supported on Windows and macOS"))
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        if let Err(e) = self.icon.set_visible(visible) {
+            log::warn!("Failed to change tray icon visibility: {}", e);
+        }
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let _ = visible;
+    }
+
+    pub fn try_recv_action(&self) -> Option<TrayAction> {
+        self.actions.try_recv().ok()
+    }
+}
+
+/// Show a desktop notification, e.g. for a clip detected while the window is hidden
+pub fn show_notification(summary: &str, body: &str) {
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    if let Err(e) = notify_rust::Notification::new()
+        .appname("Clip Helper")
+        .summary(summary)
+        .body(body)
+        .show()
+    {
+        log::warn!("Failed to show notification: {}", e);
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    log::info!("{}: {}", summary, body);
+}
+
+/// Tray icon pixels: a red record dot on a dark disc
+fn tray_icon_rgba(size: u32) -> Vec<u8> {
+    let center = (size as f32 - 1.0) / 2.0;
+    let mut rgba = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let distance = ((x as f32 - center).powi(2) + (y as f32 - center).powi(2)).sqrt() / center;
+            let pixel = if distance <= 0.5 {
+                [220, 40, 40, 255]
+            } else if distance <= 1.0 {
+                [40, 40, 40, 255]
+            } else {
+                [0, 0, 0, 0]
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    rgba
+}
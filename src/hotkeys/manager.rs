@@ -2,77 +2,203 @@ use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
 use global_hotkey::hotkey::HotKey;
 use tokio::sync::broadcast;
 use crate::hotkeys::{HotkeyEvent, HotkeyId};
-use crate::core::AppConfig;
+use crate::core::{AppConfig, ClipDuration, HotkeyConfig};
 use std::collections::HashMap;
 
+/// Config key, hotkey and description of every global hotkey
+pub const HOTKEY_BINDINGS: [(&str, HotkeyId, &str); 5] = [
+    ("clip_15s", HotkeyId::Clip15s, "15s clip"),
+    ("clip_30s", HotkeyId::Clip30s, "30s clip"),
+    ("clip_1m", HotkeyId::Clip1m, "1m clip"),
+    ("clip_2m", HotkeyId::Clip2m, "2m clip"),
+    ("clip_5m", HotkeyId::Clip5m, "5m clip"),
+];
+
+/// Why global hotkeys may not fire in this session even though they registered.
+///
+/// `global-hotkey` registers through `RegisterHotKey` on Windows, Carbon hotkeys on macOS
+/// (which, unlike event taps, need no accessibility permission) and X11 key grabs on Linux.
+/// Only the last has a gap: Wayland compositors don't let apps grab keys globally.
+pub fn platform_hotkey_warning() -> Option<&'static str> {
+    #[cfg(target_os = "linux")]
+    {
+        let wayland_display = std::env::var("WAYLAND_DISPLAY").ok();
+        let display = std::env::var("DISPLAY").ok();
+        linux_session_warning(wayland_display.as_deref(), display.as_deref())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Warning for a Linux session with these `WAYLAND_DISPLAY` and `DISPLAY` values
+#[cfg(any(target_os = "linux", test))]
+pub(crate) fn linux_session_warning(wayland_display: Option<&str>, display: Option<&str>) -> Option<&'static str> {
+    let set = |value: Option<&str>| value.is_some_and(|value| !value.is_empty());
+    match (set(wayland_display), set(display)) {
+        (true, true) => Some("Wayland session: hotkeys are grabbed through XWayland and only fire while an X11 app has focus. \
+            Bind them to a command in your desktop's shortcut settings, or log into an X11 session."),
+        (true, false) => Some("Wayland session without XWayland: global hotkeys aren't available. \
+            Log into an X11 session to use them."),
+        (false, false) => Some("No X11 display found, global hotkeys aren't available."),
+        (false, true) => None,
+    }
+}
+
+/// Two clip durations bound to the same key combination
+#[derive(Debug, Clone, PartialEq)]
+pub struct HotkeyConflict {
+    pub first: ClipDuration,
+    pub second: ClipDuration,
+    /// The shared binding as written in the config, e.g. "Ctrl+Numpad1"
+    pub binding: String,
+}
+
+impl std::fmt::Display for HotkeyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let describe = |duration: ClipDuration| HOTKEY_BINDINGS.iter()
+            .find(|(_, hotkey_id, _)| hotkey_id.to_clip_duration() == duration)
+            .map_or("unknown", |(_, _, description)| *description);
+        write!(f, "{} and {} both use {}", describe(self.first), describe(self.second), self.binding)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HotkeyError {
+    #[error("Failed to create global hotkey manager: {0}")]
+    ManagerUnavailable(String),
+    #[error("Missing hotkey configuration for {0}")]
+    MissingBinding(String),
+    #[error("Invalid hotkey configuration for {key}: {reason}")]
+    InvalidBinding { key: String, reason: String },
+    #[error("Failed to register {key}: {reason}")]
+    RegistrationFailed { key: String, reason: String },
+}
+
 pub struct HotkeyManager {
-    _manager: GlobalHotKeyManager, // Keep reference alive
+    manager: GlobalHotKeyManager,
     event_sender: broadcast::Sender<HotkeyEvent>,
     hotkey_map: HashMap<u32, HotkeyId>,
+    /// Currently registered hotkeys, unregistered before registering new ones
+    registered: Vec<HotKey>,
+}
+
+/// Outcome of registering one configured hotkey
+#[derive(Debug)]
+pub struct BindingRegistration {
+    pub description: &'static str,
+    /// Binding as written in the config, empty if missing
+    pub binding: String,
+    pub result: Result<(), HotkeyError>,
 }
 
 impl HotkeyManager {
-    pub fn new(config: &AppConfig) -> anyhow::Result<(Self, broadcast::Receiver<HotkeyEvent>)> {
+    /// Find clip durations sharing a key combination. Bindings that don't parse are
+    /// left to registration to report.
+    pub fn validate_bindings(bindings: &HashMap<String, HotkeyConfig>) -> Vec<HotkeyConflict> {
+        let parsed: Vec<(ClipDuration, &HotkeyConfig, HotKey)> = HOTKEY_BINDINGS.iter()
+            .filter_map(|(config_key, hotkey_id, _)| {
+                let hotkey_config = bindings.get(*config_key)?;
+                let (modifiers, code) = hotkey_config.to_global_hotkey().ok()?;
+                Some((hotkey_id.to_clip_duration(), hotkey_config, HotKey::new(modifiers, code)))
+            })
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for (i, (first, first_config, first_hotkey)) in parsed.iter().enumerate() {
+            for (second, _, second_hotkey) in &parsed[i + 1..] {
+                if first_hotkey.id() == second_hotkey.id() {
+                    conflicts.push(HotkeyConflict {
+                        first: *first,
+                        second: *second,
+                        binding: first_config.label(),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Create the manager and register the hotkeys from `config`. Only failing to create the
+    /// OS hotkey manager is an error; per-binding results are returned for the GUI to show.
+    pub fn new(config: &AppConfig) -> Result<(Self, broadcast::Receiver<HotkeyEvent>, Vec<BindingRegistration>), HotkeyError> {
         log::info!("Initializing HotkeyManager...");
+        if let Some(warning) = platform_hotkey_warning() {
+            log::warn!("{}", warning);
+        }
         
         let manager = GlobalHotKeyManager::new()
-            .map_err(|e| anyhow::anyhow!("Failed to create GlobalHotKeyManager: {}", e))?;
+            .map_err(|e| HotkeyError::ManagerUnavailable(e.to_string()))?;
         log::info!("GlobalHotKeyManager created successfully");
         
         let (event_sender, event_receiver) = broadcast::channel(32);
-        let mut hotkey_map = HashMap::new();
+        let mut hotkey_manager = HotkeyManager {
+            manager,
+            event_sender,
+            hotkey_map: HashMap::new(),
+            registered: Vec::new(),
+        };
+        let registrations = hotkey_manager.register_bindings(config);
+
+        Ok((hotkey_manager, event_receiver, registrations))
+    }
+
+    /// Replace the registered hotkeys with the ones in `config`. Old bindings are unregistered
+    /// first so they don't keep holding OS hotkey slots. A binding that repeats an earlier
+    /// one is skipped (see `validate_bindings`).
+    pub fn register_bindings(&mut self, config: &AppConfig) -> Vec<BindingRegistration> {
+        if !self.registered.is_empty() {
+            log::info!("Unregistering {} global hotkeys", self.registered.len());
+            if let Err(e) = self.manager.unregister_all(&self.registered) {
+                log::warn!("Failed to unregister previous hotkeys: {}", e);
+            }
+            self.registered.clear();
+        }
+        self.hotkey_map.clear();
         
-        // Register hotkeys from config
-        let hotkey_mappings = [
-            ("clip_15s", HotkeyId::Clip15s, "15s clip"),
-            ("clip_30s", HotkeyId::Clip30s, "30s clip"),
-            ("clip_1m", HotkeyId::Clip1m, "1m clip"),
-            ("clip_2m", HotkeyId::Clip2m, "2m clip"),
-            ("clip_5m", HotkeyId::Clip5m, "5m clip"),
-        ];
-
-        log::info!("Registering {} global hotkeys...", hotkey_mappings.len());
-        for (config_key, hotkey_id, description) in hotkey_mappings {
-            if let Some(hotkey_config) = config.hotkeys.get(config_key) {
-                match hotkey_config.to_global_hotkey() {
-                    Ok((modifiers, code)) => {
-                        let hotkey = HotKey::new(modifiers, code);
-                        match manager.register(hotkey) {
-                            Ok(_) => {
-                                log::info!("Successfully registered {} -> {}", 
-                                    format!("{}+{}", hotkey_config.modifiers, hotkey_config.key),
-                                    description);
-                                hotkey_map.insert(hotkey.id(), hotkey_id);
-                            }
-                            Err(e) => {
-                                log::error!("Failed to register {} ({}+{}): {}", 
-                                    description, hotkey_config.modifiers, hotkey_config.key, e);
-                                return Err(anyhow::anyhow!("Failed to register {} ({}+{}): {}", 
-                                    description, hotkey_config.modifiers, hotkey_config.key, e));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        log::error!("Invalid hotkey configuration for {}: {}", config_key, e);
-                        return Err(anyhow::anyhow!("Invalid hotkey configuration for {}: {}", config_key, e));
-                    }
+        log::info!("Registering {} global hotkeys...", HOTKEY_BINDINGS.len());
+        let registrations: Vec<BindingRegistration> = HOTKEY_BINDINGS.iter()
+            .map(|&(config_key, hotkey_id, description)| {
+                let hotkey_config = config.hotkeys.get(config_key);
+                let result = match hotkey_config {
+                    Some(hotkey_config) => self.register_binding(hotkey_config, hotkey_id, description),
+                    None => Err(HotkeyError::MissingBinding(config_key.to_string())),
+                };
+                if let Err(ref e) = result {
+                    log::error!("Hotkey for {} not registered: {}", description, e);
                 }
-            } else {
-                log::error!("Missing hotkey configuration for: {}", config_key);
-                return Err(anyhow::anyhow!("Missing hotkey configuration for: {}", config_key));
-            }
+                BindingRegistration {
+                    description,
+                    binding: hotkey_config.map(HotkeyConfig::label).unwrap_or_default(),
+                    result,
+                }
+            })
+            .collect();
+        
+        log::info!("Registered {} of {} hotkeys", self.hotkey_map.len(), HOTKEY_BINDINGS.len());
+        registrations
+    }
+
+    fn register_binding(&mut self, hotkey_config: &HotkeyConfig, hotkey_id: HotkeyId, description: &str) -> Result<(), HotkeyError> {
+        let (modifiers, code) = hotkey_config.to_global_hotkey()
+            .map_err(|e| HotkeyError::InvalidBinding { key: hotkey_config.label(), reason: e.to_string() })?;
+        let hotkey = HotKey::new(modifiers, code);
+        
+        if let Some(existing) = self.hotkey_map.get(&hotkey.id()) {
+            return Err(HotkeyError::RegistrationFailed {
+                key: hotkey_config.label(),
+                reason: format!("already used by the {}s clip hotkey", existing.to_clip_duration() as u32),
+            });
         }
         
-        log::info!("All hotkeys registered successfully. Hotkey map size: {}", hotkey_map.len());
-
-        Ok((
-            HotkeyManager {
-                _manager: manager,
-                event_sender,
-                hotkey_map,
-            },
-            event_receiver,
-        ))
+        // Registration fails when another application already owns the combination
+        self.manager.register(hotkey)
+            .map_err(|e| HotkeyError::RegistrationFailed { key: hotkey_config.label(), reason: e.to_string() })?;
+        log::info!("Successfully registered {} -> {}", hotkey_config.label(), description);
+        self.hotkey_map.insert(hotkey.id(), hotkey_id);
+        self.registered.push(hotkey);
+        Ok(())
     }
 
     pub fn process_events(&self) {
@@ -31,4 +31,32 @@ mod tests {
         assert_eq!(ClipDuration::Minutes2.clone() as u32, 120);
         assert_eq!(ClipDuration::Minutes5.clone() as u32, 300);
     }
+
+    #[test]
+    fn test_validate_bindings_reports_shared_combinations() {
+        use crate::core::{AppConfig, HotkeyConfig};
+        use crate::hotkeys::HotkeyManager;
+
+        let mut hotkeys = AppConfig::default().hotkeys;
+        assert!(HotkeyManager::validate_bindings(&hotkeys).is_empty());
+
+        // Same combination, written slightly differently
+        hotkeys.insert("clip_30s".to_string(), HotkeyConfig { modifiers: "Ctrl".to_string(), key: "Numpad5".to_string() });
+        hotkeys.insert("clip_5m".to_string(), HotkeyConfig { modifiers: "Ctrl+".to_string(), key: "Numpad5".to_string() });
+        let conflicts = HotkeyManager::validate_bindings(&hotkeys);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].first, ClipDuration::Seconds30);
+        assert_eq!(conflicts[0].second, ClipDuration::Minutes5);
+        assert_eq!(conflicts[0].to_string(), "30s clip and 5m clip both use Ctrl+Numpad5");
+    }
+
+    #[test]
+    fn test_linux_session_warning() {
+        use crate::hotkeys::manager::linux_session_warning;
+
+        assert_eq!(linux_session_warning(None, Some(":0")), None);
+        assert_eq!(linux_session_warning(Some(""), Some(":0")), None);
+        assert!(linux_session_warning(Some("wayland-0"), Some(":0")).unwrap().contains("XWayland"));
+        assert!(linux_session_warning(Some("wayland-0"), None).unwrap().contains("aren't available"));
+    }
 }
@@ -11,12 +11,28 @@ use eframe::egui;
 use gui::ClipHelperApp;
 
 fn main() -> anyhow::Result<()> {
-    env_logger::init();
+    // Logging is configured from the config file, so peek at it before the logger exists
+    let config = core::AppConfig::load().ok();
+    core::logging::init_logging(config.as_ref().map_or(log::LevelFilter::Info, |c| c.log_level));
+    
+    // Restore the window layout from the last run
+    let window_rect = config.as_ref().and_then(|config| config.restorable_window_rect(desktop_rect()));
+    let maximized = config.as_ref().is_some_and(|config| config.window_maximized);
+    
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size(core::config::DEFAULT_WINDOW_SIZE)
+        .with_title("Clip Helper - OBS Replay Buffer Trimmer");
+    if let Some(rect) = window_rect {
+        viewport = viewport
+            .with_inner_size(rect.size())
+            .with_position(rect.min);
+    }
+    if maximized {
+        viewport = viewport.with_maximized(true);
+    }
     
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 800.0])
-            .with_title("Clip Helper - OBS Replay Buffer Trimmer"),
+        viewport,
         ..Default::default()
     };
 
@@ -36,3 +52,30 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Bounding box of all monitors in logical pixels, to keep restored windows on screen
+#[cfg(target_os = "windows")]
+fn desktop_rect() -> Option<egui::Rect> {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+    };
+    // SAFETY: GetSystemMetrics only reads system settings
+    let (x, y, width, height) = unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    };
+    (width > 0 && height > 0).then(|| egui::Rect::from_min_size(
+        egui::pos2(x as f32, y as f32),
+        egui::vec2(width as f32, height as f32),
+    ))
+}
+
+/// Monitor layouts aren't known before the window exists here; only sizes get sanitized
+#[cfg(not(target_os = "windows"))]
+fn desktop_rect() -> Option<egui::Rect> {
+    None
+}
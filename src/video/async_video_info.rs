@@ -3,7 +3,9 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::collections::HashMap;
 use tokio::sync::mpsc;
+use crate::video::FfmpegProcessPool;
 use crate::video::processor::{VideoProcessor, VideoInfo};
+use crate::core::VideoWriteState;
 
 /// Request to load video info for a file
 #[derive(Debug, Clone)]
@@ -18,6 +20,7 @@ pub struct VideoInfoResult {
     pub request_id: u64,
     pub file_path: PathBuf,
     pub result: Result<VideoInfo, String>,
+    pub write_state: VideoWriteState,
 }
 
 /// Asynchronous video info loader that runs FFmpeg in background threads
@@ -28,7 +31,7 @@ pub struct AsyncVideoInfoLoader {
 }
 
 impl AsyncVideoInfoLoader {
-    pub fn new() -> Self {
+    pub fn new(pool: Arc<FfmpegProcessPool>) -> Self {
         let (request_tx, mut request_rx) = mpsc::unbounded_channel::<VideoInfoRequest>();
         let (result_tx, result_rx) = mpsc::unbounded_channel::<VideoInfoResult>();
         
@@ -41,20 +44,24 @@ impl AsyncVideoInfoLoader {
                     let result_tx = result_tx.clone();
                     let request_clone = request.clone();
                     
-                    // Spawn background task for each FFmpeg call
-                    tokio::task::spawn_blocking(move || {
+                    // Run each ffprobe call in the shared FFmpeg process pool
+                    pool.submit(move || {
                         log::debug!("Loading video info for: {:?}", request_clone.file_path);
                         
-                        let result = match VideoProcessor::get_video_info(&request_clone.file_path) {
-                            Ok(info) => {
-                                log::debug!("Successfully loaded video info for: {:?} (duration: {:.2}s)", 
-                                    request_clone.file_path, info.duration);
+                        let (info, write_state) = VideoProcessor::probe_write_state(&request_clone.file_path);
+                        let result = match info {
+                            Some(info) => {
+                                log::debug!("Successfully loaded video info for: {:?} (duration: {:.2}s, {:?})", 
+                                    request_clone.file_path, info.duration, write_state);
                                 Ok(info)
                             }
-                            Err(e) => {
-                                log::debug!("Failed to load video info for: {:?} - {}", 
-                                    request_clone.file_path, e);
-                                Err(e.to_string())
+                            None => {
+                                log::debug!("Failed to load video info for: {:?} - {:?}", 
+                                    request_clone.file_path, write_state);
+                                Err(match &write_state {
+                                    VideoWriteState::Corrupted { reason } => reason.clone(),
+                                    _ => "File is still being written".to_string(),
+                                })
                             }
                         };
                         
@@ -62,12 +69,14 @@ impl AsyncVideoInfoLoader {
                             request_id: request_clone.request_id,
                             file_path: request_clone.file_path,
                             result,
+                            write_state,
                         };
                         
                         // Send result back to main thread
                         if let Err(e) = result_tx.send(response) {
                             log::error!("Failed to send video info result: {}", e);
                         }
+                        Ok(())
                     });
                 }
             });
@@ -121,9 +130,9 @@ pub struct VideoInfoManager {
 }
 
 impl VideoInfoManager {
-    pub fn new() -> Self {
+    pub fn new(pool: Arc<FfmpegProcessPool>) -> Self {
         Self {
-            loader: AsyncVideoInfoLoader::new(),
+            loader: AsyncVideoInfoLoader::new(pool),
             pending_requests: HashMap::new(),
         }
     }
@@ -13,7 +13,7 @@ use std::thread;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use rodio::{OutputStream, Sink, Source};
-use crate::core::clip::AudioTrack;
+use crate::core::clip::{AudioTrack, SurroundMode};
 
 pub struct SynchronizedAudioPlayer {
     audio_sink: Option<Arc<Mutex<Sink>>>,
@@ -32,7 +32,7 @@ pub struct SynchronizedAudioPlayer {
 pub struct AudioTrackState {
     pub index: usize,
     pub enabled: bool,
-    pub surround_mode: bool,
+    pub surround_mode: SurroundMode,
     pub name: String,
 }
 
@@ -422,9 +422,9 @@ impl SynchronizedAudioPlayer {
         // Build filter complex for mixing
         if enabled_tracks.len() == 1 {
             let track = enabled_tracks[0];
-            if track.surround_mode {
+            if let Some(pan) = track.surround_mode.pan_filter() {
                 cmd.arg("-filter_complex")
-                    .arg(format!("[0:a:{}]channelmap=map=FL|FR[mixed]", track.index))
+                    .arg(format!("[0:a:{}]{}[mixed]", track.index, pan))
                     .arg("-map").arg("[mixed]");
             } else {
                 cmd.arg("-map").arg(format!("0:a:{}", track.index));
@@ -435,8 +435,8 @@ impl SynchronizedAudioPlayer {
             let mut mix_inputs = Vec::new();
             
             for (i, track) in enabled_tracks.iter().enumerate() {
-                if track.surround_mode {
-                    filter_parts.push(format!("[0:a:{}]channelmap=map=FL|FR[a{}]", track.index, i));
+                if let Some(pan) = track.surround_mode.pan_filter() {
+                    filter_parts.push(format!("[0:a:{}]{}[a{}]", track.index, pan, i));
                     mix_inputs.push(format!("[a{}]", i));
                 } else {
                     mix_inputs.push(format!("[0:a:{}]", track.index));
@@ -498,9 +498,9 @@ impl SynchronizedAudioPlayer {
         // Build filter complex for mixing
         if enabled_tracks.len() == 1 {
             let track = enabled_tracks[0];
-            if track.surround_mode {
+            if let Some(pan) = track.surround_mode.pan_filter() {
                 cmd.arg("-filter_complex")
-                    .arg(format!("[0:a:{}]channelmap=map=FL|FR[mixed]", track.index))
+                    .arg(format!("[0:a:{}]{}[mixed]", track.index, pan))
                     .arg("-map").arg("[mixed]");
             } else {
                 cmd.arg("-map").arg(format!("0:a:{}", track.index));
@@ -511,8 +511,8 @@ impl SynchronizedAudioPlayer {
             let mut mix_inputs = Vec::new();
             
             for (i, track) in enabled_tracks.iter().enumerate() {
-                if track.surround_mode {
-                    filter_parts.push(format!("[0:a:{}]channelmap=map=FL|FR[a{}]", track.index, i));
+                if let Some(pan) = track.surround_mode.pan_filter() {
+                    filter_parts.push(format!("[0:a:{}]{}[a{}]", track.index, pan, i));
                     mix_inputs.push(format!("[a{}]", i));
                 } else {
                     mix_inputs.push(format!("[0:a:{}]", track.index));
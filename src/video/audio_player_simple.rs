@@ -13,7 +13,7 @@ use std::thread;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use rodio::{OutputStream, Sink};
-use crate::core::clip::AudioTrack;
+use crate::core::clip::{AudioTrack, SurroundMode};
 
 pub struct SynchronizedAudioPlayer {
     audio_sink: Option<Arc<Mutex<Sink>>>,
@@ -30,7 +30,7 @@ pub struct SynchronizedAudioPlayer {
 pub struct AudioTrackState {
     pub index: usize,
     pub enabled: bool,
-    pub surround_mode: bool,
+    pub surround_mode: SurroundMode,
     pub name: String,
 }
 
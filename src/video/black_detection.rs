@@ -0,0 +1,75 @@
+use std::path::Path;
+use std::process::Command;
+use crate::video::ffmpeg_manager::stderr_tail;
+
+/// Only this much of the start of a replay is searched. Black that hasn't ended by then
+/// is taken to be dark footage rather than a capture that hadn't started yet.
+pub const LEADING_BLACK_MAX_SECONDS: f64 = 5.0;
+/// Luma (0-1) a pixel may have to count as black. blackdetect's default of 0.10 also
+/// matches night scenes, so only near-zero luma counts here.
+const BLACK_PIXEL_THRESHOLD: f64 = 0.02;
+/// Share of a frame's pixels that have to be black
+const BLACK_PICTURE_RATIO: f64 = 0.98;
+/// Shorter black stretches (e.g. a single dropped frame) are ignored
+const MIN_BLACK_SECONDS: f64 = 0.1;
+/// Black starting later than this isn't leading black
+const BLACK_START_TOLERANCE_SECONDS: f64 = 0.05;
+
+/// Where the black frames at the start of `path` end, or 0.0 if it doesn't start with black.
+/// Decodes the first `LEADING_BLACK_MAX_SECONDS` (plus a second), so call this off the UI thread.
+pub fn detect_leading_black(path: &Path, ffmpeg: &Path) -> anyhow::Result<f64> {
+    // The whole stderr is needed here, not just its tail; -nostats keeps it short
+    let output = Command::new(ffmpeg)
+        .arg("-hide_banner")
+        .arg("-nostats")
+        .arg("-t").arg(format!("{:.3}", LEADING_BLACK_MAX_SECONDS + 1.0))
+        .arg("-i").arg(path)
+        .arg("-an")
+        .arg("-vf").arg(format!(
+            "blackdetect=d={}:pic_th={}:pix_th={}",
+            MIN_BLACK_SECONDS, BLACK_PICTURE_RATIO, BLACK_PIXEL_THRESHOLD
+        ))
+        .arg("-f").arg("null")
+        .arg("-")
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", ffmpeg.display(), e))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Black frame detection failed: {}", stderr_tail(&output.stderr)));
+    }
+    Ok(leading_black_end(&String::from_utf8_lossy(&output.stderr)))
+}
+
+/// End of a black stretch starting at the very beginning, from blackdetect's
+/// "black_start:0 black_end:1.2 black_duration:1.2" lines. Stretches running past
+/// `LEADING_BLACK_MAX_SECONDS` count as none.
+fn leading_black_end(stderr: &str) -> f64 {
+    let value = |line: &str, key: &str| -> Option<f64> {
+        line.split(key).nth(1)?.split_whitespace().next()?.parse().ok()
+    };
+    stderr.lines()
+        .filter(|line| line.contains("blackdetect"))
+        .filter_map(|line| Some((value(line, "black_start:")?, value(line, "black_end:")?)))
+        .find(|&(start, _)| start <= BLACK_START_TOLERANCE_SECONDS)
+        .map(|(_, end)| end)
+        .filter(|&end| end <= LEADING_BLACK_MAX_SECONDS)
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leading_black_end() {
+        let stderr = "Input #0, matroska,webm, from 'Replay.mkv':\n\
+            [blackdetect @ 0x55d1] black_start:0 black_end:1.25 black_duration:1.25\n\
+            [blackdetect @ 0x55d1] black_start:3.5 black_end:3.8 black_duration:0.3\n";
+        assert_eq!(leading_black_end(stderr), 1.25);
+
+        // Black later in the clip is a cut, not a late capture start
+        assert_eq!(leading_black_end("[blackdetect @ 0x55d1] black_start:2 black_end:2.5 black_duration:0.5"), 0.0);
+        // Black filling the whole searched window is most likely a dark scene
+        assert_eq!(leading_black_end("[blackdetect @ 0x55d1] black_start:0 black_end:6 black_duration:6"), 0.0);
+        assert_eq!(leading_black_end(""), 0.0);
+    }
+}
@@ -0,0 +1,106 @@
+use std::path::Path;
+use crate::core::format_file_size;
+
+/// Free space below this multiple of the estimated export size only warns, since the
+/// estimate is rough; below the estimate itself the export is refused
+pub const DISK_SPACE_HEADROOM: f64 = 1.2;
+/// Assumed bitrate of the re-encoded audio mix `trim_file` writes on top of the copied streams
+const MIXED_AUDIO_BITS_PER_SECOND: f64 = 192_000.0;
+
+/// Whether an export of the estimated size fits on the target volume
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskSpaceCheck {
+    Enough,
+    /// Fits, but with less headroom than `DISK_SPACE_HEADROOM`
+    Low { free: u64, estimated: u64 },
+    Insufficient { free: u64, estimated: u64 },
+}
+
+impl DiskSpaceCheck {
+    pub fn evaluate(free: u64, estimated: u64) -> Self {
+        if free < estimated {
+            Self::Insufficient { free, estimated }
+        } else if (free as f64) < estimated as f64 * DISK_SPACE_HEADROOM {
+            Self::Low { free, estimated }
+        } else {
+            Self::Enough
+        }
+    }
+
+    /// Warning or error text, None if there's enough space
+    pub fn message(&self) -> Option<String> {
+        match self {
+            Self::Enough => None,
+            Self::Low { free, estimated } => Some(format!(
+                "Low disk space: ~{} export, {} free",
+                format_file_size(*estimated),
+                format_file_size(*free)
+            )),
+            Self::Insufficient { free, estimated } => Some(format!(
+                "Not enough disk space: ~{} export, only {} free",
+                format_file_size(*estimated),
+                format_file_size(*free)
+            )),
+        }
+    }
+}
+
+/// Free space on the volume holding `path`. The path doesn't have to exist yet;
+/// the closest existing ancestor is asked instead.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|ancestor| ancestor.exists())?;
+    match fs2::available_space(existing) {
+        Ok(free) => Some(free),
+        Err(e) => {
+            log::debug!("Failed to read free space of {}: {}", existing.display(), e);
+            None
+        }
+    }
+}
+
+/// Check the estimate against the free space where `output_path` will be written.
+/// Reports `Enough` if the free space can't be read.
+pub fn check_disk_space(output_path: &Path, estimated: u64) -> DiskSpaceCheck {
+    available_space(output_path)
+        .map(|free| DiskSpaceCheck::evaluate(free, estimated))
+        .unwrap_or(DiskSpaceCheck::Enough)
+}
+
+/// Stream-copy export size: the trimmed share of the source file, plus the re-encoded
+/// audio mix if one is written
+pub fn estimate_stream_copy_size(input_size: u64, source_duration: f64, trim_duration: f64, mixes_audio: bool) -> u64 {
+    let share = if source_duration > 0.0 {
+        (trim_duration / source_duration).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let mix = if mixes_audio {
+        MIXED_AUDIO_BITS_PER_SECOND / 8.0 * trim_duration.max(0.0)
+    } else {
+        0.0
+    };
+    (input_size as f64 * share + mix) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_space_check() {
+        assert_eq!(DiskSpaceCheck::evaluate(1000, 500), DiskSpaceCheck::Enough);
+        assert_eq!(DiskSpaceCheck::evaluate(550, 500), DiskSpaceCheck::Low { free: 550, estimated: 500 });
+        assert_eq!(DiskSpaceCheck::evaluate(400, 500), DiskSpaceCheck::Insufficient { free: 400, estimated: 500 });
+        assert!(DiskSpaceCheck::Enough.message().is_none());
+    }
+
+    #[test]
+    fn test_estimate_stream_copy_size() {
+        // A 30s slice of a 120s, 400 MB replay
+        assert_eq!(estimate_stream_copy_size(400_000_000, 120.0, 30.0, false), 100_000_000);
+        // The mix adds 24 KB per second
+        assert_eq!(estimate_stream_copy_size(400_000_000, 120.0, 30.0, true), 100_720_000);
+        // Unknown source duration: assume the whole file
+        assert_eq!(estimate_stream_copy_size(400_000_000, 0.0, 30.0, false), 400_000_000);
+    }
+}
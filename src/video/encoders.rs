@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Encoders found by `available_encoders`, detected once per run
+static AVAILABLE_ENCODERS: OnceLock<Vec<VideoEncoder>> = OnceLock::new();
+
+/// Video encoder for exports that re-encode (target-size exports and merges)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum VideoEncoder {
+    /// Software H.264, always available
+    #[default]
+    Libx264,
+    H264Nvenc,
+    HevcNvenc,
+    H264Qsv,
+    H264Amf,
+}
+
+/// What a re-encode aims for; each encoder spells these differently
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncoderQuality {
+    /// Constant quality on libx264's CRF scale (0-51, lower is better)
+    Crf(u8),
+    /// Average video bitrate in kbit/s
+    Bitrate(f64),
+}
+
+impl VideoEncoder {
+    pub const ALL: [VideoEncoder; 5] = [Self::Libx264, Self::H264Nvenc, Self::HevcNvenc, Self::H264Qsv, Self::H264Amf];
+
+    /// FFmpeg's name for the encoder
+    pub fn codec_name(&self) -> &'static str {
+        match self {
+            Self::Libx264 => "libx264",
+            Self::H264Nvenc => "h264_nvenc",
+            Self::HevcNvenc => "hevc_nvenc",
+            Self::H264Qsv => "h264_qsv",
+            Self::H264Amf => "h264_amf",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Libx264 => "CPU (libx264)",
+            Self::H264Nvenc => "NVIDIA NVENC H.264",
+            Self::HevcNvenc => "NVIDIA NVENC HEVC",
+            Self::H264Qsv => "Intel Quick Sync H.264",
+            Self::H264Amf => "AMD AMF H.264",
+        }
+    }
+
+    /// Only libx264 takes FFmpeg's `-pass 1`/`-pass 2`; the hardware encoders do their
+    /// rate control in a single pass
+    pub fn supports_two_pass(&self) -> bool {
+        *self == Self::Libx264
+    }
+
+    /// `-c:v` and the encoder's own preset and rate control options for `quality`
+    pub fn args(&self, quality: EncoderQuality) -> Vec<String> {
+        let mut args = vec!["-c:v".to_string(), self.codec_name().to_string()];
+        let extra: Vec<String> = match (self, quality) {
+            (Self::Libx264, EncoderQuality::Crf(crf)) => vec!["-preset".into(), "medium".into(), "-crf".into(), crf.to_string()],
+            (Self::Libx264, EncoderQuality::Bitrate(kbps)) => vec!["-preset".into(), "medium".into(), "-b:v".into(), kbit(kbps)],
+            // NVENC's constant quality (-cq) is roughly on the CRF scale; -b:v 0 lifts its default bitrate cap
+            (Self::H264Nvenc | Self::HevcNvenc, EncoderQuality::Crf(crf)) => vec![
+                "-preset".into(), "p5".into(), "-rc".into(), "vbr".into(), "-cq".into(), crf.to_string(), "-b:v".into(), "0".into(),
+            ],
+            (Self::H264Nvenc | Self::HevcNvenc, EncoderQuality::Bitrate(kbps)) => vec![
+                "-preset".into(), "p5".into(), "-rc".into(), "vbr".into(),
+                "-b:v".into(), kbit(kbps), "-maxrate".into(), kbit(kbps * 1.5), "-bufsize".into(), kbit(kbps * 2.0),
+            ],
+            (Self::H264Qsv, EncoderQuality::Crf(crf)) => vec!["-preset".into(), "medium".into(), "-global_quality".into(), crf.to_string()],
+            (Self::H264Qsv, EncoderQuality::Bitrate(kbps)) => vec![
+                "-preset".into(), "medium".into(), "-b:v".into(), kbit(kbps), "-maxrate".into(), kbit(kbps * 1.5),
+            ],
+            (Self::H264Amf, EncoderQuality::Crf(crf)) => vec![
+                "-quality".into(), "balanced".into(), "-rc".into(), "cqp".into(),
+                "-qp_i".into(), crf.to_string(), "-qp_p".into(), crf.to_string(),
+            ],
+            (Self::H264Amf, EncoderQuality::Bitrate(kbps)) => vec![
+                "-quality".into(), "balanced".into(), "-rc".into(), "vbr_peak".into(),
+                "-b:v".into(), kbit(kbps), "-maxrate".into(), kbit(kbps * 1.5),
+            ],
+        };
+        args.extend(extra);
+        args
+    }
+
+    /// How far above a target size the output can end up: two-pass libx264 lands close to it,
+    /// the single-pass hardware encoders are only held to their `-maxrate` of 1.5x
+    pub fn target_size_overshoot(&self) -> f64 {
+        if self.supports_two_pass() {
+            1.0
+        } else {
+            1.5
+        }
+    }
+
+    /// This encoder if it works here, otherwise libx264
+    pub fn or_fallback(self) -> Self {
+        if self == Self::Libx264 || available_encoders().contains(&self) {
+            self
+        } else {
+            log::warn!("{} isn't available, encoding with libx264 instead", self.codec_name());
+            Self::Libx264
+        }
+    }
+}
+
+fn kbit(kbps: f64) -> String {
+    format!("{:.0}k", kbps)
+}
+
+/// Encoders that FFmpeg lists and that also initialize when tried. Some builds list
+/// NVENC/QSV/AMF without a matching GPU or driver, so each is test-encoded once.
+/// Detected on first use and cached; the first call blocks for a few seconds at most.
+pub fn available_encoders() -> &'static [VideoEncoder] {
+    AVAILABLE_ENCODERS.get_or_init(|| {
+        let listed = Command::new("ffmpeg")
+            .arg("-hide_banner")
+            .arg("-encoders")
+            .output()
+            .map(|output| listed_encoders(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default();
+        let available: Vec<VideoEncoder> = listed.into_iter()
+            .filter(|&encoder| encoder == VideoEncoder::Libx264 || encoder_initializes(encoder))
+            .collect();
+        log::info!("Usable video encoders: {:?}", available);
+        available
+    })
+}
+
+/// The encoders, if detection has already finished; for the UI, which shouldn't wait for it
+pub fn detected_encoders() -> Option<&'static [VideoEncoder]> {
+    AVAILABLE_ENCODERS.get().map(Vec::as_slice)
+}
+
+/// Known encoders in `ffmpeg -encoders` output (" V....D h264_nvenc  NVIDIA NVENC H.264 encoder")
+fn listed_encoders(output: &str) -> Vec<VideoEncoder> {
+    let names: Vec<&str> = output.lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .collect();
+    VideoEncoder::ALL.into_iter()
+        .filter(|encoder| names.contains(&encoder.codec_name()))
+        .collect()
+}
+
+/// Encode a few frames of a generated picture to see whether the encoder really works
+fn encoder_initializes(encoder: VideoEncoder) -> bool {
+    let result = Command::new("ffmpeg")
+        .arg("-hide_banner")
+        .arg("-f").arg("lavfi")
+        .arg("-i").arg("color=black:s=256x256:d=0.2")
+        .arg("-frames:v").arg("3")
+        .arg("-c:v").arg(encoder.codec_name())
+        .arg("-f").arg("null")
+        .arg("-")
+        .output();
+    match result {
+        Ok(output) if output.status.success() => true,
+        Ok(output) => {
+            log::info!("{} is listed but doesn't initialize: {}", encoder.codec_name(),
+                crate::video::ffmpeg_manager::stderr_tail(&output.stderr));
+            false
+        }
+        Err(e) => {
+            log::warn!("Failed to test {}: {}", encoder.codec_name(), e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listed_encoders() {
+        let output = "Encoders:\n V..... = Video\n ------\n V....D libx264              libx264 H.264 / AVC\n \
+            V....D h264_nvenc           NVIDIA NVENC H.264 encoder (codec h264)\n A....D aac                  AAC";
+        assert_eq!(listed_encoders(output), vec![VideoEncoder::Libx264, VideoEncoder::H264Nvenc]);
+    }
+
+    #[test]
+    fn test_encoder_quality_args() {
+        assert_eq!(VideoEncoder::Libx264.args(EncoderQuality::Crf(18)), ["-c:v", "libx264", "-preset", "medium", "-crf", "18"]);
+        let nvenc = VideoEncoder::H264Nvenc.args(EncoderQuality::Bitrate(2000.0));
+        assert_eq!(&nvenc[..2], ["-c:v", "h264_nvenc"]);
+        assert!(nvenc.windows(2).any(|pair| pair == ["-b:v", "2000k"]));
+        assert!(VideoEncoder::H264Amf.args(EncoderQuality::Crf(20)).windows(2).any(|pair| pair == ["-qp_i", "20"]));
+    }
+
+    #[test]
+    fn test_target_size_overshoot() {
+        assert_eq!(VideoEncoder::Libx264.target_size_overshoot(), 1.0);
+        for encoder in [VideoEncoder::H264Nvenc, VideoEncoder::HevcNvenc, VideoEncoder::H264Qsv, VideoEncoder::H264Amf] {
+            assert_eq!(encoder.target_size_overshoot(), 1.5);
+        }
+    }
+}
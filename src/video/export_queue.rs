@@ -0,0 +1,483 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use crate::core::{AudioTrack, ClipMetadata};
+use crate::video::processor::{
+    AnimatedExportSettings, ExportFormat, VideoProcessor, STREAM_COPY_DURATION_TOLERANCE_SECONDS,
+    VERIFY_DURATION_TOLERANCE_SECONDS,
+};
+use crate::video::{FfmpegProcessPool, VideoEncoder};
+
+/// Name of the preset used when no specific preset was chosen
+pub const DEFAULT_PRESET_NAME: &str = "default";
+
+/// Export settings applied to every video export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportPreset {
+    pub name: String,
+    /// Passed to FFmpeg verbatim, one argument per entry, right before the output path.
+    /// Edited as one argument per line, so blank entries can appear and are skipped.
+    #[serde(default)]
+    pub extra_ffmpeg_args: Vec<String>,
+    /// Re-encode with two-pass H.264 to hit `target_size_mb` instead of stream copying
+    #[serde(default)]
+    pub two_pass: bool,
+    #[serde(default)]
+    pub target_size_mb: Option<f64>,
+    /// Also extract every enabled audio track to its own WAV next to the export
+    #[serde(default)]
+    pub export_audio_tracks_separately: bool,
+    /// Encoder for target-size exports. Hardware encoders fall back to libx264 when
+    /// they aren't available and encode in one pass instead of two.
+    #[serde(default)]
+    pub video_encoder: VideoEncoder,
+    /// Write a `<output>.json` metadata file next to every video export
+    #[serde(default)]
+    pub write_sidecar: bool,
+    /// Frame rate of GIF (and WebP) exports
+    #[serde(default = "default_gif_fps")]
+    pub gif_fps: u8,
+    /// Output width of GIF (and WebP) exports; the height follows the aspect ratio
+    #[serde(default = "default_gif_width")]
+    pub gif_width: u32,
+    /// libwebp quality, 0-100
+    #[serde(default = "default_webp_quality")]
+    pub webp_quality: u8,
+}
+
+fn default_gif_fps() -> u8 {
+    15
+}
+
+fn default_gif_width() -> u32 {
+    640
+}
+
+fn default_webp_quality() -> u8 {
+    75
+}
+
+impl ExportPreset {
+    /// The extra arguments without blank entries and surrounding whitespace
+    pub fn ffmpeg_args(&self) -> Vec<String> {
+        self.extra_ffmpeg_args.iter()
+            .map(|arg| arg.trim())
+            .filter(|arg| !arg.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Target size for video exports, if two-pass encoding is on and a size is set
+    pub fn two_pass_target_mb(&self) -> Option<f64> {
+        self.target_size_mb.filter(|&size| self.two_pass && size > 0.0)
+    }
+
+    /// Settings handed to GIF/WebP export jobs
+    pub fn animated_settings(&self) -> AnimatedExportSettings {
+        AnimatedExportSettings {
+            fps: self.gif_fps as u32,
+            width: self.gif_width,
+            webp_quality: self.webp_quality,
+        }
+    }
+}
+
+impl Default for ExportPreset {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_PRESET_NAME.to_string(),
+            extra_ffmpeg_args: Vec::new(),
+            two_pass: false,
+            target_size_mb: None,
+            export_audio_tracks_separately: false,
+            video_encoder: VideoEncoder::default(),
+            write_sidecar: false,
+            gif_fps: default_gif_fps(),
+            gif_width: default_gif_width(),
+            webp_quality: default_webp_quality(),
+        }
+    }
+}
+
+/// Serializable description of an export, persisted so queued exports survive restarts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportJobSpec {
+    pub clip_path: PathBuf,
+    pub output_path: PathBuf,
+    pub preset_name: String,
+    pub trim_start: f64,
+    pub trim_end: f64,
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub animated: AnimatedExportSettings,
+    #[serde(default)]
+    pub sidecar: Option<ClipMetadata>,
+    #[serde(default)]
+    pub export_audio_tracks_separately: bool,
+    #[serde(default)]
+    pub extra_ffmpeg_args: Vec<String>,
+    #[serde(default)]
+    pub target_size_mb: Option<f64>,
+    #[serde(default)]
+    pub video_encoder: VideoEncoder,
+}
+
+/// A single export waiting in (or being processed by) the export queue
+#[derive(Debug, Clone)]
+pub struct ExportJob {
+    pub id: u64,
+    pub clip_path: PathBuf,
+    pub output_path: PathBuf,
+    pub preset_name: String,
+    pub trim_start: f64,
+    pub trim_end: f64,
+    /// Audio track layout; empty means FFmpeg's default stream selection
+    pub audio_tracks: Vec<AudioTrack>,
+    pub force_overwrite: bool,
+    pub format: ExportFormat,
+    /// Only used for GIF/WebP exports
+    pub animated: AnimatedExportSettings,
+    /// Written as `<output_stem>.json` after a successful video export
+    pub sidecar: Option<ClipMetadata>,
+    /// Also extract each enabled audio track to `<output_stem>_track<index>.wav`
+    /// after the video export has finished
+    pub export_audio_tracks_separately: bool,
+    /// From the preset, appended to the FFmpeg command of video exports
+    pub extra_ffmpeg_args: Vec<String>,
+    /// Two-pass re-encode to roughly this size instead of a stream copy
+    pub target_size_mb: Option<f64>,
+    /// Encoder for the target-size re-encode
+    pub video_encoder: VideoEncoder,
+}
+
+impl ExportJob {
+    pub fn spec(&self) -> ExportJobSpec {
+        ExportJobSpec {
+            clip_path: self.clip_path.clone(),
+            output_path: self.output_path.clone(),
+            preset_name: self.preset_name.clone(),
+            trim_start: self.trim_start,
+            trim_end: self.trim_end,
+            format: self.format,
+            animated: self.animated.clone(),
+            sidecar: self.sidecar.clone(),
+            export_audio_tracks_separately: self.export_audio_tracks_separately,
+            extra_ffmpeg_args: self.extra_ffmpeg_args.clone(),
+            target_size_mb: self.target_size_mb,
+            video_encoder: self.video_encoder,
+        }
+    }
+}
+
+impl From<ExportJobSpec> for ExportJob {
+    fn from(spec: ExportJobSpec) -> Self {
+        Self {
+            id: 0, // Assigned when enqueued
+            clip_path: spec.clip_path,
+            output_path: spec.output_path,
+            preset_name: spec.preset_name,
+            trim_start: spec.trim_start,
+            trim_end: spec.trim_end,
+            audio_tracks: Vec::new(),
+            force_overwrite: false,
+            format: spec.format,
+            animated: spec.animated,
+            sidecar: spec.sidecar,
+            export_audio_tracks_separately: spec.export_audio_tracks_separately,
+            extra_ffmpeg_args: spec.extra_ffmpeg_args,
+            target_size_mb: spec.target_size_mb,
+            video_encoder: spec.video_encoder,
+        }
+    }
+}
+
+/// Result of a finished export job
+#[derive(Debug, Clone)]
+pub struct ExportResult {
+    pub job: ExportJob,
+    pub result: Result<(), String>,
+    /// Separately extracted audio tracks: track index and the WAV path or error
+    pub audio_track_results: Vec<(usize, Result<PathBuf, String>)>,
+    /// Where the export was moved after failing verification, None if it was deleted as
+    /// unplayable; `result` holds the reason
+    pub corrupt_output: Option<PathBuf>,
+}
+
+/// Sequential export queue backed by a background worker thread.
+/// Only one job is handed to FFmpeg at a time; the rest wait in order.
+pub struct ExportQueue {
+    job_sender: mpsc::UnboundedSender<ExportJob>,
+    result_receiver: Arc<Mutex<mpsc::UnboundedReceiver<ExportResult>>>,
+    queued_jobs: VecDeque<ExportJob>,
+    active_job: Option<ExportJob>,
+    next_job_id: u64,
+    /// Pass (1 or 2) of the running two-pass export, 0 otherwise
+    current_pass: Arc<AtomicU8>,
+}
+
+impl ExportQueue {
+    pub fn new(pool: Arc<FfmpegProcessPool>) -> Self {
+        let (job_tx, mut job_rx) = mpsc::unbounded_channel::<ExportJob>();
+        let (result_tx, result_rx) = mpsc::unbounded_channel::<ExportResult>();
+        let current_pass = Arc::new(AtomicU8::new(0));
+        let worker_pass = current_pass.clone();
+
+        // Spawn worker thread that runs exports one after another
+        thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().expect("Failed to create export runtime");
+
+            rt.block_on(async {
+                while let Some(job) = job_rx.recv().await {
+                    let result_tx = result_tx.clone();
+                    let current_pass = worker_pass.clone();
+
+                    let handle = pool.submit(move || {
+                        log::info!("Exporting {} -> {}", job.clip_path.display(), job.output_path.display());
+
+                        let mut result = if job.format.is_animated() {
+                            VideoProcessor::export_animated(
+                                &job.clip_path,
+                                job.trim_start,
+                                job.trim_end,
+                                job.format,
+                                &job.animated,
+                                &job.output_path,
+                                job.force_overwrite,
+                            )
+                        } else if let Some(target_size_mb) = job.target_size_mb {
+                            let result = VideoProcessor::trim_clip_two_pass(&job, target_size_mb, &current_pass);
+                            current_pass.store(0, Ordering::Relaxed);
+                            result
+                        } else {
+                            VideoProcessor::trim_file(
+                                &job.clip_path,
+                                job.trim_start,
+                                job.trim_end,
+                                &job.audio_tracks,
+                                &job.output_path,
+                                job.force_overwrite,
+                                &job.extra_ffmpeg_args,
+                            )
+                        }.map_err(|e| e.to_string());
+
+                        // Stream copy can silently drop tracks or end early, so check what was written
+                        let mut corrupt_output = None;
+                        if result.is_ok() && !job.format.is_animated() {
+                            let expected_tracks = VideoProcessor::expected_audio_track_count(&job.audio_tracks);
+                            let tolerance = if job.target_size_mb.is_some() {
+                                VERIFY_DURATION_TOLERANCE_SECONDS
+                            } else {
+                                STREAM_COPY_DURATION_TOLERANCE_SECONDS
+                            };
+                            if let Err(e) = VideoProcessor::verify_output(&job.output_path, job.trim_end - job.trim_start, tolerance, expected_tracks) {
+                                corrupt_output = VideoProcessor::dispose_unverified_output(&job.output_path, &e);
+                                result = Err(match corrupt_output {
+                                    Some(_) => format!("Export failed verification: {}", e),
+                                    None => format!("Export failed verification: {}. The broken file was deleted.", e),
+                                });
+                            }
+                        }
+
+                        // A missing sidecar shouldn't fail an otherwise good export
+                        if let (Ok(()), Some(sidecar)) = (&result, &job.sidecar) {
+                            let mut sidecar = sidecar.clone();
+                            sidecar.exported_at = Some(chrono::Local::now());
+                            sidecar.ffmpeg_version = VideoProcessor::ffmpeg_version();
+                            if let Err(e) = sidecar.write_sidecar(&job.output_path) {
+                                log::warn!("Failed to write sidecar for {}: {}", job.output_path.display(), e);
+                            }
+                        }
+
+                        if let Err(ref e) = result {
+                            log::error!("Export of {} failed: {}", job.clip_path.display(), e);
+                        }
+
+                        // Only start on the audio tracks once the video itself is in place
+                        let mut audio_track_results = Vec::new();
+                        if result.is_ok() && job.export_audio_tracks_separately && !job.format.is_animated() {
+                            if job.audio_tracks.is_empty() {
+                                log::warn!("No audio track layout for {}, skipping separate track export", job.clip_path.display());
+                            }
+                            audio_track_results = VideoProcessor::extract_audio_tracks(
+                                &job.clip_path,
+                                job.trim_start,
+                                job.trim_end,
+                                &job.audio_tracks,
+                                &job.output_path,
+                            )
+                            .into_iter()
+                            .map(|(index, track_result)| {
+                                if let Err(ref e) = track_result {
+                                    log::error!("Extracting audio track {} of {} failed: {}", index, job.clip_path.display(), e);
+                                }
+                                (index, track_result.map_err(|e| e.to_string()))
+                            })
+                            .collect();
+                        }
+
+                        if let Err(e) = result_tx.send(ExportResult { job, result, audio_track_results, corrupt_output }) {
+                            log::error!("Failed to send export result: {}", e);
+                        }
+                        Ok(())
+                    });
+
+                    // Exports still run one at a time; wait for this one off the async runtime
+                    match tokio::task::spawn_blocking(move || handle.wait()).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => log::error!("Export task failed: {}", e),
+                        Err(e) => log::error!("Export task panicked: {}", e),
+                    }
+                }
+            });
+        });
+
+        Self {
+            job_sender: job_tx,
+            result_receiver: Arc::new(Mutex::new(result_rx)),
+            queued_jobs: VecDeque::new(),
+            active_job: None,
+            next_job_id: 0,
+            current_pass,
+        }
+    }
+
+    /// Add a job to the end of the queue, returning its id
+    pub fn enqueue(&mut self, mut job: ExportJob) -> u64 {
+        self.next_job_id += 1;
+        let job_id = self.next_job_id;
+        job.id = job_id;
+        log::debug!("Queued export job {} for {}", job_id, job.clip_path.display());
+        self.queued_jobs.push_back(job);
+        self.dispatch_next();
+        job_id
+    }
+
+    /// Collect finished jobs and start the next queued one (non-blocking)
+    pub fn process_completed(&mut self) -> Vec<ExportResult> {
+        let mut results = Vec::new();
+
+        if let Ok(mut receiver) = self.result_receiver.lock() {
+            while let Ok(result) = receiver.try_recv() {
+                results.push(result);
+            }
+        }
+
+        for result in &results {
+            if self.active_job.as_ref().map(|job| job.id) == Some(result.job.id) {
+                self.active_job = None;
+            }
+        }
+
+        self.dispatch_next();
+        results
+    }
+
+    fn dispatch_next(&mut self) {
+        if self.active_job.is_some() {
+            return;
+        }
+
+        if let Some(job) = self.queued_jobs.pop_front() {
+            if let Err(e) = self.job_sender.send(job.clone()) {
+                log::error!("Failed to send export job to worker: {}", e);
+                self.queued_jobs.push_front(job);
+                return;
+            }
+            self.active_job = Some(job);
+        }
+    }
+
+    /// Specs of every unfinished job, the running one first
+    pub fn pending_specs(&self) -> Vec<ExportJobSpec> {
+        self.active_job.iter()
+            .chain(self.queued_jobs.iter())
+            .map(ExportJob::spec)
+            .collect()
+    }
+
+    pub fn active_job(&self) -> Option<&ExportJob> {
+        self.active_job.as_ref()
+    }
+
+    /// Pass (1 or 2) the running export is on, if it's a two-pass export
+    pub fn current_pass(&self) -> Option<u8> {
+        Some(self.current_pass.load(Ordering::Relaxed)).filter(|&pass| pass > 0)
+    }
+
+    /// Number of unfinished jobs including the running one
+    pub fn len(&self) -> usize {
+        self.queued_jobs.len() + usize::from(self.active_job.is_some())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_spec() -> ExportJobSpec {
+        ExportJobSpec {
+            clip_path: PathBuf::from("Replay 2025-08-17 21-52-01.mkv"),
+            output_path: PathBuf::from("trimmed/Replay 2025-08-17 21-52-01.mkv"),
+            preset_name: DEFAULT_PRESET_NAME.to_string(),
+            trim_start: 12.5,
+            trim_end: 42.5,
+            format: ExportFormat::Video,
+            animated: AnimatedExportSettings::default(),
+            sidecar: None,
+            export_audio_tracks_separately: false,
+            extra_ffmpeg_args: vec!["-movflags".to_string(), "+faststart".to_string()],
+            target_size_mb: Some(8.0),
+            video_encoder: VideoEncoder::H264Nvenc,
+        }
+    }
+
+    #[test]
+    fn test_spec_round_trip_through_job() {
+        let spec = sample_spec();
+        let job = ExportJob::from(spec.clone());
+
+        assert!(job.audio_tracks.is_empty());
+        assert!(!job.force_overwrite);
+        assert_eq!(job.spec(), spec);
+    }
+
+    #[test]
+    fn test_spec_without_format_defaults_to_video() {
+        let json = r#"[{
+            "clip_path": "Replay 2025-08-17 21-52-01.mkv",
+            "output_path": "trimmed/Replay 2025-08-17 21-52-01.mkv",
+            "preset_name": "default",
+            "trim_start": 0.0,
+            "trim_end": 15.0
+        }]"#;
+        let specs: Vec<ExportJobSpec> = serde_json::from_str(json).expect("Failed to parse old queue file");
+        assert_eq!(specs[0].format, ExportFormat::Video);
+    }
+
+    #[test]
+    fn test_spec_serialization() {
+        let specs = vec![sample_spec()];
+        let json = serde_json::to_string(&specs).expect("Failed to serialize specs");
+        let parsed: Vec<ExportJobSpec> = serde_json::from_str(&json).expect("Failed to parse specs");
+        assert_eq!(parsed, specs);
+    }
+
+    #[test]
+    fn test_preset_gif_settings() {
+        let preset: ExportPreset = serde_json::from_str(r#"{"name": "default"}"#).expect("Failed to parse old preset");
+        assert_eq!(preset, ExportPreset::default());
+        assert_eq!((preset.gif_fps, preset.gif_width), (15, 640));
+
+        let settings = ExportPreset { gif_fps: 10, gif_width: 320, ..preset }.animated_settings();
+        assert_eq!((settings.fps, settings.width, settings.webp_quality), (10, 320, 75));
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::process::{Command, Output};
+use std::io::Read;
+use std::process::{Command, ExitStatus, Output, Stdio};
 use anyhow::Result;
 
 /// Global FFmpeg process manager that enforces a maximum of 4 concurrent processes
@@ -66,3 +67,111 @@ pub fn execute_ffmpeg(command: Command) -> Result<Output> {
     let manager = get_ffmpeg_manager();
     manager.execute_ffmpeg(command)
 }
+
+/// Bytes of stderr kept while a process runs; anything older is dropped
+const STDERR_BUFFER_BYTES: usize = 16 * 1024;
+
+/// Number of stderr lines included in error messages
+const STDERR_TAIL_LINES: usize = 8;
+
+/// Output of a finished FFmpeg/ffprobe run with stderr cut down to its last few lines
+pub struct CapturedOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    pub stderr_tail: String,
+}
+
+impl CapturedOutput {
+    /// Error message for a failed run, e.g. "FFmpeg error (exit code 1): <last stderr lines>"
+    pub fn failure_message(&self, context: &str) -> String {
+        let exit = match self.status.code() {
+            Some(code) => format!("exit code {}", code),
+            None => "terminated".to_string(),
+        };
+
+        if self.stderr_tail.is_empty() {
+            format!("{} ({})", context, exit)
+        } else {
+            format!("{} ({}):\n{}", context, exit, self.stderr_tail)
+        }
+    }
+}
+
+/// Run a command to completion like `Command::output`, but only keep the end of stderr
+/// so a chatty or runaway process can't grow memory without bound
+pub fn output_with_stderr_tail(command: &mut Command) -> std::io::Result<CapturedOutput> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stderr = child.stderr.take();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut kept = Vec::new();
+        let Some(mut stderr) = stderr else {
+            return kept;
+        };
+
+        let mut chunk = [0u8; 4096];
+        while let Ok(read) = stderr.read(&mut chunk) {
+            if read == 0 {
+                break;
+            }
+            kept.extend_from_slice(&chunk[..read]);
+            if kept.len() > STDERR_BUFFER_BYTES {
+                kept.drain(..kept.len() - STDERR_BUFFER_BYTES);
+            }
+        }
+        kept
+    });
+
+    let mut stdout = Vec::new();
+    if let Some(mut child_stdout) = child.stdout.take() {
+        child_stdout.read_to_end(&mut stdout)?;
+    }
+
+    let status = child.wait()?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    Ok(CapturedOutput {
+        status,
+        stdout,
+        stderr_tail: stderr_tail(&stderr),
+    })
+}
+
+/// Last few non-empty lines of FFmpeg stderr. Progress updates end in `\r`, so those count as lines too.
+pub fn stderr_tail(stderr: &[u8]) -> String {
+    let text = String::from_utf8_lossy(stderr);
+    let lines: Vec<&str> = text
+        .split(['\n', '\r'])
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stderr_tail_keeps_last_lines() {
+        let stderr: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let tail = stderr_tail(stderr.as_bytes());
+
+        assert_eq!(tail.lines().count(), STDERR_TAIL_LINES);
+        assert!(tail.starts_with("line 13"));
+        assert!(tail.ends_with("line 20"));
+    }
+
+    #[test]
+    fn test_stderr_tail_splits_progress_updates() {
+        let stderr = b"frame=  10 fps=0.0\rframe=  20 fps=0.0\r\n[out#0] Conversion failed!\n";
+        let tail = stderr_tail(stderr);
+
+        assert_eq!(tail, "frame=  10 fps=0.0\nframe=  20 fps=0.0\n[out#0] Conversion failed!");
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread::JoinHandle;
+use crate::video::ffmpeg_manager::output_with_stderr_tail;
+use crate::video::processor::HDR_TRANSFERS;
+
+/// Container and stream details shown in the clip editor's "Technical Info" section
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TechnicalInfo {
+    pub container: String,
+    pub video_codec: String,
+    pub width: u32,
+    pub height: u32,
+    /// Average frame rate
+    pub frame_rate: Option<f64>,
+    /// The nominal and average frame rates differ, which editors often handle badly
+    pub variable_frame_rate: bool,
+    pub bit_depth: Option<u32>,
+    pub color_space: String,
+    pub color_transfer: String,
+    /// Of the first audio stream
+    pub audio_codec: String,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+    pub audio_streams: usize,
+}
+
+impl TechnicalInfo {
+    /// Details from ffprobe's `-show_format -show_streams` JSON
+    pub fn from_ffprobe(info: &serde_json::Value) -> anyhow::Result<Self> {
+        let empty = vec![];
+        let streams = info["streams"].as_array().unwrap_or(&empty);
+        let video = streams.iter()
+            .find(|stream| stream["codec_type"] == "video")
+            .ok_or_else(|| anyhow::anyhow!("No video stream"))?;
+        let audio: Vec<&serde_json::Value> = streams.iter()
+            .filter(|stream| stream["codec_type"] == "audio")
+            .collect();
+        let text = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+        let number = |value: &serde_json::Value| value.as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+            .map(|n| n as u32);
+
+        let nominal_rate = parse_frame_rate(video["r_frame_rate"].as_str().unwrap_or_default());
+        let average_rate = parse_frame_rate(video["avg_frame_rate"].as_str().unwrap_or_default());
+        let variable_frame_rate = matches!((nominal_rate, average_rate), (Some(nominal), Some(average))
+            if (nominal - average).abs() > VFR_TOLERANCE_FPS);
+
+        Ok(Self {
+            container: text(&info["format"]["format_long_name"]),
+            video_codec: text(&video["codec_name"]),
+            width: number(&video["width"]).unwrap_or(0),
+            height: number(&video["height"]).unwrap_or(0),
+            frame_rate: average_rate.or(nominal_rate),
+            variable_frame_rate,
+            bit_depth: number(&video["bits_per_raw_sample"]).or_else(|| pixel_format_bit_depth(&text(&video["pix_fmt"]))),
+            color_space: text(&video["color_space"]),
+            color_transfer: text(&video["color_transfer"]),
+            audio_codec: audio.first().map(|stream| text(&stream["codec_name"])).unwrap_or_default(),
+            sample_rate: audio.first().and_then(|stream| number(&stream["sample_rate"])),
+            channels: audio.first().and_then(|stream| number(&stream["channels"])),
+            audio_streams: audio.len(),
+        })
+    }
+
+    pub fn probe(path: &Path) -> anyhow::Result<Self> {
+        let output = output_with_stderr_tail(Command::new("ffprobe")
+            .arg("-v").arg("error")
+            .arg("-print_format").arg("json")
+            .arg("-show_format")
+            .arg("-show_streams")
+            .arg(path))
+            .map_err(|e| anyhow::anyhow!("Failed to run ffprobe: {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(output.failure_message("ffprobe failed")));
+        }
+        let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        Self::from_ffprobe(&info)
+    }
+
+    pub fn is_hdr(&self) -> bool {
+        HDR_TRANSFERS.contains(&self.color_transfer.as_str())
+    }
+}
+
+/// Nominal and average frame rates further apart than this mark a stream as VFR
+const VFR_TOLERANCE_FPS: f64 = 0.01;
+
+/// "60000/1001" -> 59.94; None for "0/0" and other unusable values
+fn parse_frame_rate(rate: &str) -> Option<f64> {
+    let (numerator, denominator) = rate.split_once('/')?;
+    let numerator: f64 = numerator.trim().parse().ok()?;
+    let denominator: f64 = denominator.trim().parse().ok()?;
+    (numerator > 0.0 && denominator > 0.0).then(|| numerator / denominator)
+}
+
+/// Bit depth implied by a pixel format name, for encoders that don't report
+/// `bits_per_raw_sample` (e.g. "yuv420p10le" -> 10)
+fn pixel_format_bit_depth(pixel_format: &str) -> Option<u32> {
+    if pixel_format.is_empty() {
+        return None;
+    }
+    let base = pixel_format.trim_end_matches("le").trim_end_matches("be");
+    match base.rsplit_once('p') {
+        // "yuv420p10le" and "p010le" name their depth after the 'p', plain "yuv420p" is 8-bit
+        Some((_, depth)) if !depth.is_empty() && depth.chars().all(|c| c.is_ascii_digit()) => depth.parse().ok(),
+        _ => Some(8),
+    }
+}
+
+enum ProbeEntry {
+    Probing(JoinHandle<anyhow::Result<TechnicalInfo>>),
+    Done(Result<TechnicalInfo, String>),
+}
+
+/// Technical info per file, probed on a background thread the first time it's asked for
+/// and kept for the rest of the session
+#[derive(Default)]
+pub struct FfprobeCache {
+    entries: HashMap<PathBuf, ProbeEntry>,
+}
+
+impl FfprobeCache {
+    /// The cached info for `path`, starting a probe if there's none yet.
+    /// None while the probe is still running.
+    pub fn get_or_probe(&mut self, path: &Path) -> Option<&Result<TechnicalInfo, String>> {
+        let entry = self.entries.entry(path.to_path_buf()).or_insert_with(|| {
+            let path = path.to_path_buf();
+            ProbeEntry::Probing(std::thread::spawn(move || TechnicalInfo::probe(&path)))
+        });
+
+        if matches!(entry, ProbeEntry::Probing(handle) if handle.is_finished()) {
+            let ProbeEntry::Probing(handle) = std::mem::replace(entry, ProbeEntry::Done(Err(String::new()))) else {
+                unreachable!();
+            };
+            *entry = ProbeEntry::Done(match handle.join() {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(_) => Err("ffprobe thread panicked".to_string()),
+            });
+        }
+
+        match entry {
+            ProbeEntry::Done(result) => Some(result),
+            ProbeEntry::Probing(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_technical_info_from_ffprobe() {
+        let info = serde_json::json!({
+            "format": {"format_long_name": "Matroska / WebM"},
+            "streams": [
+                {"codec_type": "video", "codec_name": "hevc", "width": 3840, "height": 2160,
+                 "r_frame_rate": "60/1", "avg_frame_rate": "12000/203", "pix_fmt": "yuv420p10le",
+                 "color_space": "bt2020nc", "color_transfer": "smpte2084"},
+                {"codec_type": "audio", "codec_name": "aac", "sample_rate": "48000", "channels": 2},
+                {"codec_type": "audio", "codec_name": "aac", "sample_rate": "48000", "channels": 2},
+            ]
+        });
+        let technical = TechnicalInfo::from_ffprobe(&info).unwrap();
+        assert_eq!(technical.container, "Matroska / WebM");
+        assert_eq!((technical.width, technical.height), (3840, 2160));
+        assert!(technical.variable_frame_rate);
+        assert_eq!(technical.bit_depth, Some(10));
+        assert!(technical.is_hdr());
+        assert_eq!((technical.sample_rate, technical.channels, technical.audio_streams), (Some(48000), Some(2), 2));
+    }
+
+    #[test]
+    fn test_frame_rate_and_bit_depth_parsing() {
+        assert!((parse_frame_rate("60000/1001").unwrap() - 59.94).abs() < 0.01);
+        assert_eq!(parse_frame_rate("0/0"), None);
+        assert_eq!(pixel_format_bit_depth("yuv420p"), Some(8));
+        assert_eq!(pixel_format_bit_depth("yuv420p10le"), Some(10));
+        assert_eq!(pixel_format_bit_depth("p010le"), Some(10));
+        assert_eq!(pixel_format_bit_depth("nv12"), Some(8));
+        assert_eq!(pixel_format_bit_depth(""), None);
+    }
+}
@@ -6,6 +6,7 @@ use tokio::sync::mpsc;
 use egui::{Context, TextureHandle, ColorImage};
 use lru::LruCache;
 use std::num::NonZeroUsize;
+use crate::video::{FfmpegProcessPool, VideoProcessor};
 
 /// Request to generate thumbnails for a video file
 #[derive(Debug, Clone)]
@@ -57,7 +58,7 @@ pub struct HoverThumbnailSet {
 }
 
 impl HoverThumbnailManager {
-    pub fn new() -> Self {
+    pub fn new(pool: Arc<FfmpegProcessPool>) -> Self {
         let (request_tx, mut request_rx) = mpsc::unbounded_channel::<ThumbnailRequest>();
         let (result_tx, result_rx) = mpsc::unbounded_channel::<ThumbnailResult>();
         
@@ -70,8 +71,8 @@ impl HoverThumbnailManager {
                     let result_tx = result_tx.clone();
                     let request_clone = request.clone();
                     
-                    // Spawn background task for thumbnail generation
-                    tokio::task::spawn_blocking(move || {
+                    // Generate in the FFmpeg process pool so hovering many clips can't flood the CPU
+                    pool.submit(move || {
                         log::debug!("Generating hover thumbnails for: {:?}", request_clone.file_path);
                         
                         let mut frames = Vec::new();
@@ -102,6 +103,7 @@ impl HoverThumbnailManager {
                         if let Err(e) = result_tx.send(result) {
                             log::error!("Failed to send thumbnail result: {}", e);
                         }
+                        Ok(())
                     });
                 }
             });
@@ -163,7 +165,7 @@ impl HoverThumbnailManager {
                 .arg("-ss").arg(format!("{:.3}", timestamp))
                 .arg("-i").arg(file_path.to_str().unwrap())
                 .arg("-vframes").arg("1")
-                .arg("-vf").arg("scale=160:90:force_original_aspect_ratio=decrease")
+                .arg("-vf").arg(VideoProcessor::thumbnail_filter(file_path, "scale=160:90:force_original_aspect_ratio=decrease"))
                 .arg("-q:v").arg("2")
                 .arg("-f").arg("image2")
                 .arg("-update").arg("1")
@@ -253,7 +255,7 @@ impl HoverThumbnailManager {
             .arg("-ss").arg(format!("{:.3}", timestamp))  // Seek BEFORE input for faster positioning
             .arg("-i").arg(file_path.to_str().unwrap())
             .arg("-vframes").arg("1")
-            .arg("-vf").arg("scale=160:90:force_original_aspect_ratio=decrease")  // Scale preserving aspect ratio, no padding
+            .arg("-vf").arg(VideoProcessor::thumbnail_filter(file_path, "scale=160:90:force_original_aspect_ratio=decrease"))  // Scale preserving aspect ratio, no padding
             .arg("-q:v").arg("2")  // High quality
             .arg("-y")  // Overwrite
             .arg(&temp_file)
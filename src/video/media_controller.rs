@@ -22,7 +22,7 @@ use std::time::{Duration, Instant};
 use std::io::Read;
 use std::process::{Command, Stdio, Child};
 use std::thread::{self, JoinHandle};
-use crate::core::clip::AudioTrack;
+use crate::core::clip::{AudioTrack, SurroundMode};
 use egui::{Context, TextureHandle};
 use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
 
@@ -307,9 +307,9 @@ impl UnifiedMediaController {
             // Build audio filter for mixing tracks
             if enabled_tracks.len() == 1 {
                 let track = enabled_tracks[0];
-                if track.surround_mode {
+                if let Some(pan) = track.surround_mode.pan_filter() {
                     cmd.arg("-filter_complex")
-                        .arg(format!("[0:a:{}]channelmap=map=FL|FR[audio]", track.index))
+                        .arg(format!("[0:a:{}]{}[audio]", track.index, pan))
                         .arg("-map").arg("[audio]");
                 } else {
                     cmd.arg("-map").arg(format!("0:a:{}", track.index));
@@ -320,8 +320,8 @@ impl UnifiedMediaController {
                 let mut mix_inputs = Vec::new();
                 
                 for (i, track) in enabled_tracks.iter().enumerate() {
-                    if track.surround_mode {
-                        filter_parts.push(format!("[0:a:{}]channelmap=map=FL|FR[a{}]", track.index, i));
+                    if let Some(pan) = track.surround_mode.pan_filter() {
+                        filter_parts.push(format!("[0:a:{}]{}[a{}]", track.index, pan, i));
                         mix_inputs.push(format!("[a{}]", i));
                     } else {
                         mix_inputs.push(format!("[0:a:{}]", track.index));
@@ -1979,7 +1979,7 @@ mod tests {
         AudioTrack {
             index,
             enabled,
-            surround_mode: false,
+            surround_mode: SurroundMode::Passthrough,
             name: format!("Test Track {}", index),
         }
     }
@@ -23,8 +23,11 @@ use std::io::Read;
 use std::process::{Command, Stdio, Child};
 use std::thread::{self, JoinHandle};
 use crate::core::clip::AudioTrack;
+use crate::video::ffmpeg_manager::output_with_stderr_tail;
+use crate::video::processor::{PreviewPipeline, VideoGeometry, VideoProcessor, PREVIEW_MAX_WIDTH, PREVIEW_MAX_HEIGHT};
 use egui::{Context, TextureHandle};
-use rodio::{OutputStream, Sink, Source};
+use crate::audio::device_manager::AudioDeviceManager;
+use rodio::{Sink, Source};
 
 // =============================================================================
 // VIDEO FRAME
@@ -54,6 +57,7 @@ pub enum PlaybackCommand {
         duration: f64,
         frame_rate: f64,
         audio_tracks: Vec<AudioTrack>,
+        geometry: VideoGeometry,
     },
     /// Start playback from current position
     Play,
@@ -63,8 +67,17 @@ pub enum PlaybackCommand {
     Seek(f64),
     /// Update audio track configuration
     UpdateTracks(Vec<AudioTrack>),
-    /// Extract a single frame at timestamp (for scrubbing when paused)
+    /// Extract a single frame at timestamp without moving the playback position
     ExtractFrame(f64),
+    /// Timeline drag: move the paused position and show the frame there, without
+    /// starting a stream. Runs of these are coalesced to the newest one.
+    ScrubSeek(f64),
+    /// Switch between normal playback and audio-only mode, where no video is decoded
+    /// and seeking while paused plays a short audio snippet instead of extracting a frame
+    SetAudioOnly(bool),
+    /// Move audio output to the named device (None for the system default) without
+    /// interrupting playback
+    SetOutputDevice(Option<String>),
     /// Shutdown the playback thread
     Shutdown,
 }
@@ -196,6 +209,10 @@ struct PlaybackState {
     position: f64,
     is_playing: bool,
     
+    // Frame geometry (preview size follows the display aspect ratio)
+    geometry: VideoGeometry,
+    frame_size: (u32, u32),
+    
     // FFmpeg process management
     ffmpeg_process: Option<Child>,
     process_id: u64,
@@ -210,6 +227,13 @@ struct PlaybackState {
     
     // Sequence tracking
     frame_sequence: u64,
+    
+    // Waveform-only preview: skip video decoding entirely
+    audio_only: bool,
+    
+    // Recovery from FFmpeg dying mid-playback
+    stream_restarts: u32,
+    restart_at: Option<Instant>,
 }
 
 impl PlaybackState {
@@ -221,6 +245,8 @@ impl PlaybackState {
             frame_rate: 30.0,
             position: 0.0,
             is_playing: false,
+            geometry: VideoGeometry::default(),
+            frame_size: (PREVIEW_MAX_WIDTH, PREVIEW_MAX_HEIGHT),
             ffmpeg_process: None,
             process_id: 0,
             audio_buffer: Arc::new(Mutex::new(AudioBuffer::new(48000 * 2 * 2))), // 2 seconds buffer
@@ -228,10 +254,29 @@ impl PlaybackState {
             playback_start_time: None,
             playback_start_position: 0.0,
             frame_sequence: 0,
+            audio_only: false,
+            stream_restarts: 0,
+            restart_at: None,
+        }
+    }
+
+    /// Extract the frame at `timestamp` and hand it to the UI, outside of any stream
+    fn send_single_frame(&mut self, timestamp: f64, frame_tx: &mpsc::Sender<VideoFrame>) {
+        let Some(ref path) = self.video_path else {
+            return;
+        };
+        match extract_single_frame(path, timestamp, &self.geometry, self.frame_size) {
+            Ok(mut frame) => {
+                self.frame_sequence += 1;
+                frame.sequence = self.frame_sequence;
+                let _ = frame_tx.send(frame);
+            }
+            Err(e) => log::warn!("Failed to extract frame at {:.2}s: {}", timestamp, e),
         }
     }
 
     fn kill_ffmpeg(&mut self) {
+        self.restart_at = None;
         if let Some(mut process) = self.ffmpeg_process.take() {
             log::debug!("Killing FFmpeg process");
             let _ = process.kill();
@@ -257,12 +302,104 @@ impl PlaybackState {
     }
 }
 
+/// Length of the audio snippet played when seeking while paused in audio-only mode
+const AUDIO_SCRUB_SNIPPET_SECONDS: f64 = 0.4;
+
+/// Minimum time between frame extractions while scrubbing the timeline
+pub const SCRUB_FRAME_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often a playback stream that died mid-playback is restarted before giving up
+const MAX_STREAM_RESTARTS: u32 = 3;
+
+/// Delay before the first restart; doubles with every further attempt
+const STREAM_RESTART_BACKOFF: Duration = Duration::from_millis(250);
+
+/// A stream ending this close to the end of the video reached end of file
+const STREAM_END_TOLERANCE_SECONDS: f64 = 0.5;
+
+/// How often starting a sink re-checks that the chosen output device is still connected
+const OUTPUT_DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The preview's audio output. The stream has to stay alive for its handle to play anything.
+struct AudioOutput {
+    /// Device asked for, None for the default
+    device: Option<String>,
+    stream: Option<(rodio::OutputStream, rodio::OutputStreamHandle)>,
+    device_checked_at: Instant,
+}
+
+impl AudioOutput {
+    fn open(device: Option<String>) -> Self {
+        let stream = match AudioDeviceManager::open_output_stream(device.as_deref()) {
+            Ok(stream) => Some(stream),
+            Err(e) => {
+                log::warn!("Failed to create audio output: {}. Video will play without audio.", e);
+                None
+            }
+        };
+        Self { device, stream, device_checked_at: Instant::now() }
+    }
+
+    /// Move to another device, keeping the current output if it can't be opened
+    fn switch(&mut self, device: Option<String>) -> anyhow::Result<()> {
+        self.stream = Some(AudioDeviceManager::open_output_stream(device.as_deref())?);
+        self.device = device;
+        self.device_checked_at = Instant::now();
+        Ok(())
+    }
+
+    /// A new sink on the output. If the chosen device was unplugged since, the default
+    /// device is opened instead, so playback keeps its sound.
+    fn new_sink(&mut self) -> Option<Sink> {
+        let check_due = self.device_checked_at.elapsed() >= OUTPUT_DEVICE_CHECK_INTERVAL;
+        if let (Some(name), true) = (self.device.clone(), check_due) {
+            self.device_checked_at = Instant::now();
+            if !AudioDeviceManager::output_device_exists(&name) {
+                log::warn!("Audio device '{}' disappeared, falling back to the default device", name);
+                if let Err(e) = self.switch(None) {
+                    log::warn!("No default audio output either: {}", e);
+                    self.stream = None;
+                }
+            }
+        }
+        
+        let (_, handle) = self.stream.as_ref()?;
+        Sink::try_new(handle)
+            .inspect_err(|e| log::warn!("Failed to create audio sink: {}", e))
+            .ok()
+    }
+}
+
+fn stream_restart_delay(attempt: u32) -> Duration {
+    STREAM_RESTART_BACKOFF * 2u32.pow(attempt.saturating_sub(1).min(8))
+}
+
+/// What the playback FFmpeg process decodes
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StreamOutput {
+    /// Video frames on stdout, audio on stderr
+    VideoAndAudio,
+    /// Audio only, nothing on stdout
+    AudioOnly,
+    /// Audio only, stopping after the given number of seconds
+    AudioSnippet(f64),
+}
+
+impl StreamOutput {
+    fn for_playback(audio_only: bool) -> Self {
+        if audio_only { Self::AudioOnly } else { Self::VideoAndAudio }
+    }
+}
+
 /// Starts the unified FFmpeg process for video and audio
 fn start_ffmpeg_process(
     video_path: &PathBuf,
     audio_tracks: &[AudioTrack],
     start_time: f64,
     frame_rate: f64,
+    geometry: &VideoGeometry,
+    frame_size: (u32, u32),
+    output: StreamOutput,
 ) -> Result<Child, String> {
     let enabled_tracks: Vec<_> = audio_tracks.iter().filter(|t| t.enabled).collect();
     
@@ -270,24 +407,29 @@ fn start_ffmpeg_process(
     
     // Seek to start position
     cmd.arg("-ss").arg(format!("{:.3}", start_time));
+    if let StreamOutput::AudioSnippet(max_duration) = output {
+        cmd.arg("-t").arg(format!("{:.3}", max_duration));
+    }
     cmd.arg("-i").arg(video_path);
     
     // Video output settings - output to stdout
-    cmd.arg("-map").arg("0:v:0");
-    cmd.arg("-f").arg("rawvideo");
-    cmd.arg("-pix_fmt").arg("rgb24");
-    cmd.arg("-s").arg("854x480");
-    cmd.arg("-r").arg(format!("{:.3}", frame_rate.min(60.0))); // Cap at 60 FPS for performance
-    cmd.arg("pipe:1");
+    if output == StreamOutput::VideoAndAudio {
+        cmd.arg("-map").arg("0:v:0");
+        cmd.arg("-f").arg("rawvideo");
+        cmd.arg("-pix_fmt").arg("rgb24");
+        cmd.arg("-vf").arg(geometry.preview_filter(frame_size.0, frame_size.1, PreviewPipeline::Playback));
+        cmd.arg("-r").arg(format!("{:.3}", frame_rate.min(60.0))); // Cap at 60 FPS for performance
+        cmd.arg("pipe:1");
+    }
     
     // Audio output settings - output to stderr (fd 2)
     if !enabled_tracks.is_empty() {
         // Build audio filter for track mixing
         if enabled_tracks.len() == 1 {
             let track = enabled_tracks[0];
-            if track.surround_mode {
+            if let Some(pan) = track.surround_mode.pan_filter() {
                 cmd.arg("-filter_complex")
-                    .arg(format!("[0:a:{}]channelmap=map=FL|FR[aout]", track.index))
+                    .arg(format!("[0:a:{}]{}[aout]", track.index, pan))
                     .arg("-map").arg("[aout]");
             } else {
                 cmd.arg("-map").arg(format!("0:a:{}", track.index));
@@ -298,8 +440,8 @@ fn start_ffmpeg_process(
             let mut mix_inputs = Vec::new();
             
             for (i, track) in enabled_tracks.iter().enumerate() {
-                if track.surround_mode {
-                    filter_parts.push(format!("[0:a:{}]channelmap=map=FL|FR[a{}]", track.index, i));
+                if let Some(pan) = track.surround_mode.pan_filter() {
+                    filter_parts.push(format!("[0:a:{}]{}[a{}]", track.index, pan, i));
                     mix_inputs.push(format!("[a{}]", i));
                 } else {
                     mix_inputs.push(format!("[0:a:{}]", track.index));
@@ -335,27 +477,30 @@ fn start_ffmpeg_process(
 }
 
 /// Extract a single frame at a specific timestamp
-fn extract_single_frame(video_path: &PathBuf, timestamp: f64) -> Result<VideoFrame, String> {
-    let output = Command::new("ffmpeg")
+fn extract_single_frame(
+    video_path: &PathBuf,
+    timestamp: f64,
+    geometry: &VideoGeometry,
+    frame_size: (u32, u32),
+) -> Result<VideoFrame, String> {
+    let (width, height) = frame_size;
+    let output = output_with_stderr_tail(Command::new("ffmpeg")
         .args([
             "-ss", &format!("{:.3}", timestamp),
             "-i", video_path.to_str().ok_or("Invalid path")?,
             "-vframes", "1",
             "-f", "rawvideo",
             "-pix_fmt", "rgb24",
-            "-s", "854x480",
-            "-loglevel", "quiet",
+            "-vf", &geometry.preview_filter(width, height, PreviewPipeline::StillFrame),
+            "-loglevel", "error",
             "-"
-        ])
-        .output()
+        ]))
         .map_err(|e| format!("FFmpeg execution failed: {}", e))?;
     
     if !output.status.success() {
-        return Err("FFmpeg failed to extract frame".to_string());
+        return Err(output.failure_message("FFmpeg failed to extract frame"));
     }
     
-    let width = 854u32;
-    let height = 480u32;
     let expected_size = (width * height * 3) as usize;
     
     if output.stdout.len() != expected_size {
@@ -388,19 +533,12 @@ fn playback_thread(
     cmd_rx: mpsc::Receiver<PlaybackCommand>,
     status_tx: mpsc::Sender<PlaybackStatus>,
     frame_tx: mpsc::Sender<VideoFrame>,
+    output_device: Option<String>,
 ) {
     let mut state = PlaybackState::new();
     
     // Audio output setup
-    let audio_output = OutputStream::try_default();
-    let (audio_stream, stream_handle) = match audio_output {
-        Ok((stream, handle)) => (Some(stream), Some(handle)),
-        Err(e) => {
-            log::warn!("Failed to create audio output: {}. Video will play without audio.", e);
-            (None, None)
-        }
-    };
-    let _audio_stream = audio_stream; // Keep alive
+    let mut audio_output = AudioOutput::open(output_device);
     #[allow(unused_variables)]
     let mut audio_sink: Option<Sink> = None;
     
@@ -416,6 +554,9 @@ fn playback_thread(
     let frame_buffer: Arc<Mutex<Vec<(f64, VideoFrame)>>> = Arc::new(Mutex::new(Vec::new()));
     let frame_buffer_for_reader = frame_buffer.clone();
     
+    // Command read ahead while coalescing scrub frame requests
+    let mut deferred_command: Option<PlaybackCommand> = None;
+    
     loop {
         // Check for commands with a short timeout for responsiveness
         let timeout = if state.is_playing {
@@ -424,8 +565,19 @@ fn playback_thread(
             Duration::from_millis(50)
         };
         
-        match cmd_rx.recv_timeout(timeout) {
-            Ok(PlaybackCommand::SetVideo { path, duration, frame_rate, audio_tracks }) => {
+        // A due stream restart is handled as a seek to where playback stopped
+        let command = match (deferred_command.take(), state.restart_at) {
+            (Some(command), _) => Ok(command),
+            (None, Some(restart_at)) if state.is_playing && Instant::now() >= restart_at => {
+                state.restart_at = None;
+                log::info!("Restarting playback stream at {:.2}s (attempt {}/{})", state.position, state.stream_restarts, MAX_STREAM_RESTARTS);
+                Ok(PlaybackCommand::Seek(state.position))
+            }
+            _ => cmd_rx.recv_timeout(timeout),
+        };
+        
+        match command {
+            Ok(PlaybackCommand::SetVideo { path, duration, frame_rate, audio_tracks, geometry }) => {
                 log::info!("Setting video: {:?} (duration: {:.2}s, fps: {:.2})", path, duration, frame_rate);
                 
                 // Stop any existing playback
@@ -443,16 +595,25 @@ fn playback_thread(
                 state.audio_tracks = audio_tracks;
                 state.duration = duration;
                 state.frame_rate = frame_rate;
+                state.geometry = geometry;
+                state.frame_size = geometry.fit_within(PREVIEW_MAX_WIDTH, PREVIEW_MAX_HEIGHT);
                 state.position = 0.0;
                 state.is_playing = false;
                 state.process_id += 1;
+                state.stream_restarts = 0;
+                log::debug!("Preview frame size {}x{} (rotation: {}°)", state.frame_size.0, state.frame_size.1, geometry.rotation);
                 
                 // Extract initial frame
-                if let Ok(frame) = extract_single_frame(&path, 0.0) {
-                    let _ = frame_tx.send(frame);
+                match extract_single_frame(&path, 0.0, &state.geometry, state.frame_size) {
+                    Ok(frame) => {
+                        let _ = frame_tx.send(frame);
+                        let _ = status_tx.send(PlaybackStatus::Ready);
+                    }
+                    Err(e) => {
+                        log::error!("Failed to extract first frame of {}: {}", path.display(), e);
+                        let _ = status_tx.send(PlaybackStatus::Error(e));
+                    }
                 }
-                
-                let _ = status_tx.send(PlaybackStatus::Ready);
             }
             
             Ok(PlaybackCommand::Play) => {
@@ -461,6 +622,7 @@ fn playback_thread(
                 }
                 
                 log::info!("Starting playback from {:.2}s", state.position);
+                state.stream_restarts = 0;
                 
                 // Stop existing readers
                 stop_readers(&mut video_reader_handle, &video_reader_stop,
@@ -477,26 +639,28 @@ fn playback_thread(
                 
                 // Start new FFmpeg process
                 let video_path = state.video_path.as_ref().unwrap().clone();
-                match start_ffmpeg_process(&video_path, &state.audio_tracks, state.position, state.frame_rate) {
+                match start_ffmpeg_process(&video_path, &state.audio_tracks, state.position, state.frame_rate, &state.geometry, state.frame_size, StreamOutput::for_playback(state.audio_only)) {
                     Ok(mut process) => {
                         state.process_id += 1;
                         let process_id = state.process_id;
                         let frame_rate = state.frame_rate.min(60.0);
                         let start_position = state.position;
+                        let frame_size = state.frame_size;
                         
                         // Take ownership of stdout/stderr
                         let stdout = process.stdout.take();
                         let stderr = process.stderr.take();
                         state.ffmpeg_process = Some(process);
                         
-                        // Start video reader thread
-                        if let Some(stdout) = stdout {
+                        // Start video reader thread; in audio-only mode nothing is written
+                        // to stdout, so a reader would just block until FFmpeg exits
+                        if let Some(stdout) = stdout.filter(|_| !state.audio_only) {
                             video_reader_stop.store(false, Ordering::SeqCst);
                             let stop_flag = video_reader_stop.clone();
                             let buffer = frame_buffer_for_reader.clone();
                             
                             video_reader_handle = Some(thread::spawn(move || {
-                                video_reader_thread(stdout, buffer, stop_flag, frame_rate, start_position, process_id);
+                                video_reader_thread(stdout, buffer, stop_flag, frame_rate, frame_size, start_position, process_id);
                             }));
                         }
                         
@@ -512,19 +676,17 @@ fn playback_thread(
                         }
                         
                         // Start audio playback
-                        if let Some(ref handle) = stream_handle {
-                            state.audio_stop_flag.store(false, Ordering::SeqCst);
-                            if let Ok(sink) = Sink::try_new(handle) {
-                                let source = StreamingAudioSource {
-                                    buffer: state.audio_buffer.clone(),
-                                    stop_flag: state.audio_stop_flag.clone(),
-                                    sample_rate: 48000,
-                                    channels: 2,
-                                };
-                                sink.append(source);
-                                sink.play();
-                                audio_sink = Some(sink);
-                            }
+                        state.audio_stop_flag.store(false, Ordering::SeqCst);
+                        if let Some(sink) = audio_output.new_sink() {
+                            let source = StreamingAudioSource {
+                                buffer: state.audio_buffer.clone(),
+                                stop_flag: state.audio_stop_flag.clone(),
+                                sample_rate: 48000,
+                                channels: 2,
+                            };
+                            sink.append(source);
+                            sink.play();
+                            audio_sink = Some(sink);
                         }
                         
                         state.is_playing = true;
@@ -579,12 +741,48 @@ fn playback_thread(
                 state.position = clamped;
                 state.is_playing = false;
                 
+                // In audio-only mode a paused seek plays a short snippet instead of showing a frame
+                if state.audio_only && !was_playing {
+                    if let Some(ref path) = state.video_path {
+                        match start_ffmpeg_process(path, &state.audio_tracks, clamped, state.frame_rate, &state.geometry, state.frame_size, StreamOutput::AudioSnippet(AUDIO_SCRUB_SNIPPET_SECONDS)) {
+                            Ok(mut process) => {
+                                if let Some(stderr) = process.stderr.take() {
+                                    audio_reader_stop.store(false, Ordering::SeqCst);
+                                    let stop_flag = audio_reader_stop.clone();
+                                    let audio_buf = state.audio_buffer.clone();
+                                    
+                                    audio_reader_handle = Some(thread::spawn(move || {
+                                        audio_reader_thread(stderr, audio_buf, stop_flag);
+                                    }));
+                                }
+                                state.ffmpeg_process = Some(process);
+                                
+                                state.audio_stop_flag.store(false, Ordering::SeqCst);
+                                if let Some(sink) = audio_output.new_sink() {
+                                    sink.append(StreamingAudioSource {
+                                        buffer: state.audio_buffer.clone(),
+                                        stop_flag: state.audio_stop_flag.clone(),
+                                        sample_rate: 48000,
+                                        channels: 2,
+                                    });
+                                    sink.play();
+                                    audio_sink = Some(sink);
+                                }
+                            }
+                            Err(e) => log::warn!("Failed to play audio snippet at {:.2}s: {}", clamped, e),
+                        }
+                    }
+                }
+                
                 // Extract frame at new position
-                if let Some(ref path) = state.video_path {
-                    if let Ok(mut frame) = extract_single_frame(path, clamped) {
-                        state.frame_sequence += 1;
-                        frame.sequence = state.frame_sequence;
-                        let _ = frame_tx.send(frame);
+                if let Some(path) = state.video_path.as_ref().filter(|_| !state.audio_only) {
+                    match extract_single_frame(path, clamped, &state.geometry, state.frame_size) {
+                        Ok(mut frame) => {
+                            state.frame_sequence += 1;
+                            frame.sequence = state.frame_sequence;
+                            let _ = frame_tx.send(frame);
+                        }
+                        Err(e) => log::warn!("Failed to extract frame at {:.2}s: {}", clamped, e),
                     }
                 }
                 
@@ -594,23 +792,24 @@ fn playback_thread(
                     let _ = cmd_rx; // We can't send to ourselves, so we'll restart inline
                     
                     if let Some(ref video_path) = state.video_path {
-                        match start_ffmpeg_process(video_path, &state.audio_tracks, clamped, state.frame_rate) {
+                        match start_ffmpeg_process(video_path, &state.audio_tracks, clamped, state.frame_rate, &state.geometry, state.frame_size, StreamOutput::for_playback(state.audio_only)) {
                             Ok(mut process) => {
                                 state.process_id += 1;
                                 let process_id = state.process_id;
                                 let frame_rate = state.frame_rate.min(60.0);
+                                let frame_size = state.frame_size;
                                 
                                 let stdout = process.stdout.take();
                                 let stderr = process.stderr.take();
                                 state.ffmpeg_process = Some(process);
                                 
-                                if let Some(stdout) = stdout {
+                                if let Some(stdout) = stdout.filter(|_| !state.audio_only) {
                                     video_reader_stop.store(false, Ordering::SeqCst);
                                     let stop_flag = video_reader_stop.clone();
                                     let buffer = frame_buffer_for_reader.clone();
                                     
                                     video_reader_handle = Some(thread::spawn(move || {
-                                        video_reader_thread(stdout, buffer, stop_flag, frame_rate, clamped, process_id);
+                                        video_reader_thread(stdout, buffer, stop_flag, frame_rate, frame_size, clamped, process_id);
                                     }));
                                 }
                                 
@@ -624,19 +823,17 @@ fn playback_thread(
                                     }));
                                 }
                                 
-                                if let Some(ref handle) = stream_handle {
-                                    state.audio_stop_flag.store(false, Ordering::SeqCst);
-                                    if let Ok(sink) = Sink::try_new(handle) {
-                                        let source = StreamingAudioSource {
-                                            buffer: state.audio_buffer.clone(),
-                                            stop_flag: state.audio_stop_flag.clone(),
-                                            sample_rate: 48000,
-                                            channels: 2,
-                                        };
-                                        sink.append(source);
-                                        sink.play();
-                                        audio_sink = Some(sink);
-                                    }
+                                state.audio_stop_flag.store(false, Ordering::SeqCst);
+                                if let Some(sink) = audio_output.new_sink() {
+                                    let source = StreamingAudioSource {
+                                        buffer: state.audio_buffer.clone(),
+                                        stop_flag: state.audio_stop_flag.clone(),
+                                        sample_rate: 48000,
+                                        channels: 2,
+                                    };
+                                    sink.append(source);
+                                    sink.play();
+                                    audio_sink = Some(sink);
                                 }
                                 
                                 state.is_playing = true;
@@ -676,14 +873,60 @@ fn playback_thread(
             }
             
             Ok(PlaybackCommand::ExtractFrame(timestamp)) => {
+                let (timestamp, next) = latest_frame_request(timestamp, &cmd_rx, |command| match command {
+                    PlaybackCommand::ExtractFrame(timestamp) => Some(*timestamp),
+                    _ => None,
+                });
+                deferred_command = next;
+                
                 if !state.is_playing {
-                    if let Some(ref path) = state.video_path {
-                        if let Ok(mut frame) = extract_single_frame(path, timestamp) {
-                            state.frame_sequence += 1;
-                            frame.sequence = state.frame_sequence;
-                            let _ = frame_tx.send(frame);
+                    state.send_single_frame(timestamp, &frame_tx);
+                }
+            }
+            
+            Ok(PlaybackCommand::ScrubSeek(timestamp)) => {
+                let (timestamp, next) = latest_frame_request(timestamp, &cmd_rx, |command| match command {
+                    PlaybackCommand::ScrubSeek(timestamp) => Some(*timestamp),
+                    _ => None,
+                });
+                deferred_command = next;
+                
+                // The controller pauses before scrubbing; a Play afterwards starts from here
+                if !state.is_playing {
+                    state.position = timestamp.clamp(0.0, state.duration);
+                    state.send_single_frame(timestamp, &frame_tx);
+                }
+            }
+            
+            Ok(PlaybackCommand::SetAudioOnly(enabled)) => {
+                // The controller follows this with a seek (while playing) or a frame
+                // extraction (when returning to video) at the current position
+                log::info!("Audio-only preview {}", if enabled { "enabled" } else { "disabled" });
+                state.audio_only = enabled;
+            }
+            
+            Ok(PlaybackCommand::SetOutputDevice(device_name)) => {
+                let label = device_name.clone().unwrap_or_else(|| "the default device".to_string());
+                match audio_output.switch(device_name) {
+                    Ok(()) => {
+                        log::info!("Audio output moved to {}", label);
+                        // The readers keep filling the shared buffer and the playback clock
+                        // isn't tied to the sink, so a new sink resumes where the old one was
+                        if audio_sink.is_some() {
+                            if let Some(sink) = audio_output.new_sink() {
+                                sink.append(StreamingAudioSource {
+                                    buffer: state.audio_buffer.clone(),
+                                    stop_flag: state.audio_stop_flag.clone(),
+                                    sample_rate: 48000,
+                                    channels: 2,
+                                });
+                                sink.play();
+                                audio_sink = Some(sink);
+                            }
                         }
                     }
+                    // Playback itself is fine, so this isn't reported as a playback error
+                    Err(e) => log::warn!("Keeping the current audio output: {}", e),
                 }
             }
             
@@ -717,6 +960,38 @@ fn playback_thread(
                         continue;
                     }
                     
+                    // FFmpeg exiting with an error before the end means the stream was cut off
+                    // (e.g. the process got killed); restart it a few times before giving up
+                    let failed_exit = state.ffmpeg_process.as_mut()
+                        .and_then(|process| process.try_wait().ok().flatten())
+                        .filter(|status| !status.success());
+                    if let Some(status) = failed_exit.filter(|_| current_time < state.duration - STREAM_END_TOLERANCE_SECONDS) {
+                        stop_readers(&mut video_reader_handle, &video_reader_stop,
+                                   &mut audio_reader_handle, &audio_reader_stop);
+                        state.audio_stop_flag.store(true, Ordering::SeqCst);
+                        state.kill_ffmpeg();
+                        audio_sink = None;
+                        
+                        // Hold the playback clock until the stream is back
+                        state.position = current_time;
+                        state.playback_start_time = None;
+                        
+                        if state.stream_restarts >= MAX_STREAM_RESTARTS {
+                            log::error!("Playback stream died at {:.2}s ({}), giving up after {} restarts", current_time, status, MAX_STREAM_RESTARTS);
+                            state.is_playing = false;
+                            let _ = status_tx.send(PlaybackStatus::Error(format!(
+                                "Playback stopped at {:.1}s: FFmpeg exited unexpectedly ({}) and could not be restarted after {} attempts",
+                                current_time, status, MAX_STREAM_RESTARTS
+                            )));
+                        } else {
+                            state.stream_restarts += 1;
+                            let delay = stream_restart_delay(state.stream_restarts);
+                            log::warn!("Playback stream died at {:.2}s ({}), restarting in {}ms", current_time, status, delay.as_millis());
+                            state.restart_at = Some(Instant::now() + delay);
+                        }
+                        continue;
+                    }
+                    
                     // Release frames that are due
                     if let Ok(mut buffer) = frame_buffer.lock() {
                         while let Some((pts, _)) = buffer.first() {
@@ -748,6 +1023,23 @@ fn playback_thread(
     log::info!("Playback thread exited");
 }
 
+/// Skip a run of queued frame requests (those `timestamp_of` matches) to the newest one,
+/// since extracting a frame is slower than dragging can request them. Returns the newest
+/// timestamp and the first other command read, which still has to be handled.
+fn latest_frame_request(
+    mut timestamp: f64,
+    cmd_rx: &mpsc::Receiver<PlaybackCommand>,
+    timestamp_of: impl Fn(&PlaybackCommand) -> Option<f64>,
+) -> (f64, Option<PlaybackCommand>) {
+    while let Ok(next) = cmd_rx.try_recv() {
+        match timestamp_of(&next) {
+            Some(next_timestamp) => timestamp = next_timestamp,
+            None => return (timestamp, Some(next)),
+        }
+    }
+    (timestamp, None)
+}
+
 fn stop_readers(
     video_handle: &mut Option<JoinHandle<()>>,
     video_stop: &Arc<AtomicBool>,
@@ -770,10 +1062,11 @@ fn video_reader_thread(
     frame_buffer: Arc<Mutex<Vec<(f64, VideoFrame)>>>,
     stop_flag: Arc<AtomicBool>,
     frame_rate: f64,
+    (width, height): (u32, u32),
     start_position: f64,
     process_id: u64,
 ) {
-    let frame_size = 854 * 480 * 3; // RGB24
+    let frame_size = (width * height * 3) as usize; // RGB24
     let frame_duration = 1.0 / frame_rate;
     let mut frame_index = 0u64;
     let mut buffer = vec![0u8; frame_size];
@@ -784,7 +1077,7 @@ fn video_reader_thread(
         match stdout.read_exact(&mut buffer) {
             Ok(()) => {
                 // Convert RGB24 to RGBA
-                let mut rgba_data = Vec::with_capacity(854 * 480 * 4);
+                let mut rgba_data = Vec::with_capacity((width * height * 4) as usize);
                 for chunk in buffer.chunks(3) {
                     if chunk.len() == 3 {
                         rgba_data.push(chunk[0]);
@@ -797,8 +1090,8 @@ fn video_reader_thread(
                 let pts = start_position + (frame_index as f64 * frame_duration);
                 let frame = VideoFrame {
                     image_data: rgba_data,
-                    width: 854,
-                    height: 480,
+                    width,
+                    height,
                     timestamp: pts,
                     sequence: frame_index,
                     process_id,
@@ -916,6 +1209,103 @@ impl MediaControllerState {
     }
 }
 
+/// Busy states shorter than this don't disable the playback buttons
+pub const CONTROL_DISABLE_DELAY: Duration = Duration::from_millis(300);
+
+/// Which playback commands the UI should currently offer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackAvailability {
+    pub can_play: bool,
+    pub can_pause: bool,
+    pub can_seek: bool,
+}
+
+impl PlaybackAvailability {
+    /// Everything enabled, used while no controller is attached
+    pub const ALL: Self = Self { can_play: true, can_pause: true, can_seek: true };
+
+    pub fn from_state(state: &MediaControllerState) -> Self {
+        Self {
+            can_play: state.can_play(),
+            can_pause: state.can_pause(),
+            can_seek: state.can_seek(),
+        }
+    }
+}
+
+/// Keeps the last settled availability through brief Loading/Seeking states
+/// so buttons don't flicker while scrubbing
+pub struct AvailabilityDebouncer {
+    settled: PlaybackAvailability,
+    busy_since: Option<Instant>,
+}
+
+impl AvailabilityDebouncer {
+    pub fn new() -> Self {
+        Self { settled: PlaybackAvailability::ALL, busy_since: None }
+    }
+
+    pub fn update(&mut self, state: &MediaControllerState, now: Instant) -> PlaybackAvailability {
+        if !state.is_busy() {
+            self.busy_since = None;
+            self.settled = PlaybackAvailability::from_state(state);
+            return self.settled;
+        }
+
+        let busy_since = *self.busy_since.get_or_insert(now);
+        if now.duration_since(busy_since) < CONTROL_DISABLE_DELAY {
+            self.settled
+        } else {
+            PlaybackAvailability::from_state(state)
+        }
+    }
+}
+
+/// Two preview textures used in turn: each frame is uploaded into the one not being shown,
+/// and an existing slot of the right size is updated in place instead of reallocated
+pub struct TexturePool {
+    slots: [Option<TextureHandle>; 2],
+    active: usize,
+}
+
+impl TexturePool {
+    pub fn new() -> Self {
+        Self {
+            slots: [None, None],
+            active: 0,
+        }
+    }
+
+    /// Upload `new_frame` into the inactive slot and make that slot the active one
+    pub fn swap(&mut self, new_frame: egui::ColorImage, ctx: &Context) {
+        let inactive = 1 - self.active;
+        match &mut self.slots[inactive] {
+            Some(handle) if handle.size() == new_frame.size => {
+                handle.set(new_frame, egui::TextureOptions::NEAREST);
+            }
+            slot => {
+                *slot = Some(ctx.load_texture(
+                    format!("video_frame_{}", inactive),
+                    new_frame,
+                    egui::TextureOptions::NEAREST,
+                ));
+            }
+        }
+        self.active = inactive;
+    }
+
+    /// The most recently uploaded frame
+    pub fn active(&self) -> Option<&TextureHandle> {
+        self.slots[self.active].as_ref()
+    }
+}
+
+impl Default for TexturePool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct MediaController {
     // Communication with playback thread
     command_sender: mpsc::Sender<PlaybackCommand>,
@@ -929,23 +1319,42 @@ pub struct MediaController {
     total_duration: f64,
     video_path: Option<PathBuf>,
     video_frame_rate: f64,
+    video_geometry: Option<VideoGeometry>,
     is_playing: bool,
+    /// Latest playback error not yet picked up by the UI
+    unreported_error: Option<String>,
+    audio_only: bool,
+    /// Set between `begin_scrub` and `end_scrub`
+    scrub: Option<ScrubState>,
     
     // Rendering
-    texture_handle: Option<TextureHandle>,
+    textures: TexturePool,
     
     // Shutdown flag
     is_shutting_down: bool,
 }
 
+/// Timeline drag in progress: frames are extracted one at a time instead of restarting playback
+struct ScrubState {
+    was_playing: bool,
+    last_frame_request: Option<Instant>,
+    /// Position still waiting for a frame because of the rate limit
+    pending_position: Option<f64>,
+}
+
 impl MediaController {
     pub fn new() -> Self {
+        Self::with_output_device(None)
+    }
+    
+    /// Controller playing audio on the named output device (None for the system default)
+    pub fn with_output_device(output_device: Option<String>) -> Self {
         let (cmd_tx, cmd_rx) = mpsc::channel();
         let (status_tx, status_rx) = mpsc::channel();
         let (frame_tx, frame_rx) = mpsc::channel();
         
         let thread_handle = thread::spawn(move || {
-            playback_thread(cmd_rx, status_tx, frame_tx);
+            playback_thread(cmd_rx, status_tx, frame_tx, output_device);
         });
         
         Self {
@@ -958,8 +1367,12 @@ impl MediaController {
             total_duration: 0.0,
             video_path: None,
             video_frame_rate: 30.0,
+            video_geometry: None,
             is_playing: false,
-            texture_handle: None,
+            unreported_error: None,
+            audio_only: false,
+            scrub: None,
+            textures: TexturePool::new(),
             is_shutting_down: false,
         }
     }
@@ -980,6 +1393,13 @@ impl MediaController {
         let frame_rate = Self::get_video_frame_rate(&video_path).unwrap_or(30.0);
         self.video_frame_rate = frame_rate;
         
+        // Get frame geometry so portrait and anamorphic clips aren't squished
+        let geometry = VideoProcessor::probe_geometry(&video_path).unwrap_or_else(|e| {
+            log::warn!("Failed to probe video geometry, assuming landscape: {}", e);
+            VideoGeometry::default()
+        });
+        self.video_geometry = Some(geometry);
+        
         // Enable first audio track by default
         let mut tracks = audio_tracks.to_vec();
         if !tracks.is_empty() {
@@ -991,6 +1411,7 @@ impl MediaController {
             duration,
             frame_rate,
             audio_tracks: tracks,
+            geometry,
         });
         
         self.video_path = Some(video_path);
@@ -1047,6 +1468,95 @@ impl MediaController {
         self.seek(timestamp);
     }
     
+    /// Start scrubbing: playback pauses and `scrub_to` only extracts single frames, so no
+    /// stream is restarted until the drag ends
+    pub fn begin_scrub(&mut self) {
+        if self.scrub.is_some() || !self.state.can_seek() {
+            return;
+        }
+        let was_playing = self.is_playing;
+        if was_playing {
+            self.pause();
+        }
+        self.scrub = Some(ScrubState { was_playing, last_frame_request: None, pending_position: None });
+    }
+    
+    /// Show the frame at `position`, at most once per `SCRUB_FRAME_INTERVAL`. Falls back
+    /// to a regular seek when no scrub is in progress.
+    pub fn scrub_to(&mut self, position: f64) {
+        let position = position.clamp(0.0, self.total_duration);
+        let Some(scrub) = self.scrub.as_mut() else {
+            self.seek(position);
+            return;
+        };
+        if (position - self.current_position).abs() < f64::EPSILON && scrub.pending_position.is_none() {
+            return;
+        }
+        self.current_position = position;
+        
+        // Audio-only previews have no frame to show; end_scrub plays a snippet instead
+        if self.audio_only {
+            return;
+        }
+        if scrub.last_frame_request.is_some_and(|last| last.elapsed() < SCRUB_FRAME_INTERVAL) {
+            scrub.pending_position = Some(position);
+        } else {
+            scrub.last_frame_request = Some(Instant::now());
+            scrub.pending_position = None;
+            let _ = self.command_sender.send(PlaybackCommand::ScrubSeek(position));
+        }
+    }
+    
+    /// Finish scrubbing at `position`, resuming playback there if it was playing
+    pub fn end_scrub(&mut self, position: f64) {
+        let Some(scrub) = self.scrub.take() else {
+            return;
+        };
+        if self.audio_only {
+            // A paused seek plays the audio snippet at the release point
+            self.seek(position);
+        } else {
+            // Frame and paused position are enough; Play starts the stream from there
+            let position = position.clamp(0.0, self.total_duration);
+            let _ = self.command_sender.send(PlaybackCommand::ScrubSeek(position));
+            self.current_position = position;
+        }
+        if scrub.was_playing {
+            self.play();
+        }
+    }
+    
+    pub fn is_scrubbing(&self) -> bool {
+        self.scrub.is_some()
+    }
+    
+    /// Switch to (or back from) audio-only preview. Playback continues from the
+    /// current position in the new mode, so audio and video stay in sync.
+    pub fn set_audio_only(&mut self, audio_only: bool) {
+        if self.audio_only == audio_only {
+            return;
+        }
+        self.audio_only = audio_only;
+        let _ = self.command_sender.send(PlaybackCommand::SetAudioOnly(audio_only));
+        
+        if self.is_playing {
+            // Restart the stream where it is now, with or without video
+            let _ = self.command_sender.send(PlaybackCommand::Seek(self.current_position));
+        } else if !audio_only {
+            // The frame on screen may be from before audio-only scrubbing
+            let _ = self.command_sender.send(PlaybackCommand::ExtractFrame(self.current_position));
+        }
+    }
+    
+    /// Switch audio output to another device, keeping position and play state
+    pub fn set_output_device(&mut self, device_name: Option<String>) {
+        let _ = self.command_sender.send(PlaybackCommand::SetOutputDevice(device_name));
+    }
+    
+    pub fn is_audio_only(&self) -> bool {
+        self.audio_only
+    }
+    
     /// Update audio track configuration
     pub fn update_audio_tracks(&mut self, audio_tracks: &[AudioTrack]) {
         let _ = self.command_sender.send(PlaybackCommand::UpdateTracks(audio_tracks.to_vec()));
@@ -1077,6 +1587,7 @@ impl MediaController {
                         self.current_position = pos;
                     }
                     PlaybackStatus::Error(msg) => {
+                        self.unreported_error = Some(msg.clone());
                         self.state = MediaControllerState::Error(msg);
                         self.is_playing = false;
                     }
@@ -1084,6 +1595,19 @@ impl MediaController {
             }
         }
         
+        // Send the scrub frame held back by the rate limit
+        if let Some(scrub) = self.scrub.as_mut() {
+            if let Some(position) = scrub.pending_position {
+                if scrub.last_frame_request.is_none_or(|last| last.elapsed() >= SCRUB_FRAME_INTERVAL) {
+                    scrub.pending_position = None;
+                    scrub.last_frame_request = Some(Instant::now());
+                    let _ = self.command_sender.send(PlaybackCommand::ScrubSeek(position));
+                } else {
+                    ctx.request_repaint_after(SCRUB_FRAME_INTERVAL);
+                }
+            }
+        }
+        
         // Process video frames
         if let Ok(receiver) = self.frame_receiver.lock() {
             // Get the latest frame (skip old ones)
@@ -1099,11 +1623,7 @@ impl MediaController {
                         &frame.image_data,
                     );
                     
-                    self.texture_handle = Some(ctx.load_texture(
-                        "video_frame",
-                        color_image,
-                        egui::TextureOptions::LINEAR,
-                    ));
+                    self.textures.swap(color_image, ctx);
                 }
             }
         }
@@ -1122,6 +1642,10 @@ impl MediaController {
         self.is_playing
     }
     
+    pub fn is_shutting_down(&self) -> bool {
+        self.is_shutting_down
+    }
+    
     pub fn current_position(&self) -> f64 {
         self.current_position
     }
@@ -1138,12 +1662,25 @@ impl MediaController {
         self.video_path.as_ref()
     }
     
+    pub fn frame_rate(&self) -> f64 {
+        self.video_frame_rate
+    }
+    
+    /// Take the most recent playback error, if it hasn't been reported yet
+    pub fn take_error(&mut self) -> Option<String> {
+        self.unreported_error.take()
+    }
+    
+    pub fn video_geometry(&self) -> Option<VideoGeometry> {
+        self.video_geometry
+    }
+    
     pub fn state(&self) -> &MediaControllerState {
         &self.state
     }
     
     pub fn get_frame_texture(&mut self, _ctx: &Context) -> Option<TextureHandle> {
-        self.texture_handle.clone()
+        self.textures.active().cloned()
     }
     
     pub fn has_error(&self) -> bool {
@@ -1235,6 +1772,79 @@ mod tests {
         assert!(!controller.state().can_pause());
     }
     
+    #[test]
+    fn test_availability_debounces_short_busy_states() {
+        let mut debouncer = AvailabilityDebouncer::new();
+        let start = Instant::now();
+        
+        let paused = debouncer.update(&MediaControllerState::Paused, start);
+        assert!(paused.can_play && paused.can_seek && !paused.can_pause);
+        
+        // A brief seek keeps the previous availability
+        assert_eq!(debouncer.update(&MediaControllerState::Seeking, start + Duration::from_millis(50)), paused);
+        
+        // A long one disables the controls
+        let busy = debouncer.update(&MediaControllerState::Seeking, start + CONTROL_DISABLE_DELAY + Duration::from_millis(50));
+        assert!(!busy.can_play && !busy.can_seek);
+        
+        let error = debouncer.update(&MediaControllerState::Error("boom".to_string()), start + Duration::from_secs(1));
+        assert!(!error.can_play && !error.can_pause && !error.can_seek);
+    }
+    
+    #[test]
+    fn test_texture_pool_alternates_slots() {
+        let ctx = Context::default();
+        let mut pool = TexturePool::new();
+        assert!(pool.active().is_none());
+        
+        pool.swap(egui::ColorImage::new([4, 2], egui::Color32::BLACK), &ctx);
+        let first = pool.active().unwrap().id();
+        pool.swap(egui::ColorImage::new([4, 2], egui::Color32::WHITE), &ctx);
+        let second = pool.active().unwrap().id();
+        assert_ne!(first, second);
+        
+        // Same size: the first slot's texture is reused
+        pool.swap(egui::ColorImage::new([4, 2], egui::Color32::RED), &ctx);
+        assert_eq!(pool.active().unwrap().id(), first);
+        
+        // New size: that slot gets a new texture
+        pool.swap(egui::ColorImage::new([8, 4], egui::Color32::RED), &ctx);
+        assert_ne!(pool.active().unwrap().id(), second);
+        assert_eq!(pool.active().unwrap().size(), [8, 4]);
+    }
+    
+    #[test]
+    fn test_latest_frame_request_keeps_only_newest_scrub() {
+        let (tx, rx) = mpsc::channel();
+        for command in [
+            PlaybackCommand::ScrubSeek(2.0),
+            PlaybackCommand::ScrubSeek(3.0),
+            PlaybackCommand::Play,
+            PlaybackCommand::ScrubSeek(9.0),
+        ] {
+            tx.send(command).unwrap();
+        }
+        let scrub_seek = |command: &PlaybackCommand| match command {
+            PlaybackCommand::ScrubSeek(timestamp) => Some(*timestamp),
+            _ => None,
+        };
+        
+        let (timestamp, next) = latest_frame_request(1.0, &rx, scrub_seek);
+        assert_eq!(timestamp, 3.0);
+        assert!(matches!(next, Some(PlaybackCommand::Play)));
+        // Requests after the other command are left for later
+        let (timestamp, next) = latest_frame_request(0.0, &rx, scrub_seek);
+        assert_eq!(timestamp, 9.0);
+        assert!(next.is_none());
+    }
+    
+    #[test]
+    fn test_stream_restart_backoff() {
+        assert_eq!(stream_restart_delay(1), STREAM_RESTART_BACKOFF);
+        assert_eq!(stream_restart_delay(2), STREAM_RESTART_BACKOFF * 2);
+        assert_eq!(stream_restart_delay(MAX_STREAM_RESTARTS), STREAM_RESTART_BACKOFF * 4);
+    }
+    
     #[test]
     fn test_audio_buffer() {
         let mut buffer = AudioBuffer::new(10);
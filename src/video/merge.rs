@@ -0,0 +1,282 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::video::ffmpeg_manager::output_with_stderr_tail;
+use crate::video::{EncoderQuality, VideoEncoder, VideoProcessor};
+
+/// How `merge_files` joined its inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConcatMode {
+    /// Concat demuxer with `-c copy`: fast and lossless, needs identical stream layouts
+    StreamCopy,
+    /// Concat filter with re-encoding, for inputs whose codecs or resolutions differ
+    ReEncode,
+}
+
+/// The stream parameters that have to match for a stream-copy concat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamLayout {
+    pub video_codec: String,
+    pub width: u32,
+    pub height: u32,
+    pub pixel_format: String,
+    /// "codec/sample rate/channels" per audio track
+    pub audio_tracks: Vec<String>,
+}
+
+impl StreamLayout {
+    /// Layout from ffprobe's `-show_streams` JSON
+    pub fn from_ffprobe(info: &serde_json::Value) -> anyhow::Result<Self> {
+        let empty = vec![];
+        let streams = info["streams"].as_array().unwrap_or(&empty);
+        let video = streams.iter()
+            .find(|stream| stream["codec_type"] == "video")
+            .ok_or_else(|| anyhow::anyhow!("No video stream"))?;
+        let text = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+
+        Ok(Self {
+            video_codec: text(&video["codec_name"]),
+            width: video["width"].as_u64().unwrap_or(0) as u32,
+            height: video["height"].as_u64().unwrap_or(0) as u32,
+            pixel_format: text(&video["pix_fmt"]),
+            audio_tracks: streams.iter()
+                .filter(|stream| stream["codec_type"] == "audio")
+                .map(|stream| format!(
+                    "{}/{}/{}",
+                    text(&stream["codec_name"]),
+                    text(&stream["sample_rate"]),
+                    stream["channels"].as_u64().unwrap_or(0)
+                ))
+                .collect(),
+        })
+    }
+
+    pub fn probe(path: &Path) -> anyhow::Result<Self> {
+        let output = output_with_stderr_tail(Command::new("ffprobe")
+            .arg("-v").arg("error")
+            .arg("-print_format").arg("json")
+            .arg("-show_streams")
+            .arg(path))
+            .map_err(|e| anyhow::anyhow!("Failed to run ffprobe: {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(output.failure_message("ffprobe failed")));
+        }
+        let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        Self::from_ffprobe(&info).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))
+    }
+}
+
+/// "<first stem> merged.mkv" next to the first input, numbered if that exists already.
+/// Keeps the first replay's timestamp in the name so the merged clip sorts with it.
+pub fn merged_output_path(first_input: &Path) -> PathBuf {
+    let stem = first_input.file_stem().and_then(|s| s.to_str()).unwrap_or("Replay");
+    let mut candidate = first_input.with_file_name(format!("{} merged.mkv", stem));
+    let mut number = 2;
+    while candidate.exists() {
+        candidate = first_input.with_file_name(format!("{} merged {}.mkv", stem, number));
+        number += 1;
+    }
+    candidate
+}
+
+/// "<date>_<start>_session.mkv" in `directory`, numbered if that exists already. The
+/// session's "14:56" start is written as "14-56", since ':' can't appear in Windows file names.
+pub fn session_output_path(directory: &Path, date: &str, start_time: &str) -> PathBuf {
+    let stem = format!("{}_{}_session", date, start_time.replace(':', "-"));
+    let mut candidate = directory.join(format!("{}.mkv", stem));
+    let mut number = 2;
+    while candidate.exists() {
+        candidate = directory.join(format!("{} {}.mkv", stem, number));
+        number += 1;
+    }
+    candidate
+}
+
+/// The trimmed part of a replay to join with `merge_clips`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeSegment {
+    pub path: PathBuf,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Join the trimmed `segments` in the given order into `output`. Each segment is first cut
+/// out of its replay with a stream copy (so cuts land on keyframes), then the pieces are
+/// joined like `merge_files` does. `progress` holds the index of the segment being cut,
+/// and `segments.len()` while joining.
+/// Blocks until FFmpeg is done, so call this off the UI thread.
+pub fn merge_clips(segments: &[MergeSegment], output: &Path, encoder: VideoEncoder, progress: &AtomicUsize) -> anyhow::Result<ConcatMode> {
+    if segments.len() < 2 {
+        return Err(anyhow::anyhow!("Merging needs at least two clips"));
+    }
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let pieces_dir = std::env::temp_dir().join(format!("clip-helper-session-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&pieces_dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", pieces_dir.display(), e))?;
+
+    let result = cut_segments(segments, &pieces_dir, progress).and_then(|pieces| {
+        progress.store(segments.len(), Ordering::Relaxed);
+        merge_files(&pieces, output, encoder)
+    });
+    let _ = std::fs::remove_dir_all(&pieces_dir);
+    result
+}
+
+/// Stream-copy every segment into its own file in `directory`
+fn cut_segments(segments: &[MergeSegment], directory: &Path, progress: &AtomicUsize) -> anyhow::Result<Vec<PathBuf>> {
+    let mut pieces = Vec::with_capacity(segments.len());
+    for (index, segment) in segments.iter().enumerate() {
+        progress.store(index, Ordering::Relaxed);
+        let piece = directory.join(format!("{:03}.mkv", index));
+        let mut cmd = Command::new("ffmpeg");
+        cmd.arg("-ss").arg(format!("{:.3}", segment.start.max(0.0)))
+            .arg("-i").arg(&segment.path)
+            .arg("-t").arg(format!("{:.3}", (segment.end - segment.start).max(0.0)))
+            .arg("-map").arg("0")
+            .arg("-c").arg("copy")
+            .arg("-avoid_negative_ts").arg("make_zero")
+            .arg("-y").arg(&piece);
+
+        let output = output_with_stderr_tail(&mut cmd)
+            .map_err(|e| anyhow::anyhow!("Failed to run ffmpeg: {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(output.failure_message(&format!("Cutting {} failed", segment.path.display()))));
+        }
+        pieces.push(piece);
+    }
+    Ok(pieces)
+}
+
+/// Concat demuxer input list. Single quotes in paths are escaped the way the demuxer expects.
+pub fn concat_list(inputs: &[PathBuf]) -> String {
+    inputs.iter()
+        .map(|path| format!("file '{}'\n", path.to_string_lossy().replace('\'', "'\\''")))
+        .collect()
+}
+
+/// `-filter_complex` for a re-encoding concat: every input is scaled and padded to
+/// `width`x`height` and the first `audio_tracks` tracks of each are joined track by track.
+/// Returns the filter and the output labels to map, video first.
+pub fn reencode_concat_filter(input_count: usize, audio_tracks: usize, width: u32, height: u32) -> (String, Vec<String>) {
+    let mut filter = String::new();
+    let mut concat_inputs = String::new();
+    for input in 0..input_count {
+        filter.push_str(&format!(
+            "[{input}:v:0]scale={width}:{height}:force_original_aspect_ratio=decrease,\
+             pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,setsar=1[v{input}];"
+        ));
+        concat_inputs.push_str(&format!("[v{input}]"));
+        for track in 0..audio_tracks {
+            filter.push_str(&format!(
+                "[{input}:a:{track}]aformat=sample_rates=48000:channel_layouts=stereo[a{input}_{track}];"
+            ));
+            concat_inputs.push_str(&format!("[a{input}_{track}]"));
+        }
+    }
+
+    let mut outputs = vec!["[v]".to_string()];
+    outputs.extend((0..audio_tracks).map(|track| format!("[a{}]", track)));
+    filter.push_str(&format!("{}concat=n={}:v=1:a={}{}", concat_inputs, input_count, audio_tracks, outputs.concat()));
+    (filter, outputs)
+}
+
+/// Join `inputs` in the given order into `output`. Uses a stream copy when every input
+/// has the same layout, otherwise re-encodes to the first input's resolution with `encoder`
+/// (or libx264 if that isn't available).
+/// Blocks until FFmpeg is done, so call this off the UI thread.
+pub fn merge_files(inputs: &[PathBuf], output: &Path, encoder: VideoEncoder) -> anyhow::Result<ConcatMode> {
+    if inputs.len() < 2 {
+        return Err(anyhow::anyhow!("Merging needs at least two clips"));
+    }
+    let layouts = inputs.iter()
+        .map(|input| StreamLayout::probe(input))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mode = if layouts.windows(2).all(|pair| pair[0] == pair[1]) {
+        ConcatMode::StreamCopy
+    } else {
+        ConcatMode::ReEncode
+    };
+
+    let temp_path = VideoProcessor::temp_output_path(output);
+    let mut cmd = Command::new("ffmpeg");
+    let list_path = std::env::temp_dir().join(format!("clip-helper-concat-{}.txt", uuid::Uuid::new_v4()));
+    match mode {
+        ConcatMode::StreamCopy => {
+            std::fs::write(&list_path, concat_list(inputs))
+                .map_err(|e| anyhow::anyhow!("Failed to write concat list: {}", e))?;
+            cmd.arg("-f").arg("concat")
+                .arg("-safe").arg("0")
+                .arg("-i").arg(&list_path)
+                .arg("-map").arg("0")
+                .arg("-c").arg("copy");
+        }
+        ConcatMode::ReEncode => {
+            log::warn!("Clips to merge differ in codec or resolution, re-encoding: {:?}", layouts);
+            let audio_tracks = layouts.iter().map(|layout| layout.audio_tracks.len()).min().unwrap_or(0);
+            let (filter, outputs) = reencode_concat_filter(inputs.len(), audio_tracks, layouts[0].width, layouts[0].height);
+            for input in inputs {
+                cmd.arg("-i").arg(input);
+            }
+            cmd.arg("-filter_complex").arg(filter);
+            for label in outputs {
+                cmd.arg("-map").arg(label);
+            }
+            cmd.args(encoder.or_fallback().args(EncoderQuality::Crf(18)))
+                .arg("-c:a").arg("aac")
+                .arg("-b:a").arg("192k");
+        }
+    }
+    cmd.arg("-y").arg(&temp_path);
+
+    let result = output_with_stderr_tail(&mut cmd);
+    if list_path.exists() {
+        let _ = std::fs::remove_file(&list_path);
+    }
+    VideoProcessor::finish_temp_output(result, &temp_path, output, "FFmpeg merge failed")?;
+    Ok(mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_layout_from_ffprobe() {
+        let info = serde_json::json!({"streams": [
+            {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080, "pix_fmt": "yuv420p"},
+            {"codec_type": "audio", "codec_name": "aac", "sample_rate": "48000", "channels": 2},
+            {"codec_type": "audio", "codec_name": "aac", "sample_rate": "48000", "channels": 2},
+        ]});
+        let layout = StreamLayout::from_ffprobe(&info).unwrap();
+        assert_eq!((layout.width, layout.height), (1920, 1080));
+        assert_eq!(layout.audio_tracks, vec!["aac/48000/2", "aac/48000/2"]);
+
+        assert!(StreamLayout::from_ffprobe(&serde_json::json!({"streams": []})).is_err());
+    }
+
+    #[test]
+    fn test_concat_list_and_filter() {
+        let list = concat_list(&[PathBuf::from("/replays/Replay 1.mkv"), PathBuf::from("/replays/Bob's clip.mkv")]);
+        assert_eq!(list, "file '/replays/Replay 1.mkv'\nfile '/replays/Bob'\\''s clip.mkv'\n");
+
+        let (filter, outputs) = reencode_concat_filter(2, 1, 1280, 720);
+        assert_eq!(outputs, vec!["[v]", "[a0]"]);
+        assert!(filter.ends_with("[v0][a0_0][v1][a1_0]concat=n=2:v=1:a=1[v][a0]"));
+    }
+
+    #[test]
+    fn test_session_output_path() {
+        let directory = std::env::temp_dir().join(format!("clip-helper-session-path-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).expect("Failed to create test directory");
+
+        let first = session_output_path(&directory, "2025-08-19", "14:56");
+        assert_eq!(first, directory.join("2025-08-19_14-56_session.mkv"));
+        std::fs::write(&first, b"merged").expect("Failed to write test file");
+        assert_eq!(session_output_path(&directory, "2025-08-19", "14:56"), directory.join("2025-08-19_14-56_session 2.mkv"));
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
@@ -8,6 +8,15 @@ pub mod media_controller_new;
 pub mod async_video_info;
 pub mod hover_thumbnails;
 pub mod ffmpeg_manager;
+pub mod export_queue;
+pub mod process_pool;
+pub mod scene_detection;
+pub mod disk_space;
+pub mod merge;
+pub mod black_detection;
+pub mod motion_detection;
+pub mod ffprobe_cache;
+pub mod encoders;
 
 pub use processor::*;
 pub use preview::*;
@@ -18,3 +27,11 @@ pub use media_controller_new::*;
 pub use async_video_info::*;
 pub use hover_thumbnails::*;
 pub use ffmpeg_manager::execute_ffmpeg;
+pub use export_queue::*;
+pub use process_pool::*;
+pub use scene_detection::*;
+pub use disk_space::*;
+pub use black_detection::*;
+pub use motion_detection::*;
+pub use ffprobe_cache::*;
+pub use encoders::*;
@@ -0,0 +1,110 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use crate::video::ffmpeg_manager::stderr_tail;
+
+/// Scene score (0-1) the first action frame needs. Lower than scene detection's default,
+/// since a loading screen turning into gameplay is often a fade rather than a cut.
+pub const DEFAULT_MOTION_THRESHOLD: f64 = 0.2;
+/// Only this much after `start` is searched; action that late isn't a slow start anymore
+pub const MOTION_SEARCH_MAX_SECONDS: f64 = 30.0;
+/// FFmpeg is stopped after this long, so a slow disk can't keep a decoder busy forever
+const MOTION_DETECTION_TIMEOUT: Duration = Duration::from_secs(10);
+/// Frames are scored at this width; scene scores barely change and decoding gets cheaper
+const MOTION_ANALYSIS_WIDTH: u32 = 320;
+
+pub struct VideoAnalyzer;
+
+impl VideoAnalyzer {
+    /// Time of the first frame in `start`..`end` (at most `MOTION_SEARCH_MAX_SECONDS` of it)
+    /// whose scene score exceeds `threshold`, e.g. where a loading screen ends.
+    /// Returns `start` if nothing in the window changes that much.
+    ///
+    /// FFmpeg stops at the first matching frame and is killed after 10 seconds,
+    /// but this still blocks until then, so call it off the UI thread.
+    pub fn detect_motion_start(path: &Path, start: f64, end: f64, threshold: f64) -> anyhow::Result<f64> {
+        let window = (end - start).min(MOTION_SEARCH_MAX_SECONDS);
+        if window <= 0.0 {
+            return Ok(start);
+        }
+
+        let mut child = Command::new("ffmpeg")
+            .arg("-hide_banner")
+            .arg("-v").arg("error")
+            .arg("-ss").arg(format!("{:.3}", start.max(0.0)))
+            .arg("-t").arg(format!("{:.3}", window))
+            .arg("-i").arg(path)
+            .arg("-an")
+            .arg("-vf").arg(format!(
+                "scale={}:-2,select=gt(scene\\,{:.3})",
+                MOTION_ANALYSIS_WIDTH, threshold
+            ))
+            .arg("-vsync").arg("vfr")
+            .arg("-frames:v").arg("1")
+            .arg("-f").arg("framemd5")
+            .arg("-")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to run ffmpeg: {}", e))?;
+
+        // Both outputs stay tiny (one frame line, errors only), so polling can't fill the pipes
+        let started = Instant::now();
+        while child.try_wait()?.is_none() {
+            if started.elapsed() >= MOTION_DETECTION_TIMEOUT {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(anyhow::anyhow!(
+                    "Motion detection of {} took longer than {}s",
+                    path.display(),
+                    MOTION_DETECTION_TIMEOUT.as_secs()
+                ));
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        let output = child.wait_with_output()
+            .map_err(|e| anyhow::anyhow!("Failed to read FFmpeg output: {}", e))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!("Motion detection failed: {}", stderr_tail(&output.stderr)));
+        }
+        Ok(start + first_frame_time(&String::from_utf8_lossy(&output.stdout)).unwrap_or(0.0))
+    }
+}
+
+/// Timestamp of the first frame in framemd5 output, which declares the stream's time base
+/// ("#tb 0: 1/1000") and then lists "stream, dts, pts, duration, size, hash" per frame
+fn first_frame_time(framemd5: &str) -> Option<f64> {
+    let mut time_base = None;
+    for line in framemd5.lines() {
+        if let Some(rest) = line.strip_prefix("#tb 0:") {
+            let (num, den) = rest.trim().split_once('/')?;
+            time_base = Some(num.trim().parse::<f64>().ok()? / den.trim().parse::<f64>().ok()?);
+        } else if !line.starts_with('#') {
+            let pts: f64 = line.split(',').nth(2)?.trim().parse().ok()?;
+            return Some(pts * time_base?);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_frame_time() {
+        let output = "#format: frame checksums\n\
+            #version: 2\n\
+            #hash: MD5\n\
+            #tb 0: 1/1000\n\
+            #media_type 0: video\n\
+            #stream#, dts,        pts, duration,     size, hash\n\
+            0,       4233,       4233,       17,   115200, 6d0e8ba5e6e0b3a40ef4c0b4a4a5b1f4\n";
+        assert_eq!(first_frame_time(output), Some(4.233));
+
+        // No frame scored above the threshold
+        assert_eq!(first_frame_time("#tb 0: 1/1000\n#media_type 0: video\n"), None);
+        assert_eq!(first_frame_time(""), None);
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use anyhow::Result;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Number of FFmpeg/ffprobe jobs allowed to run at once unless configured otherwise
+pub const DEFAULT_MAX_CONCURRENT_FFMPEG: usize = 4;
+
+type BoxedJob = Box<dyn FnOnce() -> Result<()> + Send + 'static>;
+
+/// A queued job together with the state its `JobHandle` watches
+struct PendingJob {
+    job: BoxedJob,
+    state: Arc<JobState>,
+}
+
+#[derive(Default)]
+struct JobState {
+    result: Mutex<Option<Result<(), String>>>,
+    finished: Condvar,
+}
+
+impl JobState {
+    fn finish(&self, result: Result<(), String>) {
+        if let Ok(mut slot) = self.result.lock() {
+            *slot = Some(result);
+        }
+        self.finished.notify_all();
+    }
+}
+
+/// Handle to a job submitted to an `FfmpegProcessPool`
+#[derive(Clone)]
+pub struct JobHandle {
+    state: Arc<JobState>,
+}
+
+impl JobHandle {
+    pub fn is_finished(&self) -> bool {
+        self.state.result.lock().map(|result| result.is_some()).unwrap_or(true)
+    }
+
+    /// Block until the job has run, returning its result
+    pub fn wait(&self) -> Result<()> {
+        let mut result = self.state.result.lock()
+            .map_err(|_| anyhow::anyhow!("FFmpeg job state poisoned"))?;
+        while result.is_none() {
+            result = self.state.finished.wait(result)
+                .map_err(|_| anyhow::anyhow!("FFmpeg job state poisoned"))?;
+        }
+        result.clone().unwrap_or(Ok(())).map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+struct PoolInner {
+    queue: Mutex<VecDeque<PendingJob>>,
+    semaphore: Arc<Semaphore>,
+    /// Current limit and the number of permits still held by running jobs
+    /// that have to be dropped after the limit was lowered
+    limits: Mutex<PoolLimits>,
+}
+
+struct PoolLimits {
+    max_concurrent: usize,
+    permits_to_forget: usize,
+}
+
+/// Limits how many FFmpeg-backed jobs (thumbnails, waveforms, probes, exports)
+/// run at the same time. Jobs beyond the limit wait in a FIFO queue.
+pub struct FfmpegProcessPool {
+    inner: Arc<PoolInner>,
+}
+
+impl FfmpegProcessPool {
+    pub fn new(max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            inner: Arc::new(PoolInner {
+                queue: Mutex::new(VecDeque::new()),
+                semaphore: Arc::new(Semaphore::new(max_concurrent)),
+                limits: Mutex::new(PoolLimits { max_concurrent, permits_to_forget: 0 }),
+            }),
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.inner.limits.lock().map(|limits| limits.max_concurrent).unwrap_or(1)
+    }
+
+    /// Change the limit. Lowering it lets running jobs finish; they just aren't replaced.
+    pub fn set_max_concurrent(&self, max_concurrent: usize) {
+        let max_concurrent = max_concurrent.max(1);
+        {
+            let Ok(mut limits) = self.inner.limits.lock() else {
+                return;
+            };
+            if max_concurrent > limits.max_concurrent {
+                let mut extra = max_concurrent - limits.max_concurrent;
+                let cancelled = extra.min(limits.permits_to_forget);
+                limits.permits_to_forget -= cancelled;
+                extra -= cancelled;
+                self.inner.semaphore.add_permits(extra);
+            } else {
+                let removed = limits.max_concurrent - max_concurrent;
+                let forgotten = self.inner.semaphore.forget_permits(removed);
+                limits.permits_to_forget += removed - forgotten;
+            }
+            limits.max_concurrent = max_concurrent;
+        }
+        log::debug!("FFmpeg process pool limit set to {}", max_concurrent);
+        Self::dispatch(&self.inner);
+    }
+
+    /// Number of jobs waiting for a free slot
+    pub fn queued_count(&self) -> usize {
+        self.inner.queue.lock().map(|queue| queue.len()).unwrap_or(0)
+    }
+
+    /// Queue a job; it runs on its own thread once a slot is free
+    pub fn submit(&self, job: impl FnOnce() -> Result<()> + Send + 'static) -> JobHandle {
+        let state = Arc::new(JobState::default());
+        if let Ok(mut queue) = self.inner.queue.lock() {
+            queue.push_back(PendingJob { job: Box::new(job), state: state.clone() });
+        }
+        Self::dispatch(&self.inner);
+        JobHandle { state }
+    }
+
+    /// Queue a job and block the calling thread until it has run, returning its value.
+    /// Meant for background threads whose FFmpeg work should count towards the limit.
+    pub fn run<T: Send + 'static>(&self, job: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        self.submit(move || {
+            let _ = result_tx.send(job());
+            Ok(())
+        }).wait()?;
+        result_rx.recv()?
+    }
+
+    /// Start queued jobs while permits are available
+    fn dispatch(inner: &Arc<PoolInner>) {
+        loop {
+            let Ok(permit) = inner.semaphore.clone().try_acquire_owned() else {
+                return;
+            };
+            let pending = inner.queue.lock().ok().and_then(|mut queue| queue.pop_front());
+            let Some(pending) = pending else {
+                return;
+            };
+
+            let worker_inner = inner.clone();
+            thread::spawn(move || {
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(pending.job))
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("FFmpeg job panicked")));
+                pending.state.finish(result.map_err(|e| e.to_string()));
+
+                Self::release(&worker_inner, permit);
+                Self::dispatch(&worker_inner);
+            });
+        }
+    }
+
+    fn release(inner: &PoolInner, permit: OwnedSemaphorePermit) {
+        if let Ok(mut limits) = inner.limits.lock() {
+            if limits.permits_to_forget > 0 {
+                limits.permits_to_forget -= 1;
+                permit.forget();
+            }
+        }
+    }
+}
+
+impl Default for FfmpegProcessPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_FFMPEG)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn run_tracked_jobs(pool: &FfmpegProcessPool, count: usize) -> usize {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<JobHandle> = (0..count)
+            .map(|_| {
+                let running = running.clone();
+                let peak = peak.clone();
+                pool.submit(move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.wait().expect("job failed");
+        }
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_pool_limits_concurrency() {
+        let pool = FfmpegProcessPool::new(2);
+        let peak = run_tracked_jobs(&pool, 8);
+        assert!(peak <= 2, "peak concurrency was {}", peak);
+        assert_eq!(pool.queued_count(), 0);
+    }
+
+    #[test]
+    fn test_pool_limit_can_be_lowered() {
+        let pool = FfmpegProcessPool::new(4);
+        pool.set_max_concurrent(1);
+        assert_eq!(pool.max_concurrent(), 1);
+        assert_eq!(run_tracked_jobs(&pool, 4), 1);
+    }
+
+    #[test]
+    fn test_job_errors_reach_handle() {
+        let pool = FfmpegProcessPool::new(1);
+        let failing = pool.submit(|| Err(anyhow::anyhow!("boom")));
+        let panicking = pool.submit(|| panic!("job panic"));
+        let succeeding = pool.submit(|| Ok(()));
+
+        assert_eq!(failing.wait().unwrap_err().to_string(), "boom");
+        assert!(panicking.wait().is_err());
+        assert!(succeeding.wait().is_ok());
+        assert!(succeeding.is_finished());
+    }
+
+    #[test]
+    fn test_run_returns_job_value() {
+        let pool = FfmpegProcessPool::new(1);
+        assert_eq!(pool.run(|| Ok(42)).unwrap(), 42);
+        assert_eq!(pool.run(|| Err::<(), _>(anyhow::anyhow!("boom"))).unwrap_err().to_string(), "boom");
+    }
+}
@@ -1,22 +1,208 @@
-use crate::core::Clip;
-use std::path::Path;
+use crate::core::{AudioTrack, Clip, SurroundMode, VideoWriteState};
+use crate::core::config::ScreenshotFormat;
+use crate::video::ffmpeg_manager::output_with_stderr_tail;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use crate::video::{EncoderQuality, ExportJob};
+
+/// Bounding box for preview frames; the actual size follows the video's display aspect ratio
+pub const PREVIEW_MAX_WIDTH: u32 = 854;
+pub const PREVIEW_MAX_HEIGHT: u32 = 480;
+
+/// Converts PQ/HLG frames to SDR BT.709 the way a tonemapped export would
+pub const HDR_TONEMAP_FILTER: &str =
+    "zscale=t=linear:npl=100,format=gbrpf32le,zscale=p=bt709,tonemap=tonemap=hable:desat=0,zscale=t=bt709:m=bt709:r=tv,format=yuv420p";
+
+/// Bitrate of each AAC track in a two-pass export
+pub const TWO_PASS_AUDIO_KBPS: f64 = 128.0;
+/// Target sizes that leave less than this for the video are refused rather than
+/// producing an unwatchable export
+const MIN_TARGET_VIDEO_KBPS: f64 = 100.0;
+
+/// Gap between the two probes that tell a finished replay from one OBS is still writing
+pub const WRITE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+/// Replays last modified longer ago than this are assumed finished and probed only once
+const RECENT_WRITE_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How far a re-encoded export's container duration may be from the trimmed range
+pub const VERIFY_DURATION_TOLERANCE_SECONDS: f64 = 0.1;
+/// The same for stream-copied video, which can only start on a keyframe and so may run
+/// up to one keyframe interval (2s in OBS by default) longer
+pub const STREAM_COPY_DURATION_TOLERANCE_SECONDS: f64 = 2.0;
+
+/// Transfer characteristics (as reported by ffprobe) that mark a stream as HDR
+pub(crate) const HDR_TRANSFERS: [&str; 2] = ["smpte2084", "arib-std-b67"];
+
+static TONEMAP_STILL_FRAMES: AtomicBool = AtomicBool::new(true);
+static TONEMAP_PLAYBACK: AtomicBool = AtomicBool::new(false);
+
+/// Which pipeline a preview filter is built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewPipeline {
+    /// Single frames: paused preview, seeks, thumbnails and screenshots
+    StillFrame,
+    /// Continuous playback, where tonemapping adds noticeable latency
+    Playback,
+}
+
+/// Enable or disable HDR tonemapping for still frames and for continuous playback
+pub fn set_hdr_tonemapping(still_frames: bool, playback: bool) {
+    TONEMAP_STILL_FRAMES.store(still_frames, Ordering::Relaxed);
+    TONEMAP_PLAYBACK.store(playback, Ordering::Relaxed);
+}
 
 pub struct VideoProcessor;
 
+/// libx264's two-pass stats files in the temp directory, deleted on drop
+struct PassLogFiles {
+    /// `-passlogfile` value; FFmpeg appends "-0.log" and libx264 adds ".mbtree" and ".temp" files
+    prefix: PathBuf,
+}
+
+impl PassLogFiles {
+    fn new() -> Self {
+        Self {
+            prefix: std::env::temp_dir().join(format!("clip-helper-passlog-{}", uuid::Uuid::new_v4())),
+        }
+    }
+}
+
+impl Drop for PassLogFiles {
+    fn drop(&mut self) {
+        let prefix = self.prefix.to_string_lossy();
+        for suffix in ["-0.log", "-0.log.temp", "-0.log.mbtree", "-0.log.mbtree.temp"] {
+            let path = PathBuf::from(format!("{}{}", prefix, suffix));
+            if path.exists() {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::warn!("Failed to remove pass log {}: {}", path.display(), e);
+                }
+            }
+        }
+    }
+}
+
 impl VideoProcessor {
-    pub fn trim_clip(clip: &Clip, output_path: &Path, force_overwrite: bool) -> anyhow::Result<()> {
-        let start_time = format!("{:.3}", clip.trim_start);
-        let duration = format!("{:.3}", clip.trim_end - clip.trim_start);
+    /// Trim a clip; with `separate_audio_tracks` every enabled track is also extracted to a WAV
+    /// once the video export has finished
+    pub fn trim_clip(
+        clip: &Clip,
+        output_path: &Path,
+        force_overwrite: bool,
+        separate_audio_tracks: bool,
+        extra_args: &[String],
+    ) -> anyhow::Result<()> {
+        Self::trim_file(
+            &clip.original_file,
+            clip.trim_start,
+            clip.trim_end,
+            &clip.audio_tracks,
+            output_path,
+            force_overwrite,
+            extra_args,
+        )?;
+
+        let expected_tracks = Self::expected_audio_track_count(&clip.audio_tracks);
+        let expected_duration = clip.trim_end - clip.trim_start;
+        if let Err(e) = Self::verify_output(output_path, expected_duration, STREAM_COPY_DURATION_TOLERANCE_SECONDS, expected_tracks) {
+            return Err(match Self::dispose_unverified_output(output_path, &e) {
+                Some(corrupt_path) => anyhow::anyhow!("Export failed verification ({}), kept as {}", e, corrupt_path.display()),
+                None => anyhow::anyhow!("Export failed verification ({}) and was deleted", e),
+            });
+        }
+
+        if separate_audio_tracks {
+            for (_, result) in Self::extract_audio_tracks(&clip.original_file, clip.trim_start, clip.trim_end, &clip.audio_tracks, output_path) {
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `<output_stem>_track<index>.<wav|flac|mp3>` next to `output_path`
+    pub fn audio_track_output_path(output_path: &Path, track_index: usize, format: AudioExportFormat) -> PathBuf {
+        let stem = output_path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("clip");
+        output_path.with_file_name(format!("{}_track{}.{}", stem, track_index, format.extension()))
+    }
+
+    /// Extract every enabled track of `audio_tracks` next to `output_path`, one after another.
+    /// Returns each track index with the written path or the error.
+    pub fn extract_audio_tracks(
+        input_path: &Path,
+        start: f64,
+        end: f64,
+        audio_tracks: &[AudioTrack],
+        output_path: &Path,
+    ) -> Vec<(usize, anyhow::Result<PathBuf>)> {
+        audio_tracks.iter()
+            .filter(|track| track.enabled)
+            .map(|track| {
+                let track_output = Self::audio_track_output_path(output_path, track.index, AudioExportFormat::Wav);
+                let result = Self::extract_audio_track(input_path, track, start, end, AudioExportFormat::Wav, &track_output)
+                    .map(|()| track_output);
+                (track.index, result)
+            })
+            .collect()
+    }
+
+    /// Write one audio track of the given range to `output`, folded down with the track's
+    /// surround mode like in the export mix. An existing file is replaced.
+    pub fn extract_audio_track(
+        path: &Path,
+        track: &AudioTrack,
+        start: f64,
+        end: f64,
+        format: AudioExportFormat,
+        output: &Path,
+    ) -> anyhow::Result<()> {
+        let temp_path = Self::temp_output_path(output);
+        let mut cmd = Command::new("ffmpeg");
+        cmd.arg("-ss").arg(format!("{:.3}", start))
+            .arg("-to").arg(format!("{:.3}", end))
+            .arg("-i").arg(path)
+            .arg("-map").arg(format!("0:a:{}", track.index));
+        if let Some(pan) = track.surround_mode.pan_filter() {
+            cmd.arg("-af").arg(pan);
+        }
+        cmd.args(format.codec_args())
+            .arg("-y")
+            .arg(&temp_path);
+        let result = output_with_stderr_tail(&mut cmd);
+        Self::finish_temp_output(result, &temp_path, output, "FFmpeg audio track extraction failed")
+    }
+
+    /// Trim a source file to the given range, mixing enabled audio tracks into track 1.
+    /// `extra_args` come from the export preset and go after everything computed here.
+    pub fn trim_file(
+        input_path: &Path,
+        trim_start: f64,
+        trim_end: f64,
+        audio_tracks: &[AudioTrack],
+        output_path: &Path,
+        force_overwrite: bool,
+        extra_args: &[String],
+    ) -> anyhow::Result<()> {
+        let start_time = format!("{:.3}", trim_start);
+        let duration = format!("{:.3}", trim_end - trim_start);
         
         // Check if output file exists and prompt if not force overwrite
         if output_path.exists() && !force_overwrite {
             return Err(anyhow::anyhow!("Output file exists. Use shift+click to overwrite."));
         }
         
+        // A full disk would leave a truncated output behind, so don't start if it clearly won't fit
+        let mixes_audio = audio_tracks.iter().any(|track| track.enabled);
+        let estimated_size = Self::estimate_trim_size(input_path, trim_end - trim_start, mixes_audio);
+        Self::ensure_disk_space(output_path, estimated_size)?;
+        
         let mut cmd = Command::new("ffmpeg");
         cmd.arg("-i")
-            .arg(&clip.original_file)
+            .arg(input_path)
             .arg("-ss")
             .arg(&start_time)
             .arg("-t")
@@ -24,68 +210,515 @@ impl VideoProcessor {
             .arg("-c:v")
             .arg("copy"); // Copy video without re-encoding for speed
 
-        // Handle audio tracks
-        if !clip.audio_tracks.is_empty() {
-            // Create mixed track (track 1)
-            let mut filter_complex = String::new();
-            let mut audio_inputs = Vec::new();
-            
-            for (i, track) in clip.audio_tracks.iter().enumerate() {
-                if track.enabled {
-                    if track.surround_mode {
-                        // Map to surround left/right
-                        audio_inputs.push(format!("[0:a:{}]channelmap=map=FL|FR[a{}]", track.index, i));
-                    } else {
-                        audio_inputs.push(format!("[0:a:{}][a{}]", track.index, i));
-                    }
-                }
+        cmd.args(Self::audio_mapping_args(audio_tracks));
+
+        // Stream copy doesn't carry every container's rotation info over (MKV in particular),
+        // so write the rotation explicitly to keep portrait clips upright in the export
+        if let Ok(geometry) = Self::probe_geometry(input_path) {
+            if geometry.rotation != 0 {
+                cmd.arg("-metadata:s:v:0").arg(format!("rotate={}", geometry.rotation));
             }
-            
-            if !audio_inputs.is_empty() {
-                // Mix enabled tracks
-                filter_complex = format!("{}{}amix=inputs={}[mixed]", 
-                    audio_inputs.join(";"), 
-                    if audio_inputs.len() > 1 { ";" } else { "" },
-                    audio_inputs.len()
-                );
-                
-                cmd.arg("-filter_complex").arg(&filter_complex);
-                cmd.arg("-map").arg("0:v"); // Map video
-                cmd.arg("-map").arg("[mixed]"); // Map mixed audio to track 1
-                
-                // Map original audio tracks
-                for track in &clip.audio_tracks {
-                    cmd.arg("-map").arg(format!("0:a:{}", track.index));
+        }
+
+        // The existing-output check above already honoured force_overwrite; the temp file
+        // only exists if an earlier run of this process crashed mid-export
+        let temp_path = Self::temp_output_path(output_path);
+        cmd.args(extra_args);
+        cmd.arg("-y");
+        cmd.arg(&temp_path);
+
+        let output = output_with_stderr_tail(&mut cmd);
+        Self::finish_temp_output(output, &temp_path, output_path, "FFmpeg trim failed")
+    }
+
+    /// `-filter_complex`/`-map` arguments that mix the enabled tracks into track 1 and keep
+    /// every original track after it. Empty (FFmpeg's default selection) without enabled tracks.
+    fn audio_mapping_args(audio_tracks: &[AudioTrack]) -> Vec<String> {
+        let mut filter_parts = Vec::new();
+        let mut mix_inputs = Vec::new();
+        for (i, track) in audio_tracks.iter().enumerate().filter(|(_, track)| track.enabled) {
+            if let Some(pan) = track.surround_mode.pan_filter() {
+                filter_parts.push(format!("[0:a:{}]{}[a{}]", track.index, pan, i));
+                mix_inputs.push(format!("[a{}]", i));
+            } else {
+                mix_inputs.push(format!("[0:a:{}]", track.index));
+            }
+        }
+        if mix_inputs.is_empty() {
+            return Vec::new();
+        }
+
+        // Mix enabled tracks
+        filter_parts.push(format!("{}amix=inputs={}[mixed]", mix_inputs.concat(), mix_inputs.len()));
+        let filter_complex = filter_parts.join(";");
+        let mut args = vec![
+            "-filter_complex".to_string(), filter_complex,
+            "-map".to_string(), "0:v".to_string(), // Map video
+            "-map".to_string(), "[mixed]".to_string(), // Map mixed audio to track 1
+        ];
+        // Map original audio tracks
+        for track in audio_tracks {
+            args.push("-map".to_string());
+            args.push(format!("0:a:{}", track.index));
+        }
+        args
+    }
+
+    /// Video bitrate in kbit/s that makes a `duration` second export land at `target_size_mb`
+    /// once `audio_kbps` of audio is taken off. Errors if the audio alone fills the target.
+    pub fn target_video_bitrate_kbps(target_size_mb: f64, audio_kbps: f64, duration: f64) -> anyhow::Result<f64> {
+        if duration <= 0.0 {
+            return Err(anyhow::anyhow!("Can't target a file size for an empty clip"));
+        }
+        let bitrate = (target_size_mb * 8192.0 - audio_kbps * duration) / duration;
+        if bitrate < MIN_TARGET_VIDEO_KBPS {
+            return Err(anyhow::anyhow!(
+                "{:.0} MB is too small for {:.1}s of video (would be {:.0} kbit/s)",
+                target_size_mb, duration, bitrate
+            ));
+        }
+        Ok(bitrate)
+    }
+
+    /// Re-encode the job's range so the output lands close to `target_size_mb`: two-pass
+    /// with libx264, one bitrate-targeted pass with a hardware encoder. The libx264 pass
+    /// being run (1 or 2) is stored in `current_pass`.
+    /// Blocks until the encode is done, so call this off the UI thread.
+    pub fn trim_clip_two_pass(job: &ExportJob, target_size_mb: f64, current_pass: &AtomicU8) -> anyhow::Result<()> {
+        if job.output_path.exists() && !job.force_overwrite {
+            return Err(anyhow::anyhow!("Output file exists. Use shift+click to overwrite."));
+        }
+
+        let duration = job.trim_end - job.trim_start;
+        let audio_track_count = Self::expected_audio_track_count(&job.audio_tracks).max(1);
+        let audio_kbps = TWO_PASS_AUDIO_KBPS * audio_track_count as f64;
+        let video_kbps = Self::target_video_bitrate_kbps(target_size_mb, audio_kbps, duration)?;
+        let encoder = job.video_encoder.or_fallback();
+        Self::ensure_disk_space(&job.output_path, (target_size_mb * 1024.0 * 1024.0 * encoder.target_size_overshoot()) as u64)?;
+        let temp_path = Self::temp_output_path(&job.output_path);
+        let input_args = [
+            "-ss".to_string(), format!("{:.3}", job.trim_start),
+            "-t".to_string(), format!("{:.3}", duration),
+            "-i".to_string(), job.clip_path.to_string_lossy().to_string(),
+        ];
+
+        if !encoder.supports_two_pass() {
+            log::info!("{} export of {} at {:.0} kbit/s video", encoder.codec_name(), job.clip_path.display(), video_kbps);
+            let result = output_with_stderr_tail(Command::new("ffmpeg")
+                .args(&input_args)
+                .args(encoder.args(EncoderQuality::Bitrate(video_kbps)))
+                .args(Self::audio_mapping_args(&job.audio_tracks))
+                .arg("-c:a").arg("aac")
+                .arg("-b:a").arg(format!("{:.0}k", TWO_PASS_AUDIO_KBPS))
+                .args(&job.extra_ffmpeg_args)
+                .arg("-y")
+                .arg(&temp_path));
+            return Self::finish_temp_output(result, &temp_path, &job.output_path, "FFmpeg export failed");
+        }
+        log::info!("Two-pass export of {} at {:.0} kbit/s video", job.clip_path.display(), video_kbps);
+
+        // Removes libx264's stats files when this goes out of scope, whatever happens below
+        let passlog = PassLogFiles::new();
+        let encode_args = |pass: u8| -> Vec<String> {
+            let mut args = input_args.to_vec();
+            args.extend(encoder.args(EncoderQuality::Bitrate(video_kbps)));
+            args.extend([
+                "-pass".to_string(), pass.to_string(),
+                "-passlogfile".to_string(), passlog.prefix.to_string_lossy().to_string(),
+            ]);
+            args
+        };
+
+        current_pass.store(1, Ordering::Relaxed);
+        let null_output = if cfg!(windows) { "NUL" } else { "/dev/null" };
+        let first_pass = output_with_stderr_tail(Command::new("ffmpeg")
+            .args(encode_args(1))
+            .arg("-an")
+            .arg("-f").arg("null")
+            .arg("-y")
+            .arg(null_output))
+            .map_err(|e| anyhow::anyhow!("FFmpeg pass 1 failed: {}", e))?;
+        if !first_pass.status.success() {
+            return Err(anyhow::anyhow!(first_pass.failure_message("FFmpeg pass 1 failed")));
+        }
+
+        current_pass.store(2, Ordering::Relaxed);
+        let second_pass = output_with_stderr_tail(Command::new("ffmpeg")
+            .args(encode_args(2))
+            .args(Self::audio_mapping_args(&job.audio_tracks))
+            .arg("-c:a").arg("aac")
+            .arg("-b:a").arg(format!("{:.0}k", TWO_PASS_AUDIO_KBPS))
+            .args(&job.extra_ffmpeg_args)
+            .arg("-y")
+            .arg(&temp_path));
+        Self::finish_temp_output(second_pass, &temp_path, &job.output_path, "FFmpeg pass 2 failed")
+    }
+
+    /// Warnings for preset arguments that fight the command `trim_file` builds: extra inputs,
+    /// overwrite flags and anything else that would change where or whether the output is written
+    pub fn validate_extra_args(args: &[String]) -> Vec<String> {
+        args.iter()
+            .map(|arg| arg.trim())
+            .filter_map(|arg| match arg {
+                "-i" => Some("-i adds another input; the clip is already the only input".to_string()),
+                "-y" | "-n" => Some(format!("{} is redundant, overwriting is handled by Clip Helper", arg)),
+                "-f" => Some("-f changes the container and may not match the output extension".to_string()),
+                "-ss" | "-t" | "-to" => Some(format!("{} overrides the trim range", arg)),
+                _ if !arg.starts_with('-') && !arg.contains('=') && Self::has_file_extension(arg) => {
+                    Some(format!("\"{}\" looks like a file name; FFmpeg may treat it as a second output", arg))
                 }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether `arg` ends in a media or other file extension FFmpeg would write to, unlike
+    /// option values such as "2.5M" or "18.5"
+    fn has_file_extension(arg: &str) -> bool {
+        const OTHER_FILE_EXTENSIONS: [&str; 16] = [
+            "wav", "flac", "mp3", "aac", "m4a", "ogg", "opus", "gif", "webp", "png", "jpg", "jpeg", "srt", "ass", "txt", "json",
+        ];
+        Path::new(arg).extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                crate::core::import::VIDEO_EXTENSIONS.iter()
+                    .chain(OTHER_FILE_EXTENSIONS.iter())
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+            })
+    }
+
+    /// Estimated size of a `trim_file` output, from the source's size and duration
+    pub fn estimate_trim_size(input_path: &Path, trim_duration: f64, mixes_audio: bool) -> u64 {
+        let input_size = std::fs::metadata(input_path).map(|metadata| metadata.len()).unwrap_or(0);
+        let source_duration = Self::get_video_info(input_path).map(|info| info.duration).unwrap_or(0.0);
+        crate::video::estimate_stream_copy_size(input_size, source_duration, trim_duration, mixes_audio)
+    }
+
+    /// Refuse an export that won't fit on the output's volume; only log one that barely fits
+    pub fn ensure_disk_space(output_path: &Path, estimated_size: u64) -> anyhow::Result<()> {
+        let check = crate::video::check_disk_space(output_path, estimated_size);
+        match (check, check.message()) {
+            (crate::video::DiskSpaceCheck::Insufficient { .. }, Some(message)) => Err(anyhow::anyhow!(message)),
+            (_, Some(message)) => {
+                log::warn!("{} ({})", message, output_path.display());
+                Ok(())
             }
+            (_, None) => Ok(()),
+        }
+    }
+
+    /// Audio tracks `trim_file` writes for this layout: the mix plus every original track.
+    /// Without an enabled track FFmpeg picks the streams itself, so nothing is expected.
+    pub fn expected_audio_track_count(audio_tracks: &[AudioTrack]) -> usize {
+        if audio_tracks.iter().any(|track| track.enabled) {
+            1 + audio_tracks.len()
+        } else {
+            0
         }
+    }
 
-        if force_overwrite {
-            cmd.arg("-y"); // Only overwrite when explicitly requested (shift+click)
+    /// Check with ffprobe that a finished export isn't empty, is readable, lasts
+    /// `expected_duration` (give or take `tolerance` seconds) and has at least
+    /// `expected_track_count` audio tracks
+    pub fn verify_output(output_path: &Path, expected_duration: f64, tolerance: f64, expected_track_count: usize) -> Result<(), VerifyError> {
+        match std::fs::metadata(output_path) {
+            Ok(metadata) if metadata.len() == 0 => return Err(VerifyError::EmptyFile),
+            Ok(_) => {}
+            Err(e) => return Err(VerifyError::CorruptContainer(format!("Can't read the output: {}", e))),
         }
-        cmd.arg(output_path);
 
-        let output = cmd.output()?;
-        
+        let output = output_with_stderr_tail(Command::new("ffprobe")
+            .arg("-v").arg("error")
+            .arg("-print_format").arg("json")
+            .arg("-show_format")
+            .arg("-show_streams")
+            .arg(output_path))
+            .map_err(|e| VerifyError::ProbeFailed(e.to_string()))?;
+
         if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("FFmpeg error: {}", error));
+            return Err(VerifyError::CorruptContainer(output.failure_message("ffprobe failed")));
+        }
+
+        let info: serde_json::Value = serde_json::from_slice(&output.stdout)
+            .map_err(|e| VerifyError::CorruptContainer(format!("Unreadable ffprobe output: {}", e)))?;
+        Self::check_probe_info(&info, expected_duration, tolerance, expected_track_count)
+    }
+
+    /// The checks of `verify_output` on ffprobe's `-show_format -show_streams` JSON
+    pub fn check_probe_info(info: &serde_json::Value, expected_duration: f64, tolerance: f64, expected_track_count: usize) -> Result<(), VerifyError> {
+        let empty_vec = vec![];
+        let streams = info["streams"].as_array().unwrap_or(&empty_vec);
+        if !streams.iter().any(|stream| stream["codec_type"].as_str() == Some("video")) {
+            return Err(VerifyError::CorruptContainer("No video stream".to_string()));
         }
 
+        let actual = info["format"]["duration"]
+            .as_str()
+            .and_then(|s| s.parse::<f64>().ok())
+            .ok_or_else(|| VerifyError::CorruptContainer("No container duration".to_string()))?;
+        if (actual - expected_duration).abs() > tolerance {
+            return Err(VerifyError::DurationMismatch { expected: expected_duration, actual });
+        }
+
+        let found = streams.iter()
+            .filter(|stream| stream["codec_type"].as_str() == Some("audio"))
+            .count();
+        if found < expected_track_count {
+            return Err(VerifyError::MissingAudioTrack { expected: expected_track_count, found });
+        }
         Ok(())
     }
 
+    /// `clip.mkv` -> `clip.corrupt.mkv`
+    pub fn corrupt_output_path(output_path: &Path) -> PathBuf {
+        let extension = output_path.extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("mkv");
+        output_path.with_extension(format!("corrupt.{}", extension))
+    }
+
+    /// Get an export that failed verification out of the way. Empty and unreadable files
+    /// are deleted (returns None); anything that may still play is renamed with
+    /// `set_aside_corrupt_output` and its new path returned.
+    pub fn dispose_unverified_output(output_path: &Path, error: &VerifyError) -> Option<PathBuf> {
+        if !error.is_unplayable() {
+            return Some(Self::set_aside_corrupt_output(output_path));
+        }
+        match std::fs::remove_file(output_path) {
+            Ok(()) => None,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                log::error!("Failed to delete unplayable export {}: {}", output_path.display(), e);
+                Some(output_path.to_path_buf())
+            }
+        }
+    }
+
+    /// Rename an export that failed verification out of the way, so it isn't mistaken
+    /// for a good one. Returns where it is now (unchanged if the rename failed).
+    pub fn set_aside_corrupt_output(output_path: &Path) -> PathBuf {
+        let corrupt_path = Self::corrupt_output_path(output_path);
+        match std::fs::rename(output_path, &corrupt_path) {
+            Ok(()) => corrupt_path,
+            Err(e) => {
+                log::error!("Failed to rename unverified export {}: {}", output_path.display(), e);
+                output_path.to_path_buf()
+            }
+        }
+    }
+
+    /// Where an export is written before being renamed into place, e.g.
+    /// `clip.mkv` -> `clip.1234.tmp.mkv` (1234 being our process ID)
+    pub fn temp_output_path(output_path: &Path) -> PathBuf {
+        let extension = output_path.extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("mkv");
+        output_path.with_extension(format!("{}.tmp.{}", std::process::id(), extension))
+    }
+
+    /// Whether `path` looks like a leftover from `temp_output_path`
+    pub fn is_temp_output(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.contains(".tmp."))
+    }
+
+    /// Temp export files in `directory` left behind by earlier runs
+    pub fn find_stale_temp_outputs(directory: &Path) -> Vec<PathBuf> {
+        let own_marker = format!(".{}.tmp.", std::process::id());
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && Self::is_temp_output(path))
+            .filter(|path| !path.to_string_lossy().contains(&own_marker))
+            .collect()
+    }
+
+    /// Move a finished temp export into place, or clean it up if FFmpeg failed
+    pub(crate) fn finish_temp_output(
+        output: std::io::Result<crate::video::ffmpeg_manager::CapturedOutput>,
+        temp_path: &Path,
+        output_path: &Path,
+        context: &str,
+    ) -> anyhow::Result<()> {
+        let result = match output {
+            Ok(output) if output.status.success() => std::fs::rename(temp_path, output_path)
+                .map_err(|e| anyhow::anyhow!("Failed to move {} into place: {}", temp_path.display(), e)),
+            Ok(output) => Err(anyhow::anyhow!(output.failure_message(context))),
+            Err(e) => Err(anyhow::anyhow!("{}: {}", context, e)),
+        };
+
+        if result.is_err() && temp_path.exists() {
+            if let Err(e) = std::fs::remove_file(temp_path) {
+                log::warn!("Failed to remove partial export {}: {}", temp_path.display(), e);
+            }
+        }
+        result
+    }
+
+    /// Export the range as a looping animated GIF or WebP without audio.
+    /// GIFs use FFmpeg's palettegen/paletteuse two-pass for decent colours.
+    pub fn export_animated(
+        input_path: &Path,
+        trim_start: f64,
+        trim_end: f64,
+        format: ExportFormat,
+        settings: &AnimatedExportSettings,
+        output_path: &Path,
+        force_overwrite: bool,
+    ) -> anyhow::Result<()> {
+        if output_path.exists() && !force_overwrite {
+            return Err(anyhow::anyhow!("Output file exists. Use shift+click to overwrite."));
+        }
+
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        Self::ensure_disk_space(output_path, settings.estimate_size_bytes(format, trim_end - trim_start, 16.0 / 9.0))?;
+
+        let start_time = format!("{:.3}", trim_start);
+        let duration = format!("{:.3}", trim_end - trim_start);
+        let scale_filter = format!("fps={},scale={}:-2:flags=lanczos", settings.fps, settings.width);
+        let temp_path = Self::temp_output_path(output_path);
+
+        let output = match format {
+            ExportFormat::Gif => {
+                let palette_path = std::env::temp_dir()
+                    .join(format!("clip-helper-palette-{}.png", uuid::Uuid::new_v4()));
+
+                // Pass 1: build an optimal 256-colour palette for this range
+                let palette_output = output_with_stderr_tail(Command::new("ffmpeg")
+                    .arg("-ss").arg(&start_time)
+                    .arg("-t").arg(&duration)
+                    .arg("-i").arg(input_path)
+                    .arg("-vf").arg(format!("{},palettegen=stats_mode=diff", scale_filter))
+                    .arg("-y")
+                    .arg(&palette_path))?;
+
+                if !palette_output.status.success() {
+                    let _ = std::fs::remove_file(&palette_path);
+                    return Err(anyhow::anyhow!(palette_output.failure_message("FFmpeg palette generation failed")));
+                }
+
+                // Pass 2: encode with the palette
+                let encode_output = output_with_stderr_tail(Command::new("ffmpeg")
+                    .arg("-ss").arg(&start_time)
+                    .arg("-t").arg(&duration)
+                    .arg("-i").arg(input_path)
+                    .arg("-i").arg(&palette_path)
+                    .arg("-lavfi").arg(format!("{}[x];[x][1:v]paletteuse=dither=bayer", scale_filter))
+                    .arg("-an")
+                    .arg("-loop").arg("0")
+                    .arg("-y")
+                    .arg(&temp_path));
+
+                let _ = std::fs::remove_file(&palette_path);
+                encode_output
+            }
+            ExportFormat::WebP => {
+                output_with_stderr_tail(Command::new("ffmpeg")
+                    .arg("-ss").arg(&start_time)
+                    .arg("-t").arg(&duration)
+                    .arg("-i").arg(input_path)
+                    .arg("-vf").arg(&scale_filter)
+                    .arg("-c:v").arg("libwebp")
+                    .arg("-quality").arg(settings.webp_quality.to_string())
+                    .arg("-an")
+                    .arg("-loop").arg("0")
+                    .arg("-y")
+                    .arg(&temp_path))
+            }
+            ExportFormat::Video => {
+                return Err(anyhow::anyhow!("Video exports go through trim_file"));
+            }
+        };
+
+        Self::finish_temp_output(output, &temp_path, output_path, "FFmpeg animated export failed")
+    }
+
+    /// First line of `ffmpeg -version`, looked up once per run
+    pub fn ffmpeg_version() -> Option<String> {
+        static FFMPEG_VERSION: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+        FFMPEG_VERSION.get_or_init(|| {
+            let output = Command::new("ffmpeg").arg("-version").output().ok()?;
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .map(|line| line.trim().to_string())
+        }).clone()
+    }
+
+    /// Hash the first and last 1 MB of a file with SHA-256.
+    /// Cheap enough to run on every new replay and stable for identical recordings.
+    pub fn compute_file_fingerprint(path: &Path) -> anyhow::Result<String> {
+        const CHUNK_SIZE: u64 = 1024 * 1024;
+
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open {} for fingerprinting: {}", path.display(), e))?;
+        let file_len = file.metadata()?.len();
+        let mut hasher = Sha256::new();
+
+        // First chunk
+        let mut buffer = Vec::with_capacity(CHUNK_SIZE as usize);
+        (&mut file).take(CHUNK_SIZE).read_to_end(&mut buffer)?;
+        hasher.update(&buffer);
+
+        // Last chunk, without re-reading bytes already covered by the first one
+        if file_len > CHUNK_SIZE {
+            let tail_start = file_len.saturating_sub(CHUNK_SIZE).max(CHUNK_SIZE);
+            file.seek(SeekFrom::Start(tail_start))?;
+            buffer.clear();
+            file.take(CHUNK_SIZE).read_to_end(&mut buffer)?;
+            hasher.update(&buffer);
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Probe a replay and tell whether OBS is still writing it: files modified within
+    /// `RECENT_WRITE_WINDOW` are probed twice, `WRITE_CHECK_INTERVAL` apart, and count as
+    /// still being written if the duration or file size grew. Returns the latest info if
+    /// ffprobe could read the file at all.
+    pub fn probe_write_state(file_path: &Path) -> (Option<VideoInfo>, VideoWriteState) {
+        let size = || std::fs::metadata(file_path).map(|metadata| metadata.len()).ok();
+        let recently_modified = std::fs::metadata(file_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_none_or(|age| age < RECENT_WRITE_WINDOW);
+        let duration_of = |info: &anyhow::Result<VideoInfo>| match info {
+            Ok(info) => Ok(info.duration),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let size_before = size();
+        let first = Self::get_video_info(file_path);
+        if !recently_modified {
+            let state = VideoWriteState::from_probes(duration_of(&first), duration_of(&first), false);
+            return (first.ok(), state);
+        }
+
+        std::thread::sleep(WRITE_CHECK_INTERVAL);
+        let second = Self::get_video_info(file_path);
+        let state = VideoWriteState::from_probes(duration_of(&first), duration_of(&second), size() != size_before);
+        (second.ok(), state)
+    }
+
     pub fn get_video_info(file_path: &Path) -> anyhow::Result<VideoInfo> {
-        let output = Command::new("ffprobe")
-            .arg("-v").arg("quiet")
+        let output = output_with_stderr_tail(Command::new("ffprobe")
+            .arg("-v").arg("error")
             .arg("-print_format").arg("json")
             .arg("-show_format")
             .arg("-show_streams")
-            .arg(file_path)
-            .output()?;
+            .arg(file_path))?;
 
         if !output.status.success() {
-            return Err(anyhow::anyhow!("ffprobe failed"));
+            return Err(anyhow::anyhow!(output.failure_message("ffprobe failed")));
         }
 
         let json_str = String::from_utf8(output.stdout)?;
@@ -111,7 +744,7 @@ impl VideoProcessor {
                 audio_tracks.push(crate::core::AudioTrack {
                     index: audio_index,
                     enabled: true,
-                    surround_mode: false,
+                    surround_mode: SurroundMode::default(),
                     name: track_name.to_string(),
                 });
                 audio_index += 1;
@@ -124,27 +757,623 @@ impl VideoProcessor {
         })
     }
 
+    /// Probe width, height, SAR and rotation of the first video stream
+    pub fn probe_geometry(file_path: &Path) -> anyhow::Result<VideoGeometry> {
+        let output = output_with_stderr_tail(Command::new("ffprobe")
+            .arg("-v").arg("error")
+            .arg("-print_format").arg("json")
+            .arg("-select_streams").arg("v:0")
+            .arg("-show_streams")
+            .arg(file_path))?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(output.failure_message("ffprobe failed")));
+        }
+
+        let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        let stream = info["streams"]
+            .as_array()
+            .and_then(|streams| streams.first())
+            .ok_or_else(|| anyhow::anyhow!("No video stream in {}", file_path.display()))?;
+
+        Ok(VideoGeometry::from_ffprobe_stream(stream))
+    }
+
+    /// "<clip_name>_<mm>-<ss>-<ms>.<ext>" in `output_dir`, with the name made filename-safe
+    pub fn screenshot_path(output_dir: &Path, clip_name: &str, timestamp: f64, format: ScreenshotFormat) -> PathBuf {
+        let total_ms = (timestamp.max(0.0) * 1000.0).round() as u64;
+        output_dir.join(format!(
+            "{}_{:02}-{:02}-{:03}.{}",
+            crate::core::naming::sanitize_filename(clip_name),
+            total_ms / 60_000,
+            total_ms / 1000 % 60,
+            total_ms % 1000,
+            format.extension()
+        ))
+    }
+
+    /// Save the frame of `path` at `timestamp` to `output_path` at the source's display
+    /// resolution, decoded again from the file rather than taken from the downscaled preview
+    pub fn extract_frame(path: &Path, timestamp: f64, output_path: &Path, format: ScreenshotFormat) -> anyhow::Result<()> {
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut cmd = Command::new("ffmpeg");
+        cmd.arg("-ss").arg(format!("{:.3}", timestamp.max(0.0)))
+            .arg("-i").arg(path)
+            .arg("-vframes").arg("1");
+        // Without the geometry the frame is written as decoded, which is still full size
+        match Self::probe_geometry(path) {
+            Ok(geometry) => {
+                let (width, height) = geometry.display_dimensions();
+                cmd.arg("-vf").arg(geometry.preview_filter(width, height, PreviewPipeline::StillFrame));
+            }
+            Err(e) => log::warn!("Failed to probe geometry for screenshot of {}: {}", path.display(), e),
+        }
+        if format == ScreenshotFormat::Jpeg {
+            cmd.arg("-q:v").arg("2");
+        }
+        let output = output_with_stderr_tail(cmd.arg("-y").arg(output_path))?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(output.failure_message("Screenshot extraction failed")));
+        }
+
+        Ok(())
+    }
+
+    /// Whether this FFmpeg build has the `zscale` filter, checked once per run
+    pub fn zscale_available() -> bool {
+        static ZSCALE_AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+        *ZSCALE_AVAILABLE.get_or_init(|| {
+            let available = Command::new("ffmpeg")
+                .arg("-hide_banner")
+                .arg("-filters")
+                .output()
+                .map(|output| String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .any(|line| line.split_whitespace().nth(1) == Some("zscale")))
+                .unwrap_or(false);
+            if !available {
+                log::warn!("FFmpeg has no zscale filter, HDR previews won't be tonemapped");
+            }
+            available
+        })
+    }
+
+    /// Whether frames of an HDR video should be tonemapped in `pipeline` with the current settings
+    pub fn tonemap_enabled(pipeline: PreviewPipeline) -> bool {
+        let enabled = match pipeline {
+            PreviewPipeline::StillFrame => TONEMAP_STILL_FRAMES.load(Ordering::Relaxed),
+            PreviewPipeline::Playback => TONEMAP_PLAYBACK.load(Ordering::Relaxed),
+        };
+        enabled && Self::zscale_available()
+    }
+
+    /// Whether the file's first video stream is HDR, probed once per path
+    pub fn is_hdr(file_path: &Path) -> bool {
+        static HDR_CACHE: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<PathBuf, bool>>> =
+            std::sync::OnceLock::new();
+        let cache = HDR_CACHE.get_or_init(Default::default);
+
+        if let Some(hdr) = cache.lock().ok().and_then(|cache| cache.get(file_path).copied()) {
+            return hdr;
+        }
+        let hdr = Self::probe_geometry(file_path).map(|geometry| geometry.hdr).unwrap_or(false);
+        if let Ok(mut cache) = cache.lock() {
+            cache.insert(file_path.to_path_buf(), hdr);
+        }
+        hdr
+    }
+
+    /// `-vf` chain for a thumbnail of `file_path`: `scale`, tonemapped first if the file is HDR
+    pub fn thumbnail_filter(file_path: &Path, scale: &str) -> String {
+        if Self::tonemap_enabled(PreviewPipeline::StillFrame) && Self::is_hdr(file_path) {
+            format!("{},{}", HDR_TONEMAP_FILTER, scale)
+        } else {
+            scale.to_string()
+        }
+    }
+
     pub fn extract_thumbnail(file_path: &Path, timestamp: f64, output_path: &Path) -> anyhow::Result<()> {
-        let output = Command::new("ffmpeg")
+        let output = output_with_stderr_tail(Command::new("ffmpeg")
             .arg("-i").arg(file_path)
             .arg("-ss").arg(format!("{:.3}", timestamp))
             .arg("-vframes").arg("1")
             .arg("-f").arg("image2")
             .arg("-y")
-            .arg(output_path)
-            .output()?;
+            .arg(output_path))?;
 
         if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("Thumbnail extraction failed: {}", error));
+            return Err(anyhow::anyhow!(output.failure_message("Thumbnail extraction failed")));
         }
 
         Ok(())
     }
 }
 
+/// Why `VideoProcessor::verify_output` rejected an export
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum VerifyError {
+    #[error("duration is {actual:.2}s instead of {expected:.2}s")]
+    DurationMismatch { expected: f64, actual: f64 },
+    #[error("{found} of {expected} audio tracks present")]
+    MissingAudioTrack { expected: usize, found: usize },
+    #[error("unreadable container: {0}")]
+    CorruptContainer(String),
+    #[error("output file is empty")]
+    EmptyFile,
+    /// ffprobe couldn't be run at all, so nothing is known about the file
+    #[error("ffprobe failed to run: {0}")]
+    ProbeFailed(String),
+}
+
+impl VerifyError {
+    /// Whether the file is certainly useless, as opposed to playable but not what was asked for
+    pub fn is_unplayable(&self) -> bool {
+        matches!(self, Self::EmptyFile | Self::CorruptContainer(_))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct VideoInfo {
     pub duration: f64,
     pub audio_tracks: Vec<crate::core::AudioTrack>,
 }
+
+/// Container/codec family of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// Trimmed video with mixed + original audio tracks
+    #[default]
+    Video,
+    /// Looping animated GIF, no audio
+    Gif,
+    /// Looping animated WebP, no audio
+    WebP,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Video => "mkv",
+            ExportFormat::Gif => "gif",
+            ExportFormat::WebP => "webp",
+        }
+    }
+
+    pub fn is_animated(&self) -> bool {
+        matches!(self, ExportFormat::Gif | ExportFormat::WebP)
+    }
+}
+
+/// File type of a single extracted audio track
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioExportFormat {
+    /// 24-bit PCM
+    #[default]
+    Wav,
+    Flac,
+    /// VBR around 190 kbit/s
+    Mp3,
+}
+
+impl AudioExportFormat {
+    pub const ALL: [AudioExportFormat; 3] = [Self::Wav, Self::Flac, Self::Mp3];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Wav => "WAV",
+            Self::Flac => "FLAC",
+            Self::Mp3 => "MP3",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Flac => "flac",
+            Self::Mp3 => "mp3",
+        }
+    }
+
+    /// Rough size of `duration` seconds of a 48 kHz stereo track in this format
+    pub fn estimate_size_bytes(&self, duration: f64) -> u64 {
+        let wav_bytes_per_second = 48_000.0 * 2.0 * 3.0;
+        let bytes_per_second = match self {
+            Self::Wav => wav_bytes_per_second,
+            // Lossless compression of game audio rarely does better than this
+            Self::Flac => wav_bytes_per_second * 0.6,
+            Self::Mp3 => 190_000.0 / 8.0,
+        };
+        (bytes_per_second * duration.max(0.0)) as u64
+    }
+
+    fn codec_args(&self) -> &'static [&'static str] {
+        match self {
+            Self::Wav => &["-c:a", "pcm_s24le"],
+            Self::Flac => &["-c:a", "flac"],
+            Self::Mp3 => &["-c:a", "libmp3lame", "-q:a", "2"],
+        }
+    }
+}
+
+/// How much smaller a palette-encoded GIF is than raw 24-bit frames, for size estimates
+pub const GIF_COMPRESSION_FACTOR: f64 = 12.0;
+
+/// Discord's upload limit without Nitro; larger exports get a warning
+pub const DISCORD_UPLOAD_LIMIT_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Settings for GIF/WebP exports, taken from the export preset when the job is queued
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimatedExportSettings {
+    pub fps: u32,
+    pub width: u32,
+    /// libwebp quality, 0-100 (ignored for GIF)
+    pub webp_quality: u8,
+}
+
+impl Default for AnimatedExportSettings {
+    fn default() -> Self {
+        Self {
+            fps: 15,
+            width: 640,
+            webp_quality: 75,
+        }
+    }
+}
+
+impl AnimatedExportSettings {
+    /// Very rough output size estimate in bytes, `width * height * fps * duration * 3`
+    /// divided by a per-format compression factor; `aspect` is display width / height
+    pub fn estimate_size_bytes(&self, format: ExportFormat, duration: f64, aspect: f64) -> u64 {
+        let height = self.width as f64 / aspect.max(0.01);
+        let raw_bytes = self.width as f64 * height * self.fps as f64 * duration.max(0.0) * 3.0;
+        let compression_factor = match format {
+            ExportFormat::Gif => GIF_COMPRESSION_FACTOR,
+            // Lower quality compresses much better
+            ExportFormat::WebP => 20.0 + (100.0 - self.webp_quality as f64) * 0.8,
+            ExportFormat::Video => return 0,
+        };
+        (raw_bytes / compression_factor) as u64
+    }
+}
+
+/// Frame geometry of the first video stream, used to display portrait/anamorphic clips correctly
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoGeometry {
+    /// Coded width in pixels
+    pub width: u32,
+    /// Coded height in pixels
+    pub height: u32,
+    /// Sample (pixel) aspect ratio as numerator/denominator
+    pub sar: (u32, u32),
+    /// Clockwise rotation needed for display: 0, 90, 180 or 270
+    pub rotation: u32,
+    /// True when the rotation comes from a display matrix, which FFmpeg applies automatically
+    /// when decoding. A bare `rotate` tag (e.g. in MKV) is not applied and must be done by hand.
+    pub rotation_auto_applied: bool,
+    /// PQ or HLG transfer, which looks washed out unless tonemapped to SDR
+    pub hdr: bool,
+}
+
+impl Default for VideoGeometry {
+    fn default() -> Self {
+        Self {
+            width: 1920,
+            height: 1080,
+            sar: (1, 1),
+            rotation: 0,
+            rotation_auto_applied: false,
+            hdr: false,
+        }
+    }
+}
+
+impl VideoGeometry {
+    /// Build geometry from one entry of ffprobe's `streams` array
+    pub fn from_ffprobe_stream(stream: &serde_json::Value) -> Self {
+        let width = stream["width"].as_u64().unwrap_or(0) as u32;
+        let height = stream["height"].as_u64().unwrap_or(0) as u32;
+
+        let sar = stream["sample_aspect_ratio"]
+            .as_str()
+            .and_then(|s| s.split_once(':'))
+            .and_then(|(num, den)| Some((num.parse::<u32>().ok()?, den.parse::<u32>().ok()?)))
+            .filter(|&(num, den)| num > 0 && den > 0) // "0:1" means unknown
+            .unwrap_or((1, 1));
+
+        // Display matrix rotation is counter-clockwise; convert to the clockwise rotation to apply
+        let matrix_rotation = stream["side_data_list"]
+            .as_array()
+            .and_then(|list| list.iter().find_map(|data| data["rotation"].as_f64()))
+            .map(|degrees| -degrees);
+        let tag_rotation = stream["tags"]["rotate"]
+            .as_str()
+            .and_then(|s| s.trim().parse::<f64>().ok());
+
+        let (rotation, rotation_auto_applied) = match (matrix_rotation, tag_rotation) {
+            (Some(degrees), _) => (Self::normalize_rotation(degrees), true),
+            (None, Some(degrees)) => (Self::normalize_rotation(degrees), false),
+            (None, None) => (0, false),
+        };
+
+        let hdr = stream["color_transfer"]
+            .as_str()
+            .is_some_and(|transfer| HDR_TRANSFERS.contains(&transfer));
+
+        Self {
+            width,
+            height,
+            sar,
+            rotation,
+            rotation_auto_applied,
+            hdr,
+        }
+    }
+
+    fn normalize_rotation(degrees: f64) -> u32 {
+        let quarter_turns = (degrees / 90.0).round() as i64;
+        (quarter_turns.rem_euclid(4) * 90) as u32
+    }
+
+    fn is_quarter_turn(&self) -> bool {
+        self.rotation == 90 || self.rotation == 270
+    }
+
+    /// Size the frame should be shown at, after applying SAR and rotation
+    pub fn display_dimensions(&self) -> (u32, u32) {
+        let width = (self.width as u64 * self.sar.0 as u64 / self.sar.1 as u64) as u32;
+        if self.is_quarter_turn() {
+            (self.height, width)
+        } else {
+            (width, self.height)
+        }
+    }
+
+    /// Largest size with the display aspect ratio that fits in the box (even, as FFmpeg prefers)
+    pub fn fit_within(&self, max_width: u32, max_height: u32) -> (u32, u32) {
+        let (display_width, display_height) = self.display_dimensions();
+        if display_width == 0 || display_height == 0 {
+            return (max_width, max_height);
+        }
+
+        let scale = (max_width as f64 / display_width as f64).min(max_height as f64 / display_height as f64);
+        let width = ((display_width as f64 * scale) as u32 / 2 * 2).max(2);
+        let height = ((display_height as f64 * scale) as u32 / 2 * 2).max(2);
+        (width, height)
+    }
+
+    /// Filter that applies a rotation FFmpeg won't apply on its own (None if not needed)
+    pub fn manual_rotation_filter(&self) -> Option<&'static str> {
+        if self.rotation_auto_applied {
+            return None;
+        }
+        match self.rotation {
+            90 => Some("transpose=clock"),
+            180 => Some("hflip,vflip"),
+            270 => Some("transpose=cclock"),
+            _ => None,
+        }
+    }
+
+    /// `-vf` chain that scales (and if needed rotates) a decoded frame to exactly `width`x`height`,
+    /// tonemapping HDR frames if that's enabled for `pipeline`
+    pub fn preview_filter(&self, width: u32, height: u32, pipeline: PreviewPipeline) -> String {
+        let tonemap = self.hdr && VideoProcessor::tonemap_enabled(pipeline);
+        self.build_preview_filter(width, height, tonemap)
+    }
+
+    fn build_preview_filter(&self, width: u32, height: u32, tonemap: bool) -> String {
+        let mut filters = Vec::new();
+        if tonemap {
+            filters.push(HDR_TONEMAP_FILTER.to_string());
+        }
+        if let Some(rotate) = self.manual_rotation_filter() {
+            filters.push(rotate.to_string());
+        }
+        filters.push(format!("scale={}:{},setsar=1", width, height));
+        filters.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_mapping_args_pans_tracks() {
+        let track = |index, enabled, surround_mode| AudioTrack {
+            index,
+            enabled,
+            surround_mode,
+            name: format!("Track {}", index + 1),
+        };
+        let args = VideoProcessor::audio_mapping_args(&[
+            track(0, true, SurroundMode::Passthrough),
+            track(1, false, SurroundMode::Stereo),
+            track(2, true, SurroundMode::SurroundLeft),
+        ]);
+        assert_eq!(args[1], "[0:a:2]pan=stereo|c0=FL|c1=FL[a2];[0:a:0][a2]amix=inputs=2[mixed]");
+        // Every original track is kept after the mix
+        assert_eq!(&args[6..], ["-map", "0:a:0", "-map", "0:a:1", "-map", "0:a:2"]);
+
+        assert!(VideoProcessor::audio_mapping_args(&[track(0, false, SurroundMode::Passthrough)]).is_empty());
+    }
+
+    #[test]
+    fn test_check_probe_info() {
+        let info = serde_json::json!({
+            "format": { "duration": "30.040000" },
+            "streams": [
+                { "codec_type": "video" },
+                { "codec_type": "audio" },
+                { "codec_type": "audio" },
+            ],
+        });
+        assert_eq!(VideoProcessor::check_probe_info(&info, 30.0, VERIFY_DURATION_TOLERANCE_SECONDS, 2), Ok(()));
+        assert_eq!(
+            VideoProcessor::check_probe_info(&info, 29.5, VERIFY_DURATION_TOLERANCE_SECONDS, 2),
+            Err(VerifyError::DurationMismatch { expected: 29.5, actual: 30.04 })
+        );
+        // Starting on an earlier keyframe is fine for a stream copy
+        assert_eq!(VideoProcessor::check_probe_info(&info, 29.5, STREAM_COPY_DURATION_TOLERANCE_SECONDS, 2), Ok(()));
+        assert_eq!(
+            VideoProcessor::check_probe_info(&info, 30.0, VERIFY_DURATION_TOLERANCE_SECONDS, 3),
+            Err(VerifyError::MissingAudioTrack { expected: 3, found: 2 })
+        );
+        assert!(matches!(
+            VideoProcessor::check_probe_info(&serde_json::json!({ "streams": [{ "codec_type": "video" }] }), 30.0, 0.1, 0),
+            Err(VerifyError::CorruptContainer(_))
+        ));
+
+        assert_eq!(
+            VideoProcessor::corrupt_output_path(Path::new("trimmed/clip.mkv")),
+            PathBuf::from("trimmed/clip.corrupt.mkv")
+        );
+
+        let empty = std::env::temp_dir().join(format!("clip-helper-empty-export-{}.mkv", std::process::id()));
+        std::fs::write(&empty, b"").expect("Failed to write test file");
+        let error = VideoProcessor::verify_output(&empty, 30.0, VERIFY_DURATION_TOLERANCE_SECONDS, 0).unwrap_err();
+        assert_eq!(error, VerifyError::EmptyFile);
+        assert_eq!(VideoProcessor::dispose_unverified_output(&empty, &error), None);
+        assert!(!empty.exists());
+    }
+
+    #[test]
+    fn test_portrait_display_matrix() {
+        let stream = serde_json::json!({
+            "width": 1920,
+            "height": 1080,
+            "sample_aspect_ratio": "1:1",
+            "side_data_list": [{ "side_data_type": "Display Matrix", "rotation": -90 }]
+        });
+        let geometry = VideoGeometry::from_ffprobe_stream(&stream);
+
+        assert_eq!(geometry.rotation, 90);
+        assert!(geometry.rotation_auto_applied);
+        assert_eq!(geometry.display_dimensions(), (1080, 1920));
+        assert_eq!(geometry.fit_within(854, 480), (270, 480));
+        assert!(geometry.manual_rotation_filter().is_none());
+    }
+
+    #[test]
+    fn test_rotate_tag_needs_manual_filter() {
+        let stream = serde_json::json!({
+            "width": 1280,
+            "height": 720,
+            "tags": { "rotate": "270" }
+        });
+        let geometry = VideoGeometry::from_ffprobe_stream(&stream);
+
+        assert_eq!(geometry.rotation, 270);
+        assert!(!geometry.rotation_auto_applied);
+        assert_eq!(geometry.manual_rotation_filter(), Some("transpose=cclock"));
+    }
+
+    #[test]
+    fn test_anamorphic_sar() {
+        let stream = serde_json::json!({
+            "width": 1440,
+            "height": 1080,
+            "sample_aspect_ratio": "4:3"
+        });
+        let geometry = VideoGeometry::from_ffprobe_stream(&stream);
+
+        assert_eq!(geometry.display_dimensions(), (1920, 1080));
+        assert_eq!(geometry.fit_within(854, 480), (852, 480));
+    }
+
+    #[test]
+    fn test_hdr_transfer_detection() {
+        let pq = serde_json::json!({
+            "width": 3840,
+            "height": 2160,
+            "color_primaries": "bt2020",
+            "color_transfer": "smpte2084",
+            "tags": { "rotate": "180" }
+        });
+        let geometry = VideoGeometry::from_ffprobe_stream(&pq);
+        assert!(geometry.hdr);
+        assert_eq!(
+            geometry.build_preview_filter(854, 480, true),
+            format!("{},hflip,vflip,scale=854:480,setsar=1", HDR_TONEMAP_FILTER)
+        );
+        assert_eq!(geometry.build_preview_filter(854, 480, false), "hflip,vflip,scale=854:480,setsar=1");
+
+        let sdr = serde_json::json!({ "width": 1920, "height": 1080, "color_transfer": "bt709" });
+        assert!(!VideoGeometry::from_ffprobe_stream(&sdr).hdr);
+    }
+
+    #[test]
+    fn test_target_video_bitrate() {
+        // 8 MB over 30s with 128 kbit/s of audio
+        let bitrate = VideoProcessor::target_video_bitrate_kbps(8.0, 128.0, 30.0).unwrap();
+        assert!((bitrate - (8.0 * 8192.0 - 128.0 * 30.0) / 30.0).abs() < 1e-9);
+
+        // The audio alone is bigger than the target
+        assert!(VideoProcessor::target_video_bitrate_kbps(1.0, 384.0, 60.0).is_err());
+        assert!(VideoProcessor::target_video_bitrate_kbps(8.0, 128.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_validate_extra_args() {
+        let args = ["-movflags", "+faststart", "-metadata", "title=Clip"].map(String::from);
+        assert!(VideoProcessor::validate_extra_args(&args).is_empty());
+
+        let args = ["-y", "-i", "other.mkv", "-ss", "5"].map(String::from);
+        assert_eq!(VideoProcessor::validate_extra_args(&args).len(), 4);
+
+        // Decimal option values aren't file names
+        let args = ["-b:v", "2.5M", "-crf", "18.5", "-r", "59.94"].map(String::from);
+        assert!(VideoProcessor::validate_extra_args(&args).is_empty());
+        let args = ["copy.MP4", "cover.png"].map(String::from);
+        assert_eq!(VideoProcessor::validate_extra_args(&args).len(), 2);
+    }
+
+    #[test]
+    fn test_audio_track_output_path() {
+        assert_eq!(
+            VideoProcessor::audio_track_output_path(Path::new("trimmed/Replay - Clutch.mkv"), 2, AudioExportFormat::Wav),
+            PathBuf::from("trimmed/Replay - Clutch_track2.wav")
+        );
+        assert_eq!(
+            VideoProcessor::audio_track_output_path(Path::new("trimmed/Replay - Clutch.mkv"), 0, AudioExportFormat::Flac),
+            PathBuf::from("trimmed/Replay - Clutch_track0.flac")
+        );
+    }
+
+    #[test]
+    fn test_temp_output_path_keeps_extension() {
+        let temp = VideoProcessor::temp_output_path(Path::new("trimmed/Replay - Clutch.mkv"));
+        let name = temp.file_name().unwrap().to_string_lossy().to_string();
+
+        assert_eq!(name, format!("Replay - Clutch.{}.tmp.mkv", std::process::id()));
+        assert!(VideoProcessor::is_temp_output(&temp));
+        assert!(!VideoProcessor::is_temp_output(Path::new("trimmed/Replay - Clutch.mkv")));
+    }
+
+    #[test]
+    fn test_gif_size_estimate() {
+        let settings = AnimatedExportSettings { fps: 15, width: 640, webp_quality: 75 };
+        // 640x360 * 15 fps * 10 s * 3 bytes / 12
+        let estimate = settings.estimate_size_bytes(ExportFormat::Gif, 10.0, 16.0 / 9.0);
+        assert_eq!(estimate, 8_640_000);
+        assert!(estimate > DISCORD_UPLOAD_LIMIT_BYTES);
+        assert!(settings.estimate_size_bytes(ExportFormat::Gif, 5.0, 16.0 / 9.0) < DISCORD_UPLOAD_LIMIT_BYTES);
+    }
+
+    #[test]
+    fn test_screenshot_path() {
+        let dir = Path::new("shots");
+        assert_eq!(
+            VideoProcessor::screenshot_path(dir, "Ace: round 3", 83.4567, ScreenshotFormat::Png),
+            dir.join("Ace_ round 3_01-23-457.png")
+        );
+        assert_eq!(
+            VideoProcessor::screenshot_path(dir, "Replay 2025-08-17 21-52-01", 0.0, ScreenshotFormat::Jpeg),
+            dir.join("Replay 2025-08-17 21-52-01_00-00-000.jpg")
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Scene score (0-1) a frame needs to count as a scene change
+pub const DEFAULT_SCENE_THRESHOLD: f64 = 0.4;
+/// At most this many markers are placed for one clip, the strongest changes win
+pub const MAX_SCENE_MARKERS: usize = 20;
+/// Scene changes closer together than this are one cut (e.g. a flash or a fade)
+pub const MIN_SCENE_SPACING_SECONDS: f64 = 1.0;
+
+/// A frame FFmpeg scored as a scene change
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneChange {
+    pub time: f64,
+    pub score: f64,
+}
+
+/// Find scene changes in `path` with FFmpeg's `scene` select filter.
+///
+/// Decodes the whole video, so call this off the UI thread. The position FFmpeg has
+/// decoded up to is stored in `progress` in milliseconds while it runs. Setting `cancel`
+/// kills FFmpeg at its next output line and returns an error.
+pub fn detect_scenes(path: &Path, threshold: f64, ffmpeg: &Path, progress: &AtomicU64, cancel: &AtomicBool) -> anyhow::Result<Vec<SceneChange>> {
+    if cancel.load(Ordering::Relaxed) {
+        return Err(anyhow::anyhow!("Scene detection cancelled"));
+    }
+    let mut child = Command::new(ffmpeg)
+        .arg("-hide_banner")
+        .arg("-i").arg(path)
+        .arg("-an")
+        .arg("-vf").arg(format!("select='gt(scene,{:.3})',metadata=print", threshold))
+        .arg("-f").arg("null")
+        .arg("-")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", ffmpeg.display(), e))?;
+
+    let stderr = child.stderr.take().ok_or_else(|| anyhow::anyhow!("FFmpeg stderr not captured"))?;
+    let mut parser = SceneOutputParser::default();
+    let mut line = Vec::new();
+    let mut tail: Vec<String> = Vec::new();
+    // FFmpeg ends its progress lines with \r, so split on both line endings
+    for byte in BufReader::new(stderr).bytes() {
+        let byte = byte.map_err(|e| anyhow::anyhow!("Failed to read FFmpeg output: {}", e))?;
+        if byte != b'\r' && byte != b'\n' {
+            line.push(byte);
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        if cancel.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow::anyhow!("Scene detection cancelled"));
+        }
+        let text = String::from_utf8_lossy(&line).to_string();
+        line.clear();
+        if let Some(position) = parse_progress_time(&text) {
+            progress.store((position * 1000.0) as u64, Ordering::Relaxed);
+        }
+        parser.push_line(&text);
+        tail.push(text);
+        if tail.len() > 5 {
+            tail.remove(0);
+        }
+    }
+
+    let status = child.wait().map_err(|e| anyhow::anyhow!("Failed to wait for FFmpeg: {}", e))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("Scene detection failed: {}", tail.join("\n")));
+    }
+    Ok(parser.changes)
+}
+
+/// Collects scene changes from `metadata=print` output, which prints a frame's
+/// `pts_time` on one line and its `lavfi.scene_score` on the next
+#[derive(Debug, Default)]
+struct SceneOutputParser {
+    frame_time: Option<f64>,
+    changes: Vec<SceneChange>,
+}
+
+impl SceneOutputParser {
+    fn push_line(&mut self, line: &str) {
+        if let Some(rest) = line.split("pts_time:").nth(1) {
+            self.frame_time = rest.split_whitespace().next().and_then(|value| value.parse().ok());
+        } else if let Some(rest) = line.split("lavfi.scene_score=").nth(1) {
+            let score = rest.trim().parse::<f64>().ok();
+            if let (Some(time), Some(score)) = (self.frame_time.take(), score) {
+                self.changes.push(SceneChange { time, score });
+            }
+        }
+    }
+}
+
+/// Position from an FFmpeg progress line ("... time=00:01:02.50 bitrate=...")
+fn parse_progress_time(line: &str) -> Option<f64> {
+    let value = line.split("time=").nth(1)?.split_whitespace().next()?;
+    let mut parts = value.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Pick up to `max` marker times from `changes`, strongest first, dropping changes within
+/// `min_spacing` seconds of a stronger one. Returned in time order.
+pub fn select_scene_markers(changes: &[SceneChange], max: usize, min_spacing: f64) -> Vec<f64> {
+    let mut by_score = changes.to_vec();
+    by_score.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut times: Vec<f64> = Vec::new();
+    for change in by_score {
+        if times.len() >= max {
+            break;
+        }
+        if times.iter().all(|time| (time - change.time).abs() >= min_spacing) {
+            times.push(change.time);
+        }
+    }
+    times.sort_by(f64::total_cmp);
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scene_output() {
+        let mut parser = SceneOutputParser::default();
+        for line in [
+            "[Parsed_metadata_1 @ 0x55d1] frame:0    pts:4200    pts_time:4.2",
+            "[Parsed_metadata_1 @ 0x55d1] lavfi.scene_score=0.512345",
+            "frame=  250 fps=120 q=-0.0 size=N/A time=00:00:08.33 bitrate=N/A speed=4x",
+            "[Parsed_metadata_1 @ 0x55d1] frame:1    pts:9900    pts_time:9.9",
+            "[Parsed_metadata_1 @ 0x55d1] lavfi.scene_score=0.9",
+        ] {
+            parser.push_line(line);
+        }
+        assert_eq!(parser.changes, vec![
+            SceneChange { time: 4.2, score: 0.512345 },
+            SceneChange { time: 9.9, score: 0.9 },
+        ]);
+
+        assert_eq!(parse_progress_time("frame=  250 fps=120 time=00:01:02.50 bitrate=N/A"), Some(62.5));
+        assert_eq!(parse_progress_time("no progress here"), None);
+    }
+
+    #[test]
+    fn test_cancelled_detection_does_not_start() {
+        let missing = std::env::temp_dir().join(format!("clip-helper-scenes-missing-{}.mkv", std::process::id()));
+        let error = detect_scenes(&missing, DEFAULT_SCENE_THRESHOLD, Path::new("ffmpeg"), &AtomicU64::new(0), &AtomicBool::new(true))
+            .expect_err("Cancelled detection must fail");
+        assert_eq!(error.to_string(), "Scene detection cancelled");
+    }
+
+    #[test]
+    fn test_select_scene_markers() {
+        let changes = [
+            SceneChange { time: 1.0, score: 0.5 },
+            SceneChange { time: 1.4, score: 0.8 },
+            SceneChange { time: 5.0, score: 0.6 },
+            SceneChange { time: 9.0, score: 0.45 },
+        ];
+        // 1.0 is too close to the stronger 1.4
+        assert_eq!(select_scene_markers(&changes, 10, 1.0), vec![1.4, 5.0, 9.0]);
+        assert_eq!(select_scene_markers(&changes, 2, 1.0), vec![1.4, 5.0]);
+        assert!(select_scene_markers(&[], 10, 1.0).is_empty());
+    }
+}
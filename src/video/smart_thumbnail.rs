@@ -1,13 +1,14 @@
 use std::path::{Path, PathBuf};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 use lru::LruCache;
-use std::num::NonZeroUsize;
 use anyhow::Result;
 use log;
+use crate::video::{FfmpegProcessPool, VideoProcessor};
 
 // Thumbnail dimensions - maximum size while preserving aspect ratio
 const THUMBNAIL_MAX_WIDTH: u32 = 480;
@@ -15,10 +16,15 @@ const THUMBNAIL_MAX_HEIGHT: u32 = 360;
 const THUMBNAIL_CHANNELS: usize = 4; // RGBA
 // Note: Actual buffer size will vary based on video aspect ratio, so we'll allocate dynamically
 
+/// Default texture memory limit, `AppConfig::thumbnail_cache_mb`
+pub const DEFAULT_THUMBNAIL_CACHE_MB: usize = 512;
+/// Prefetched thumbnails generated at once, so they never hold up the visible clip's
+const MAX_CONCURRENT_PREFETCHES: usize = 1;
+
 /// Smart thumbnail cache with LRU eviction and async generation
 pub struct SmartThumbnailCache {
-    /// LRU cache of loaded textures (max 15 total across all videos)
-    texture_cache: Arc<Mutex<LruCache<String, CachedThumbnail>>>,
+    /// Loaded textures, evicted least recently used first once they exceed the size limit
+    texture_cache: Arc<Mutex<TextureCache<CachedThumbnail>>>,
     /// Track pending generation requests to prevent duplicates
     pending_requests: Arc<Mutex<HashSet<String>>>,
     /// Background worker for thumbnail generation
@@ -27,6 +33,10 @@ pub struct SmartThumbnailCache {
     result_receiver: Arc<Mutex<mpsc::Receiver<ThumbnailResult>>>,
     /// Temporary directory for intermediate files
     temp_dir: PathBuf,
+    /// Thumbnails wanted soon but not yet, started one at a time while the pool is idle
+    prefetch_queue: Mutex<VecDeque<ThumbnailJob>>,
+    active_prefetches: AtomicUsize,
+    pool: Arc<FfmpegProcessPool>,
 }
 
 /// Cached thumbnail with metadata
@@ -37,12 +47,106 @@ pub struct CachedThumbnail {
     pub generated_at: Instant,
 }
 
+/// Thumbnail cache counters for the statistics panel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub current_bytes: usize,
+    pub max_bytes: usize,
+    pub hit_count: u64,
+    pub miss_count: u64,
+    pub eviction_count: u64,
+}
+
+/// LRU map limited by the total size of its entries rather than their number
+struct TextureCache<T> {
+    /// Entries with their size in bytes; every lookup moves an entry to the back
+    entries: LruCache<String, (T, usize)>,
+    current_bytes: usize,
+    max_cache_bytes: usize,
+    hit_count: u64,
+    miss_count: u64,
+    eviction_count: u64,
+}
+
+impl<T: Clone> TextureCache<T> {
+    fn new(max_cache_bytes: usize) -> Self {
+        Self {
+            entries: LruCache::unbounded(),
+            current_bytes: 0,
+            max_cache_bytes,
+            hit_count: 0,
+            miss_count: 0,
+            eviction_count: 0,
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<T> {
+        match self.entries.get(key) {
+            Some((value, _)) => {
+                self.hit_count += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.miss_count += 1;
+                None
+            }
+        }
+    }
+
+    /// Add an entry, evicting the least recently used ones until it fits. An entry
+    /// bigger than the whole limit is still kept, alone.
+    fn insert(&mut self, key: String, value: T, bytes: usize) {
+        self.remove(&key);
+        self.evict_to(self.max_cache_bytes.saturating_sub(bytes));
+        self.current_bytes += bytes;
+        self.entries.put(key, (value, bytes));
+    }
+
+    /// Whether `key` is cached, without counting a hit or touching its position
+    fn contains(&self, key: &str) -> bool {
+        self.entries.contains(key)
+    }
+
+    fn remove(&mut self, key: &str) -> Option<T> {
+        let (value, bytes) = self.entries.pop(key)?;
+        self.current_bytes -= bytes;
+        Some(value)
+    }
+
+    fn set_max_bytes(&mut self, max_cache_bytes: usize) {
+        self.max_cache_bytes = max_cache_bytes;
+        self.evict_to(max_cache_bytes);
+    }
+
+    fn evict_to(&mut self, limit: usize) {
+        while self.current_bytes > limit {
+            let Some((key, (_, bytes))) = self.entries.pop_lru() else {
+                break;
+            };
+            self.current_bytes -= bytes;
+            self.eviction_count += 1;
+            log::debug!("Evicted thumbnail {} ({} bytes)", key, bytes);
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            current_bytes: self.current_bytes,
+            max_bytes: self.max_cache_bytes,
+            hit_count: self.hit_count,
+            miss_count: self.miss_count,
+            eviction_count: self.eviction_count,
+        }
+    }
+}
+
 /// Background job for thumbnail generation
 #[derive(Debug)]
 struct ThumbnailJob {
     video_path: PathBuf,
     timestamp: f64,
     cache_key: String,
+    prefetch: bool,
 }
 
 /// Result from background thumbnail generation
@@ -54,17 +158,15 @@ struct ThumbnailResult {
     height: u32,
     timestamp: f64,
     error: Option<String>,
+    prefetch: bool,
 }
 
 impl SmartThumbnailCache {
-    pub fn new() -> Result<Self> {
+    pub fn new(pool: Arc<FfmpegProcessPool>, max_cache_bytes: usize) -> Result<Self> {
         let temp_dir = std::env::temp_dir().join("clip-helper-smart-thumbnails");
         std::fs::create_dir_all(&temp_dir)?;
         
-        // Create LRU cache with capacity for 15 thumbnails total
-        let texture_cache = Arc::new(Mutex::new(
-            LruCache::new(NonZeroUsize::new(15).unwrap())
-        ));
+        let texture_cache = Arc::new(Mutex::new(TextureCache::new(max_cache_bytes)));
         let pending_requests = Arc::new(Mutex::new(HashSet::new()));
         
         let (job_sender, job_receiver) = mpsc::channel::<ThumbnailJob>();
@@ -73,8 +175,9 @@ impl SmartThumbnailCache {
         
         // Background worker thread for thumbnail generation
         let worker_temp_dir = temp_dir.clone();
+        let worker_pool = pool.clone();
         thread::spawn(move || {
-            Self::thumbnail_worker(job_receiver, result_sender, worker_temp_dir);
+            Self::thumbnail_worker(job_receiver, result_sender, worker_temp_dir, worker_pool);
         });
         
         Ok(Self {
@@ -83,6 +186,9 @@ impl SmartThumbnailCache {
             generation_sender: job_sender,
             result_receiver,
             temp_dir,
+            prefetch_queue: Mutex::new(VecDeque::new()),
+            active_prefetches: AtomicUsize::new(0),
+            pool,
         })
     }
     
@@ -105,14 +211,25 @@ impl SmartThumbnailCache {
         // Check if already in cache
         if let Ok(mut cache) = self.texture_cache.lock() {
             if let Some(cached) = cache.get(&cache_key) {
-                return Some(cached.clone());
+                return Some(cached);
             }
         }
         
+        // Asked for now, so a prefetch that hasn't started yet is generated right away instead
+        let was_queued_prefetch = self.prefetch_queue.lock()
+            .map(|mut queue| {
+                let queued = queue.len();
+                queue.retain(|job| job.cache_key != cache_key);
+                queue.len() < queued
+            })
+            .unwrap_or(false);
+        
         // Check if generation is already pending
-        if let Ok(pending) = self.pending_requests.lock() {
-            if pending.contains(&cache_key) {
-                return None; // Generation in progress
+        if !was_queued_prefetch {
+            if let Ok(pending) = self.pending_requests.lock() {
+                if pending.contains(&cache_key) {
+                    return None; // Generation in progress
+                }
             }
         }
         
@@ -125,22 +242,79 @@ impl SmartThumbnailCache {
             video_path: video_path.to_path_buf(),
             timestamp,
             cache_key,
+            prefetch: false,
         };
         
         let _ = self.generation_sender.send(job);
         None // Will be available in future frames
     }
     
+    /// Generate these thumbnails in the background ahead of time. Replaces the previous
+    /// prefetch list, so thumbnails queued for an earlier selection that haven't started
+    /// yet are dropped.
+    pub fn prefetch_thumbnails(&self, requests: &[(PathBuf, f64)]) {
+        let (Ok(mut queue), Ok(mut pending), Ok(cache)) =
+            (self.prefetch_queue.lock(), self.pending_requests.lock(), self.texture_cache.lock()) else {
+            return;
+        };
+        for cancelled in queue.drain(..) {
+            pending.remove(&cancelled.cache_key);
+        }
+        for (video_path, timestamp) in requests {
+            let cache_key = Self::generate_cache_key(video_path, *timestamp);
+            if cache.contains(&cache_key) || !pending.insert(cache_key.clone()) {
+                continue;
+            }
+            queue.push_back(ThumbnailJob {
+                video_path: video_path.clone(),
+                timestamp: *timestamp,
+                cache_key,
+                prefetch: true,
+            });
+        }
+        drop((queue, pending, cache));
+        self.start_prefetches();
+    }
+    
+    /// Hand queued prefetches to the worker while fewer than `MAX_CONCURRENT_PREFETCHES`
+    /// run and nothing else waits for the FFmpeg pool
+    fn start_prefetches(&self) {
+        while self.active_prefetches.load(Ordering::Relaxed) < MAX_CONCURRENT_PREFETCHES
+            && self.pool.queued_count() == 0 {
+            let Some(job) = self.prefetch_queue.lock().ok().and_then(|mut queue| queue.pop_front()) else {
+                return;
+            };
+            self.active_prefetches.fetch_add(1, Ordering::Relaxed);
+            if self.generation_sender.send(job).is_err() {
+                self.active_prefetches.fetch_sub(1, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+    
     /// Get thumbnail if available in cache
     pub fn get_cached_thumbnail(&self, video_path: &Path, timestamp: f64) -> Option<CachedThumbnail> {
         let cache_key = Self::generate_cache_key(video_path, timestamp);
         
         if let Ok(mut cache) = self.texture_cache.lock() {
-            cache.get(&cache_key).cloned()
+            cache.get(&cache_key)
         } else {
             None
         }
     }
+
+    /// Change the texture memory limit, evicting right away if it shrank
+    pub fn set_max_cache_bytes(&self, max_cache_bytes: usize) {
+        if let Ok(mut cache) = self.texture_cache.lock() {
+            cache.set_max_bytes(max_cache_bytes);
+        }
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.texture_cache.lock()
+            .map(|cache| cache.stats())
+            .unwrap_or_default()
+    }
     
     /// Pre-cache thumbnails around a timestamp (predictive caching)
     pub fn precache_around_timestamp(&self, video_path: &Path, center_timestamp: f64, duration: f64) {
@@ -165,6 +339,9 @@ impl SmartThumbnailCache {
                     if let Ok(mut pending) = self.pending_requests.lock() {
                         pending.remove(&result.cache_key);
                     }
+                    if result.prefetch {
+                        self.active_prefetches.fetch_sub(1, Ordering::Relaxed);
+                    }
                     
                     if let Some(image_data) = result.image_data {
                         self.create_texture_from_data(ctx, &result.cache_key, image_data, result.width, result.height, result.timestamp);
@@ -176,58 +353,69 @@ impl SmartThumbnailCache {
                 Err(mpsc::TryRecvError::Disconnected) => break, // Worker thread died
             }
         }
+        self.start_prefetches();
     }
     
-    /// Background worker for generating thumbnails
+    /// Background worker that hands thumbnail jobs to the FFmpeg process pool
     fn thumbnail_worker(
         job_receiver: mpsc::Receiver<ThumbnailJob>,
         result_sender: mpsc::Sender<ThumbnailResult>,
         temp_dir: PathBuf,
+        pool: Arc<FfmpegProcessPool>,
     ) {
         while let Ok(job) = job_receiver.recv() {
-            // Try up to 3 times for transient failures (file being written, etc.)
-            let mut result = Self::generate_thumbnail_data(&job.video_path, job.timestamp, &temp_dir);
-            
-            // Retry on file access errors (likely temporary)
-            if let Err(ref e) = result {
-                let error_str = e.to_string().to_lowercase();
-                if error_str.contains("cannot find the file") || 
-                   error_str.contains("cannot access video file") ||
-                   error_str.contains("permission denied") {
-                    // Wait briefly and retry
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    result = Self::generate_thumbnail_data(&job.video_path, job.timestamp, &temp_dir);
-                    
-                    // One more try after a longer wait
-                    if result.is_err() {
-                        std::thread::sleep(std::time::Duration::from_millis(500));
-                        result = Self::generate_thumbnail_data(&job.video_path, job.timestamp, &temp_dir);
-                    }
+            let result_sender = result_sender.clone();
+            let temp_dir = temp_dir.clone();
+            pool.submit(move || {
+                let thumbnail_result = Self::generate_with_retries(job, &temp_dir);
+                // The receiver is gone once the main thread drops the cache
+                let _ = result_sender.send(thumbnail_result);
+                Ok(())
+            });
+        }
+    }
+
+    fn generate_with_retries(job: ThumbnailJob, temp_dir: &Path) -> ThumbnailResult {
+        // Try up to 3 times for transient failures (file being written, etc.)
+        let mut result = Self::generate_thumbnail_data(&job.video_path, job.timestamp, temp_dir);
+        
+        // Retry on file access errors (likely temporary)
+        if let Err(ref e) = result {
+            let error_str = e.to_string().to_lowercase();
+            if error_str.contains("cannot find the file") || 
+               error_str.contains("cannot access video file") ||
+               error_str.contains("permission denied") {
+                // Wait briefly and retry
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                result = Self::generate_thumbnail_data(&job.video_path, job.timestamp, temp_dir);
+                
+                // One more try after a longer wait
+                if result.is_err() {
+                    std::thread::sleep(std::time::Duration::from_millis(500));
+                    result = Self::generate_thumbnail_data(&job.video_path, job.timestamp, temp_dir);
                 }
             }
-            
-            let thumbnail_result = match result {
-                Ok((image_data, width, height)) => ThumbnailResult {
-                    cache_key: job.cache_key,
-                    image_data: Some(image_data),
-                    width,
-                    height,
-                    timestamp: job.timestamp,
-                    error: None,
-                },
-                Err(e) => ThumbnailResult {
-                    cache_key: job.cache_key,
-                    image_data: None,
-                    width: 0,
-                    height: 0,
-                    timestamp: job.timestamp,
-                    error: Some(e.to_string()),
-                },
-            };
-            
-            if result_sender.send(thumbnail_result).is_err() {
-                break; // Main thread dropped the receiver
-            }
+        }
+        
+        match result {
+            Ok((image_data, width, height)) => ThumbnailResult {
+                cache_key: job.cache_key,
+                image_data: Some(image_data),
+                width,
+                height,
+                timestamp: job.timestamp,
+                error: None,
+                prefetch: job.prefetch,
+            },
+            Err(e) => ThumbnailResult {
+                cache_key: job.cache_key,
+                image_data: None,
+                width: 0,
+                height: 0,
+                timestamp: job.timestamp,
+                error: Some(e.to_string()),
+                prefetch: job.prefetch,
+            },
         }
     }
     
@@ -243,9 +431,10 @@ impl SmartThumbnailCache {
             return Err(anyhow::anyhow!("Cannot access video file: {}", e));
         }
         
-        let temp_file = temp_dir.join(format!("temp_thumb_{}_{}.jpg", 
-            std::process::id() % 10000,
-            (timestamp * 10.0) as u64));
+        // Jobs run in parallel, so the name has to differ per video as well as per timestamp
+        let temp_file = temp_dir.join(format!("{}_{}.jpg", 
+            Self::generate_cache_key(video_path, timestamp),
+            std::process::id() % 10000));
         
         // Use FFmpeg to extract frame - optimized for performance
         let output = std::process::Command::new("ffmpeg")
@@ -253,7 +442,7 @@ impl SmartThumbnailCache {
             .arg("-ss").arg(format!("{:.3}", timestamp))  // Seek BEFORE input for faster positioning
             .arg("-i").arg(video_path)
             .arg("-vframes").arg("1")
-            .arg("-vf").arg(format!("scale={}:{}:force_original_aspect_ratio=decrease", THUMBNAIL_MAX_WIDTH, THUMBNAIL_MAX_HEIGHT))  // Scale preserving aspect ratio, no padding
+            .arg("-vf").arg(VideoProcessor::thumbnail_filter(video_path, &format!("scale={}:{}:force_original_aspect_ratio=decrease", THUMBNAIL_MAX_WIDTH, THUMBNAIL_MAX_HEIGHT)))  // Scale preserving aspect ratio, no padding
             .arg("-q:v").arg("2")  // High quality
             .arg("-y")  // Overwrite
             .arg(&temp_file)
@@ -307,7 +496,7 @@ impl SmartThumbnailCache {
         };
         
         if let Ok(mut cache) = self.texture_cache.lock() {
-            cache.put(cache_key.to_string(), cached_thumbnail);
+            cache.insert(cache_key.to_string(), cached_thumbnail, width as usize * height as usize * THUMBNAIL_CHANNELS);
         }
         
         log::debug!("Created texture for thumbnail: {} at {:.1}s", cache_key, timestamp);
@@ -321,16 +510,67 @@ impl SmartThumbnailCache {
         let cutoff = Instant::now() - Duration::from_secs(30);
         
         if let Ok(mut cache) = self.texture_cache.lock() {
-            let keys_to_remove: Vec<String> = cache
+            let keys_to_remove: Vec<String> = cache.entries
                 .iter()
-                .filter(|(_, thumbnail)| thumbnail.generated_at < cutoff)
+                .filter(|(_, (thumbnail, _))| thumbnail.generated_at < cutoff)
                 .map(|(key, _)| key.clone())
                 .collect();
             
             for key in keys_to_remove {
-                cache.pop(&key);
+                cache.remove(&key);
                 log::debug!("Cleaned up old thumbnail: {}", key);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_texture_cache_evicts_least_recently_used() {
+        let mut cache = TextureCache::new(300);
+        cache.insert("a".to_string(), 1, 100);
+        cache.insert("b".to_string(), 2, 100);
+        cache.insert("c".to_string(), 3, 100);
+        // Touching "a" makes "b" the oldest
+        assert_eq!(cache.get("a"), Some(1));
+        cache.insert("d".to_string(), 4, 100);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some(3));
+
+        cache.set_max_bytes(150);
+        assert_eq!(cache.stats(), CacheStats {
+            current_bytes: 100,
+            max_bytes: 150,
+            hit_count: 2,
+            miss_count: 1,
+            eviction_count: 3,
+        });
+        // "c" was used last
+        assert_eq!(cache.get("c"), Some(3));
+    }
+
+    #[test]
+    fn test_prefetch_replaces_queued_requests() {
+        let cache = SmartThumbnailCache::new(Arc::new(FfmpegProcessPool::new(1)), 1024).unwrap();
+        let queued = |cache: &SmartThumbnailCache| -> Vec<PathBuf> {
+            cache.prefetch_queue.lock().unwrap().iter().map(|job| job.video_path.clone()).collect()
+        };
+
+        // The first prefetch starts right away, the second waits for it
+        cache.prefetch_thumbnails(&[(PathBuf::from("missing-a.mkv"), 0.0), (PathBuf::from("missing-b.mkv"), 0.0)]);
+        assert_eq!(queued(&cache), vec![PathBuf::from("missing-b.mkv")]);
+
+        // A new selection drops the waiting one
+        cache.prefetch_thumbnails(&[(PathBuf::from("missing-c.mkv"), 0.0)]);
+        assert_eq!(queued(&cache), vec![PathBuf::from("missing-c.mkv")]);
+        let b_key = SmartThumbnailCache::generate_cache_key(Path::new("missing-b.mkv"), 0.0);
+        assert!(!cache.pending_requests.lock().unwrap().contains(&b_key));
+
+        // Asking for it directly takes it out of the queue
+        assert!(cache.request_thumbnail(Path::new("missing-c.mkv"), 0.0).is_none());
+        assert!(queued(&cache).is_empty());
+    }
+}
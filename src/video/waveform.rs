@@ -1,5 +1,48 @@
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::video::FfmpegProcessPool;
+
+/// Keeps temp WAV names unique while several extractions run in the pool
+static NEXT_TEMP_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Quietest level shown on the dB scale; silence (log of zero) is floored to this
+pub const WAVEFORM_MIN_DB: f32 = -60.0;
+
+/// Resolution of the loudness envelope used to find loud moments
+pub const PEAK_WINDOW_SECONDS: f64 = 0.01;
+/// A loud region has to stay loud this long to count, so pops and clipping spikes are ignored
+pub const MIN_PEAK_SUSTAIN_SECONDS: f64 = 0.03;
+/// Loud moments quieter than this fraction of the loudest one aren't peaks
+pub const PEAK_THRESHOLD_RATIO: f32 = 0.7;
+
+/// How waveform peaks are mapped to bar heights. Peaks are always computed linearly,
+/// so switching only changes the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WaveformScale {
+    /// Height proportional to amplitude
+    #[default]
+    Linear,
+    /// Height proportional to level in dB, from `WAVEFORM_MIN_DB` to 0, so quiet parts stay visible
+    Decibels,
+}
+
+impl WaveformScale {
+    /// Map a linear peak (0..=1) to a bar height (0..=1)
+    pub fn height(&self, peak: f32) -> f32 {
+        let peak = peak.abs().min(1.0);
+        match self {
+            WaveformScale::Linear => peak,
+            WaveformScale::Decibels => {
+                let db = if peak > 0.0 { 20.0 * peak.log10() } else { WAVEFORM_MIN_DB };
+                ((db.max(WAVEFORM_MIN_DB) - WAVEFORM_MIN_DB) / -WAVEFORM_MIN_DB).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
 
 pub struct WaveformData {
     pub samples: Vec<f32>,
@@ -8,11 +51,22 @@ pub struct WaveformData {
 }
 
 impl WaveformData {
-    pub fn generate(audio_file: &Path, track_index: usize) -> anyhow::Result<Self> {
+    /// Decode `track_index` of `audio_file` into mono samples. Setting `cancel` kills FFmpeg
+    /// and returns an error, e.g. when another clip was selected in the meantime.
+    pub fn generate(audio_file: &Path, track_index: usize, cancel: &AtomicBool) -> anyhow::Result<Self> {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(anyhow::anyhow!("Waveform extraction cancelled"));
+        }
         // Extract audio to temporary WAV file for processing
-        let temp_path = std::env::temp_dir().join("temp_audio.wav");
+        let temp_path = std::env::temp_dir().join(format!(
+            "clip-helper-waveform-{}-{}.wav",
+            std::process::id(),
+            NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed)
+        ));
         
-        let output = Command::new("ffmpeg")
+        let mut child = Command::new("ffmpeg")
+            .arg("-hide_banner")
+            .arg("-v").arg("error")
             .arg("-i").arg(audio_file)
             .arg("-map").arg(format!("0:a:{}", track_index))
             .arg("-acodec").arg("pcm_s16le")
@@ -20,9 +74,26 @@ impl WaveformData {
             .arg("-ac").arg("1") // Mono for waveform
             .arg("-y")
             .arg(&temp_path)
-            .output()?;
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to run ffmpeg: {}", e))?;
 
-        if !output.status.success() {
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if cancel.load(Ordering::Relaxed) {
+                let _ = child.kill();
+                let _ = child.wait();
+                let _ = std::fs::remove_file(&temp_path);
+                return Err(anyhow::anyhow!("Waveform extraction cancelled"));
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        };
+
+        if !status.success() {
+            let _ = std::fs::remove_file(&temp_path);
             return Err(anyhow::anyhow!("Failed to extract audio for waveform"));
         }
 
@@ -50,6 +121,18 @@ impl WaveformData {
         })
     }
 
+    /// Like `generate`, but waits for a free slot in the FFmpeg process pool first.
+    /// A load cancelled while waiting gives its slot up without starting FFmpeg.
+    pub fn generate_in_pool(pool: &FfmpegProcessPool, audio_file: &Path, track_index: usize, cancel: Arc<AtomicBool>) -> anyhow::Result<Self> {
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        let audio_file = audio_file.to_path_buf();
+        pool.submit(move || {
+            let _ = result_tx.send(Self::generate(&audio_file, track_index, &cancel));
+            Ok(())
+        }).wait()?;
+        result_rx.recv()?
+    }
+
     pub fn get_peak_at_time(&self, time: f64, window_size: f64) -> f32 {
         let start_sample = ((time - window_size / 2.0) * self.sample_rate as f64) as usize;
         let end_sample = ((time + window_size / 2.0) * self.sample_rate as f64) as usize;
@@ -67,6 +150,83 @@ impl WaveformData {
             .fold(0.0f32, |acc, s| acc.max(s))
     }
 
+    /// Linear peaks of `buckets` equal slices of `start..end` seconds, for drawing
+    /// the visible part of a zoomed timeline
+    pub fn peaks_in_range(&self, start: f64, end: f64, buckets: usize) -> Vec<f32> {
+        if buckets == 0 || end <= start {
+            return Vec::new();
+        }
+        let rate = self.sample_rate as f64;
+        let bucket_duration = (end - start) / buckets as f64;
+        
+        (0..buckets)
+            .map(|i| {
+                let bucket_start = start + i as f64 * bucket_duration;
+                let first = ((bucket_start * rate).max(0.0) as usize).min(self.samples.len());
+                let last = (((bucket_start + bucket_duration) * rate).max(0.0).ceil() as usize)
+                    .clamp(first, self.samples.len());
+                self.samples[first..last].iter().fold(0.0f32, |acc, s| acc.max(s.abs()))
+            })
+            .collect()
+    }
+
+    /// Level each envelope window stays at for at least `MIN_PEAK_SUSTAIN_SECONDS`,
+    /// paired with the time in the middle of that span
+    fn sustained_envelope(&self) -> Vec<(f64, f32)> {
+        let window = ((PEAK_WINDOW_SECONDS * self.sample_rate as f64) as usize).max(1);
+        let levels: Vec<f32> = self.samples
+            .chunks(window)
+            .map(|chunk| chunk.iter().fold(0.0f32, |acc, s| acc.max(s.abs())))
+            .collect();
+        let sustain = ((MIN_PEAK_SUSTAIN_SECONDS / PEAK_WINDOW_SECONDS).ceil() as usize).max(1);
+        if levels.len() < sustain {
+            return Vec::new();
+        }
+        
+        let window_duration = window as f64 / self.sample_rate as f64;
+        levels.windows(sustain)
+            .enumerate()
+            .map(|(i, span)| {
+                let time = (i as f64 + sustain as f64 / 2.0) * window_duration;
+                (time, span.iter().copied().fold(f32::INFINITY, f32::min))
+            })
+            .collect()
+    }
+
+    /// Time of the loudest sustained moment, None for silence
+    pub fn loudest_moment(&self) -> Option<f64> {
+        self.sustained_envelope()
+            .into_iter()
+            .filter(|&(_, level)| level > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(time, _)| time)
+    }
+
+    /// Times of the loudest point of every sustained region above
+    /// `PEAK_THRESHOLD_RATIO` of the loudest moment, in order
+    pub fn loud_peaks(&self) -> Vec<f64> {
+        let envelope = self.sustained_envelope();
+        let loudest = envelope.iter().fold(0.0f32, |acc, &(_, level)| acc.max(level));
+        if loudest <= 0.0 {
+            return Vec::new();
+        }
+        let threshold = loudest * PEAK_THRESHOLD_RATIO;
+        
+        let mut peaks = Vec::new();
+        let mut region_peak: Option<(f64, f32)> = None;
+        for &(time, level) in &envelope {
+            if level >= threshold {
+                if region_peak.is_none_or(|(_, peak)| level > peak) {
+                    region_peak = Some((time, level));
+                }
+            } else if let Some((peak_time, _)) = region_peak.take() {
+                peaks.push(peak_time);
+            }
+        }
+        peaks.extend(region_peak.map(|(time, _)| time));
+        peaks
+    }
+
     pub fn downsample_for_display(&self, target_width: usize) -> Vec<f32> {
         if self.samples.is_empty() {
             return vec![0.0; target_width];
@@ -93,3 +253,61 @@ impl WaveformData {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decibel_scale_floors_silence() {
+        assert_eq!(WaveformScale::Decibels.height(0.0), 0.0);
+        assert_eq!(WaveformScale::Decibels.height(1.0), 1.0);
+        // -20 dB is a tenth of the amplitude but two thirds of the height
+        assert!((WaveformScale::Decibels.height(0.1) - 2.0 / 3.0).abs() < 1e-5);
+        assert_eq!(WaveformScale::Linear.height(0.1), 0.1);
+    }
+
+    #[test]
+    fn test_loud_peaks_ignore_short_spikes() {
+        let sample_rate = 1000;
+        let mut samples = vec![0.05f32; 3000];
+        // A 2 ms pop at full scale, then two sustained loud regions
+        samples[500..502].fill(1.0);
+        samples[1000..1200].fill(0.6);
+        samples[2000..2100].fill(0.8);
+        let waveform = WaveformData { samples, sample_rate, duration: 3.0 };
+
+        let loudest = waveform.loudest_moment().expect("Failed to find loudest moment");
+        assert!((2.0..2.1).contains(&loudest), "loudest moment at {}", loudest);
+
+        let peaks = waveform.loud_peaks();
+        assert_eq!(peaks.len(), 2);
+        assert!((1.0..1.2).contains(&peaks[0]));
+        assert!((2.0..2.1).contains(&peaks[1]));
+
+        let silence = WaveformData { samples: vec![0.0; 100], sample_rate, duration: 0.1 };
+        assert_eq!(silence.loudest_moment(), None);
+    }
+
+    #[test]
+    fn test_cancelled_generate_does_not_start() {
+        let missing = std::env::temp_dir().join(format!("clip-helper-waveform-missing-{}.mkv", std::process::id()));
+        let Err(error) = WaveformData::generate(&missing, 0, &AtomicBool::new(true)) else {
+            panic!("Cancelled extraction must fail");
+        };
+        assert_eq!(error.to_string(), "Waveform extraction cancelled");
+    }
+
+    #[test]
+    fn test_peaks_in_range() {
+        let waveform = WaveformData {
+            samples: vec![0.1, -0.5, 0.2, 0.0, 0.9, -0.3, 0.0, 0.0],
+            sample_rate: 4,
+            duration: 2.0,
+        };
+        assert_eq!(waveform.peaks_in_range(0.0, 2.0, 2), vec![0.5, 0.9]);
+        assert_eq!(waveform.peaks_in_range(1.0, 1.5, 1), vec![0.9]);
+        // Past the end of the audio
+        assert_eq!(waveform.peaks_in_range(2.0, 3.0, 2), vec![0.0, 0.0]);
+    }
+}